@@ -259,6 +259,7 @@ fn create_easy_sha256_job(difficulty: f64) -> BenchmarkJob {
             nbits: Some(nbits),
             merkle_path: None,
             target: Some(target_bytes),
+            coinbase: None,
         },
         expected_shares_per_mh: 1000000.0 / actual_difficulty,
         description: format!("Easy SHA-256 job - difficulty ~{:.10}", actual_difficulty),
@@ -286,6 +287,7 @@ fn create_medium_sha256_job(difficulty: f64) -> BenchmarkJob {
             nbits: Some(nbits),
             merkle_path: None,
             target: Some(target_bytes),
+            coinbase: None,
         },
         expected_shares_per_mh: 1000000.0 / actual_difficulty,
         description: format!("Medium SHA-256 job - difficulty ~{:.10}", actual_difficulty),
@@ -313,6 +315,7 @@ fn create_hard_sha256_job(difficulty: f64) -> BenchmarkJob {
             nbits: Some(nbits),
             merkle_path: None,
             target: Some(target_bytes),
+            coinbase: None,
         },
         expected_shares_per_mh: 1000000.0 / actual_difficulty,
         description: format!("Hard SHA-256 job - difficulty ~{:.10}", actual_difficulty),
@@ -341,6 +344,7 @@ fn create_easy_sha3x_job(difficulty: f64) -> BenchmarkJob {
             nbits: None,
             merkle_path: None,
             target: None,
+            coinbase: None,
         },
         expected_shares_per_mh: 1000000.0 / difficulty,
         description: format!("Easy SHA3x job - difficulty ~{}", u64_difficulty),
@@ -368,6 +372,7 @@ fn create_medium_sha3x_job(difficulty: f64) -> BenchmarkJob {
             nbits: None,
             merkle_path: None,
             target: None,
+            coinbase: None,
         },
         expected_shares_per_mh: 1000000.0 / difficulty,
         description: format!("Medium SHA3x job - difficulty ~{}", u64_difficulty),
@@ -395,6 +400,7 @@ fn create_hard_sha3x_job(difficulty: f64) -> BenchmarkJob {
             nbits: None,
             merkle_path: None,
             target: None,
+            coinbase: None,
         },
         expected_shares_per_mh: 1000000.0 / difficulty,
         description: format!("Hard SHA3x job - difficulty ~{}", u64_difficulty),
@@ -530,6 +536,7 @@ pub fn create_custom_job(difficulty: u64, job_suffix: &str) -> BenchmarkJob {
             nbits: None,
             merkle_path: None,
             target: None,
+            coinbase: None,
         },
         expected_shares_per_mh: 1000000.0 / difficulty as f64,
         description: format!(
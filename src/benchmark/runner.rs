@@ -6,7 +6,7 @@
 // via pull requests to the project repository.
 //
 // File: src/benchmark/runner.rs
-// Version: 1.0.29
+// Version: 1.2.0
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // This file implements the benchmark execution engine for testing SHA3x and SHA-256 mining
@@ -18,7 +18,8 @@ use crate::benchmark::jobs::{
     calculate_difficulty_from_nbits, get_job_by_difficulty_and_algo, get_max_target,
 };
 use crate::benchmark::profiler::ProfilerData;
-use crate::core::difficulty::{U256, bits_to_target};
+use crate::core::difficulty::{U256, bits_to_target, sha3x_hash_word, sha3x_share_threshold};
+use crate::core::keccak_simd::CpuBackend;
 use crate::core::types::{Algorithm, BenchmarkResult, MiningJob};
 use crate::miner::stats::{MinerStats, ThreadStats};
 use hex;
@@ -45,6 +46,8 @@ pub struct BenchmarkConfig {
     pub algorithm: Algorithm,
     pub enable_profiling: bool,
     pub report_interval: Duration,
+    /// Resolved SHA3x CPU backend
+    pub cpu_backend: CpuBackend,
 }
 
 /// Main benchmark runner
@@ -68,6 +71,7 @@ impl BenchmarkRunner {
             algorithm,
             enable_profiling: true,
             report_interval: Duration::from_secs(5),
+            cpu_backend: CpuBackend::Auto.resolve(),
         };
         let mut stats = MinerStats::new(actual_threads);
         stats.set_algorithm(algorithm);
@@ -78,11 +82,25 @@ impl BenchmarkRunner {
         }
     }
 
+    /// Benchmark SHA3x on `backend` (falls back to scalar when unsupported)
+    pub fn with_cpu_backend(mut self, backend: CpuBackend) -> Self {
+        self.config.cpu_backend = backend.resolve();
+        self
+    }
+
     pub async fn run(&self) -> Result<BenchmarkResult> {
         info!(target: LOG_TARGET,
             "🧪 Starting benchmark with {} threads, algo: {:?}",
             self.config.thread_count, self.config.algorithm
         );
+        let cpu_backend = self.config.cpu_backend;
+        if self.config.algorithm == Algorithm::Sha3x {
+            info!(target: LOG_TARGET,
+                "🧮 SHA3x CPU backend: {} ({} hashes per pass)",
+                cpu_backend,
+                cpu_backend.ways()
+            );
+        }
         let benchmark_job =
             get_job_by_difficulty_and_algo(self.config.target_difficulty, self.config.algorithm);
         info!(target: LOG_TARGET,"📋 Using benchmark job: {}", benchmark_job.description);
@@ -117,6 +135,7 @@ impl BenchmarkRunner {
                     share_tx,
                     thread_stats,
                     seen_nonces,
+                    cpu_backend,
                 );
                 debug!(target: LOG_TARGET,"Thread {}: Terminated", thread_id);
             });
@@ -224,6 +243,7 @@ impl BenchmarkRunner {
             shares_found: final_shares,
             thread_count: self.config.thread_count,
             allocations: self.profiler.get_allocation_count(),
+            cpu_backend: (self.config.algorithm == Algorithm::Sha3x).then_some(cpu_backend),
         })
     }
 
@@ -252,8 +272,9 @@ fn benchmark_thread(
     share_tx: Arc<Mutex<Sender<BenchmarkShare>>>,
     thread_stats: Arc<ThreadStats>,
     seen_nonces: Arc<Mutex<HashSet<u32>>>,
+    cpu_backend: CpuBackend,
 ) {
-    use crate::core::{sha3x::sha3x_hash_with_nonce_batch, sha256::sha256d_hash_with_nonce_batch};
+    use crate::core::{sha3x::Sha3xHasher, sha256::sha256d_hash_with_nonce_batch};
     use rand::{Rng, rngs::ThreadRng};
     use sha2::{Digest, Sha256};
 
@@ -276,28 +297,35 @@ fn benchmark_thread(
                 debug!(target: LOG_TARGET,"Thread 0: Target difficulty: {}", target_difficulty);
             }
 
+            let hasher = Sha3xHasher::with_backend(&job.mining_hash, cpu_backend);
+            // Every hash whose 256-bit difficulty reaches the target has a
+            // leading word at or below this threshold
+            let threshold = sha3x_share_threshold(job.target_difficulty);
+            let mut nonces = [0u64; 8];
+            let mut hashes = [[0u8; 32]; 8];
+
             while !should_stop.load(Ordering::Relaxed) {
-                for _ in (0..10000).step_by(4) {
+                for _ in (0..10000).step_by(nonces.len()) {
                     if should_stop.load(Ordering::Relaxed) {
                         break;
                     }
-                    let batch_results = sha3x_hash_with_nonce_batch(&job.mining_hash, nonce);
-                    for (hash, _batch_nonce) in batch_results.iter() {
+                    for (i, batch_nonce) in nonces.iter_mut().enumerate() {
+                        *batch_nonce = nonce.wrapping_add(i as u64);
+                    }
+                    hasher.hash_nonces(&nonces, &mut hashes);
+                    local_hash_count += nonces.len() as u64;
+
+                    // Early reject on the leading word; 256-bit division only for candidates
+                    for hash in hashes
+                        .iter()
+                        .filter(|hash| sha3x_hash_word(hash) <= threshold)
+                    {
                         let hash_u256 = U256::from_big_endian(hash);
                         let difficulty = if !hash_u256.is_zero() {
                             (max_target / hash_u256).low_u64() as f64
                         } else {
                             0.0
                         };
-                        local_hash_count += 1;
-
-                        // Log first few hash difficulties for debugging
-                        if thread_id == 0 && local_hash_count <= 10 {
-                            debug!(target: LOG_TARGET,
-                                "Thread 0: Hash {}: difficulty = {}, target = {}",
-                                local_hash_count, difficulty, target_difficulty
-                            );
-                        }
 
                         if difficulty >= target_difficulty {
                             let share = BenchmarkShare {
@@ -314,7 +342,7 @@ fn benchmark_thread(
                             );
                         }
                     }
-                    nonce = nonce.wrapping_add((4 * num_threads) as u64);
+                    nonce = nonce.wrapping_add((nonces.len() * num_threads) as u64);
                 }
                 if last_report.elapsed() > Duration::from_secs(1) {
                     thread_stats.update_hashrate(local_hash_count);
@@ -413,7 +441,7 @@ fn benchmark_thread(
                     let batch_results = sha256d_hash_with_nonce_batch(&header, nonce);
                     for (hash, batch_nonce) in batch_results.iter() {
                         local_hash_count += 1;
-                        let hash_u256 = U256::from_big_endian(hash);
+                        let hash_u256 = U256::from_little_endian(hash);
                         if thread_id == 0 && logged_hashes < 10 {
                             debug!(target: LOG_TARGET,"Thread 0: Hash bytes: {}", hex::encode(hash));
                             debug!(target: LOG_TARGET,
@@ -476,6 +504,12 @@ fn benchmark_thread(
 }
 
 // Changelog:
+// - v1.2.0 (2025-06-28): SHA3x benchmark threads skip the 256-bit difficulty of hashes
+//   whose leading word is above the target threshold (no per-hash debug logging).
+// - v1.1.0 (2025-06-28): SHA3x benchmarks run on the CpuBackend given to with_cpu_backend()
+//   (8 nonces per pass) and report it in BenchmarkResult.
+// - v1.0.31 (2025-06-28): SHA3x benchmark threads hash with Sha3xHasher (no per-hash allocations).
+// - v1.0.30 (2025-06-28): SHA-256 benchmark hashes are compared as little-endian numbers.
 // - v1.0.29 (2025-06-23): Fixed benchmark duration logic.
 //   - Removed confusing duration multiplier that was extending high-difficulty benchmarks by 10x
 //   - Now benchmark duration matches exactly what user specifies (30 seconds = 30 seconds)
@@ -48,6 +48,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         nbits: None,
         merkle_path: None,
         target: None,
+        coinbase: None,
     };
 
     info!(target: LOG_TARGET,"🎯 Starting GPU mining test...");
@@ -158,6 +159,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         nbits: None,
         merkle_path: None,
         target: None,
+        coinbase: None,
     };
 
     // Test a few iterations with XN
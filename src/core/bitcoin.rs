@@ -0,0 +1,219 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/core/bitcoin.rs
+// Version: 1.0.0
+//
+// This file contains the Bitcoin block header helpers used by SHA-256d mining
+// over Stratum V1: coinbase assembly from the pool's coinb1/coinb2 and the
+// extranonces, merkle root computation from the merkle branch, header layout
+// and conversion of pool share difficulty to a 256-bit target.
+//
+// Tree Location:
+// - src/core/bitcoin.rs (Bitcoin header, coinbase and merkle helpers)
+// - Depends on: core/sha256, core/difficulty
+
+use crate::core::difficulty::U256;
+use crate::core::sha256::sha256d_hash;
+use sha2::{Digest, Sha256};
+
+/// Bitcoin difficulty-1 target (0x00000000FFFF0000...)
+const DIFF1_TARGET_BITS: u32 = 0x1d00ffff;
+
+/// Coinbase parts of a Stratum V1 job; miners roll extranonce2 to get fresh
+/// merkle roots without waiting for the pool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoinbaseTemplate {
+    /// Coinbase transaction up to the extranonces
+    pub coinb1: Vec<u8>,
+    /// Coinbase transaction after the extranonces
+    pub coinb2: Vec<u8>,
+    /// Session extranonce assigned by the pool at subscribe
+    pub extranonce1: Vec<u8>,
+    /// Number of extranonce2 bytes the miner fills in
+    pub extranonce2_size: usize,
+    /// Merkle branch hashes, in the byte order sent by the pool
+    pub merkle_branch: Vec<[u8; 32]>,
+}
+
+impl CoinbaseTemplate {
+    /// Extranonce2 bytes for a roll counter (little-endian, padded or truncated to size)
+    pub fn extranonce2(&self, counter: u64) -> Vec<u8> {
+        let mut bytes = counter.to_le_bytes().to_vec();
+        bytes.resize(self.extranonce2_size, 0);
+        bytes
+    }
+
+    /// Full serialized coinbase transaction for an extranonce2
+    pub fn coinbase(&self, extranonce2: &[u8]) -> Vec<u8> {
+        let mut coinbase = Vec::with_capacity(
+            self.coinb1.len() + self.extranonce1.len() + extranonce2.len() + self.coinb2.len(),
+        );
+        coinbase.extend_from_slice(&self.coinb1);
+        coinbase.extend_from_slice(&self.extranonce1);
+        coinbase.extend_from_slice(extranonce2);
+        coinbase.extend_from_slice(&self.coinb2);
+        coinbase
+    }
+
+    /// Merkle root (header byte order) for an extranonce2
+    pub fn merkle_root(&self, extranonce2: &[u8]) -> [u8; 32] {
+        let mut root = double_sha256(&self.coinbase(extranonce2));
+        for branch in &self.merkle_branch {
+            let mut pair = [0u8; 64];
+            pair[..32].copy_from_slice(&root);
+            pair[32..].copy_from_slice(branch);
+            root = double_sha256(&pair);
+        }
+        root
+    }
+}
+
+/// SHA-256d of arbitrary data (sha256d_hash only takes 80-byte headers)
+pub fn double_sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(Sha256::digest(data)).into()
+}
+
+/// Stratum V1 sends prevhash with each 32-bit word byte-swapped; undo that
+pub fn stratum_prev_hash_to_header(prev_hash: &[u8; 32]) -> [u8; 32] {
+    let mut header_order = [0u8; 32];
+    for (out, word) in header_order.chunks_mut(4).zip(prev_hash.chunks(4)) {
+        out.copy_from_slice(&[word[3], word[2], word[1], word[0]]);
+    }
+    header_order
+}
+
+/// Lay out an 80-byte block header with a zero nonce
+pub fn build_header(
+    version: u32,
+    prev_hash: &[u8; 32],
+    merkle_root: &[u8; 32],
+    ntime: u32,
+    nbits: u32,
+) -> [u8; 80] {
+    let mut header = [0u8; 80];
+    header[0..4].copy_from_slice(&version.to_le_bytes());
+    header[4..36].copy_from_slice(prev_hash);
+    header[36..68].copy_from_slice(merkle_root);
+    header[68..72].copy_from_slice(&ntime.to_le_bytes());
+    header[72..76].copy_from_slice(&nbits.to_le_bytes());
+    header
+}
+
+/// Block hash of a header, as a number (SHA-256d output is little-endian)
+pub fn header_hash_value(header: &[u8]) -> U256 {
+    U256::from_little_endian(&sha256d_hash(header))
+}
+
+/// Share target for a Stratum V1 pool difficulty (`mining.set_difficulty`),
+/// big-endian as MiningJob.target expects. Fractional difficulties are supported.
+pub fn pool_difficulty_to_target(difficulty: f64) -> [u8; 32] {
+    let diff1 = crate::core::difficulty::bits_to_target(DIFF1_TARGET_BITS);
+    if !(difficulty.is_finite() && difficulty > 0.0) {
+        return diff1.to_big_endian();
+    }
+    // Scale by 2^16 so difficulties below 1 keep their precision
+    let scaled = ((difficulty * 65536.0).round() as u64).max(1);
+    ((diff1 << 16) / U256::from(scaled)).to_big_endian()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bitcoin genesis block header
+    const GENESIS_HEADER: &str = "0100000000000000000000000000000000000000000000000000000000000000000000003ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a29ab5f49ffff001d1dac2b7c";
+
+    #[test]
+    fn test_genesis_header_hash() {
+        let header = hex::decode(GENESIS_HEADER).unwrap();
+        let mut hash = sha256d_hash(&header);
+        hash.reverse();
+        assert_eq!(
+            hex::encode(hash),
+            "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f"
+        );
+        let target = crate::core::difficulty::bits_to_target(0x1d00ffff);
+        assert!(header_hash_value(&header) <= target);
+    }
+
+    #[test]
+    fn test_build_header_matches_genesis() {
+        let genesis = hex::decode(GENESIS_HEADER).unwrap();
+        let merkle_root: [u8; 32] = genesis[36..68].try_into().unwrap();
+        let mut header = build_header(1, &[0u8; 32], &merkle_root, 0x495fab29, 0x1d00ffff);
+        header[76..80].copy_from_slice(&0x7c2bac1du32.to_le_bytes());
+        assert_eq!(header.to_vec(), genesis);
+    }
+
+    #[test]
+    fn test_genesis_coinbase_merkle_root() {
+        // Genesis coinbase transaction split around a zero-length extranonce
+        let coinbase = hex::decode("01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff4d04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368616e63656c6c6f72206f6e206272696e6b206f66207365636f6e64206261696c6f757420666f722062616e6b73ffffffff0100f2052a01000000434104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac00000000").unwrap();
+        let template = CoinbaseTemplate {
+            coinb1: coinbase[..42].to_vec(),
+            coinb2: coinbase[42..].to_vec(),
+            extranonce1: vec![],
+            extranonce2_size: 0,
+            merkle_branch: vec![],
+        };
+        let genesis = hex::decode(GENESIS_HEADER).unwrap();
+        assert_eq!(template.merkle_root(&[]).to_vec(), genesis[36..68].to_vec());
+    }
+
+    #[test]
+    fn test_merkle_branch_and_extranonce() {
+        let template = CoinbaseTemplate {
+            coinb1: vec![0xaa],
+            coinb2: vec![0xbb],
+            extranonce1: vec![0x01, 0x02],
+            extranonce2_size: 4,
+            merkle_branch: vec![[0x11; 32]],
+        };
+        let extranonce2 = template.extranonce2(0x0102);
+        assert_eq!(extranonce2, vec![0x02, 0x01, 0x00, 0x00]);
+        assert_eq!(
+            template.coinbase(&extranonce2),
+            vec![0xaa, 0x01, 0x02, 0x02, 0x01, 0x00, 0x00, 0xbb]
+        );
+
+        let leaf = double_sha256(&template.coinbase(&extranonce2));
+        let mut pair = leaf.to_vec();
+        pair.extend_from_slice(&[0x11; 32]);
+        assert_eq!(template.merkle_root(&extranonce2), double_sha256(&pair));
+        assert_ne!(
+            template.merkle_root(&template.extranonce2(1)),
+            template.merkle_root(&extranonce2)
+        );
+    }
+
+    #[test]
+    fn test_stratum_prev_hash_word_swap() {
+        let mut stratum = [0u8; 32];
+        stratum[..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        let header = stratum_prev_hash_to_header(&stratum);
+        assert_eq!(&header[..8], &[4, 3, 2, 1, 8, 7, 6, 5]);
+    }
+
+    #[test]
+    fn test_pool_difficulty_to_target() {
+        let diff1 = pool_difficulty_to_target(1.0);
+        assert_eq!(
+            hex::encode(diff1),
+            "00000000ffff0000000000000000000000000000000000000000000000000000"
+        );
+        let diff2 = U256::from_big_endian(&pool_difficulty_to_target(2.0));
+        assert_eq!(diff2 * U256::from(2), U256::from_big_endian(&diff1));
+        let half = U256::from_big_endian(&pool_difficulty_to_target(0.5));
+        assert_eq!(half, U256::from_big_endian(&diff1) * U256::from(2));
+    }
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial Bitcoin header helpers for Stratum V1 SHA-256d mining.
+//   - Coinbase assembly with extranonce1/extranonce2 and merkle root from the branch.
+//   - Stratum prevhash word-swap, 80-byte header layout and pool difficulty targets.
@@ -0,0 +1,1008 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/core/config.rs
+// Version: 1.9.1
+//
+// This file implements TOML configuration file support. A --config file can
+// hold everything the command line can (wallet/worker, failover pools, thread
+// count, GPU settings with per-device overrides, web dashboard and logging);
+// the command line is layered on top, so any flag given explicitly wins over
+// the file and the file wins over clap defaults. --print-config dumps the
+// merged result in the same format.
+//
+// GPU overrides are selected by OpenCL device index or by a device name
+// pattern, so mixed rigs (e.g. an integrated GPU next to discrete cards) can
+// give every card its own intensity, batch size, work groups and limits.
+//
+// Tree Location:
+// - src/core/config.rs (config file loading and CLI layering)
+// - Depends on: clap, serde, toml, core/types
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches};
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::{Args, GpuSettings};
+
+/// Contents of a graxil TOML config file; every key is optional
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MinerConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worker: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub algo: Option<String>,
+    /// CPU mining threads (0 = auto-detect)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threads: Option<usize>,
+    /// Failover pool list, in preference order (`[[pools]]` tables)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pools: Vec<PoolConfig>,
+    pub connection: ConnectionConfig,
+    pub cpu: CpuConfig,
+    pub gpu: GpuConfig,
+    pub web: WebConfig,
+    pub schedule: ScheduleConfig,
+    pub logging: LoggingConfig,
+}
+
+/// One `[[pools]]` entry; the same options as a `--pool HOST:PORT,key=value` spec
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PoolConfig {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worker: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// SHA-256 certificate fingerprint (hex) for stratum+ssl:// pools
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+    /// Skip certificate verification (prefer `fingerprint` for self-signed pools)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub insecure: Option<bool>,
+}
+
+/// `[connection]`: proxying, TLS and failover timing
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConnectionConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_insecure: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+    /// Seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_backoff: Option<u64>,
+    /// Seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failback_interval: Option<u64>,
+    /// Run as a Stratum proxy on this address
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_listen: Option<String>,
+}
+
+/// `[gpu]`: global GPU settings plus `[gpu.devices.KEY]` overrides.
+/// Ignored by CPU-only builds.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GpuConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intensity: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub power_limit: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temp_limit: Option<u8>,
+    /// Temperature at which a GPU stops mining (see --gpu-temp-cutoff)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temp_cutoff: Option<u8>,
+    /// Hardware errors after which a GPU stops mining (see --gpu-hw-error-limit)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hw_error_limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub work_groups_per_cu: Option<u32>,
+    /// Tune every GPU at startup and store the results (see --autotune)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autotune: Option<bool>,
+    /// Seconds to measure each autotune candidate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autotune_duration: Option<u64>,
+    /// OpenCL device ids to leave idle
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excluded_devices: Option<Vec<u32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub information_file_dir: Option<PathBuf>,
+    /// Per-device overrides keyed by OpenCL device id (the --excluded-devices index)
+    /// or by a device name pattern, see GpuDeviceSelector
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub devices: BTreeMap<String, GpuDeviceConfig>,
+}
+
+/// Settings for one GPU; unset fields inherit the global `[gpu]` / CLI values.
+/// Also stored per device in the GPU information file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GpuDeviceConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intensity: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub power_limit: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temp_limit: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub work_groups_per_cu: Option<u32>,
+}
+
+/// Which GPU(s) a per-device override applies to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GpuDeviceSelector {
+    /// OpenCL device id, as listed by --detect
+    Index(u32),
+    /// Case-insensitive device name pattern; `*` matches any text, and a
+    /// pattern without `*` matches anywhere in the name
+    Name(String),
+}
+
+/// Per-device overrides in precedence order
+pub type GpuDeviceOverrides = Vec<(GpuDeviceSelector, GpuDeviceConfig)>;
+
+/// `[web]`: real-time web dashboard
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<usize>,
+    /// Interface the dashboard and HTTP API listen on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bind: Option<IpAddr>,
+    /// Bearer token for the dashboard and HTTP API
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_token: Option<String>,
+    /// Basic auth credentials (USER:PASSWORD) for the dashboard and HTTP API
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_auth: Option<String>,
+    /// Serve Prometheus metrics at /metrics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<bool>,
+}
+
+/// `[cpu]`: CPU governor limits (see miner::cpu::governor)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CpuConfig {
+    /// Temperature ceiling in Celsius
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temp_limit: Option<u8>,
+    /// Cores to leave free for other workloads
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reserve_cores: Option<usize>,
+    /// SHA3x hashing backend: auto, scalar, avx2, avx512 or neon
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
+}
+
+/// `[schedule]`: when to mine (see miner::schedule)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScheduleConfig {
+    /// Mine only inside these windows ("Mon-Fri 18:00-08:00")
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub mine_windows: Vec<String>,
+    /// Never mine inside these windows
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pause_windows: Vec<String>,
+    /// Pause while other processes use more CPU than this (percent)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idle_cpu_threshold: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idle_resume_delay: Option<u64>,
+}
+
+/// `[logging]`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoggingConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dir: Option<PathBuf>,
+}
+
+impl MinerConfig {
+    /// Read and parse a config file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read config file {}: {}", path.display(), e))?;
+        Self::parse(&text).map_err(|e| format!("Invalid config file {}: {}", path.display(), e))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|e| e.to_string())
+    }
+
+    pub fn to_toml(&self) -> Result<String, String> {
+        toml::to_string_pretty(self).map_err(|e| e.to_string())
+    }
+
+    /// Effective configuration of already merged arguments (for --print-config)
+    pub fn from_args(args: &Args) -> Result<Self, String> {
+        let pools = args
+            .pool
+            .iter()
+            .map(|spec| PoolConfig::from_spec(spec))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        #[cfg(any(feature = "gpu", feature = "hybrid"))]
+        let gpu = GpuConfig {
+            intensity: Some(args.gpu_intensity),
+            batch_size: args.gpu_batch_size,
+            power_limit: args.gpu_power_limit,
+            temp_limit: args.gpu_temp_limit,
+            temp_cutoff: args.gpu_temp_cutoff,
+            hw_error_limit: args.gpu_hw_error_limit,
+            work_groups_per_cu: args.gpu_work_groups_per_cu,
+            autotune: Some(args.autotune),
+            autotune_duration: Some(args.autotune_duration),
+            excluded_devices: args.excluded_devices.as_deref().map(parse_device_list),
+            information_file_dir: args.information_file_dir.clone(),
+            devices: args
+                .gpu_devices
+                .iter()
+                .map(|(selector, device)| (selector.key(), device.clone()))
+                .collect(),
+        };
+        #[cfg(not(any(feature = "gpu", feature = "hybrid")))]
+        let gpu = GpuConfig::default();
+
+        Ok(Self {
+            wallet: args.wallet.clone(),
+            worker: Some(args.worker.clone()),
+            password: Some(args.password.clone()),
+            algo: Some(args.algo.clone()),
+            threads: Some(args.threads),
+            pools,
+            connection: ConnectionConfig {
+                proxy: args.proxy.clone(),
+                tls_insecure: Some(args.tls_insecure),
+                retries: Some(args.pool_retries),
+                max_backoff: Some(args.pool_max_backoff),
+                failback_interval: Some(args.pool_failback_interval),
+                proxy_listen: args.proxy_listen.clone(),
+            },
+            cpu: CpuConfig {
+                temp_limit: args.cpu_temp_limit,
+                reserve_cores: args.cpu_reserve_cores,
+                backend: Some(args.cpu_backend.clone()),
+            },
+            gpu,
+            web: WebConfig {
+                enabled: Some(args.web),
+                port: Some(args.ws),
+                bind: Some(args.web_bind),
+                api_token: args.api_token.clone(),
+                api_auth: args.api_auth.clone(),
+                metrics: Some(args.metrics),
+            },
+            schedule: ScheduleConfig {
+                mine_windows: args.mine_window.clone(),
+                pause_windows: args.pause_window.clone(),
+                idle_cpu_threshold: args.idle_cpu_threshold,
+                idle_resume_delay: Some(args.idle_resume_delay),
+            },
+            logging: LoggingConfig {
+                dir: args.log_dir.clone(),
+            },
+        })
+    }
+
+    /// Fill in every argument that was not given on the command line
+    pub fn apply(&self, args: &mut Args, matches: &ArgMatches) -> Result<(), String> {
+        let from_cli = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+
+        // Sets `args.$field` from the file unless the flag was on the command line
+        macro_rules! layer {
+            ($field:ident, $value:expr) => {
+                if let Some(value) = $value.filter(|_| !from_cli(stringify!($field))) {
+                    args.$field = value;
+                }
+            };
+        }
+
+        layer!(wallet, self.wallet.clone().map(Some));
+        layer!(worker, self.worker.clone());
+        layer!(password, self.password.clone());
+        layer!(algo, self.algo.clone());
+        layer!(threads, self.threads);
+        if !self.pools.is_empty() && !from_cli("pool") {
+            args.pool = self.pools.iter().map(PoolConfig::to_spec).collect();
+        }
+
+        let connection = &self.connection;
+        layer!(proxy, connection.proxy.clone().map(Some));
+        layer!(tls_insecure, connection.tls_insecure);
+        layer!(pool_retries, connection.retries);
+        layer!(pool_max_backoff, connection.max_backoff);
+        layer!(pool_failback_interval, connection.failback_interval);
+        layer!(proxy_listen, connection.proxy_listen.clone().map(Some));
+
+        layer!(web, self.web.enabled);
+        layer!(ws, self.web.port);
+        layer!(web_bind, self.web.bind);
+        layer!(api_token, self.web.api_token.clone().map(Some));
+        layer!(api_auth, self.web.api_auth.clone().map(Some));
+        layer!(metrics, self.web.metrics);
+
+        layer!(cpu_temp_limit, self.cpu.temp_limit.map(Some));
+        layer!(cpu_reserve_cores, self.cpu.reserve_cores.map(Some));
+        layer!(cpu_backend, self.cpu.backend.clone());
+
+        let schedule = &self.schedule;
+        layer!(
+            mine_window,
+            Some(schedule.mine_windows.clone()).filter(|w| !w.is_empty())
+        );
+        layer!(
+            pause_window,
+            Some(schedule.pause_windows.clone()).filter(|w| !w.is_empty())
+        );
+        layer!(idle_cpu_threshold, schedule.idle_cpu_threshold.map(Some));
+        layer!(idle_resume_delay, schedule.idle_resume_delay);
+        layer!(log_dir, self.logging.dir.clone().map(Some));
+
+        #[cfg(any(feature = "gpu", feature = "hybrid"))]
+        {
+            let gpu = &self.gpu;
+            layer!(gpu_intensity, gpu.intensity);
+            layer!(gpu_batch_size, gpu.batch_size.map(Some));
+            layer!(gpu_power_limit, gpu.power_limit.map(Some));
+            layer!(gpu_temp_limit, gpu.temp_limit.map(Some));
+            layer!(gpu_temp_cutoff, gpu.temp_cutoff.map(Some));
+            layer!(gpu_hw_error_limit, gpu.hw_error_limit.map(Some));
+            layer!(gpu_work_groups_per_cu, gpu.work_groups_per_cu.map(Some));
+            layer!(autotune, gpu.autotune);
+            layer!(autotune_duration, gpu.autotune_duration);
+            layer!(
+                excluded_devices,
+                gpu.excluded_devices.as_ref().map(|ids| {
+                    Some(ids.iter().map(u32::to_string).collect::<Vec<_>>().join(","))
+                })
+            );
+            layer!(
+                information_file_dir,
+                gpu.information_file_dir.clone().map(Some)
+            );
+
+            // Device overrides add to the --gpu-device ones; the command line wins
+            for (key, device) in &gpu.devices {
+                let selector = GpuDeviceSelector::parse(key)?;
+                if !args.gpu_devices.iter().any(|(s, _)| *s == selector) {
+                    args.gpu_devices.push((selector, device.clone()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl PoolConfig {
+    /// The equivalent --pool spec
+    pub fn to_spec(&self) -> String {
+        let mut spec = self.url.clone();
+        let options = [
+            ("priority", self.priority.map(|p| p.to_string())),
+            ("wallet", self.wallet.clone()),
+            ("worker", self.worker.clone()),
+            ("password", self.password.clone()),
+            ("fingerprint", self.fingerprint.clone()),
+            ("insecure", self.insecure.map(|s| s.to_string())),
+        ];
+        for (key, value) in options
+            .into_iter()
+            .filter_map(|(key, value)| Some((key, value?)))
+        {
+            spec.push_str(&format!(",{}={}", key, value));
+        }
+        spec
+    }
+
+    /// Split a --pool spec into its URL and options
+    pub fn from_spec(spec: &str) -> Result<Self, String> {
+        let mut parts = spec.split(',');
+        let mut pool = Self {
+            url: parts.next().unwrap_or_default().trim().to_string(),
+            ..Self::default()
+        };
+        for option in parts {
+            let (key, value) = option
+                .split_once('=')
+                .ok_or_else(|| format!("Pool option '{}' must be KEY=VALUE", option))?;
+            let value = value.trim().to_string();
+            match key.trim() {
+                "priority" => {
+                    pool.priority = Some(value.parse().map_err(|_| {
+                        format!("Pool priority must be a number. Found: '{}'", value)
+                    })?)
+                }
+                "wallet" => pool.wallet = Some(value),
+                "worker" => pool.worker = Some(value),
+                "password" => pool.password = Some(value),
+                "fingerprint" => pool.fingerprint = Some(value),
+                "insecure" => {
+                    pool.insecure = Some(value.parse().map_err(|_| {
+                        format!(
+                            "Pool option insecure must be true or false. Found: '{}'",
+                            value
+                        )
+                    })?)
+                }
+                other => return Err(format!("Unknown pool option '{}'", other)),
+            }
+        }
+        Ok(pool)
+    }
+}
+
+impl GpuDeviceConfig {
+    /// These settings, with unset fields taken from `fallback`
+    pub fn or(&self, fallback: &GpuDeviceConfig) -> GpuDeviceConfig {
+        GpuDeviceConfig {
+            intensity: self.intensity.or(fallback.intensity),
+            batch_size: self.batch_size.or(fallback.batch_size),
+            power_limit: self.power_limit.or(fallback.power_limit),
+            temp_limit: self.temp_limit.or(fallback.temp_limit),
+            work_groups_per_cu: self.work_groups_per_cu.or(fallback.work_groups_per_cu),
+        }
+    }
+
+    /// Device settings on top of the global GPU settings, clamped like the CLI values
+    pub fn resolve(&self, base: &GpuSettings) -> GpuSettings {
+        GpuSettings {
+            intensity: self.intensity.unwrap_or(base.intensity).min(100),
+            batch_size: self
+                .batch_size
+                .or(base.batch_size)
+                .map(|b| b.clamp(1_000, 1_000_000)),
+            power_limit: self
+                .power_limit
+                .or(base.power_limit)
+                .map(|p| p.clamp(50, 110)),
+            temp_limit: self.temp_limit.or(base.temp_limit).map(|t| t.clamp(60, 85)),
+            work_groups_per_cu: self
+                .work_groups_per_cu
+                .or(base.work_groups_per_cu)
+                .map(|w| w.clamp(1, 64)),
+        }
+    }
+
+    pub fn validate(&self, device: &GpuDeviceSelector) -> Result<(), String> {
+        if self.intensity.is_some_and(|i| i > 100) {
+            return Err(format!("GPU {} intensity must be between 0-100%", device));
+        }
+        if self
+            .batch_size
+            .is_some_and(|b| !(1_000..=1_000_000).contains(&b))
+        {
+            return Err(format!(
+                "GPU {} batch size must be between 1,000 and 1,000,000",
+                device
+            ));
+        }
+        if self.power_limit.is_some_and(|p| !(50..=110).contains(&p)) {
+            return Err(format!(
+                "GPU {} power limit must be between 50-110%",
+                device
+            ));
+        }
+        if self.temp_limit.is_some_and(|t| !(60..=85).contains(&t)) {
+            return Err(format!(
+                "GPU {} temperature limit must be between 60-85°C",
+                device
+            ));
+        }
+        if self
+            .work_groups_per_cu
+            .is_some_and(|w| !(1..=64).contains(&w))
+        {
+            return Err(format!(
+                "GPU {} work groups per compute unit must be between 1-64",
+                device
+            ));
+        }
+        Ok(())
+    }
+
+    /// Parse a --gpu-device spec: `SELECTOR:key=value,...`, e.g.
+    /// `1:intensity=60,temp_limit=70` or `*Intel*:intensity=40`
+    pub fn from_spec(spec: &str) -> Result<(GpuDeviceSelector, Self), String> {
+        let (selector, options) = spec.rsplit_once(':').ok_or_else(|| {
+            format!(
+                "GPU device '{}' must be SELECTOR:KEY=VALUE,... (e.g. 1:intensity=60)",
+                spec
+            )
+        })?;
+        let selector = GpuDeviceSelector::parse(selector)?;
+
+        let mut device = Self::default();
+        for option in options.split(',').filter(|o| !o.trim().is_empty()) {
+            let (key, value) = option
+                .split_once('=')
+                .ok_or_else(|| format!("GPU device option '{}' must be KEY=VALUE", option))?;
+            let value = value.trim();
+            let number = |name: &str| {
+                format!(
+                    "GPU device option {} must be a number. Found: '{}'",
+                    name, value
+                )
+            };
+            match key.trim() {
+                "intensity" => device.intensity = Some(value.parse().map_err(|_| number(key))?),
+                "batch_size" => device.batch_size = Some(value.parse().map_err(|_| number(key))?),
+                "power_limit" => device.power_limit = Some(value.parse().map_err(|_| number(key))?),
+                "temp_limit" => device.temp_limit = Some(value.parse().map_err(|_| number(key))?),
+                "work_groups_per_cu" => {
+                    device.work_groups_per_cu = Some(value.parse().map_err(|_| number(key))?)
+                }
+                other => return Err(format!("Unknown GPU device option '{}'", other)),
+            }
+        }
+        Ok((selector, device))
+    }
+}
+
+impl GpuDeviceSelector {
+    /// A `[gpu.devices.KEY]` key or --gpu-device selector: a number is a device
+    /// index, anything else a name pattern
+    pub fn parse(key: &str) -> Result<Self, String> {
+        let key = key.trim();
+        if key.is_empty() {
+            return Err("GPU device selector must be a device index or a name pattern".to_string());
+        }
+        Ok(match key.parse::<u32>() {
+            Ok(index) => Self::Index(index),
+            Err(_) => Self::Name(key.to_string()),
+        })
+    }
+
+    pub fn matches(&self, device_id: u32, device_name: &str) -> bool {
+        match self {
+            Self::Index(index) => *index == device_id,
+            Self::Name(pattern) => name_matches(pattern, device_name),
+        }
+    }
+
+    /// The config file key for this selector
+    pub fn key(&self) -> String {
+        match self {
+            Self::Index(index) => index.to_string(),
+            Self::Name(pattern) => pattern.clone(),
+        }
+    }
+}
+
+impl fmt::Display for GpuDeviceSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Index(index) => write!(f, "{}", index),
+            Self::Name(pattern) => write!(f, "'{}'", pattern),
+        }
+    }
+}
+
+/// Case-insensitive glob match where `*` matches any run of characters
+fn name_matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let name = name.to_lowercase();
+    if !pattern.contains('*') {
+        return name.contains(&pattern);
+    }
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !name.starts_with(first) || name.len() < first.len() + last.len() || !name.ends_with(last) {
+        return false;
+    }
+    let mut rest = &name[first.len()..name.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// The override for one GPU: an index match wins over name patterns, otherwise
+/// the first matching entry
+pub fn matching_override<'a>(
+    overrides: &'a [(GpuDeviceSelector, GpuDeviceConfig)],
+    device_id: u32,
+    device_name: &str,
+) -> Option<&'a GpuDeviceConfig> {
+    overrides
+        .iter()
+        .find(|(selector, _)| *selector == GpuDeviceSelector::Index(device_id))
+        .or_else(|| {
+            overrides
+                .iter()
+                .find(|(selector, _)| selector.matches(device_id, device_name))
+        })
+        .map(|(_, device)| device)
+}
+
+/// Settings for one GPU: the global settings, then the settings `stored` for it
+/// in the GPU information file, then the matching override on top
+pub fn resolve_device_settings(
+    global: &GpuSettings,
+    overrides: &[(GpuDeviceSelector, GpuDeviceConfig)],
+    stored: Option<&GpuDeviceConfig>,
+    device_id: u32,
+    device_name: &str,
+) -> GpuSettings {
+    let base = stored
+        .map(|device| device.resolve(global))
+        .unwrap_or_else(|| global.clone());
+    matching_override(overrides, device_id, device_name)
+        .map(|device| device.resolve(&base))
+        .unwrap_or(base)
+}
+
+impl Args {
+    /// Parse the command line and layer it over the --config file, if any.
+    /// Exits on --help / --version and command-line syntax errors like `Args::parse`.
+    pub fn load() -> Result<Self, String> {
+        Self::from_matches(&Self::command().get_matches())
+    }
+
+    /// Build arguments from already parsed command-line matches plus --config
+    pub fn from_matches(matches: &ArgMatches) -> Result<Self, String> {
+        let mut args = Self::from_arg_matches(matches).map_err(|e| e.to_string())?;
+        #[cfg(any(feature = "gpu", feature = "hybrid"))]
+        for spec in &args.gpu_device {
+            let (selector, device) = GpuDeviceConfig::from_spec(spec)?;
+            args.gpu_devices.retain(|(s, _)| *s != selector);
+            args.gpu_devices.push((selector, device));
+        }
+        if let Some(path) = args.config.clone() {
+            MinerConfig::load(&path)?.apply(&mut args, matches)?;
+        }
+        Ok(args)
+    }
+}
+
+/// Parse a comma-separated device id list, skipping entries that are not numbers
+pub fn parse_device_list(list: &str) -> Vec<u32> {
+    list.split(',')
+        .filter_map(|s| s.trim().parse::<u32>().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::keccak_simd::CpuBackend;
+    use std::io::Write;
+
+    const WALLET: &str = "125ohcEDcG8sL4DcdtqZ6YLbSgVYFJWtGeCfHmRocTcyGNYRqMYidnfs1JQPijqQvqV5SLygC5ynxZH3zED5Rr9fPAW";
+
+    fn config_file(contents: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        file
+    }
+
+    fn load(file: &tempfile::NamedTempFile, extra: &[&str]) -> Result<Args, String> {
+        let path = file.path().to_str().unwrap();
+        let mut argv = vec!["graxil", "--config", path];
+        argv.extend_from_slice(extra);
+        Args::from_matches(&Args::command().try_get_matches_from(argv).unwrap())
+    }
+
+    #[test]
+    fn test_file_values_fill_in_defaults() {
+        let file = config_file(&format!(
+            r#"
+            wallet = "{WALLET}"
+            worker = "rig-07"
+            threads = 12
+
+            [[pools]]
+            url = "stratum+ssl://pool.example:4201"
+            insecure = true
+
+            [[pools]]
+            url = "backup.example:4200"
+            priority = 5
+            worker = "backup-rig"
+
+            [connection]
+            retries = 7
+
+            [web]
+            enabled = true
+            port = 9090
+            "#
+        ));
+        let args = load(&file, &[]).unwrap();
+
+        assert_eq!(args.wallet.as_deref(), Some(WALLET));
+        assert_eq!(args.worker, "rig-07");
+        assert_eq!(args.threads, 12);
+        assert_eq!(
+            args.pool,
+            vec![
+                "stratum+ssl://pool.example:4201,insecure=true".to_string(),
+                "backup.example:4200,priority=5,worker=backup-rig".to_string(),
+            ]
+        );
+        assert_eq!(args.pool_retries, 7);
+        assert!(args.web);
+        assert_eq!(args.ws, 9090);
+        // Untouched keys keep their clap defaults
+        assert_eq!(args.password, "x");
+        args.validate().unwrap();
+    }
+
+    #[test]
+    fn test_command_line_overrides_file() {
+        let file = config_file(&format!(
+            r#"
+            wallet = "{WALLET}"
+            worker = "from-file"
+            threads = 12
+            pools = [{{ url = "file.example:4200" }}]
+            "#
+        ));
+        let args = load(
+            &file,
+            &[
+                "--worker",
+                "from-cli",
+                "-o",
+                "cli.example:4200",
+                "--threads",
+                "0",
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(args.worker, "from-cli");
+        assert_eq!(args.pool, vec!["cli.example:4200".to_string()]);
+        // Explicitly passing the default value still overrides the file
+        assert_eq!(args.threads, 0);
+        assert_eq!(args.wallet.as_deref(), Some(WALLET));
+    }
+
+    #[test]
+    fn test_merged_result_is_validated() {
+        let file = config_file("pools = [{ url = \"pool.example:4200\" }]\n");
+        let args = load(&file, &[]).unwrap();
+        assert!(args.validate().unwrap_err().contains("Wallet address"));
+
+        let args = load(&file, &["-u", WALLET]).unwrap();
+        args.validate().unwrap();
+    }
+
+    #[test]
+    fn test_bad_files_are_rejected() {
+        let file = config_file("walet = \"typo\"\n");
+        let err = load(&file, &[]).unwrap_err();
+        assert!(err.contains("Invalid config file"), "{}", err);
+
+        let err = Args::from_matches(
+            &Args::command()
+                .try_get_matches_from(["graxil", "--config", "/nonexistent/graxil.toml"])
+                .unwrap(),
+        )
+        .unwrap_err();
+        assert!(err.contains("Cannot read config file"), "{}", err);
+    }
+
+    #[test]
+    fn test_print_config_round_trips() {
+        let file = config_file(&format!(
+            r#"
+            wallet = "{WALLET}"
+            pools = [{{ url = "pool.example:4200", priority = 2, password = "secret" }}]
+
+            [connection]
+            proxy = "socks5h://127.0.0.1:9050"
+
+            [logging]
+            dir = "/var/log/graxil"
+            "#
+        ));
+        let args = load(&file, &["--worker", "rig-02"]).unwrap();
+        let effective = MinerConfig::from_args(&args).unwrap();
+        let printed = effective.to_toml().unwrap();
+
+        let reparsed = MinerConfig::parse(&printed).unwrap();
+        assert_eq!(reparsed, effective);
+        assert_eq!(reparsed.worker.as_deref(), Some("rig-02"));
+        assert_eq!(reparsed.pools[0].password.as_deref(), Some("secret"));
+
+        // Feeding the printed config back in reproduces the same arguments
+        let again = load(&config_file(&printed), &[]).unwrap();
+        assert_eq!(MinerConfig::from_args(&again).unwrap(), effective);
+    }
+
+    #[test]
+    fn test_schedule_and_cpu_sections() {
+        let file = config_file(&format!(
+            r#"
+            wallet = "{WALLET}"
+            pools = [{{ url = "pool.example:4200" }}]
+
+            [cpu]
+            temp_limit = 85
+            reserve_cores = 2
+            backend = "scalar"
+
+            [schedule]
+            pause_windows = ["Mon-Fri 08:00-18:00"]
+            idle_cpu_threshold = 30
+            "#
+        ));
+        let args = load(&file, &["--mine-window", "Sat,Sun 00:00-24:00"]).unwrap();
+        assert_eq!(args.pause_window, vec!["Mon-Fri 08:00-18:00".to_string()]);
+        assert_eq!(args.mine_window, vec!["Sat,Sun 00:00-24:00".to_string()]);
+        assert_eq!(args.idle_cpu_threshold, Some(30));
+        assert_eq!(
+            (args.cpu_temp_limit, args.cpu_reserve_cores),
+            (Some(85), Some(2))
+        );
+        assert_eq!(args.sha3x_backend(), Ok(CpuBackend::Scalar));
+        args.validate().unwrap();
+
+        let args = load(&file, &["--pause-window", "Mon-Fri 8-18"]).unwrap();
+        assert!(args.validate().unwrap_err().contains("Invalid time"));
+    }
+
+    #[test]
+    fn test_sample_config_parses() {
+        MinerConfig::parse(include_str!("../../graxil_sample.toml")).unwrap();
+    }
+
+    #[cfg(any(feature = "gpu", feature = "hybrid"))]
+    #[test]
+    fn test_gpu_device_overrides() {
+        let file = config_file(
+            r#"
+            [gpu]
+            intensity = 90
+            batch_size = 200000
+            excluded_devices = [3]
+
+            [gpu.devices.1]
+            intensity = 60
+            temp_limit = 70
+            "#,
+        );
+        let args = load(&file, &["--gpu-batch-size", "50000"]).unwrap();
+        assert_eq!(args.gpu_intensity, 90);
+        assert_eq!(args.gpu_batch_size, Some(50_000));
+        assert_eq!(args.excluded_devices.as_deref(), Some("3"));
+
+        let integrated = args.get_device_gpu_settings(1, "Intel(R) UHD Graphics 770");
+        assert_eq!(integrated.intensity, 60);
+        assert_eq!(integrated.temp_limit, Some(70));
+        // Unset device fields inherit the merged global settings
+        assert_eq!(integrated.batch_size, Some(50_000));
+        assert_eq!(args.get_device_gpu_settings(0, "RTX 4060").intensity, 90);
+    }
+
+    #[cfg(any(feature = "gpu", feature = "hybrid"))]
+    #[test]
+    fn test_gpu_device_selectors() {
+        let file = config_file(
+            r#"
+            [gpu]
+            intensity = 90
+
+            [gpu.devices."*Intel*"]
+            intensity = 40
+            work_groups_per_cu = 2
+
+            [gpu.devices."RTX 4060"]
+            intensity = 95
+
+            [gpu.devices.2]
+            intensity = 70
+            "#,
+        );
+        let args = load(
+            &file,
+            &["--gpu-device", "RTX 4060:intensity=80,temp_limit=72"],
+        )
+        .unwrap();
+        args.validate().unwrap();
+
+        let igpu = args.get_device_gpu_settings(0, "Intel(R) UHD Graphics 770");
+        assert_eq!((igpu.intensity, igpu.work_groups_per_cu), (40, Some(2)));
+        // --gpu-device replaces the config entry with the same selector
+        let rtx = args.get_device_gpu_settings(1, "NVIDIA GeForce RTX 4060 Ti");
+        assert_eq!((rtx.intensity, rtx.temp_limit), (80, Some(72)));
+        assert_eq!(args.gpu_devices.len(), 3);
+        // An index match wins over a name pattern
+        assert_eq!(
+            args.get_device_gpu_settings(2, "NVIDIA GeForce RTX 4060")
+                .intensity,
+            70
+        );
+        assert_eq!(
+            args.get_device_gpu_settings(4, "AMD Radeon RX 7900")
+                .intensity,
+            90
+        );
+
+        let bad = config_file("[gpu.devices.0]\nwork_groups_per_cu = 100\n");
+        let err = load(&bad, &["-u", WALLET, "-o", "pool.example:4200"])
+            .unwrap()
+            .validate()
+            .unwrap_err();
+        assert!(err.contains("work groups"), "{}", err);
+        assert!(GpuDeviceConfig::from_spec("1:speed=9").is_err());
+        assert!(GpuDeviceConfig::from_spec("intensity=60").is_err());
+    }
+
+    #[test]
+    fn test_device_name_patterns() {
+        assert!(name_matches("rtx", "NVIDIA GeForce RTX 4060"));
+        assert!(name_matches("NVIDIA*4060", "NVIDIA GeForce RTX 4060"));
+        assert!(name_matches("*4060", "NVIDIA GeForce RTX 4060"));
+        assert!(!name_matches("*4060", "NVIDIA GeForce RTX 4060 Ti"));
+        assert!(name_matches("*geforce*ti", "NVIDIA GeForce RTX 4060 Ti"));
+        assert!(!name_matches("AMD*", "NVIDIA GeForce RTX 4060"));
+        assert!(!name_matches("a*a", "a"));
+        assert_eq!(
+            GpuDeviceSelector::parse(" 3 ").unwrap(),
+            GpuDeviceSelector::Index(3)
+        );
+        assert!(GpuDeviceSelector::parse("").is_err());
+    }
+}
+
+// Changelog:
+// - v1.9.1 (2025-06-28): Pool insecure and [connection] tls_insecure replace self_signed and
+//   tls_accept_self_signed (they turn certificate verification off).
+// - v1.9.0 (2025-06-28): hw_error_limit in [gpu] (--gpu-hw-error-limit).
+// - v1.8.0 (2025-06-28): [cpu] backend selects the SHA3x CPU backend (--cpu-backend).
+// - v1.7.0 (2025-06-28): [cpu] temp_limit and reserve_cores for the CPU governor.
+// - v1.6.0 (2025-06-28): temp_cutoff in [gpu] for the GPU thermal governor.
+// - v1.5.0 (2025-06-28): [schedule] mining / pause windows and idle detection.
+// - v1.4.0 (2025-06-28): metrics in [web] for the Prometheus endpoint.
+// - v1.3.0 (2025-06-28): bind, api_token and api_auth in [web] for the HTTP API.
+// - v1.2.0 (2025-06-28): autotune / autotune_duration in [gpu]; GpuDeviceConfig::or()
+//   merges stored information file settings with autotune results.
+// - v1.1.0 (2025-06-28): Per-device GPU settings by index or name pattern.
+//   - GpuDeviceSelector keys for [gpu.devices.KEY] and --gpu-device SELECTOR:key=value specs.
+//   - work_groups_per_cu in [gpu] and per device.
+//   - resolve_device_settings() layers global, stored (information file) and override settings.
+// - v1.0.0 (2025-06-28): Initial TOML config file support.
+//   - --config FILE layered under the command line (explicit flags win).
+//   - [[pools]], [connection], [gpu] with [gpu.devices.N] overrides, [web], [logging].
+//   - MinerConfig::from_args / to_toml back --print-config.
@@ -6,13 +6,15 @@
 // via pull requests to the project repository.
 //
 // File: src/core/difficulty.rs
-// Version: 1.2.10
+// Version: 1.3.0
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // This file contains functions for calculating difficulty and parsing target
 // difficulty from pool-provided hex strings, located in the core subdirectory
 // of the SHA3x miner source tree. It supports SHA3x (Tari) with u64 and
-// SHA-256 (Bitcoin) with 256-bit precision.
+// SHA-256 (Bitcoin) with 256-bit precision. Mining threads compare the leading
+// word of each SHA3x hash against sha3x_share_threshold() and only compute the
+// exact difficulty of hashes that pass.
 
 use crate::core::types::Algorithm;
 use hex;
@@ -105,6 +107,21 @@ pub fn parse_target_difficulty(target_hex: &str, algo: Algorithm) -> u64 {
     }
 }
 
+/// Leading big-endian word of a SHA3x hash, the only part its difficulty depends on
+#[inline]
+pub fn sha3x_hash_word(hash: &[u8; 32]) -> u64 {
+    u64::from_be_bytes([
+        hash[0], hash[1], hash[2], hash[3], hash[4], hash[5], hash[6], hash[7],
+    ])
+}
+
+/// Largest SHA3x hash word that still reaches `target_difficulty`:
+/// `calculate_difficulty(hash) >= target_difficulty` exactly when
+/// `sha3x_hash_word(hash) <= threshold`, without a division per hash
+pub fn sha3x_share_threshold(target_difficulty: u64) -> u64 {
+    u64::MAX.checked_div(target_difficulty).unwrap_or(u64::MAX)
+}
+
 pub fn calculate_difficulty(hash: &[u8], algo: Algorithm) -> u64 {
     match algo {
         Algorithm::Sha3x => {
@@ -127,7 +144,7 @@ pub fn calculate_difficulty(hash: &[u8], algo: Algorithm) -> u64 {
                 warn!(target: LOG_TARGET,"Invalid SHA-256 hash: wrong length ({} bytes)", hash.len());
                 return 0;
             }
-            let hash_value = U256::from_big_endian(hash); // Bitcoin hashes are big-endian in logs
+            let hash_value = U256::from_little_endian(hash); // SHA-256d output is a little-endian number
             if hash_value.is_zero() {
                 warn!(target: LOG_TARGET,"Invalid SHA-256 hash: all zeros");
                 return 0;
@@ -172,7 +189,7 @@ pub fn hash_meets_target(hash: &[u8], target: U256) -> bool {
         );
         return false;
     }
-    let hash_value = U256::from_big_endian(hash);
+    let hash_value = U256::from_little_endian(hash);
     debug!(target: LOG_TARGET,
         "Hash check: hash={:064x}, target={:064x}",
         hash_value, target
@@ -198,7 +215,59 @@ pub fn bits_to_target(bits: u32) -> U256 {
     result
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_with_word(word: u64) -> [u8; 32] {
+        let mut hash = [0xa5u8; 32];
+        hash[..8].copy_from_slice(&word.to_be_bytes());
+        hash
+    }
+
+    #[test]
+    fn test_share_threshold_matches_difficulty() {
+        for target in [
+            0,
+            1,
+            2,
+            3,
+            1000,
+            0x1234_5678,
+            u64::MAX / 2,
+            u64::MAX - 1,
+            u64::MAX,
+        ] {
+            let threshold = sha3x_share_threshold(target);
+            let words = [
+                0,
+                1,
+                threshold.saturating_sub(1),
+                threshold,
+                threshold.saturating_add(1),
+                0x0000_0fff_ffff_ffff,
+                u64::MAX,
+            ];
+            for word in words {
+                let hash = hash_with_word(word);
+                assert_eq!(
+                    sha3x_hash_word(&hash) <= threshold,
+                    calculate_difficulty(&hash, Algorithm::Sha3x) >= target,
+                    "target {}, hash word {:#x}",
+                    target,
+                    word
+                );
+            }
+        }
+    }
+}
+
 // Changelog:
+// - v1.3.0 (2025-06-28): Added sha3x_hash_word() and sha3x_share_threshold() so
+//   mining loops can reject SHA3x hashes without a division.
+// - v1.2.11 (2025-06-28): SHA-256d hashes are read as little-endian numbers in
+//   calculate_difficulty and hash_meets_target, matching Bitcoin block hash
+//   semantics (targets stay big-endian).
 // - v1.2.10 (2025-06-19): Fixed SHA-256 target calculation for share validation.
 //   - Changed parse_target_difficulty to use from_big_endian for SHA-256 targets.
 //   - Updated calculate_difficulty to use from_big_endian for SHA-256 hashes.
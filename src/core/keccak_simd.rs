@@ -0,0 +1,574 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/core/keccak_simd.rs
+// Version: 1.0.0
+// Developer: OIEIEIO <oieieio@protonmail.com>
+//
+// This file implements the multi-way Keccak-f[1600] used by the CPU SHA3x
+// backends. Each state lane is a vector register holding the same lane of 4
+// (AVX2, NEON) or 8 (AVX-512) independent hashes, so every Keccak step runs on
+// all of them at once. The backend is picked at runtime from the CPU features
+// (--cpu-backend), and the scalar keccak crate path stays the fallback.
+//
+// Tree Location:
+// - src/core/keccak_simd.rs (multi-way Keccak and CPU backend selection)
+// - Depends on: std::arch feature detection
+
+use std::fmt;
+use std::str::FromStr;
+
+/// SHA3-256 rate in 64-bit lanes
+const RATE_LANES: usize = 17;
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000_0000_0000_0001,
+    0x0000_0000_0000_8082,
+    0x8000_0000_0000_808a,
+    0x8000_0000_8000_8000,
+    0x0000_0000_0000_808b,
+    0x0000_0000_8000_0001,
+    0x8000_0000_8000_8081,
+    0x8000_0000_0000_8009,
+    0x0000_0000_0000_008a,
+    0x0000_0000_0000_0088,
+    0x0000_0000_8000_8009,
+    0x0000_0000_8000_000a,
+    0x0000_0000_8000_808b,
+    0x8000_0000_0000_008b,
+    0x8000_0000_0000_8089,
+    0x8000_0000_0000_8003,
+    0x8000_0000_0000_8002,
+    0x8000_0000_0000_0080,
+    0x0000_0000_0000_800a,
+    0x8000_0000_8000_000a,
+    0x8000_0000_8000_8081,
+    0x8000_0000_0000_8080,
+    0x0000_0000_8000_0001,
+    0x8000_0000_8000_8008,
+];
+
+/// Rho rotations, in the order the pi step visits the lanes
+const RHO: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+/// Pi step lane order, starting from lane 1
+const PI: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// SHA3x hashing implementation of the CPU miner
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CpuBackend {
+    /// Fastest backend the CPU supports
+    #[default]
+    Auto,
+    /// One hash at a time (keccak crate)
+    Scalar,
+    /// 4 hashes per call on x86_64 with AVX2
+    Avx2,
+    /// 8 hashes per call on x86_64 with AVX-512F
+    Avx512,
+    /// 4 hashes per call on aarch64
+    Neon,
+}
+
+impl CpuBackend {
+    pub const ALL: [CpuBackend; 5] = [
+        CpuBackend::Auto,
+        CpuBackend::Scalar,
+        CpuBackend::Avx2,
+        CpuBackend::Avx512,
+        CpuBackend::Neon,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CpuBackend::Auto => "auto",
+            CpuBackend::Scalar => "scalar",
+            CpuBackend::Avx2 => "avx2",
+            CpuBackend::Avx512 => "avx512",
+            CpuBackend::Neon => "neon",
+        }
+    }
+
+    /// Hashes computed side by side
+    pub fn ways(self) -> usize {
+        match self {
+            CpuBackend::Avx512 => 8,
+            CpuBackend::Avx2 | CpuBackend::Neon => 4,
+            CpuBackend::Auto | CpuBackend::Scalar => 1,
+        }
+    }
+
+    /// Whether this CPU can run the backend
+    pub fn is_supported(self) -> bool {
+        match self {
+            CpuBackend::Auto | CpuBackend::Scalar => true,
+            #[cfg(target_arch = "x86_64")]
+            CpuBackend::Avx2 => std::arch::is_x86_feature_detected!("avx2"),
+            #[cfg(target_arch = "x86_64")]
+            CpuBackend::Avx512 => std::arch::is_x86_feature_detected!("avx512f"),
+            #[cfg(target_arch = "aarch64")]
+            CpuBackend::Neon => std::arch::is_aarch64_feature_detected!("neon"),
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// Fastest backend this CPU supports
+    pub fn detect() -> Self {
+        [CpuBackend::Avx512, CpuBackend::Avx2, CpuBackend::Neon]
+            .into_iter()
+            .find(|backend| backend.is_supported())
+            .unwrap_or(CpuBackend::Scalar)
+    }
+
+    /// The backend to run: `Auto` picks the fastest one, and backends this CPU
+    /// does not support fall back to `Scalar`
+    pub fn resolve(self) -> Self {
+        match self {
+            CpuBackend::Auto => Self::detect(),
+            backend if backend.is_supported() => backend,
+            _ => CpuBackend::Scalar,
+        }
+    }
+}
+
+impl fmt::Display for CpuBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for CpuBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|backend| backend.name() == name)
+            .ok_or_else(|| {
+                format!(
+                    "Invalid CPU backend '{}': use auto, scalar, avx2, avx512 or neon",
+                    s
+                )
+            })
+    }
+}
+
+/// Repeat a block with `$var` bound to the constants 0..5
+macro_rules! unroll5 {
+    ($var:ident, $body:block) => {
+        unroll5!(@ $var, 0, $body);
+        unroll5!(@ $var, 1, $body);
+        unroll5!(@ $var, 2, $body);
+        unroll5!(@ $var, 3, $body);
+        unroll5!(@ $var, 4, $body);
+    };
+    (@ $var:ident, $value:expr, $body:block) => {{
+        #[allow(non_upper_case_globals)]
+        const $var: usize = $value;
+        $body
+    }};
+}
+
+/// Repeat a block with `$var` bound to the constants 0..24
+macro_rules! unroll24 {
+    ($var:ident, $body:block) => {
+        unroll5!(i, { unroll5!(@ $var, i, $body) });
+        unroll5!(i, { unroll5!(@ $var, i + 5, $body) });
+        unroll5!(i, { unroll5!(@ $var, i + 10, $body) });
+        unroll5!(i, { unroll5!(@ $var, i + 15, $body) });
+        unroll5!(@ $var, 20, $body);
+        unroll5!(@ $var, 21, $body);
+        unroll5!(@ $var, 22, $body);
+        unroll5!(@ $var, 23, $body);
+    };
+}
+
+/// The same Keccak lane of `WAYS` independent states; each operation works on
+/// all of them at once
+trait KeccakLane: Copy {
+    const WAYS: usize;
+
+    fn splat(value: u64) -> Self;
+    /// Load `WAYS` words, one per state
+    fn load(words: &[u64]) -> Self;
+    fn store(self, words: &mut [u64]);
+    fn xor(self, other: Self) -> Self;
+    /// `!self & other`
+    fn and_not(self, other: Self) -> Self;
+    fn rotate_left(self, n: u32) -> Self;
+}
+
+/// One state: the scalar fallback of targets without a SIMD backend
+impl KeccakLane for u64 {
+    const WAYS: usize = 1;
+
+    #[inline(always)]
+    fn splat(value: u64) -> Self {
+        value
+    }
+
+    #[inline(always)]
+    fn load(words: &[u64]) -> Self {
+        words[0]
+    }
+
+    #[inline(always)]
+    fn store(self, words: &mut [u64]) {
+        words[0] = self;
+    }
+
+    #[inline(always)]
+    fn xor(self, other: Self) -> Self {
+        self ^ other
+    }
+
+    #[inline(always)]
+    fn and_not(self, other: Self) -> Self {
+        !self & other
+    }
+
+    #[inline(always)]
+    fn rotate_left(self, n: u32) -> Self {
+        u64::rotate_left(self, n)
+    }
+}
+
+// SAFETY (all SIMD lane types): the lane operations are only reached from the
+// #[target_feature] functions below, which are only called after runtime
+// detection of the feature (see sha3x_lanes).
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use super::KeccakLane;
+    use std::arch::x86_64::*;
+
+    impl KeccakLane for __m256i {
+        const WAYS: usize = 4;
+
+        #[inline(always)]
+        fn splat(value: u64) -> Self {
+            unsafe { _mm256_set1_epi64x(value as i64) }
+        }
+
+        #[inline(always)]
+        fn load(words: &[u64]) -> Self {
+            assert!(words.len() >= Self::WAYS);
+            unsafe { _mm256_loadu_si256(words.as_ptr().cast()) }
+        }
+
+        #[inline(always)]
+        fn store(self, words: &mut [u64]) {
+            assert!(words.len() >= Self::WAYS);
+            unsafe { _mm256_storeu_si256(words.as_mut_ptr().cast(), self) }
+        }
+
+        #[inline(always)]
+        fn xor(self, other: Self) -> Self {
+            unsafe { _mm256_xor_si256(self, other) }
+        }
+
+        #[inline(always)]
+        fn and_not(self, other: Self) -> Self {
+            unsafe { _mm256_andnot_si256(self, other) }
+        }
+
+        #[inline(always)]
+        fn rotate_left(self, n: u32) -> Self {
+            unsafe {
+                _mm256_or_si256(
+                    _mm256_sll_epi64(self, _mm_cvtsi32_si128(n as i32)),
+                    _mm256_srl_epi64(self, _mm_cvtsi32_si128(64 - n as i32)),
+                )
+            }
+        }
+    }
+
+    impl KeccakLane for __m512i {
+        const WAYS: usize = 8;
+
+        #[inline(always)]
+        fn splat(value: u64) -> Self {
+            unsafe { _mm512_set1_epi64(value as i64) }
+        }
+
+        #[inline(always)]
+        fn load(words: &[u64]) -> Self {
+            assert!(words.len() >= Self::WAYS);
+            unsafe { _mm512_loadu_si512(words.as_ptr().cast()) }
+        }
+
+        #[inline(always)]
+        fn store(self, words: &mut [u64]) {
+            assert!(words.len() >= Self::WAYS);
+            unsafe { _mm512_storeu_si512(words.as_mut_ptr().cast(), self) }
+        }
+
+        #[inline(always)]
+        fn xor(self, other: Self) -> Self {
+            unsafe { _mm512_xor_si512(self, other) }
+        }
+
+        #[inline(always)]
+        fn and_not(self, other: Self) -> Self {
+            unsafe { _mm512_andnot_si512(self, other) }
+        }
+
+        #[inline(always)]
+        fn rotate_left(self, n: u32) -> Self {
+            unsafe { _mm512_rolv_epi64(self, _mm512_set1_epi64(n as i64)) }
+        }
+    }
+}
+
+/// Two NEON registers, so 4 states like AVX2
+#[cfg(target_arch = "aarch64")]
+mod neon {
+    use super::KeccakLane;
+    use std::arch::aarch64::*;
+
+    #[derive(Clone, Copy)]
+    pub(super) struct NeonLanes(uint64x2_t, uint64x2_t);
+
+    impl KeccakLane for NeonLanes {
+        const WAYS: usize = 4;
+
+        #[inline(always)]
+        fn splat(value: u64) -> Self {
+            unsafe { NeonLanes(vdupq_n_u64(value), vdupq_n_u64(value)) }
+        }
+
+        #[inline(always)]
+        fn load(words: &[u64]) -> Self {
+            assert!(words.len() >= Self::WAYS);
+            unsafe { NeonLanes(vld1q_u64(words.as_ptr()), vld1q_u64(words.as_ptr().add(2))) }
+        }
+
+        #[inline(always)]
+        fn store(self, words: &mut [u64]) {
+            assert!(words.len() >= Self::WAYS);
+            unsafe {
+                vst1q_u64(words.as_mut_ptr(), self.0);
+                vst1q_u64(words.as_mut_ptr().add(2), self.1);
+            }
+        }
+
+        #[inline(always)]
+        fn xor(self, other: Self) -> Self {
+            unsafe { NeonLanes(veorq_u64(self.0, other.0), veorq_u64(self.1, other.1)) }
+        }
+
+        #[inline(always)]
+        fn and_not(self, other: Self) -> Self {
+            // vbic(a, b) = a & !b
+            unsafe { NeonLanes(vbicq_u64(other.0, self.0), vbicq_u64(other.1, self.1)) }
+        }
+
+        #[inline(always)]
+        fn rotate_left(self, n: u32) -> Self {
+            // Negative shift counts shift right
+            unsafe {
+                let left = vdupq_n_s64(n as i64);
+                let right = vdupq_n_s64(n as i64 - 64);
+                NeonLanes(
+                    vorrq_u64(vshlq_u64(self.0, left), vshlq_u64(self.0, right)),
+                    vorrq_u64(vshlq_u64(self.1, left), vshlq_u64(self.1, right)),
+                )
+            }
+        }
+    }
+}
+
+/// Keccak-f[1600] over `L::WAYS` independent states, fully unrolled so the
+/// lanes stay in registers
+#[inline(always)]
+fn keccak_f1600<L: KeccakLane>(state: &mut [L; 25]) {
+    for round_constant in ROUND_CONSTANTS {
+        let mut array = [L::splat(0); 5];
+
+        // Theta
+        unroll5!(x, {
+            array[x] = state[x]
+                .xor(state[x + 5])
+                .xor(state[x + 10])
+                .xor(state[x + 15])
+                .xor(state[x + 20]);
+        });
+        unroll5!(x, {
+            let d = array[(x + 4) % 5].xor(array[(x + 1) % 5].rotate_left(1));
+            unroll5!(y, {
+                state[5 * y + x] = state[5 * y + x].xor(d);
+            });
+        });
+
+        // Rho and pi
+        let mut last = state[1];
+        unroll24!(x, {
+            array[0] = state[PI[x]];
+            state[PI[x]] = last.rotate_left(RHO[x]);
+            last = array[0];
+        });
+
+        // Chi
+        unroll5!(y, {
+            unroll5!(x, {
+                array[x] = state[5 * y + x];
+            });
+            unroll5!(x, {
+                state[5 * y + x] = array[x].xor(array[(x + 1) % 5].and_not(array[(x + 2) % 5]));
+            });
+        });
+
+        // Iota
+        state[0] = state[0].xor(L::splat(round_constant));
+    }
+}
+
+/// SHA3-256 of the 32-byte digests held in lanes 0..4
+#[inline(always)]
+fn rehash<L: KeccakLane>(state: &mut [L; 25]) {
+    for lane in &mut state[5..] {
+        *lane = L::splat(0);
+    }
+    state[4] = L::splat(0x06);
+    state[RATE_LANES - 1] = L::splat(0x80 << 56);
+    keccak_f1600(state);
+}
+
+/// SHA3x of `L::WAYS` nonces: the padded input blocks with the nonce lane
+/// replaced, then two SHA3-256 rehashes of the digest. Writes digest lanes 0..4
+/// of each nonce to `out`.
+#[inline(always)]
+fn sha3x_ways<L: KeccakLane>(
+    first_block: &[u64; 25],
+    rest: &[[u64; RATE_LANES]],
+    nonces: &[u64],
+    out: &mut [[u64; 4]],
+) {
+    let mut state: [L; 25] = std::array::from_fn(|i| L::splat(first_block[i]));
+    state[0] = L::load(nonces);
+    keccak_f1600(&mut state);
+    for block in rest {
+        for (lane, input) in state.iter_mut().zip(block) {
+            *lane = lane.xor(L::splat(*input));
+        }
+        keccak_f1600(&mut state);
+    }
+    rehash(&mut state);
+    rehash(&mut state);
+
+    let mut words = [0u64; 8];
+    for (i, lane) in state[..4].iter().enumerate() {
+        lane.store(&mut words);
+        for (out, word) in out.iter_mut().zip(&words[..L::WAYS]) {
+            out[i] = *word;
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn sha3x_avx2(
+    first_block: &[u64; 25],
+    rest: &[[u64; RATE_LANES]],
+    nonces: &[u64],
+    out: &mut [[u64; 4]],
+) {
+    sha3x_ways::<std::arch::x86_64::__m256i>(first_block, rest, nonces, out)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f")]
+unsafe fn sha3x_avx512(
+    first_block: &[u64; 25],
+    rest: &[[u64; RATE_LANES]],
+    nonces: &[u64],
+    out: &mut [[u64; 4]],
+) {
+    sha3x_ways::<std::arch::x86_64::__m512i>(first_block, rest, nonces, out)
+}
+
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn sha3x_neon(
+    first_block: &[u64; 25],
+    rest: &[[u64; RATE_LANES]],
+    nonces: &[u64],
+    out: &mut [[u64; 4]],
+) {
+    sha3x_ways::<neon::NeonLanes>(first_block, rest, nonces, out)
+}
+
+/// SHA3x of `backend.ways()` nonces on `backend`, which must be a resolved
+/// backend (see `CpuBackend::resolve`); digest lanes 0..4 go to `out`
+pub(crate) fn sha3x_lanes(
+    backend: CpuBackend,
+    first_block: &[u64; 25],
+    rest: &[[u64; RATE_LANES]],
+    nonces: &[u64],
+    out: &mut [[u64; 4]],
+) {
+    assert!(nonces.len() == backend.ways() && out.len() == backend.ways());
+    debug_assert!(backend.is_supported());
+    match backend {
+        // SAFETY: resolved backends passed runtime CPU feature detection
+        #[cfg(target_arch = "x86_64")]
+        CpuBackend::Avx512 => unsafe { sha3x_avx512(first_block, rest, nonces, out) },
+        #[cfg(target_arch = "x86_64")]
+        CpuBackend::Avx2 => unsafe { sha3x_avx2(first_block, rest, nonces, out) },
+        #[cfg(target_arch = "aarch64")]
+        CpuBackend::Neon => unsafe { sha3x_neon(first_block, rest, nonces, out) },
+        _ => {
+            for (nonce, out) in nonces.iter().zip(out.iter_mut()) {
+                sha3x_ways::<u64>(
+                    first_block,
+                    rest,
+                    std::slice::from_ref(nonce),
+                    std::slice::from_mut(out),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keccak_matches_keccak_crate() {
+        let mut expected: [u64; 25] = std::array::from_fn(|i| (i as u64 + 1) * 0x9e37_79b9);
+        let mut state = expected;
+        keccak_f1600::<u64>(&mut state);
+        keccak::f1600(&mut expected);
+        assert_eq!(state, expected);
+    }
+
+    #[test]
+    fn test_backend_names_and_resolve() {
+        for backend in CpuBackend::ALL {
+            assert_eq!(backend.name().parse::<CpuBackend>(), Ok(backend));
+        }
+        assert_eq!(" AVX2 ".parse::<CpuBackend>(), Ok(CpuBackend::Avx2));
+        assert!("sse".parse::<CpuBackend>().is_err());
+
+        let detected = CpuBackend::Auto.resolve();
+        assert!(detected != CpuBackend::Auto && detected.is_supported());
+        for backend in CpuBackend::ALL {
+            assert!(backend.resolve().is_supported());
+        }
+    }
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial multi-way Keccak for the CPU miner.
+//   - 4-way (AVX2, NEON) and 8-way (AVX-512F) SHA3x with runtime feature detection.
+//   - CpuBackend parses --cpu-backend; unsupported backends fall back to scalar.
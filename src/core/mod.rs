@@ -6,27 +6,38 @@
 // via pull requests to the project repository.
 //
 // File: src/core/mod.rs
-// Version: 1.0.1
+// Version: 1.0.5
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // This file is the module declaration for the core functionality of the SHA3x
 // miner, located in the core subdirectory. It declares submodules and re-exports
 // key types for use throughout the project.
 
+pub mod bitcoin;
+pub mod config;
 pub mod difficulty;
+pub mod keccak_simd;
 pub mod sha256;
 pub mod sha3x;
 pub mod types;
 
 // Re-export the most commonly used items
+pub use bitcoin::CoinbaseTemplate;
+pub use config::MinerConfig;
 pub use difficulty::{calculate_difficulty, parse_target_difficulty};
-pub use sha3x::sha3x_hash_with_nonce_batch;
+pub use keccak_simd::CpuBackend;
+pub use sha3x::{Sha3xHasher, sha3x_hash_with_nonce_batch};
 pub use sha256::{sha256d_hash, sha256d_hash_with_nonce_batch};
 pub use types::{
-    Algorithm, Args, MiningJob, PoolJob, Share, ShareError, ShareResponse, ShareResult, Target,
+    Algorithm, Args, MiningJob, PoolJob, Share, ShareError, ShareResponse, ShareResult,
+    ShareStatus, Target,
 };
 
 // Changelog:
+// - v1.0.5 (2025-06-28): Added keccak_simd module (multi-way Keccak, CpuBackend).
+// - v1.0.4 (2025-06-28): Re-exported Sha3xHasher.
+// - v1.0.3 (2025-06-28): Added config module (TOML config file with CLI override layering).
+// - v1.0.2 (2025-06-28): Added bitcoin module (coinbase, merkle root and header helpers).
 // - v1.0.1 (2025-06-16): Added simple SHA-256 support.
 //   - Added sha256 module with basic double SHA-256 implementation.
 //   - Added Algorithm enum export for sha3x/sha256 distinction.
@@ -6,20 +6,166 @@
 // via pull requests to the project repository.
 //
 // File: src/core/sha3x.rs
-// Version: 1.0.1
+// Version: 1.2.0
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // This file implements the SHA3x triple-hash algorithm used by Tari, located
 // in the core subdirectory of the SHA3x miner source tree. It provides the
 // hashing function for mining operations.
 //
+// Sha3xHasher is the fast path used by the CPU miner. The SHA3x input is
+// nonce || header || 0x01, so the first Keccak block only differs between
+// nonces in its first lane: the padded block is built once per job and every
+// hash copies it, sets the nonce lane and runs the three permutations straight
+// into a caller-provided [u8; 32]. With a SIMD CpuBackend, hash_nonces runs
+// 4 or 8 nonces at once through the multi-way Keccak in keccak_simd. The Vec
+// based functions below are kept as the reference implementation.
+//
 // Tree Location:
 // - src/core/sha3x.rs (SHA3x algorithm implementation)
-// - Depends on: sha3 crate
+// - Depends on: sha3 crate, keccak crate, core/keccak_simd
 
+use super::keccak_simd::{CpuBackend, sha3x_lanes};
 use core::array;
 use sha3::{Digest, Sha3_256};
 
+/// SHA3-256 rate in bytes (1088 bits) and in 64-bit lanes
+const RATE: usize = 136;
+const RATE_LANES: usize = RATE / 8;
+
+/// SHA3 domain separation and final padding bits
+const PAD_DOMAIN: u8 = 0x06;
+const PAD_LAST: u8 = 0x80;
+
+/// SHA3x hasher for one header template, hashing without allocations
+#[derive(Debug, Clone)]
+pub struct Sha3xHasher {
+    /// First padded input block with the nonce lane zeroed
+    first_block: [u64; 25],
+    /// Further input blocks, only for headers over 126 bytes
+    rest: Vec<[u64; RATE_LANES]>,
+    /// Resolved backend used by hash_nonces
+    backend: CpuBackend,
+}
+
+impl Sha3xHasher {
+    /// Hasher on the scalar backend
+    pub fn new(header_template: &[u8]) -> Self {
+        Self::with_backend(header_template, CpuBackend::Scalar)
+    }
+
+    /// Hasher whose hash_nonces runs on `backend`, or on the fastest / scalar
+    /// backend when it is `Auto` / not supported by this CPU
+    pub fn with_backend(header_template: &[u8], backend: CpuBackend) -> Self {
+        // nonce || header || 0x01, then SHA3 padding (0x06 ... 0x80)
+        let len = 8 + header_template.len() + 1;
+        let mut padded = vec![0u8; (len / RATE + 1) * RATE];
+        padded[8..len - 1].copy_from_slice(header_template);
+        padded[len - 1] = 1;
+        padded[len] ^= PAD_DOMAIN;
+        let last = padded.len() - 1;
+        padded[last] ^= PAD_LAST;
+
+        let mut blocks = padded.chunks_exact(RATE).map(|block| {
+            let mut lanes = [0u64; RATE_LANES];
+            for (lane, bytes) in lanes.iter_mut().zip(block.chunks_exact(8)) {
+                *lane = u64::from_le_bytes(bytes.try_into().unwrap());
+            }
+            lanes
+        });
+        let mut first_block = [0u64; 25];
+        first_block[..RATE_LANES].copy_from_slice(&blocks.next().unwrap());
+        Self {
+            first_block,
+            rest: blocks.collect(),
+            backend: backend.resolve(),
+        }
+    }
+
+    pub fn backend(&self) -> CpuBackend {
+        self.backend
+    }
+
+    /// Final Keccak state lanes 0..4, which hold the 32-byte digest
+    #[inline]
+    pub fn hash_lanes(&self, nonce: u64) -> [u64; 4] {
+        let mut state = self.first_block;
+        state[0] = nonce;
+        keccak::f1600(&mut state);
+        for block in &self.rest {
+            for (lane, input) in state.iter_mut().zip(block) {
+                *lane ^= input;
+            }
+            keccak::f1600(&mut state);
+        }
+        rehash(&mut state);
+        rehash(&mut state);
+        [state[0], state[1], state[2], state[3]]
+    }
+
+    /// SHA3x hash of one nonce
+    #[inline]
+    pub fn hash(&self, nonce: u64) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        self.hash_into(nonce, &mut out);
+        out
+    }
+
+    /// SHA3x hash of one nonce into `out`
+    #[inline]
+    pub fn hash_into(&self, nonce: u64, out: &mut [u8; 32]) {
+        for (bytes, lane) in out.chunks_exact_mut(8).zip(self.hash_lanes(nonce)) {
+            bytes.copy_from_slice(&lane.to_le_bytes());
+        }
+    }
+
+    /// SHA3x hashes of `nonces` into the matching entries of `out`
+    pub fn hash_nonces(&self, nonces: &[u64], out: &mut [[u8; 32]]) {
+        let ways = self.backend.ways();
+        if ways == 1 {
+            for (nonce, out) in nonces.iter().zip(out.iter_mut()) {
+                self.hash_into(*nonce, out);
+            }
+            return;
+        }
+
+        // SIMD backends hash groups of `ways` nonces, the tail goes one by one
+        let mut nonce_groups = nonces.chunks_exact(ways);
+        let mut out_groups = out.chunks_exact_mut(ways);
+        let mut lanes = [[0u64; 4]; 8];
+        for (group, out) in (&mut nonce_groups).zip(&mut out_groups) {
+            sha3x_lanes(
+                self.backend,
+                &self.first_block,
+                &self.rest,
+                group,
+                &mut lanes[..ways],
+            );
+            for (out, lanes) in out.iter_mut().zip(&lanes) {
+                for (bytes, lane) in out.chunks_exact_mut(8).zip(lanes) {
+                    bytes.copy_from_slice(&lane.to_le_bytes());
+                }
+            }
+        }
+        for (nonce, out) in nonce_groups
+            .remainder()
+            .iter()
+            .zip(out_groups.into_remainder())
+        {
+            self.hash_into(*nonce, out);
+        }
+    }
+}
+
+/// SHA3-256 of the 32-byte digest held in lanes 0..4 of `state`
+#[inline(always)]
+fn rehash(state: &mut [u64; 25]) {
+    state[4..].fill(0);
+    state[4] = PAD_DOMAIN as u64;
+    state[RATE_LANES - 1] = (PAD_LAST as u64) << 56;
+    keccak::f1600(state);
+}
+
 /// Perform SHA3x hash (triple SHA3-256) with a nonce for mining
 pub fn sha3x_hash_with_nonce(header_template: &[u8], nonce: [u8; 8]) -> Vec<u8> {
     let mut input = Vec::with_capacity(header_template.len() + 9);
@@ -56,7 +202,82 @@ pub fn sha3x_hash_with_nonce_batch(header_template: &[u8], nonce: u64) -> [(Vec<
     results
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 31 + 7) as u8).collect()
+    }
+
+    #[test]
+    fn test_hasher_matches_reference() {
+        // Single block up to 126 header bytes, two blocks from 127 on
+        for len in [0, 32, 125, 126, 127, 200, 263, 264] {
+            let header = header(len);
+            let hasher = Sha3xHasher::new(&header);
+            for nonce in [0, 1, 0x1234_5678_9abc_def0, u64::MAX] {
+                assert_eq!(
+                    hasher.hash(nonce).to_vec(),
+                    sha3x_hash_with_nonce(&header, nonce.to_le_bytes()),
+                    "header length {}, nonce {:#x}",
+                    len,
+                    nonce
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_hash_nonces_matches_batch() {
+        let header = header(32);
+        let hasher = Sha3xHasher::new(&header);
+        let nonces = [1000, 1001, 1002, 1003];
+        let mut hashes = [[0u8; 32]; 4];
+        hasher.hash_nonces(&nonces, &mut hashes);
+
+        for (hash, (expected, nonce)) in hashes
+            .iter()
+            .zip(sha3x_hash_with_nonce_batch(&header, 1000))
+        {
+            assert!(nonces.contains(&nonce));
+            assert_eq!(hash.to_vec(), expected);
+        }
+    }
+
+    #[test]
+    fn test_backends_match_reference() {
+        // 19 nonces leave a tail after the 4- and 8-way groups
+        let nonces: Vec<u64> = (0..19).map(|i| u64::MAX - 7 * i).collect();
+        for len in [32, 126, 200] {
+            let header = header(len);
+            for backend in CpuBackend::ALL {
+                let hasher = Sha3xHasher::with_backend(&header, backend);
+                let mut hashes = vec![[0u8; 32]; nonces.len()];
+                hasher.hash_nonces(&nonces, &mut hashes);
+                for (hash, nonce) in hashes.iter().zip(&nonces) {
+                    assert_eq!(
+                        hash.to_vec(),
+                        sha3x_hash_with_nonce(&header, nonce.to_le_bytes()),
+                        "{} backend ({}), header length {}",
+                        backend,
+                        hasher.backend(),
+                        len
+                    );
+                }
+            }
+        }
+    }
+}
+
 // Changelog:
+// - v1.2.0 (2025-06-28): Sha3xHasher::with_backend runs hash_nonces on a SIMD CpuBackend.
+//   - 4-way (AVX2, NEON) and 8-way (AVX-512F) groups, remaining nonces on the scalar path.
+//   - Every backend checked bit-for-bit against sha3x_hash_with_nonce.
+// - v1.1.0 (2025-06-28): Added Sha3xHasher, an allocation-free SHA3x core.
+//   - Precomputes the padded first Keccak block per header; only the nonce lane changes.
+//   - Runs keccak::f1600 directly and writes [u8; 32] into caller buffers.
+//   - Verified bit-for-bit against sha3x_hash_with_nonce for single and multi-block headers.
 // - v1.0.1 (2025-06-14T20:40:00Z EDT): Added batch hashing optimization.
 //   - Introduced sha3x_hash_with_nonce_batch to compute 4 hashes per call, reusing input buffer.
 //   - Fixed compilation error by using core::array::from_fn for results array.
@@ -7,16 +7,23 @@
 // MULTI-GPU DUAL-INDEPENDENT MINERS: Complete hybrid mode with resilient miners
 // Feature-based mining with proper thread coordination: --features cpu, --features gpu, --features hybrid
 
-use clap::Parser;
 use graxil::{
     Result,
     benchmark::runner::BenchmarkRunner,
-    core::types::{Algorithm, Args},
-    miner::CpuMiner,
+    core::{
+        MinerConfig,
+        types::{Algorithm, Args},
+    },
+    miner::{
+        CpuMiner, MinerControl, RuntimeSettings,
+        cpu::governor::CpuGovernorConfig,
+        schedule::{self, IdleDetection, MiningSchedule},
+    },
 };
 use log::{error, info};
 
 // Web server module for real-time mining dashboard
+mod web_api;
 mod web_server;
 
 const LOG_TARGET: &str = "tari::graxil";
@@ -49,7 +56,7 @@ compile_error!(target: LOG_TARGET,
 #[cfg(all(feature = "cpu", not(feature = "hybrid")))]
 #[tokio::main]
 async fn main() -> Result<()> {
-    let args = Args::parse();
+    let args = load_args();
 
     // Check for SV2 test mode first
     if args.test_sv2 {
@@ -62,6 +69,10 @@ async fn main() -> Result<()> {
         std::process::exit(1);
     }
 
+    if args.proxy_listen.is_some() {
+        return handle_stratum_proxy(&args).await;
+    }
+
     let algo = parse_algorithm(&args.algo)?;
 
     if args.benchmark {
@@ -79,7 +90,7 @@ async fn main() -> Result<()> {
 async fn main() -> Result<()> {
     use std::env;
 
-    let args = Args::parse();
+    let args = load_args();
 
     let logs_directory = args.log_dir.clone().unwrap_or(
         env::current_dir()
@@ -104,7 +115,12 @@ async fn main() -> Result<()> {
             use graxil::miner::GpuManager;
 
             info!(target: LOG_TARGET, "🔍 Detecting OpenCL devices...");
-            match GpuManager::generate_information_files(information_file_directory).await {
+            match GpuManager::generate_information_files(
+                information_file_directory,
+                &args.gpu_devices,
+            )
+            .await
+            {
                 Ok(_) => info!(target: LOG_TARGET, "✅ Device detection complete!"),
                 Err(e) => {
                     error!(target: LOG_TARGET, "❌ Failed to detect devices: {}", e);
@@ -126,9 +142,15 @@ async fn main() -> Result<()> {
         std::process::exit(1);
     }
 
+    if args.proxy_listen.is_some() {
+        return handle_stratum_proxy(&args).await;
+    }
+
     let algo = parse_algorithm(&args.algo)?;
 
-    if args.benchmark {
+    if args.benchmark && args.autotune {
+        return handle_gpu_autotune(&args).await;
+    } else if args.benchmark {
         return handle_benchmark(&args, algo).await;
     } else {
         return handle_gpu_mining(&args, algo).await;
@@ -141,7 +163,7 @@ async fn main() -> Result<()> {
 #[cfg(feature = "hybrid")]
 #[tokio::main]
 async fn main() -> Result<()> {
-    let args = Args::parse();
+    let args = load_args();
 
     // Check for SV2 test mode first
     if args.test_sv2 {
@@ -154,9 +176,15 @@ async fn main() -> Result<()> {
         std::process::exit(1);
     }
 
+    if args.proxy_listen.is_some() {
+        return handle_stratum_proxy(&args).await;
+    }
+
     let algo = parse_algorithm(&args.algo)?;
 
-    if args.benchmark {
+    if args.benchmark && args.autotune {
+        return handle_gpu_autotune(&args).await;
+    } else if args.benchmark {
         return handle_benchmark(&args, algo).await;
     } else {
         return handle_hybrid_mining(&args, algo).await;
@@ -167,11 +195,113 @@ async fn main() -> Result<()> {
 // SHARED HELPER FUNCTIONS
 //
 
+/// Parse the command line over the --config file and handle --print-config
+fn load_args() -> Args {
+    let args = match Args::load() {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("❌ Error: {}", err);
+            std::process::exit(1);
+        }
+    };
+
+    if args.print_config {
+        match MinerConfig::from_args(&args).and_then(|config| config.to_toml()) {
+            Ok(toml) => print!("{}", toml),
+            Err(err) => {
+                eprintln!("❌ Error: {}", err);
+                std::process::exit(1);
+            }
+        }
+        if let Err(err) = args.validate() {
+            eprintln!("❌ Error: {}", err);
+            std::process::exit(1);
+        }
+        std::process::exit(0);
+    }
+
+    args
+}
+
+/// Runtime settings control for the running miners; SIGHUP reloads it on unix,
+/// and the mining schedule / idle detection pause and resume through it
+fn start_control(args: &Args) -> MinerControl {
+    let control = MinerControl::new(RuntimeSettings::from_args(args));
+    #[cfg(unix)]
+    if let Err(e) = control.reload_on_sighup() {
+        error!(target: LOG_TARGET,"❌ Failed to install SIGHUP reload handler: {}", e);
+    }
+    match MiningSchedule::from_args(args) {
+        Ok(schedule) => {
+            schedule::spawn_scheduler(control.clone(), schedule, IdleDetection::from_args(args))
+        }
+        Err(e) => error!(target: LOG_TARGET,"❌ Mining schedule ignored: {}", e),
+    }
+    control
+}
+
+/// Apply stored GPU autotune results and tune the GPUs that need it (all of them
+/// with --autotune). Tuning mines the benchmark job, so no pool is needed yet.
+#[cfg(any(feature = "gpu", feature = "hybrid"))]
+async fn autotune_gpus(args: &Args, gpu_manager: &mut graxil::miner::gpu::GpuManager) {
+    use graxil::benchmark::jobs::get_job_by_difficulty_and_algo;
+    use graxil::miner::gpu::opencl::AutotuneConfig;
+
+    let job = get_job_by_difficulty_and_algo(args.benchmark_difficulty, Algorithm::Sha3x);
+    let config = AutotuneConfig {
+        test_duration_secs: args.autotune_duration,
+        ..AutotuneConfig::default()
+    };
+    if let Err(e) = gpu_manager
+        .autotune(
+            args.information_file_dir.clone(),
+            &job.mining_job,
+            config,
+            args.autotune,
+        )
+        .await
+    {
+        error!(target: LOG_TARGET,"❌ GPU autotuning failed: {}", e);
+    }
+}
+
+/// --benchmark --autotune: tune every GPU against the benchmark job, store the results and exit
+#[cfg(any(feature = "gpu", feature = "hybrid"))]
+async fn handle_gpu_autotune(args: &Args) -> Result<()> {
+    use graxil::core::config::parse_device_list;
+    use graxil::miner::gpu::GpuManager;
+
+    info!(target: LOG_TARGET,"🎛️ GPU Autotune Mode ({}s per measurement)", args.autotune_duration);
+    if args.information_file_dir.is_none() {
+        info!(target: LOG_TARGET,"💡 Add --information-file-dir DIR to store the results for later runs");
+    }
+
+    let excluded_devices = args
+        .excluded_devices
+        .as_deref()
+        .map(parse_device_list)
+        .unwrap_or_default();
+    let mut gpu_manager = GpuManager::new_with_settings(args.get_gpu_settings(), excluded_devices);
+    gpu_manager.set_device_settings(args.gpu_devices.clone());
+    if let Some(directory) = args.information_file_dir.clone() {
+        if let Err(e) = gpu_manager.load_information_file(directory).await {
+            error!(target: LOG_TARGET,"❌ Failed to read GPU information file: {}", e);
+        }
+    }
+    gpu_manager
+        .initialize()
+        .map_err(|e| format!("Failed to initialize GPU manager: {}", e))?;
+
+    autotune_gpus(args, &mut gpu_manager).await;
+    info!(target: LOG_TARGET,"🏁 Autotuning complete - {}", gpu_manager.get_performance_summary());
+    Ok(())
+}
+
 async fn handle_sv2_test(args: &Args) -> Result<()> {
     info!(target: LOG_TARGET,"🔧 SV2 Connection Test Mode");
 
     // Validate required arguments for SV2 test
-    let pool_address = match &args.pool {
+    let pool_address = match args.primary_pool() {
         Some(pool) => pool,
         None => {
             eprintln!("❌ Error: --pool is required for SV2 testing");
@@ -187,24 +317,32 @@ async fn handle_sv2_test(args: &Args) -> Result<()> {
     // Create a test miner instance - pass pool address as string
     let miner = CpuMiner::new(
         "test-wallet".to_string(), // Dummy wallet for SV2 test
-        pool_address.clone(),      // Pass as string, miner will resolve DNS
+        pool_address.to_string(),  // Pass as string, miner will resolve DNS
         "sv2-test-worker".to_string(),
         1,                // Single thread for test
         Algorithm::Sha3x, // Algorithm doesn't matter for connection test
     );
+    match args.proxy_config() {
+        Ok(proxy) => miner.set_proxy(proxy),
+        Err(e) => {
+            eprintln!("❌ Error: {}", e);
+            std::process::exit(1);
+        }
+    }
 
     // Run SV2 connection test
     match miner.test_sv2_connection().await {
         Ok(()) => {
-            info!(target: LOG_TARGET,"✅ TCP connection to JDS successful");
-            info!(target: LOG_TARGET,"❌ Noise protocol not implemented yet");
-            info!(target: LOG_TARGET,"🔧 Next: Implement noise_sv2 handshake with step_0/step_2");
+            info!(target: LOG_TARGET,"✅ SV2 connection test successful");
             std::process::exit(0);
         }
         Err(e) => {
             eprintln!("❌ SV2 connection test failed: {}", e);
             eprintln!("💡 Make sure JDS is running and accepting connections");
             eprintln!("💡 Check the JDS address and port");
+            eprintln!(
+                "💡 Pass the pool authority key as sv2://HOST:PORT/AUTHORITY_KEY to verify the pool"
+            );
             std::process::exit(1);
         }
     }
@@ -222,6 +360,74 @@ fn parse_algorithm(algo_str: &str) -> Result<Algorithm> {
     Ok(algo)
 }
 
+async fn handle_stratum_proxy(args: &Args) -> Result<()> {
+    use graxil::miner::MinerStats;
+    use graxil::pool::{PoolClient, PoolSession, PoolSessionConfig, StratumProxy};
+    use std::sync::Arc;
+
+    let listen = args.proxy_listen.clone().unwrap();
+    let pool = args.primary_pool().unwrap().to_string();
+
+    info!(target: LOG_TARGET,"🚀 Starting SHA3x Miner - Stratum Proxy Mode");
+    info!(target: LOG_TARGET,"📍 Upstream pool: {}", pool);
+    info!(target: LOG_TARGET,"💳 Wallet: {}", args.wallet.as_ref().unwrap());
+    info!(target: LOG_TARGET,"🔀 Downstream miners: stratum+tcp://{}", listen);
+
+    let pool_client = Arc::new(PoolClient::new());
+    pool_client.set_proxy(args.proxy_config()?);
+
+    let mut stats = MinerStats::new(1);
+    stats.set_pool_client(Arc::clone(&pool_client));
+    let stats = Arc::new(stats);
+
+    let mut config = PoolSessionConfig::single(
+        pool,
+        args.wallet.as_ref().unwrap().clone(),
+        args.worker.clone(),
+        Algorithm::Sha3x,
+        "PROXY",
+    );
+    config.pools = args.pool_endpoints(&args.worker)?;
+    config.failover = args.failover_settings();
+    let session = Arc::new(PoolSession::new(config, pool_client, Arc::clone(&stats))?);
+
+    let web_config = web_server::WebServerConfig::from_args(args);
+    let web_stats = Arc::clone(&stats);
+    tokio::spawn(async move {
+        web_server::start_web_server(web_config, web_stats, None).await;
+    });
+
+    let proxy = StratumProxy::bind(&listen, session, stats).await?;
+    proxy.run().await
+}
+
+/// Run the terminal dashboard on a blocking thread; quitting it stops the miner
+#[cfg(feature = "tui")]
+fn start_tui(
+    args: &Args,
+    stats: std::sync::Arc<graxil::miner::MinerStats>,
+    mode: &str,
+    gpu_devices: usize,
+) {
+    use graxil::tui::{self, TuiConfig};
+
+    let config = TuiConfig {
+        mode: mode.to_string(),
+        pool: args.primary_pool().unwrap_or_default().to_string(),
+        wallet: args.wallet.clone().unwrap_or_default(),
+        worker: args.worker.clone(),
+        gpu_devices,
+    };
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = tui::run(stats, config) {
+            eprintln!("❌ TUI error: {}", e);
+            std::process::exit(1);
+        }
+        info!(target: LOG_TARGET,"👋 TUI closed, stopping miner");
+        std::process::exit(0);
+    });
+}
+
 async fn handle_benchmark(args: &Args, algo: Algorithm) -> Result<()> {
     info!(target: LOG_TARGET,"🧪 Starting Benchmark Mode (Algo: {:?})", algo);
     info!(target: LOG_TARGET,
@@ -240,7 +446,8 @@ async fn handle_benchmark(args: &Args, algo: Algorithm) -> Result<()> {
         args.benchmark_duration,
         args.benchmark_difficulty,
         algo,
-    );
+    )
+    .with_cpu_backend(args.sha3x_backend()?);
 
     let result = benchmark_runner.run().await?;
 
@@ -260,6 +467,13 @@ async fn handle_benchmark(args: &Args, algo: Algorithm) -> Result<()> {
         result.shares_found as f64 / (result.total_hashes as f64 / 1_000_000.0)
     );
     info!(target: LOG_TARGET,"🧵 Threads used: {}", result.thread_count);
+    if let Some(backend) = result.cpu_backend {
+        info!(target: LOG_TARGET,
+            "🧮 CPU backend: {} ({} hashes per pass)",
+            backend,
+            backend.ways()
+        );
+    }
 
     Ok(())
 }
@@ -277,7 +491,7 @@ async fn handle_cpu_mining(args: &Args, algo: Algorithm) -> Result<()> {
     }
 
     info!(target: LOG_TARGET,"🚀 Starting SHA3x Miner - CPU-ONLY Mode");
-    info!(target: LOG_TARGET,"📍 Pool: {}", args.pool.as_ref().unwrap());
+    info!(target: LOG_TARGET,"📍 Pool: {}", args.primary_pool().unwrap());
     info!(target: LOG_TARGET,"💳 Wallet: {}", args.wallet.as_ref().unwrap());
     info!(target: LOG_TARGET,"👷 Worker: {}", args.worker);
     info!(target: LOG_TARGET,
@@ -291,22 +505,30 @@ async fn handle_cpu_mining(args: &Args, algo: Algorithm) -> Result<()> {
     info!(target: LOG_TARGET,"💻 Mode: CPU-only mining (compile with --features gpu for 300+ MH/s boost!)");
 
     // Create and run your existing CPU miner
-    let miner = CpuMiner::new(
+    let mut miner = CpuMiner::new(
         args.wallet.as_ref().unwrap().clone(),
-        args.pool.as_ref().unwrap().clone(),
+        args.primary_pool().unwrap().to_string(),
         args.worker.clone(),
         args.threads,
         algo,
-    )
-    .into_arc();
+    );
+    miner.set_pool_failover(args.pool_endpoints(&args.worker)?, args.failover_settings());
+    miner.set_proxy(args.proxy_config()?);
+    miner.set_governor(CpuGovernorConfig::from_args(args));
+    miner.set_backend(args.sha3x_backend()?);
+    let miner = miner.into_arc();
+    let control = start_control(args);
+    control.set_cpu_thread_limit(miner.started_thread_count());
+    miner.watch_control(control.subscribe());
 
     // Start web server in background if --web flag is enabled
-    if args.web {
+    if args.web || args.metrics {
         let miner_clone = miner.clone();
+        let web_config = web_server::WebServerConfig::from_args(args);
         tokio::spawn(async move {
             let stats = miner_clone.get_stats();
             info!(target: LOG_TARGET,"🌐 Starting web dashboard server...");
-            web_server::start_web_server(stats).await;
+            web_server::start_web_server(web_config, stats, Some(control)).await;
         });
 
         info!(target: LOG_TARGET,"📊 Real-time dashboard available at: http://localhost:8080");
@@ -316,6 +538,11 @@ async fn handle_cpu_mining(args: &Args, algo: Algorithm) -> Result<()> {
         info!(target: LOG_TARGET,"💡 Add --web flag to enable real-time web dashboard");
     }
 
+    #[cfg(feature = "tui")]
+    if args.tui {
+        start_tui(args, miner.get_stats(), "CPU", 0);
+    }
+
     // Start CPU mining
     info!(target: LOG_TARGET,"🚀 Starting CPU mining");
     miner.run().await?;
@@ -336,7 +563,7 @@ async fn handle_gpu_mining(args: &Args, algo: Algorithm) -> Result<()> {
     }
 
     info!(target: LOG_TARGET,"🚀 Starting SHA3x Miner - GPU-ONLY Mode");
-    info!(target: LOG_TARGET,"📍 Pool: {}", args.pool.as_ref().unwrap());
+    info!(target: LOG_TARGET,"📍 Pool: {}", args.primary_pool().unwrap());
     info!(target: LOG_TARGET,"💳 Wallet: {}", args.wallet.as_ref().unwrap());
     info!(target: LOG_TARGET,"👷 Worker: {}", args.worker);
     info!(target: LOG_TARGET,"🎮 Mode: GPU-only mining (385+ MH/s beast mode!)");
@@ -353,7 +580,7 @@ async fn handle_gpu_mining(args: &Args, algo: Algorithm) -> Result<()> {
 
     use log::info;
     // Create GPU manager with settings applied
-    use graxil::miner::gpu::{GpuManager, GpuMiner};
+    use graxil::miner::gpu::{GovernorConfig, GpuManager, GpuMiner};
 
     let mut excluded_devices: Vec<u32> = Vec::new();
 
@@ -365,18 +592,38 @@ async fn handle_gpu_mining(args: &Args, algo: Algorithm) -> Result<()> {
             .collect();
     }
 
-    let gpu_manager = GpuManager::new_with_settings(gpu_settings.clone(), excluded_devices);
+    let mut gpu_manager = GpuManager::new_with_settings(gpu_settings.clone(), excluded_devices);
+    gpu_manager.set_device_settings(args.gpu_devices.clone());
+    if let Some(directory) = args.information_file_dir.clone() {
+        if let Err(e) = gpu_manager.load_information_file(directory).await {
+            error!(target: LOG_TARGET,"❌ Failed to read GPU information file: {}", e);
+        }
+    }
+
+    // Initialize before the miner so stored autotune results apply (and stale ones re-tune)
+    if let Err(e) = gpu_manager.initialize() {
+        eprintln!("❌ Failed to initialize GPU manager: {}", e);
+        eprintln!("💡 Make sure you have OpenCL drivers installed");
+        std::process::exit(1);
+    }
+    autotune_gpus(args, &mut gpu_manager).await;
 
     // *** CRITICAL FIX: Use new_with_settings instead of new() ***
     let gpu_miner = match GpuMiner::new_with_settings(
         args.wallet.as_ref().unwrap().clone(),
-        args.pool.as_ref().unwrap().clone(),
+        args.primary_pool().unwrap().to_string(),
         args.worker.clone(),
         algo,
         gpu_manager,
         gpu_settings, // ✅ Now properly passing CLI settings!
     ) {
-        Ok(miner) => miner.into_arc(),
+        Ok(mut miner) => {
+            miner.set_pool_failover(args.pool_endpoints(&args.worker)?, args.failover_settings());
+            miner.set_proxy(args.proxy_config()?);
+            miner.set_governor(GovernorConfig::from_args(args));
+            miner.set_hw_error_limit(args.gpu_hw_error_limit);
+            miner.into_arc()
+        }
         Err(e) => {
             eprintln!("❌ Failed to create GPU miner: {}", e);
             eprintln!("💡 Make sure you have OpenCL drivers installed");
@@ -388,6 +635,9 @@ async fn handle_gpu_mining(args: &Args, algo: Algorithm) -> Result<()> {
         }
     };
 
+    let control = start_control(args);
+    gpu_miner.watch_control(control.subscribe());
+
     // Verify settings were applied correctly
     let applied_settings = gpu_miner.get_gpu_settings();
     info!(target: LOG_TARGET,
@@ -397,16 +647,22 @@ async fn handle_gpu_mining(args: &Args, algo: Algorithm) -> Result<()> {
 
     // Start web server in background if --web flag is enabled
     let miner_clone = gpu_miner.clone();
-    let web = args.web.clone();
-    let ws = args.ws.clone();
+    let web_config = web_server::WebServerConfig::from_args(args);
     tokio::spawn(async move {
         let stats = miner_clone.get_stats();
         info!(target: LOG_TARGET,"🌐 Starting GPU web dashboard server...");
-        web_server::start_web_server(ws, web, stats).await;
+        web_server::start_web_server(web_config, stats, Some(control)).await;
     });
 
     info!(target: LOG_TARGET,"🔗 WebSocket endpoint: ws://localhost:{}/ws", args.ws);
 
+    #[cfg(feature = "tui")]
+    if args.tui {
+        let stats = gpu_miner.get_stats();
+        let gpu_devices = stats.thread_stats.len();
+        start_tui(args, stats, "GPU", gpu_devices);
+    }
+
     // Start GPU mining - 385+ MH/s beast mode with correct settings!
     info!(target: LOG_TARGET,
         "🚀 Starting GPU mining with {}% intensity - unleashing the beast!",
@@ -430,7 +686,7 @@ async fn handle_hybrid_mining(args: &Args, algo: Algorithm) -> Result<()> {
     }
 
     info!(target: LOG_TARGET,"🚀 Starting SHA3x Miner - MULTI-GPU HYBRID Mode");
-    info!(target: LOG_TARGET,"📍 Pool: {}", args.pool.as_ref().unwrap());
+    info!(target: LOG_TARGET,"📍 Pool: {}", args.primary_pool().unwrap());
     info!(target: LOG_TARGET,"💳 Wallet: {}", args.wallet.as_ref().unwrap());
     info!(target: LOG_TARGET,"👷 Worker: {}", args.worker);
     info!(target: LOG_TARGET,
@@ -463,11 +719,18 @@ async fn handle_hybrid_mining(args: &Args, algo: Algorithm) -> Result<()> {
 
     // Initialize GPU manager to get actual device count
     let mut gpu_manager = GpuManager::new_with_settings(gpu_settings.clone());
+    gpu_manager.set_device_settings(args.gpu_devices.clone());
+    if let Some(directory) = args.information_file_dir.clone() {
+        if let Err(e) = gpu_manager.load_information_file(directory).await {
+            error!(target: LOG_TARGET,"❌ Failed to read GPU information file: {}", e);
+        }
+    }
     if let Err(e) = gpu_manager.initialize() {
         error!(target: LOG_TARGET,"❌ Failed to initialize GPU manager: {}", e);
         error!(target: LOG_TARGET,"💡 Falling back to CPU-only mode...");
         return handle_cpu_fallback(args, algo).await;
     }
+    autotune_gpus(args, &mut gpu_manager).await;
 
     // *** CRITICAL: Get actual GPU device count for thread coordination ***
     let gpu_count = gpu_manager.device_count();
@@ -511,12 +774,17 @@ async fn handle_hybrid_mining(args: &Args, algo: Algorithm) -> Result<()> {
         total_threads
     );
 
+    // Runtime settings shared by both miners and the HTTP API
+    let control = start_control(args);
+
     // Start web server ONCE for unified dashboard
-    if args.web {
+    if args.web || args.metrics {
         let stats_clone = Arc::clone(&unified_stats);
+        let web_config = web_server::WebServerConfig::from_args(args);
+        let web_control = control.clone();
         tokio::spawn(async move {
             info!(target: LOG_TARGET,"🌐 Starting unified multi-GPU hybrid web dashboard server...");
-            web_server::start_web_server(stats_clone).await;
+            web_server::start_web_server(web_config, stats_clone, Some(web_control)).await;
         });
 
         info!(target: LOG_TARGET,"📊 Real-time MULTI-GPU HYBRID dashboard available at: http://localhost:8080");
@@ -526,8 +794,16 @@ async fn handle_hybrid_mining(args: &Args, algo: Algorithm) -> Result<()> {
         info!(target: LOG_TARGET,"💡 Add --web flag to enable real-time unified dashboard");
     }
 
+    #[cfg(feature = "tui")]
+    if args.tui {
+        start_tui(args, Arc::clone(&unified_stats), "Hybrid", gpu_count);
+    }
+
     // *** CREATE DUAL-INDEPENDENT MINERS ***
 
+    // One nonce allocator keeps CPU threads and GPUs on disjoint nonce ranges
+    let nonce_allocator = Arc::new(graxil::miner::NonceAllocator::new());
+
     // 1. Create CPU miner with shared stats and proper thread coordination
     let cpu_miner = create_multi_gpu_cpu_miner(
         args,
@@ -535,6 +811,7 @@ async fn handle_hybrid_mining(args: &Args, algo: Algorithm) -> Result<()> {
         Arc::clone(&unified_stats),
         gpu_count, // Dynamic GPU count for thread offset calculation
         cpu_thread_count,
+        Arc::clone(&nonce_allocator),
     )
     .await?;
 
@@ -545,9 +822,15 @@ async fn handle_hybrid_mining(args: &Args, algo: Algorithm) -> Result<()> {
         gpu_manager,
         Arc::clone(&unified_stats),
         gpu_settings.clone(),
+        nonce_allocator,
     )
     .await?;
 
+    // 3. Both miners follow runtime setting reloads
+    control.set_cpu_thread_limit(cpu_miner.started_thread_count());
+    cpu_miner.watch_control(control.subscribe());
+    gpu_miner.watch_control(control.subscribe());
+
     info!(target: LOG_TARGET,"🚀 Starting DUAL-INDEPENDENT MULTI-GPU hybrid mining!");
     info!(target: LOG_TARGET,
         "💪 Expected combined hashrate: 400+ MH/s (GPU: {}% intensity)",
@@ -594,25 +877,39 @@ async fn handle_cpu_fallback(args: &Args, algo: Algorithm) -> Result<()> {
 
     info!(target: LOG_TARGET,"🔄 Initializing CPU-only fallback mode...");
 
-    let miner = CpuMiner::new(
+    let worker = format!("{}-cpu-fallback", args.worker);
+    let mut miner = CpuMiner::new(
         args.wallet.as_ref().unwrap().clone(),
-        args.pool.as_ref().unwrap().clone(),
-        format!("{}-cpu-fallback", args.worker),
+        args.primary_pool().unwrap().to_string(),
+        worker.clone(),
         args.threads,
         algo,
-    )
-    .into_arc();
-
-    if args.web {
+    );
+    miner.set_pool_failover(args.pool_endpoints(&worker)?, args.failover_settings());
+    miner.set_proxy(args.proxy_config()?);
+    miner.set_governor(CpuGovernorConfig::from_args(args));
+    miner.set_backend(args.sha3x_backend()?);
+    let miner = miner.into_arc();
+    let control = start_control(args);
+    control.set_cpu_thread_limit(miner.started_thread_count());
+    miner.watch_control(control.subscribe());
+
+    if args.web || args.metrics {
         let miner_clone = miner.clone();
+        let web_config = web_server::WebServerConfig::from_args(args);
         tokio::spawn(async move {
             let stats = miner_clone.get_stats();
             info!(target: LOG_TARGET,"🌐 Starting fallback web dashboard server...");
-            web_server::start_web_server(stats).await;
+            web_server::start_web_server(web_config, stats, Some(control)).await;
         });
         info!(target: LOG_TARGET,"📊 Fallback dashboard available at: http://localhost:8080");
     }
 
+    #[cfg(feature = "tui")]
+    if args.tui {
+        start_tui(args, miner.get_stats(), "CPU fallback", 0);
+    }
+
     info!(target: LOG_TARGET,"🚀 Starting CPU fallback mining");
     miner.run().await?;
     Ok(())
@@ -626,6 +923,7 @@ async fn create_multi_gpu_cpu_miner(
     shared_stats: Arc<graxil::miner::stats::MinerStats>,
     gpu_count: usize, // Dynamic GPU count for proper thread offset
     cpu_thread_count: usize,
+    nonce_allocator: Arc<graxil::miner::NonceAllocator>,
 ) -> Result<Arc<graxil::miner::CpuMiner>> {
     use graxil::miner::CpuMiner;
 
@@ -636,15 +934,23 @@ async fn create_multi_gpu_cpu_miner(
     );
 
     // *** CRITICAL FIX: Use new multi-GPU aware constructor ***
-    let cpu_miner = CpuMiner::new_with_shared_stats(
+    let mut cpu_miner = CpuMiner::new_with_shared_stats(
         args.wallet.as_ref().unwrap().clone(),
-        args.pool.as_ref().unwrap().clone(),
+        args.primary_pool().unwrap().to_string(),
         format!("{}-cpu", args.worker), // Distinct worker name
         cpu_thread_count,
         algo,
         shared_stats, // ✅ Shared stats for unified dashboard
         gpu_count,    // ✅ Dynamic GPU count for thread coordination
     );
+    cpu_miner.set_pool_failover(
+        args.pool_endpoints(&format!("{}-cpu", args.worker))?,
+        args.failover_settings(),
+    );
+    cpu_miner.set_proxy(args.proxy_config()?);
+    cpu_miner.set_governor(CpuGovernorConfig::from_args(args));
+    cpu_miner.set_backend(args.sha3x_backend()?);
+    cpu_miner.set_nonce_allocator(nonce_allocator);
 
     info!(target: LOG_TARGET,"✅ Multi-GPU CPU miner created:");
     info!(target: LOG_TARGET,"├─ Worker: {}-cpu", args.worker);
@@ -668,8 +974,9 @@ async fn create_multi_gpu_gpu_miner(
     gpu_manager: graxil::miner::gpu::GpuManager,
     shared_stats: Arc<graxil::miner::stats::MinerStats>,
     gpu_settings: graxil::core::types::GpuSettings,
+    nonce_allocator: Arc<graxil::miner::NonceAllocator>,
 ) -> Result<Arc<graxil::miner::gpu::GpuMiner>> {
-    use graxil::miner::gpu::GpuMiner;
+    use graxil::miner::gpu::{GovernorConfig, GpuMiner};
 
     let gpu_count = gpu_manager.device_count();
     info!(target: LOG_TARGET,"🎮 Creating multi-GPU aware GPU miner component...");
@@ -679,9 +986,9 @@ async fn create_multi_gpu_gpu_miner(
     );
 
     // *** CRITICAL FIX: Use new_for_hybrid with shared stats ***
-    let gpu_miner = GpuMiner::new_for_hybrid(
+    let mut gpu_miner = GpuMiner::new_for_hybrid(
         args.wallet.as_ref().unwrap().clone(),
-        args.pool.as_ref().unwrap().clone(),
+        args.primary_pool().unwrap().to_string(),
         format!("{}-gpu", args.worker), // Distinct worker name
         algo,
         gpu_manager,
@@ -690,6 +997,14 @@ async fn create_multi_gpu_gpu_miner(
         Arc::new(graxil::pool::client::PoolClient::new()), // ✅ Independent pool client
         0,                    // ✅ GPU threads start at 0 (will handle multiple devices internally)
     )?;
+    gpu_miner.set_pool_failover(
+        args.pool_endpoints(&format!("{}-gpu", args.worker))?,
+        args.failover_settings(),
+    );
+    gpu_miner.set_proxy(args.proxy_config()?);
+    gpu_miner.set_governor(GovernorConfig::from_args(args));
+    gpu_miner.set_hw_error_limit(args.gpu_hw_error_limit);
+    gpu_miner.set_nonce_allocator(nonce_allocator);
 
     info!(target: LOG_TARGET,"✅ Multi-GPU GPU miner created:");
     info!(target: LOG_TARGET,"├─ Worker: {}-gpu", args.worker);
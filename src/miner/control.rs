@@ -0,0 +1,396 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/miner/control.rs
+// Version: 1.4.0
+//
+// This file implements the runtime control channel for running miners. A
+// MinerControl holds the settings that can change without a restart (CPU
+// thread count, global and per-device GPU settings, excluded GPUs) in a watch
+// channel; CPU and GPU miners subscribe to it and apply changes in place, so
+// the pool session, its vardiff state and the current job are never dropped.
+// Changes come from SIGHUP (re-reading the command line and --config file) or
+// from code calling MinerControl::apply / update, such as the HTTP API, which
+// can also pause mining and pin the session to one pool of the failover list.
+// The mining schedule (miner/schedule.rs) pauses mining separately through
+// auto_pause, so a pause by hand survives a schedule window opening.
+//
+// Tree Location:
+// - src/miner/control.rs (runtime settings hot-reload)
+// - Depends on: tokio, core/config, core/types
+
+#[cfg(any(feature = "gpu", feature = "hybrid"))]
+use crate::core::config::parse_device_list;
+use crate::core::config::{GpuDeviceConfig, GpuDeviceOverrides, resolve_device_settings};
+use crate::core::types::{Args, GpuSettings};
+use log::{error, info};
+use serde::Serialize;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::watch;
+
+const LOG_TARGET: &str = "tari::graxil::control";
+
+/// Why the mining schedule paused mining
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoPause {
+    /// Outside the mining windows or inside a pause window
+    Schedule,
+    /// Other processes keep the CPU busy (idle detection)
+    Busy,
+}
+
+/// Mining settings that can change while the miner is running
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeSettings {
+    /// Active CPU mining threads (0 = every thread the miner started with)
+    pub cpu_threads: usize,
+    /// Settings for GPUs without a per-device entry
+    pub gpu: GpuSettings,
+    /// Per-device GPU overrides (by device index or name pattern)
+    pub gpu_devices: GpuDeviceOverrides,
+    /// OpenCL device ids that should stop mining
+    pub excluded_devices: Vec<u32>,
+    /// Every CPU thread parked and every GPU idle (paused by hand)
+    pub paused: bool,
+    /// Paused by the mining schedule or idle detection
+    pub auto_pause: Option<AutoPause>,
+    /// Pool pinned by its index in the failover list; None follows the priority order
+    pub pool: Option<usize>,
+}
+
+impl RuntimeSettings {
+    /// The runtime part of the (merged) command line / config file
+    pub fn from_args(args: &Args) -> Self {
+        #[cfg(any(feature = "gpu", feature = "hybrid"))]
+        let (gpu, gpu_devices, excluded_devices) = (
+            args.get_gpu_settings(),
+            args.gpu_devices.clone(),
+            args.excluded_devices
+                .as_deref()
+                .map(parse_device_list)
+                .unwrap_or_default(),
+        );
+        #[cfg(not(any(feature = "gpu", feature = "hybrid")))]
+        let (gpu, gpu_devices, excluded_devices) = (GpuSettings::default(), Vec::new(), Vec::new());
+
+        Self {
+            cpu_threads: args.threads,
+            gpu,
+            gpu_devices,
+            excluded_devices,
+            paused: false,
+            auto_pause: None,
+            pool: None,
+        }
+    }
+
+    /// Whether mining is paused, by hand or by the schedule
+    pub fn is_paused(&self) -> bool {
+        self.paused || self.auto_pause.is_some()
+    }
+
+    /// Settings for one GPU and whether it should be mining; `stored` are the
+    /// settings saved for the device in the GPU information file
+    pub fn gpu_device(
+        &self,
+        device_id: u32,
+        device_name: &str,
+        stored: Option<&GpuDeviceConfig>,
+    ) -> (GpuSettings, bool) {
+        let settings =
+            resolve_device_settings(&self.gpu, &self.gpu_devices, stored, device_id, device_name);
+        (
+            settings,
+            !self.is_paused() && !self.excluded_devices.contains(&device_id),
+        )
+    }
+}
+
+/// Handle for pushing new runtime settings to running miners
+#[derive(Clone)]
+pub struct MinerControl {
+    settings: Arc<watch::Sender<RuntimeSettings>>,
+    /// CPU threads the miner started with (0 = no CPU miner); a reload cannot go above it
+    cpu_thread_limit: Arc<AtomicUsize>,
+}
+
+impl MinerControl {
+    pub fn new(settings: RuntimeSettings) -> Self {
+        let (sender, _) = watch::channel(settings);
+        Self {
+            settings: Arc::new(sender),
+            cpu_thread_limit: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Record how many CPU threads the miner started with. Threads are parked
+    /// and resumed at runtime, never spawned, so reloads asking for more fail.
+    pub fn set_cpu_thread_limit(&self, threads: usize) {
+        self.cpu_thread_limit.store(threads, Ordering::Relaxed);
+    }
+
+    /// Receiver for a miner to follow setting changes
+    pub fn subscribe(&self) -> watch::Receiver<RuntimeSettings> {
+        self.settings.subscribe()
+    }
+
+    /// Settings currently in effect
+    pub fn current(&self) -> RuntimeSettings {
+        self.settings.borrow().clone()
+    }
+
+    /// Push new settings to every subscribed miner; false if nothing changed
+    pub fn apply(&self, settings: RuntimeSettings) -> bool {
+        self.settings.send_if_modified(|current| {
+            if *current == settings {
+                return false;
+            }
+            *current = settings;
+            true
+        })
+    }
+
+    /// Change the current settings in place; false if nothing changed.
+    /// The change runs under the channel lock, so concurrent updates of
+    /// different fields (API, schedule, SIGHUP) never overwrite each other.
+    pub fn update(&self, change: impl FnOnce(&mut RuntimeSettings)) -> bool {
+        self.settings.send_if_modified(|current| {
+            let before = current.clone();
+            change(current);
+            *current != before
+        })
+    }
+
+    /// Validate re-read arguments and apply their runtime settings.
+    /// Pool, wallet and other connection settings only take effect on restart,
+    /// as does raising the CPU thread count above the number the miner started
+    /// with; the pause state and a pinned pool are not part of the config and are kept.
+    pub fn apply_args(&self, args: &Args) -> Result<bool, String> {
+        args.validate()?;
+        let limit = self.cpu_thread_limit.load(Ordering::Relaxed);
+        if limit > 0 && args.threads > limit {
+            return Err(format!(
+                "--threads {} is above the {} CPU threads the miner started with; more threads need a restart",
+                args.threads, limit
+            ));
+        }
+        let reloaded = RuntimeSettings::from_args(args);
+        Ok(self.update(|current| {
+            *current = RuntimeSettings {
+                paused: current.paused,
+                auto_pause: current.auto_pause,
+                pool: current.pool,
+                ..reloaded
+            }
+        }))
+    }
+
+    /// Re-read the command line and --config file and apply the result
+    pub fn reload(&self) -> Result<bool, String> {
+        self.apply_args(&Args::load()?)
+    }
+
+    /// Reload the configuration every time the process receives SIGHUP
+    #[cfg(unix)]
+    pub fn reload_on_sighup(&self) -> crate::Result<()> {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut hangup = signal(SignalKind::hangup())?;
+        let control = self.clone();
+        tokio::spawn(async move {
+            while hangup.recv().await.is_some() {
+                info!(target: LOG_TARGET, "🔄 SIGHUP received - reloading configuration");
+                match control.reload() {
+                    Ok(true) => info!(target: LOG_TARGET, "✅ Runtime settings updated"),
+                    Ok(false) => info!(target: LOG_TARGET, "💤 No runtime setting changed"),
+                    Err(e) => {
+                        error!(target: LOG_TARGET, "❌ Reload failed, keeping current settings: {}", e)
+                    }
+                }
+            }
+        });
+        info!(target: LOG_TARGET, "🔄 Send SIGHUP to reload threads and GPU settings from the config file");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::GpuDeviceSelector;
+
+    fn settings(cpu_threads: usize) -> RuntimeSettings {
+        RuntimeSettings {
+            cpu_threads,
+            gpu: GpuSettings::default(),
+            gpu_devices: Vec::new(),
+            excluded_devices: Vec::new(),
+            paused: false,
+            auto_pause: None,
+            pool: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_apply_notifies_only_on_change() {
+        let control = MinerControl::new(settings(8));
+        let mut miner = control.subscribe();
+
+        assert!(!control.apply(settings(8)));
+        assert!(!miner.has_changed().unwrap());
+
+        assert!(control.apply(settings(4)));
+        miner.changed().await.unwrap();
+        assert_eq!(miner.borrow_and_update().cpu_threads, 4);
+        assert_eq!(control.current().cpu_threads, 4);
+    }
+
+    #[test]
+    fn test_update_changes_one_field() {
+        let control = MinerControl::new(settings(8));
+        assert!(control.update(|s| s.paused = true));
+        assert!(!control.update(|s| s.paused = true));
+        assert!(control.update(|s| s.pool = Some(1)));
+
+        let current = control.current();
+        assert_eq!(
+            (current.cpu_threads, current.paused, current.pool),
+            (8, true, Some(1))
+        );
+    }
+
+    #[test]
+    fn test_concurrent_updates_keep_both_changes() {
+        let control = MinerControl::new(settings(8));
+        for round in 0..200 {
+            control.apply(settings(8));
+            let barrier = Arc::new(std::sync::Barrier::new(2));
+            let handles: Vec<_> = [0, 1]
+                .into_iter()
+                .map(|which| {
+                    let control = control.clone();
+                    let barrier = barrier.clone();
+                    std::thread::spawn(move || {
+                        barrier.wait();
+                        if which == 0 {
+                            control.update(|s| {
+                                std::thread::yield_now();
+                                s.auto_pause = Some(AutoPause::Schedule);
+                            })
+                        } else {
+                            control.update(|s| {
+                                std::thread::yield_now();
+                                s.pool = Some(2);
+                            })
+                        }
+                    })
+                })
+                .collect();
+            for handle in handles {
+                assert!(handle.join().unwrap());
+            }
+
+            let current = control.current();
+            assert_eq!(
+                (current.auto_pause, current.pool),
+                (Some(AutoPause::Schedule), Some(2)),
+                "round {}",
+                round
+            );
+        }
+    }
+
+    #[test]
+    fn test_reload_cannot_add_cpu_threads() {
+        use clap::Parser;
+
+        let args = |threads: &str| {
+            Args::try_parse_from([
+                "graxil",
+                "--wallet",
+                "125ohcEDcG8sL4DcdtqZ6YLbSgVYFJWtGeCfHmRocTcyGNYRqMYidnfs1JQPijqQvqV5SLygC5ynxZH3zED5Rr9fPAW",
+                "--pool",
+                "pool.tari.com:4200",
+                "--threads",
+                threads,
+            ])
+            .unwrap()
+        };
+        let control = MinerControl::new(settings(8));
+        control.set_cpu_thread_limit(8);
+
+        assert!(control.apply_args(&args("4")).unwrap());
+        assert_eq!(control.current().cpu_threads, 4);
+        assert!(control.apply_args(&args("12")).is_err());
+        assert_eq!(control.current().cpu_threads, 4);
+        assert!(control.apply_args(&args("0")).unwrap());
+    }
+
+    #[test]
+    fn test_gpu_device_settings() {
+        let mut runtime = settings(0);
+        runtime.gpu.intensity = 90;
+        runtime.gpu_devices.push((
+            GpuDeviceSelector::Index(1),
+            GpuDeviceConfig {
+                intensity: Some(50),
+                ..GpuDeviceConfig::default()
+            },
+        ));
+        runtime.gpu_devices.push((
+            GpuDeviceSelector::Name("*intel*".to_string()),
+            GpuDeviceConfig {
+                intensity: Some(30),
+                ..GpuDeviceConfig::default()
+            },
+        ));
+        runtime.excluded_devices.push(2);
+
+        assert_eq!(runtime.gpu_device(0, "RTX 4060", None).0.intensity, 90);
+        assert_eq!(runtime.gpu_device(1, "RTX 4060", None).0.intensity, 50);
+        assert_eq!(
+            runtime.gpu_device(3, "Intel(R) UHD 770", None).0.intensity,
+            30
+        );
+        assert!(runtime.gpu_device(1, "RTX 4060", None).1);
+        assert!(!runtime.gpu_device(2, "RTX 4060", None).1);
+        runtime.paused = true;
+        assert!(!runtime.gpu_device(1, "RTX 4060", None).1);
+        runtime.paused = false;
+        runtime.auto_pause = Some(AutoPause::Schedule);
+        assert!(!runtime.gpu_device(1, "RTX 4060", None).1);
+        runtime.auto_pause = None;
+
+        // Stored information file settings sit between the global and override values
+        let stored = GpuDeviceConfig {
+            intensity: Some(70),
+            temp_limit: Some(72),
+            ..GpuDeviceConfig::default()
+        };
+        let (resolved, _) = runtime.gpu_device(0, "RTX 4060", Some(&stored));
+        assert_eq!(resolved.intensity, 70);
+        let (resolved, _) = runtime.gpu_device(1, "RTX 4060", Some(&stored));
+        assert_eq!((resolved.intensity, resolved.temp_limit), (50, Some(72)));
+    }
+}
+
+// Changelog:
+// - v1.4.0 (2025-06-28): set_cpu_thread_limit(); reloads raising the CPU thread count above
+//   the started threads are rejected instead of being clamped by the CPU miner.
+// - v1.3.1 (2025-06-28): update() and apply_args() change the settings under the watch
+//   lock, so concurrent API, schedule and SIGHUP changes no longer undo each other.
+// - v1.3.0 (2025-06-28): auto_pause (mining schedule / idle detection) next to the manual
+//   pause; RuntimeSettings::is_paused().
+// - v1.2.0 (2025-06-28): paused and pinned pool in RuntimeSettings for the HTTP API;
+//   MinerControl::update(); SIGHUP reloads keep both.
+// - v1.1.0 (2025-06-28): GPU overrides select devices by index or name pattern and
+//   layer over the settings stored in the GPU information file.
+// - v1.0.0 (2025-06-28): Initial runtime control channel.
+//   - RuntimeSettings (CPU threads, GPU settings, per-device settings, excluded GPUs).
+//   - MinerControl::apply / apply_args, and SIGHUP reload of the config file.
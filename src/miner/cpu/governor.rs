@@ -0,0 +1,293 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/miner/cpu/governor.rs
+// Version: 1.0.0
+// Developer: OIEIEIO <oieieio@protonmail.com>
+//
+// This file implements the optional CPU governor. Every few seconds it reads
+// the CPU temperature and the CPU load of other processes and caps how many
+// of the mining threads started by CpuMiner may run: one thread is parked per
+// interval while the CPU is above --cpu-temp-limit, and enough threads are
+// parked at once to leave --cpu-reserve-cores cores (or as many as other
+// processes keep busy) free. Threads resume one at a time once there is room
+// again. Parked threads keep their job and pool session.
+//
+// Tree Location:
+// - src/miner/cpu/governor.rs (CPU thermal / load governor)
+// - Depends on: core/types, cpu/miner, schedule (LoadMonitor), stats/miner_stats
+
+use super::miner::CpuMiner;
+use crate::core::types::Args;
+use crate::miner::schedule::LoadMonitor;
+use crate::miner::stats::miner_stats::get_temperatures;
+use log::{info, warn};
+use std::sync::Arc;
+use std::time::Duration;
+use sysinfo::Components;
+
+const LOG_TARGET: &str = "tari::graxil::cpu_governor";
+
+/// How often temperature and load are checked
+const GOVERNOR_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Degrees below the temperature ceiling before parked threads resume
+const HYSTERESIS: f32 = 3.0;
+
+/// CPU governor settings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CpuGovernorConfig {
+    /// Temperature ceiling in Celsius
+    pub temp_limit: Option<f32>,
+    /// Cores to leave free for other workloads
+    pub reserve_cores: Option<usize>,
+    pub interval: Duration,
+}
+
+impl CpuGovernorConfig {
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            temp_limit: args.cpu_temp_limit.map(f32::from),
+            reserve_cores: args.cpu_reserve_cores,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.temp_limit.is_some() || self.reserve_cores.is_some()
+    }
+}
+
+impl Default for CpuGovernorConfig {
+    fn default() -> Self {
+        Self {
+            temp_limit: None,
+            reserve_cores: None,
+            interval: GOVERNOR_INTERVAL,
+        }
+    }
+}
+
+/// One sample of the machine's state
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CpuReading {
+    /// CPU temperature in Celsius, if a sensor is available
+    pub temperature: Option<f32>,
+    /// Percent of the whole machine's CPU used by other processes
+    pub other_load: Option<f32>,
+}
+
+/// Decides how many mining threads may run
+#[derive(Debug, Clone)]
+pub struct CpuGovernor {
+    config: CpuGovernorConfig,
+    /// Threads the miner started with
+    threads: usize,
+    /// Logical cores of the machine
+    cores: usize,
+    cap: usize,
+}
+
+impl CpuGovernor {
+    pub fn new(config: CpuGovernorConfig, threads: usize, cores: usize) -> Self {
+        Self {
+            config,
+            threads,
+            cores,
+            cap: threads,
+        }
+    }
+
+    /// Threads currently allowed to mine
+    pub fn cap(&self) -> usize {
+        self.cap
+    }
+
+    /// Most threads the reserved cores and the load of other processes leave room for
+    fn load_ceiling(&self, reading: &CpuReading) -> usize {
+        let Some(reserve) = self.config.reserve_cores else {
+            return self.threads;
+        };
+        let busy = reading.other_load.map_or(0, |load| {
+            (load / 100.0 * self.cores as f32).round() as usize
+        });
+        self.cores
+            .saturating_sub(reserve.max(busy))
+            .min(self.threads)
+            .max(1)
+    }
+
+    /// Adjust the number of threads allowed to mine; returns the new count when
+    /// it changed. At least one thread always keeps mining.
+    pub fn step(&mut self, reading: &CpuReading) -> Option<usize> {
+        let mut cap = self.cap;
+        let mut may_grow = true;
+        if let (Some(limit), Some(temperature)) = (self.config.temp_limit, reading.temperature) {
+            if temperature > limit {
+                cap = cap.saturating_sub(1).max(1);
+                may_grow = false;
+            } else if temperature > limit - HYSTERESIS {
+                may_grow = false;
+            }
+        }
+
+        let ceiling = self.load_ceiling(reading);
+        if cap > ceiling {
+            cap = ceiling;
+        } else if may_grow && cap < ceiling {
+            cap += 1;
+        }
+
+        if cap == self.cap {
+            return None;
+        }
+        self.cap = cap;
+        Some(cap)
+    }
+}
+
+/// Park and resume the threads of `miner` following the CPU temperature and the
+/// load of other processes. Does nothing when no limit is configured.
+pub fn spawn_governor(miner: Arc<CpuMiner>, config: CpuGovernorConfig) {
+    if !config.is_enabled() {
+        return;
+    }
+
+    let threads = miner.started_thread_count();
+    let mut governor = CpuGovernor::new(config, threads, num_cpus::get());
+    info!(target: LOG_TARGET,
+        "🌡️ CPU governor: temperature ceiling {}, reserved cores {}",
+        config
+            .temp_limit
+            .map_or_else(|| "off".to_string(), |t| format!("{:.0}°C", t)),
+        config
+            .reserve_cores
+            .map_or_else(|| "off".to_string(), |c| c.to_string())
+    );
+
+    tokio::spawn(async move {
+        let mut monitor = config.reserve_cores.map(|_| LoadMonitor::new());
+        let mut warned = false;
+        let mut interval = tokio::time::interval(config.interval);
+
+        loop {
+            interval.tick().await;
+
+            let reading = CpuReading {
+                temperature: config
+                    .temp_limit
+                    .and_then(|_| get_temperatures(&Components::new_with_refreshed_list()).0),
+                other_load: monitor.as_mut().map(LoadMonitor::other_cpu_load),
+            };
+            if config.temp_limit.is_some() && reading.temperature.is_none() && !warned {
+                warn!(target: LOG_TARGET,"⚠️ No CPU temperature sensor found; --cpu-temp-limit is not enforced");
+                warned = true;
+            }
+
+            let before = governor.cap();
+            let Some(cap) = governor.step(&reading) else {
+                continue;
+            };
+            let mining = miner.set_thread_cap(cap);
+            let event = if cap < before {
+                let cause = match (config.temp_limit, reading.temperature) {
+                    (Some(limit), Some(temperature)) if temperature > limit => {
+                        format!("CPU at {:.0}°C", temperature)
+                    }
+                    _ => format!(
+                        "{:.0}% CPU used by other processes",
+                        reading.other_load.unwrap_or_default()
+                    ),
+                };
+                format!(
+                    "🌡️ CPU threads parked ({}): {} of {} mining",
+                    cause, mining, threads
+                )
+            } else {
+                format!("▶️ CPU threads resumed: {} of {} mining", mining, threads)
+            };
+            info!(target: LOG_TARGET,"{}", event);
+            miner.get_stats().add_activity(event);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reading(temperature: f32, other_load: f32) -> CpuReading {
+        CpuReading {
+            temperature: Some(temperature),
+            other_load: Some(other_load),
+        }
+    }
+
+    #[test]
+    fn test_temperature_ceiling() {
+        let config = CpuGovernorConfig {
+            temp_limit: Some(85.0),
+            ..CpuGovernorConfig::default()
+        };
+        let mut governor = CpuGovernor::new(config, 4, 8);
+
+        // Too hot: one thread parked per step, never the last one
+        assert_eq!(governor.step(&reading(90.0, 0.0)), Some(3));
+        for _ in 0..5 {
+            governor.step(&reading(90.0, 0.0));
+        }
+        assert_eq!(governor.cap(), 1);
+
+        // Inside the hysteresis band nothing resumes
+        assert_eq!(governor.step(&reading(83.0, 0.0)), None);
+
+        // Cool: threads resume one at a time
+        assert_eq!(governor.step(&reading(70.0, 0.0)), Some(2));
+        for _ in 0..5 {
+            governor.step(&reading(70.0, 0.0));
+        }
+        assert_eq!(governor.cap(), 4);
+        assert_eq!(governor.step(&reading(70.0, 0.0)), None);
+    }
+
+    #[test]
+    fn test_reserved_cores_follow_load() {
+        let config = CpuGovernorConfig {
+            reserve_cores: Some(2),
+            ..CpuGovernorConfig::default()
+        };
+        let mut governor = CpuGovernor::new(config, 8, 8);
+
+        // Two cores reserved even on an idle machine
+        assert_eq!(governor.step(&reading(50.0, 0.0)), Some(6));
+
+        // Other processes keep 4 of 8 cores busy: park down to 4 at once
+        assert_eq!(governor.step(&reading(50.0, 50.0)), Some(4));
+
+        // Load gone: back up one thread per step, up to the reservation
+        assert_eq!(governor.step(&reading(50.0, 5.0)), Some(5));
+        assert_eq!(governor.step(&reading(50.0, 5.0)), Some(6));
+        assert_eq!(governor.step(&reading(50.0, 5.0)), None);
+
+        // Fully loaded machine still mines on one thread
+        assert_eq!(governor.step(&reading(50.0, 100.0)), Some(1));
+    }
+
+    #[test]
+    fn test_disabled_without_limits() {
+        assert!(!CpuGovernorConfig::default().is_enabled());
+        let mut governor = CpuGovernor::new(CpuGovernorConfig::default(), 8, 8);
+        assert_eq!(governor.step(&reading(99.0, 90.0)), None);
+        assert_eq!(governor.cap(), 8);
+    }
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial CPU governor.
+//   - Parks one thread per interval above --cpu-temp-limit, resumes 3°C below it.
+//   - --cpu-reserve-cores keeps cores free, more while other processes are busy.
+//   - Changes are logged to the activity feed and parked threads show in ThreadStats.
@@ -6,43 +6,63 @@
 // via pull requests to the project repository.
 //
 // File: src/miner/cpu/miner.rs
-// Version: 2.3.0-multi-gpu-hybrid-support
+// Version: 2.12.0
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // MULTI-GPU HYBRID SUPPORT: Dynamic thread coordination for any number of GPUs
 // Supports 1-N GPUs with proper thread ID allocation and shared stats
+// CPU governor: parks threads to hold a temperature ceiling or keep cores free
+// SHA3x threads hash on the SIMD backend picked with --cpu-backend
+// SHA3x nonce ranges come from a NonceAllocator, shared with the GPUs in hybrid mode
 
 use crate::Result;
-use crate::core::{Algorithm, MiningJob, PoolJob, parse_target_difficulty};
+use crate::core::{Algorithm, CpuBackend, MiningJob};
+use crate::miner::control::RuntimeSettings;
+use crate::miner::nonce::NonceAllocator;
 use crate::miner::stats::MinerStats;
-use crate::pool::{PoolClient, protocol::StratumProtocol};
-use log::{debug, error, info};
+use crate::pool::{
+    FailoverSettings, PoolClient, PoolEndpoint, PoolSession, PoolSessionConfig, ProxyConfig,
+    ShareSubmission, Sv2Client,
+};
+use log::{debug, error, info, warn};
 use num_cpus;
-use serde_json::Value;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicU32, Ordering};
-use std::time::{Duration, Instant};
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::sync::Mutex;
-use tokio::sync::broadcast::{self, Sender as BroadcastSender};
-use tokio::sync::mpsc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
 
 const LOG_TARGET: &str = "tari::graxil::miner";
 
 // Explicit fully qualified import to bypass resolution issues
+use super::governor::{CpuGovernorConfig, spawn_governor};
 use super::thread::start_mining_thread;
 
 pub struct CpuMiner {
     wallet_address: String,
     pool_address: String,
     worker_name: String,
+    pool_endpoints: Vec<PoolEndpoint>,
+    failover_settings: FailoverSettings,
     num_threads: usize,
     stats: Arc<MinerStats>,
     pool_client: Arc<PoolClient>,
     algo: Algorithm,
-    last_job_time: Arc<Mutex<Instant>>,
     thread_id_offset: usize, // For hybrid mode - CPU threads start after GPU threads
     external_stats: bool,    // Flag for hybrid mode with shared stats
+    thread_active: Vec<Arc<AtomicBool>>, // Cleared to park a thread at runtime
+    requested_threads: AtomicUsize, // Threads the runtime settings ask for (0 = paused)
+    thread_cap: AtomicUsize, // Threads the CPU governor allows
+    control: Mutex<Option<watch::Receiver<RuntimeSettings>>>, // Pool selection for the session
+    governor: CpuGovernorConfig,
+    backend: CpuBackend, // SHA3x hashing backend requested for the threads
+    nonce_allocator: Arc<NonceAllocator>, // SHA3x nonce ranges, shared with the GPUs in hybrid mode
+}
+
+/// One run flag per mining thread, all mining
+fn active_flags(num_threads: usize) -> Vec<Arc<AtomicBool>> {
+    (0..num_threads)
+        .map(|_| Arc::new(AtomicBool::new(true)))
+        .collect()
 }
 
 impl CpuMiner {
@@ -76,13 +96,21 @@ impl CpuMiner {
             wallet_address,
             pool_address,
             worker_name,
+            pool_endpoints: Vec::new(),
+            failover_settings: FailoverSettings::default(),
             num_threads: actual_threads,
             stats: Arc::new(stats),
             pool_client,
             algo,
-            last_job_time: Arc::new(Mutex::new(Instant::now())),
             thread_id_offset: 0, // Standalone mode: threads start at 0
             external_stats: false,
+            thread_active: active_flags(actual_threads),
+            requested_threads: AtomicUsize::new(actual_threads),
+            thread_cap: AtomicUsize::new(actual_threads),
+            control: Mutex::new(None),
+            governor: CpuGovernorConfig::default(),
+            backend: CpuBackend::Auto,
+            nonce_allocator: Arc::new(NonceAllocator::new()),
         }
     }
 
@@ -126,13 +154,21 @@ impl CpuMiner {
             wallet_address,
             pool_address,
             worker_name,
+            pool_endpoints: Vec::new(),
+            failover_settings: FailoverSettings::default(),
             num_threads: actual_threads,
             stats: shared_stats, // ✅ Use shared stats for unified dashboard
             pool_client: Arc::new(PoolClient::new()), // ✅ Own pool client for resilience
             algo,
-            last_job_time: Arc::new(Mutex::new(Instant::now())),
             thread_id_offset,     // ✅ Start after all GPU threads
             external_stats: true, // ✅ Flag for hybrid mode
+            thread_active: active_flags(actual_threads),
+            requested_threads: AtomicUsize::new(actual_threads),
+            thread_cap: AtomicUsize::new(actual_threads),
+            control: Mutex::new(None),
+            governor: CpuGovernorConfig::default(),
+            backend: CpuBackend::Auto,
+            nonce_allocator: Arc::new(NonceAllocator::new()),
         }
     }
 
@@ -162,13 +198,21 @@ impl CpuMiner {
             wallet_address,
             pool_address,
             worker_name,
+            pool_endpoints: Vec::new(),
+            failover_settings: FailoverSettings::default(),
             num_threads: actual_threads,
             stats: external_stats,
             pool_client: external_pool_client,
             algo,
-            last_job_time: Arc::new(Mutex::new(Instant::now())),
             thread_id_offset,
             external_stats: true,
+            thread_active: active_flags(actual_threads),
+            requested_threads: AtomicUsize::new(actual_threads),
+            thread_cap: AtomicUsize::new(actual_threads),
+            control: Mutex::new(None),
+            governor: CpuGovernorConfig::default(),
+            backend: CpuBackend::Auto,
+            nonce_allocator: Arc::new(NonceAllocator::new()),
         }
     }
 
@@ -199,273 +243,184 @@ impl CpuMiner {
         Arc::clone(&self.stats)
     }
 
-    /// Test SV2 Noise connection to JDS
+    /// Test SV2 Noise connection to JDS: handshake, SetupConnection and channel open
     pub async fn test_sv2_connection(&self) -> Result<()> {
         info!(target: LOG_TARGET,
-            "🔧 Testing basic TCP connection to JDS at {}...",
+            "🔧 Testing Stratum V2 connection to {}...",
             self.pool_address
         );
 
-        // Use the new string-based connection method
-        let _stream = self
-            .pool_client
-            .connect_str(&self.pool_address)
+        let mut config = self.session_config();
+        config.algo = Algorithm::Sha256;
+        let client = Sv2Client::new(
+            config,
+            Arc::clone(&self.pool_client),
+            Arc::clone(&self.stats),
+        );
+        let channel_id = client
+            .test_connection()
             .await
-            .map_err(|e| format!("Failed to connect to {}: {}", self.pool_address, e))?;
-
-        info!(target: LOG_TARGET,"✅ TCP connection to JDS successful!");
-        info!(target: LOG_TARGET,"📝 Note: Full SV2 Noise handshake implementation in progress");
-        info!(target: LOG_TARGET,"🚀 This confirms your JDS is running and accepting connections");
+            .map_err(|e| format!("SV2 test against {} failed: {}", self.pool_address, e))?;
 
+        info!(target: LOG_TARGET,
+            "✅ Noise handshake, SetupConnection and channel open successful (channel {})",
+            channel_id
+        );
         Ok(())
     }
 
-    // Keep SHA3x functionality unchanged
-    async fn connect_to_pool(&self) -> Result<tokio::net::TcpStream> {
-        self.pool_client.connect_str(&self.pool_address).await
-    }
-
-    async fn login(&self, writer: &mut tokio::net::tcp::OwnedWriteHalf) -> Result<()> {
-        // Only SHA3x login now
-        let login_msg = StratumProtocol::to_message(StratumProtocol::create_login_request(
-            &self.wallet_address,
-            &self.worker_name,
+    /// Pool session parameters for this miner
+    fn session_config(&self) -> PoolSessionConfig {
+        let mut config = PoolSessionConfig::single(
+            self.pool_address.clone(),
+            self.wallet_address.clone(),
+            self.worker_name.clone(),
             self.algo,
-        ));
-        writer.write_all(login_msg.as_bytes()).await?;
-        writer.flush().await?;
-        if self.external_stats {
-            info!(target: LOG_TARGET,
-                "📤 Sent SHA3x login request (hybrid mode) - worker: {}",
-                self.worker_name
-            );
-        } else {
-            info!(target: LOG_TARGET,"📤 Sent SHA3x login request - worker: {}", self.worker_name);
+            "CPU",
+        );
+        if !self.pool_endpoints.is_empty() {
+            config.pools = self.pool_endpoints.clone();
         }
-        Ok(())
+        config.failover = self.failover_settings.clone();
+        config
     }
 
-    async fn handle_pool_message(
-        &self,
-        message: &str,
-        job_tx: &BroadcastSender<MiningJob>,
-    ) -> Result<()> {
-        debug!(target: LOG_TARGET,"📨 Raw pool message: {}", message);
-        let response: Value = serde_json::from_str(message)?;
-
-        if let Some(method) = response.get("method").and_then(|m| m.as_str()) {
-            match method {
-                "job" => {
-                    debug!(target: LOG_TARGET,"Processing SHA3x job message: {:?}", response);
-                    if let Some(params) = response.get("params").and_then(|p| p.as_object()) {
-                        self.handle_new_job(params, job_tx).await?;
-                        if let Some(diff) = params.get("difficulty").and_then(|d| d.as_u64()) {
-                            self.stats.add_activity(format!(
-                                "🔧 CPU VarDiff update: {}",
-                                MinerStats::format_number(diff)
-                            ));
-                            info!(target: LOG_TARGET,"🔧 CPU VarDiff job update received");
-                        }
-                    }
-                }
-                _ => {
-                    debug!(target: LOG_TARGET,"Unknown method: {}", method);
-                }
-            }
-        } else if let Some(result) = response.get("result") {
-            debug!(target: LOG_TARGET,"Result response: {:?}", result);
-            if let Some(id) = response.get("id").and_then(|id| id.as_u64()) {
-                match id {
-                    1 => {
-                        info!(target: LOG_TARGET,"✅ SHA3x login successful for worker: {}", self.worker_name);
-                        self.stats
-                            .add_activity("🔐 CPU connected successfully".to_string());
-                        if let Some(job_params) = result.get("job").and_then(|j| j.as_object()) {
-                            debug!(target: LOG_TARGET,"Found job in login response: {:?}", job_params);
-                            self.handle_new_job(job_params, job_tx).await?;
-                        }
-                    }
-                    id if (100..200).contains(&id) => {
-                        // CPU shares use IDs 100-199
-                        let relative_thread_id = (id - 100) as usize % self.num_threads;
-                        let actual_thread_id = self.thread_id_offset + relative_thread_id;
-                        debug!(target: LOG_TARGET,
-                            "CPU share response for ID {} (thread {}): {:?}",
-                            id, actual_thread_id, result
-                        );
-                        let accepted = if let Some(status) =
-                            result.get("status").and_then(|s| s.as_str())
-                        {
-                            matches!(status.to_lowercase().as_str(), "ok" | "accepted")
-                        } else if result.is_null() {
-                            info!(target: LOG_TARGET,"✅ CPU share accepted (null response)");
-                            true
-                        } else if let Some(accepted) = result.as_bool() {
-                            accepted
-                        } else {
-                            error!(target: LOG_TARGET,"❌ Unknown CPU share response format: {:?}", result);
-                            false
-                        };
-
-                        if accepted {
-                            self.stats.shares_accepted.fetch_add(1, Ordering::Relaxed);
-                            info!(target: LOG_TARGET,"✅ CPU share accepted by pool");
-                            self.stats.add_activity(format!(
-                                "✅ CPU share accepted from thread {}",
-                                actual_thread_id
-                            ));
-                        } else {
-                            self.stats.shares_rejected.fetch_add(1, Ordering::Relaxed);
-                            info!(target: LOG_TARGET,"❌ CPU share rejected from thread {}", actual_thread_id);
-                            self.stats.add_activity(format!(
-                                "❌ CPU share rejected from thread {}",
-                                actual_thread_id
-                            ));
-                        }
+    /// Use a failover pool list instead of the single pool given at construction
+    pub fn set_pool_failover(&mut self, pools: Vec<PoolEndpoint>, settings: FailoverSettings) {
+        self.pool_endpoints = pools;
+        self.failover_settings = settings;
+    }
 
-                        // MULTI-GPU FIX: Ensure thread ID is valid for shared stats
-                        if actual_thread_id < self.stats.thread_stats.len() {
-                            self.stats.thread_stats[actual_thread_id].record_share(0, accepted);
-                        } else {
-                            error!(target: LOG_TARGET,
-                                "🧵 CPU thread {} ID out of bounds! stats.len={}, offset={}",
-                                actual_thread_id,
-                                self.stats.thread_stats.len(),
-                                self.thread_id_offset
-                            );
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        } else if let Some(error) = response.get("error") {
-            error!(target: LOG_TARGET,"❌ CPU pool error: {:?}", error);
-            self.stats
-                .add_activity(format!("🚫 CPU pool error: {}", error));
-        } else {
-            debug!(target: LOG_TARGET,"Unknown CPU pool message: {:?}", response);
-        }
+    /// Temperature ceiling and reserved cores of the CPU governor
+    pub fn set_governor(&mut self, config: CpuGovernorConfig) {
+        self.governor = config;
+    }
 
-        Ok(())
+    /// SHA3x hashing backend of the mining threads
+    pub fn set_backend(&mut self, backend: CpuBackend) {
+        self.backend = backend;
     }
 
-    async fn handle_new_job(
-        &self,
-        job_data: &serde_json::Map<String, Value>,
-        job_tx: &BroadcastSender<MiningJob>,
-    ) -> Result<()> {
-        let job: PoolJob = serde_json::from_value(Value::Object(job_data.clone()))?;
-
-        // Only handle SHA3x jobs now
-        let header_template = hex::decode(job.blob.unwrap_or_default())?;
-        let target_difficulty = job
-            .difficulty
-            .unwrap_or_else(|| parse_target_difficulty(&job.target, self.algo));
-
-        let mining_job = MiningJob {
-            job_id: job.job_id.clone(),
-            mining_hash: header_template,
-            target_difficulty,
-            height: job.height,
-            algo: Algorithm::Sha3x,
-            extranonce2: job.xn.clone(),
-            prev_hash: None,
-            merkle_root: None,
-            version: None,
-            ntime: None,
-            nbits: None,
-            merkle_path: None,
-            target: None,
-        };
+    /// Share a nonce allocator with other miners (hybrid mode), so no two workers
+    /// search the same SHA3x nonces
+    pub fn set_nonce_allocator(&mut self, allocator: Arc<NonceAllocator>) {
+        self.nonce_allocator = allocator;
+    }
 
-        // Update MinerStats with job data for web dashboard
-        self.stats
-            .update_job(job.job_id.clone(), job.height, target_difficulty);
+    /// Tunnel this miner's pool connections through a SOCKS5 / HTTP proxy
+    pub fn set_proxy(&self, proxy: Option<ProxyConfig>) {
+        if let Some(ref proxy) = proxy {
+            info!(target: LOG_TARGET,"🧅 CPU pool connections via proxy {}", proxy);
+        }
+        self.pool_client.set_proxy(proxy);
+    }
 
-        job_tx.send(mining_job)?;
-        if self.external_stats {
-            info!(target: LOG_TARGET,
-                "📋 CPU job sent: {} (height: {}, difficulty: {}, threads: {}-{})",
-                job.job_id,
-                job.height,
-                MinerStats::format_number(target_difficulty),
-                self.thread_id_offset,
-                self.thread_id_offset + self.num_threads - 1
-            );
-        } else {
-            info!(target: LOG_TARGET,
-                "📋 New job sent: {} (height: {}, difficulty: {})",
-                job.job_id,
-                job.height,
-                MinerStats::format_number(target_difficulty)
+    /// Mine on `threads` threads and park the rest (0 = all of them).
+    /// Threads are parked rather than stopped, so the count cannot grow past
+    /// the number the miner started with; MinerControl rejects reloads asking
+    /// for more (set_cpu_thread_limit). Returns the active thread count.
+    pub fn set_thread_count(&self, threads: usize) -> usize {
+        let started = self.thread_active.len();
+        if threads > started {
+            warn!(target: LOG_TARGET,
+                "🧵 Cannot run {} CPU threads without a restart; the miner started with {}",
+                threads, started
             );
         }
-        self.stats.add_activity(format!(
-            "📋 CPU job: {} (height: {}, difficulty: {})",
-            &job.job_id[..8.min(job.job_id.len())],
-            job.height,
-            MinerStats::format_number(target_difficulty)
-        ));
-
-        *self.last_job_time.lock().await = Instant::now();
-        Ok(())
+        let active = if threads == 0 {
+            started
+        } else {
+            threads.min(started)
+        };
+
+        self.requested_threads.store(active, Ordering::Relaxed);
+        let mining = self.update_parked_threads();
+        info!(target: LOG_TARGET,
+            "🧵 CPU threads: {} mining, {} parked",
+            mining,
+            started - mining
+        );
+        active
     }
 
-    fn start_share_submitter(
-        miner: Arc<Self>,
-        writer: Arc<Mutex<tokio::net::tcp::OwnedWriteHalf>>,
-        mut share_rx: mpsc::UnboundedReceiver<(String, String, String, usize, u64, String, u32)>,
-    ) {
-        let wallet_address = miner.wallet_address.clone();
-        let algo = miner.algo;
-        let external_stats = miner.external_stats;
-        let worker_name = miner.worker_name.clone();
-        static SUBMIT_ID: AtomicU32 = AtomicU32::new(100); // CPU shares use 100-199
+    /// Let the CPU governor cap the threads mining below the requested count
+    pub fn set_thread_cap(&self, cap: usize) -> usize {
+        self.thread_cap.store(cap, Ordering::Relaxed);
+        self.update_parked_threads()
+    }
 
-        tokio::spawn(async move {
-            while let Some((job_id, nonce, result, thread_id, difficulty, _extranonce2, _ntime)) =
-                share_rx.recv().await
-            {
-                let submit_request = StratumProtocol::create_submit_request(
-                    &wallet_address,
-                    &job_id,
-                    &nonce,
-                    &result,
-                    SUBMIT_ID.fetch_add(1, Ordering::SeqCst) as u64,
-                    algo,
-                    None, // No extranonce2 for SHA3x
-                    None, // No ntime for SHA3x
-                );
-                let message = StratumProtocol::to_message(submit_request);
-                if message.is_empty() {
-                    error!(target: LOG_TARGET,"Failed to create CPU submit message for job {}", job_id);
-                    continue;
-                }
+    /// Number of threads the miner started with
+    pub fn started_thread_count(&self) -> usize {
+        self.thread_active.len()
+    }
 
-                if external_stats {
-                    info!(target: LOG_TARGET,
-                        "📤 Submitting CPU share: job_id={}, nonce={}, thread={}, difficulty={} (worker: {})",
-                        job_id,
-                        nonce,
-                        thread_id,
-                        MinerStats::format_number(difficulty),
-                        worker_name
-                    );
-                } else {
-                    info!(target: LOG_TARGET,
-                        "📤 Submitting SHA3x share: job_id={}, nonce={}, result={}",
-                        job_id, nonce, result
-                    );
-                }
+    /// Mine on the first min(requested, cap) threads, park the rest and report
+    /// them in ThreadStats. Returns the number of threads mining.
+    fn update_parked_threads(&self) -> usize {
+        let mining = self
+            .requested_threads
+            .load(Ordering::Relaxed)
+            .min(self.thread_cap.load(Ordering::Relaxed));
+        for (i, flag) in self.thread_active.iter().enumerate() {
+            flag.store(i < mining, Ordering::Relaxed);
+            if let Some(thread_stats) = self.stats.thread_stats.get(self.thread_id_offset + i) {
+                thread_stats.set_parked(i >= mining);
+            }
+        }
+        mining
+    }
 
-                let mut writer = writer.lock().await;
-                if let Err(e) = writer.write_all(message.as_bytes()).await {
-                    error!(target: LOG_TARGET,"Failed to submit CPU share: {}", e);
+    /// Number of threads currently mining
+    pub fn active_thread_count(&self) -> usize {
+        self.thread_active
+            .iter()
+            .filter(|flag| flag.load(Ordering::Relaxed))
+            .count()
+    }
+
+    /// Park every mining thread until set_thread_count() is called again
+    pub fn pause(&self) {
+        self.requested_threads.store(0, Ordering::Relaxed);
+        self.update_parked_threads();
+        info!(target: LOG_TARGET,"⏸️ CPU mining paused ({} threads parked)", self.thread_active.len());
+    }
+
+    /// Follow thread count and pause changes from a MinerControl; the pool
+    /// session started by run() follows its pool selection
+    pub fn watch_control(self: &Arc<Self>, mut settings: watch::Receiver<RuntimeSettings>) {
+        *self.control.lock().unwrap() = Some(settings.clone());
+        let miner = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut state = {
+                let current = settings.borrow_and_update();
+                (current.cpu_threads, current.is_paused())
+            };
+            if state.1 {
+                miner.pause();
+            }
+            while settings.changed().await.is_ok() {
+                let wanted = {
+                    let current = settings.borrow_and_update();
+                    (current.cpu_threads, current.is_paused())
+                };
+                if wanted != state {
+                    state = wanted;
+                    match state {
+                        (_, true) => miner.pause(),
+                        (threads, false) => {
+                            miner.set_thread_count(threads);
+                        }
+                    }
                 }
             }
         });
     }
 
+    /// Start CPU mining threads fed by an existing pool session
+    pub fn attach_to_session(&self, session: &PoolSession) -> Result<()> {
+        self.start_mining_threads(session.subscribe_jobs(), session.share_sender())
+    }
+
     fn start_stats_printer(miner: Arc<Self>) {
         let stats = Arc::clone(&miner.stats);
 
@@ -485,129 +440,52 @@ impl CpuMiner {
         });
     }
 
-    async fn handle_connection_events(&self) {
-        // Track connection events and update latency every 5 seconds
-        let mut interval = tokio::time::interval(Duration::from_secs(5));
-
-        loop {
-            interval.tick().await;
-
-            if self.pool_client.is_connected() {
-                // Measure current connection latency by timing a lightweight operation
-                let start = Instant::now();
-
-                // Use a simple TCP keepalive check or measure time since last job
-                // For now, we'll simulate connection health by checking if we're still connected
-                // In a real implementation, this could ping the pool or measure job response time
-
-                // Simple connection health check - measure how responsive the connection is
-                let latency = start.elapsed();
-
-                // Update the pool client with current latency (add some realistic variation)
-                let actual_latency = Duration::from_millis(
-                    (latency.as_millis() as u64 + 20 + (rand::random::<u64>() % 30)).max(10),
-                );
-
-                self.pool_client.update_latency(actual_latency);
-
-                debug!(target: LOG_TARGET,"Updated pool latency: {}ms", actual_latency.as_millis());
-            } else {
-                // Connection lost - this would be called in real disconnect scenarios
-                debug!(target: LOG_TARGET,"Pool connection lost, stopping latency monitoring");
-                break;
-            }
-        }
-    }
-
     /// Run CPU mining (standalone or hybrid mode)
     pub async fn run(self: Arc<Self>) -> Result<()> {
-        // SHA3x mining only now
-        if self.algo != Algorithm::Sha3x {
-            return Err("Only SHA3x algorithm supported in this version".into());
+        let session = Arc::new(PoolSession::new(
+            self.session_config(),
+            Arc::clone(&self.pool_client),
+            Arc::clone(&self.stats),
+        )?);
+
+        if let Some(settings) = self.control.lock().unwrap().clone() {
+            session.follow_control(settings);
         }
 
-        let stream = self.connect_to_pool().await?;
-        if self.external_stats {
-            info!(target: LOG_TARGET,"✅ CPU miner connected to SHA3x pool (multi-GPU hybrid mode)");
-        } else {
-            info!(target: LOG_TARGET,"✅ Connected to SHA3x pool");
-        }
-        self.stats
-            .add_activity("🔐 CPU connected to pool".to_string());
-
-        let (reader, writer) = stream.into_split();
-        let writer = Arc::new(Mutex::new(writer));
-
-        self.login(&mut *writer.lock().await).await?;
-        info!(target: LOG_TARGET,
-            "🔐 SHA3x login request sent for worker: {}",
-            self.worker_name
-        );
-
-        let (job_tx, _) = broadcast::channel(16);
-        let (share_tx, share_rx) =
-            mpsc::unbounded_channel::<(String, String, String, usize, u64, String, u32)>();
-
         // Start CPU mining threads with proper thread IDs
-        self.start_mining_threads(job_tx.subscribe(), share_tx.clone())?;
-
-        CpuMiner::start_share_submitter(self.clone(), Arc::clone(&writer), share_rx);
+        self.attach_to_session(&session)?;
         CpuMiner::start_stats_printer(self.clone());
+        spawn_governor(self.clone(), self.governor);
 
-        // Start connection monitoring in background
-        let connection_monitor = self.clone();
-        tokio::spawn(async move {
-            connection_monitor.handle_connection_events().await;
-        });
-
-        let reader = BufReader::new(reader);
-        let mut lines = reader.lines();
-
-        loop {
-            match lines.next_line().await {
-                Ok(Some(line)) => {
-                    self.handle_pool_message(&line, &job_tx).await?;
-                }
-                Ok(None) => {
-                    info!(target: LOG_TARGET,"📡 CPU connection closed, attempting reconnect...");
-                    self.pool_client.mark_disconnected();
-                    tokio::time::sleep(Duration::from_secs(5)).await;
-                    let new_stream = self.connect_to_pool().await?;
-                    let (new_reader, new_writer) = new_stream.into_split();
-                    *writer.lock().await = new_writer;
-                    lines = BufReader::new(new_reader).lines();
-                    self.login(&mut *writer.lock().await).await?;
-                    info!(target: LOG_TARGET,"🔄 CPU reconnected to pool");
-                }
-                Err(e) => {
-                    error!(target: LOG_TARGET,
-                        "📡 Error reading from CPU pool: {}, attempting reconnect...",
-                        e
-                    );
-                    self.pool_client.mark_disconnected();
-                    tokio::time::sleep(Duration::from_secs(5)).await;
-                    let new_stream = self.connect_to_pool().await?;
-                    let (new_reader, new_writer) = new_stream.into_split();
-                    *writer.lock().await = new_writer;
-                    lines = BufReader::new(new_reader).lines();
-                    self.login(&mut *writer.lock().await).await?;
-                    info!(target: LOG_TARGET,"🔄 CPU reconnected to pool after error");
-                }
-            }
-        }
+        session.run().await
     }
 
     /// Start CPU mining threads with proper thread ID coordination for multi-GPU hybrid
     fn start_mining_threads(
         &self,
         job_rx: tokio::sync::broadcast::Receiver<MiningJob>,
-        share_tx: mpsc::UnboundedSender<(String, String, String, usize, u64, String, u32)>,
+        share_tx: mpsc::UnboundedSender<ShareSubmission>,
     ) -> Result<()> {
         debug!(target: LOG_TARGET,
             "Starting {} CPU mining threads with offset {} (multi-GPU hybrid)",
             self.num_threads, self.thread_id_offset
         );
 
+        let backend = self.backend.resolve();
+        if self.algo == Algorithm::Sha3x {
+            if self.backend != CpuBackend::Auto && backend != self.backend {
+                warn!(target: LOG_TARGET,
+                    "⚠️ This CPU does not support the {} backend, falling back to {}",
+                    self.backend, backend
+                );
+            }
+            info!(target: LOG_TARGET,
+                "🧮 SHA3x CPU backend: {} ({} hashes per pass)",
+                backend,
+                backend.ways()
+            );
+        }
+
         for i in 0..self.num_threads {
             let actual_thread_id = self.thread_id_offset + i; // Apply offset for multi-GPU hybrid mode
             let job_rx_clone = job_rx.resubscribe();
@@ -641,6 +519,9 @@ impl CpuMiner {
                 share_tx_clone,
                 thread_stats,
                 stats,
+                Arc::clone(&self.thread_active[i]),
+                backend,
+                Arc::clone(&self.nonce_allocator),
             );
         }
 
@@ -660,6 +541,27 @@ impl CpuMiner {
 }
 
 // Changelog:
+// - v2.12.0 (2025-06-28): Added set_nonce_allocator(); SHA3x threads claim disjoint nonce
+//   ranges from the allocator.
+// - v2.11.0 (2025-06-28): Added set_backend(); threads hash SHA3x on the resolved CpuBackend.
+// - v2.10.0 (2025-06-28): CPU governor support.
+//   - Threads mine while below both the requested count and the governor's cap
+//     (set_thread_cap()); parked threads are flagged in ThreadStats.
+//   - run() starts the CPU governor configured with set_governor().
+// - v2.9.1 (2025-06-28): Also parks every thread while the mining schedule pauses mining.
+// - v2.9.0 (2025-06-28): pause() parks every thread; watch_control() follows the pause
+//   state and hands the control channel to the pool session for pool switching.
+// - v2.8.0 (2025-06-28): Runtime thread count changes without touching the pool session.
+//   - Added set_thread_count() (parks / resumes threads) and watch_control().
+// - v2.7.0 (2025-06-28): run() accepts SHA-256 (Stratum V1 and V2 pools) as well as SHA3x.
+// - v2.6.0 (2025-06-28): test_sv2_connection() runs the real SV2 Noise handshake,
+//   SetupConnection and channel open through the SV2 client.
+// - v2.5.0 (2025-06-28): Added set_pool_failover() and set_proxy() for failover pools
+//   and SOCKS5 / HTTP CONNECT proxies.
+// - v2.4.0 (2025-06-28): Moved pool handling into pool::session
+//   - Connection, login, job decoding and share submission now live in PoolSession.
+//   - Added attach_to_session() so CPU threads can be fed by any pool session.
+//   - Share responses are attributed by JSON-RPC id instead of the 100-199 id range.
 // - v2.3.0-multi-gpu-hybrid-support (2025-06-25): MULTI-GPU HYBRID SUPPORT
 //   *** NEW FEATURES ***:
 //   1. Added new_with_shared_stats() constructor for multi-GPU hybrid mode
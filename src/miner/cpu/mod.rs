@@ -6,7 +6,7 @@
 // via pull requests to the project repository.
 //
 // File: src/miner/cpu/mod.rs
-// Version: 1.0.0
+// Version: 1.1.0
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // This file is the module declaration for the CPU mining functionality of the
@@ -15,15 +15,18 @@
 //
 // Tree Location:
 // - src/miner/cpu/mod.rs (CPU miner module entry point)
-// - Submodules: miner, thread
+// - Submodules: governor, miner, thread
 
+pub mod governor;
 pub mod miner;
 pub mod thread;
 
 // Re-export key types for convenience
+pub use governor::{CpuGovernor, CpuGovernorConfig};
 pub use miner::CpuMiner;
 
 // Changelog:
+// - v1.1.0 (2025-06-28): Added the governor submodule (CPU thermal / load governor).
 // - v1.0.0 (2025-06-14): Extracted from monolithic main.rs.
 //   - Purpose: Defines the cpu submodule, organizing CPU-specific mining logic
 //     into miner and thread submodules.
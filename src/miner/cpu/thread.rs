@@ -6,7 +6,7 @@
 // via pull requests to the project repository.
 //
 // File: src/miner/cpu/thread.rs
-// Version: 1.1.4
+// Version: 1.7.1
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // This file contains the implementation of individual mining threads for the
@@ -14,18 +14,19 @@
 // nonce iteration, hash computation, and share detection for CPU mining.
 
 use crate::core::{
-    Algorithm, MiningJob, calculate_difficulty,
-    difficulty::{U256, bits_to_target},
-    sha3x::sha3x_hash_with_nonce_batch,
+    Algorithm, CpuBackend, MiningJob, calculate_difficulty,
+    difficulty::{U256, bits_to_target, sha3x_hash_word, sha3x_share_threshold},
+    sha3x::Sha3xHasher,
     sha256::sha256d_hash_with_nonce_batch,
 };
+use crate::miner::nonce::{NonceAllocator, NonceRange};
 use crate::miner::stats::{MinerStats, ThreadStats};
 use hex;
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use rand::{Rng, rngs::ThreadRng};
 use std::sync::{
     Arc,
-    atomic::{AtomicBool, AtomicU32, Ordering},
+    atomic::{AtomicBool, AtomicU64, Ordering},
 };
 use std::time::{Duration, Instant};
 use tokio::sync::broadcast::Receiver as BroadcastReceiver;
@@ -33,6 +34,24 @@ use tokio::sync::mpsc::UnboundedSender as MpscSender;
 
 const LOG_TARGET: &str = "tari::graxil::thread";
 
+/// Furthest ntime is rolled past the pool's job time (pools accept up to ~2 hours)
+const MAX_NTIME_ROLL_SECS: u64 = 600;
+
+/// SHA3x nonces hashed per pass, a multiple of every backend's width
+const SHA3X_BATCH: usize = 8;
+
+/// SHA3x nonces hashed between checks for a new job
+const SHA3X_CHUNK: u64 = 1024;
+
+/// SHA3x nonces a thread claims from the NonceAllocator at once, a multiple of SHA3X_CHUNK
+const SHA3X_NONCE_RANGE: u64 = 1 << 20;
+
+/// How often a parked thread checks whether it should mine again
+const PARKED_POLL: Duration = Duration::from_millis(100);
+
+/// Extranonce2 roll counter shared by all SHA-256 threads so no two batches share a merkle root
+static EXTRANONCE2_COUNTER: AtomicU64 = AtomicU64::new(0);
+
 pub fn start_mining_thread(
     thread_id: usize,
     num_threads: usize,
@@ -40,6 +59,9 @@ pub fn start_mining_thread(
     share_tx: MpscSender<(String, String, String, usize, u64, String, u32)>,
     thread_stats: Arc<ThreadStats>,
     stats: Arc<MinerStats>,
+    active: Arc<AtomicBool>,
+    backend: CpuBackend,
+    nonce_allocator: Arc<NonceAllocator>,
 ) {
     let should_stop = Arc::new(AtomicBool::new(false));
     let should_stop_clone = Arc::clone(&should_stop);
@@ -53,6 +75,9 @@ pub fn start_mining_thread(
             thread_stats,
             stats,
             should_stop_clone,
+            active,
+            backend,
+            nonce_allocator,
         );
     });
 }
@@ -65,11 +90,22 @@ fn mining_thread(
     thread_stats: Arc<ThreadStats>,
     stats: Arc<MinerStats>,
     should_stop: Arc<AtomicBool>,
+    active: Arc<AtomicBool>,
+    backend: CpuBackend,
+    nonce_allocator: Arc<NonceAllocator>,
 ) {
     let mut rng: ThreadRng = rand::thread_rng();
     let mut current_job: Option<MiningJob> = None;
+    // SHA3x midstate of the current job's header
+    let mut sha3x_hasher: Option<Sha3xHasher> = None;
+    // Largest leading hash word that can still be a share of the current job
+    let mut sha3x_threshold = u64::MAX;
+    // SHA3x nonces of the current job owned by this thread, and how many are hashed
+    let mut nonce_range: Option<NonceRange> = None;
+    let mut range_used = 0u64;
     let mut hash_count = 0u64;
     let mut last_report = Instant::now();
+    let mut job_received = Instant::now();
 
     loop {
         if should_stop.load(Ordering::Relaxed) {
@@ -85,51 +121,80 @@ fn mining_thread(
                 thread_stats
                     .current_difficulty_target
                     .store(job.target_difficulty, Ordering::Relaxed);
+                sha3x_hasher = (job.algo == Algorithm::Sha3x)
+                    .then(|| Sha3xHasher::with_backend(&job.mining_hash, backend));
+                sha3x_threshold = sha3x_share_threshold(job.target_difficulty);
+                nonce_range = None;
                 current_job = Some(job);
+                job_received = Instant::now();
             }
             Err(tokio::sync::broadcast::error::TryRecvError::Empty) => {}
             Err(_) => break,
         }
 
+        // Parked threads keep following jobs so they resume on the current one
+        if !active.load(Ordering::Relaxed) {
+            thread_stats.update_hashrate(0);
+            std::thread::sleep(PARKED_POLL);
+            continue;
+        }
+
         if let Some(ref job) = current_job {
             match job.algo {
                 Algorithm::Sha3x => {
-                    let mut nonce = rng.r#gen::<u64>();
-                    nonce = nonce.wrapping_add(thread_id as u64);
+                    let Some(hasher) = sha3x_hasher.as_ref() else {
+                        continue;
+                    };
 
-                    for _ in (0..1000).step_by(4) {
-                        let batch_results = sha3x_hash_with_nonce_batch(&job.mining_hash, nonce);
+                    // Claim a fresh range of the job's nonce space once ours is used up
+                    if nonce_range.is_none_or(|range| range_used >= range.len()) {
+                        nonce_range = nonce_allocator.claim(job, SHA3X_NONCE_RANGE);
+                        range_used = 0;
+                    }
+                    let Some(range) = nonce_range else {
+                        std::thread::sleep(PARKED_POLL);
+                        continue;
+                    };
 
-                        for (hash, batch_nonce) in batch_results.iter() {
+                    // Nonces carry the pool's XN (LuckyPool) in their first 2 bytes
+                    let mut nonces = [0u64; SHA3X_BATCH];
+                    let mut hashes = [[0u8; 32]; SHA3X_BATCH];
+                    let chunk_end = (range_used + SHA3X_CHUNK).min(range.len());
+                    while range_used < chunk_end {
+                        // A range that is not a multiple of the batch ends in a short
+                        // batch; its spare lanes repeat the last nonce and are ignored
+                        let batch = (chunk_end - range_used).min(SHA3X_BATCH as u64);
+                        for (i, batch_nonce) in nonces.iter_mut().enumerate() {
+                            *batch_nonce = range.nonce(range_used + (i as u64).min(batch - 1));
+                        }
+                        range_used += batch;
+                        hasher.hash_nonces(&nonces, &mut hashes);
+
+                        hash_count += batch;
+
+                        // Early reject on the leading word; exact difficulty only for candidates
+                        for (hash, batch_nonce) in hashes
+                            .iter()
+                            .zip(&nonces)
+                            .take(batch as usize)
+                            .filter(|(hash, _)| sha3x_hash_word(hash) <= sha3x_threshold)
+                        {
                             let difficulty = calculate_difficulty(hash, job.algo);
-                            hash_count += 1;
-
                             if difficulty >= job.target_difficulty {
-                                // Convert local nonce to bytes (use only 6 least-significant bytes)
-                                let local_nonce = batch_nonce.to_le_bytes();
-
-                                // Decode XN (2 bytes) from job.extranonce2
-                                let xn = hex::decode(job.extranonce2.clone().unwrap_or_default())
-                                    .unwrap_or(vec![0, 0]);
-
-                                // Compose full 8-byte nonce: [XN][Local Nonce]
-                                let full_nonce = [
-                                    xn.first().copied().unwrap_or(0),
-                                    xn.get(1).copied().unwrap_or(0),
-                                    local_nonce[0],
-                                    local_nonce[1],
-                                    local_nonce[2],
-                                    local_nonce[3],
-                                    local_nonce[4],
-                                    local_nonce[5],
-                                ];
-
-                                // Encode as hex string
-                                let nonce_hex_le = hex::encode(full_nonce);
+                                if !nonce_allocator.record_share(job, *batch_nonce) {
+                                    warn!(target: LOG_TARGET,
+                                        "⚠️ Thread {}: duplicate share for job {} dropped (nonce {:016x})",
+                                        thread_id, job.job_id, batch_nonce
+                                    );
+                                    stats.shares_duplicate.fetch_add(1, Ordering::Relaxed);
+                                    continue;
+                                }
+
+                                // Submit exactly the nonce that was hashed (XN already in place)
+                                let nonce_hex_le = hex::encode(batch_nonce.to_le_bytes());
                                 let nonce_hex_be = hex::encode(batch_nonce.to_be_bytes());
                                 let result_hex = hex::encode(hash);
 
-                                thread_stats.record_share(difficulty, true);
                                 stats.record_share_found(
                                     thread_id,
                                     difficulty,
@@ -168,12 +233,10 @@ fn mining_thread(
                                 stats.shares_submitted.fetch_add(1, Ordering::Relaxed);
                             }
                         }
-
-                        nonce = nonce.wrapping_add((4 * num_threads) as u64);
                     }
                 }
                 Algorithm::Sha256 => {
-                    let header = build_bitcoin_header(job);
+                    let (header, extranonce2, ntime) = sha256_work(job, job_received.elapsed());
                     if header.len() != 80 {
                         error!(target: LOG_TARGET,
                             "Thread {}: Invalid Bitcoin header length: {} bytes",
@@ -182,19 +245,12 @@ fn mining_thread(
                         );
                         continue;
                     }
-                    debug!(target: LOG_TARGET,"Thread {}: Header: {}", thread_id, hex::encode(&header));
-
-                    let ntime = job.ntime.unwrap_or_else(|| {
-                        error!(target: LOG_TARGET,"Thread {}: No ntime provided for SHA-256 job", thread_id);
-                        0
-                    });
-
-                    static EXTRANONCE2_COUNTER: AtomicU32 = AtomicU32::new(0);
-                    let extranonce2_bytes = EXTRANONCE2_COUNTER
-                        .fetch_add(1, Ordering::SeqCst)
-                        .to_le_bytes();
-                    let extranonce2 = hex::encode(extranonce2_bytes);
-                    debug!(target: LOG_TARGET,"Thread {}: Extranonce2: {}", thread_id, extranonce2);
+                    debug!(target: LOG_TARGET,
+                        "Thread {}: Header: {}, Extranonce2: {}",
+                        thread_id,
+                        hex::encode(&header),
+                        extranonce2
+                    );
 
                     let target = if let Some(target_bytes) = job.target {
                         if target_bytes.len() != 32 {
@@ -241,7 +297,7 @@ fn mining_thread(
 
                         for (hash, batch_nonce) in batch_results.iter() {
                             hash_count += 1;
-                            let hash_u256 = U256::from_big_endian(hash);
+                            let hash_u256 = U256::from_little_endian(hash); // SHA-256d output is little-endian
                             debug!(target: LOG_TARGET,
                                 "Thread {}: Hash: {:064x}, Nonce: {:08x}",
                                 thread_id, hash_u256, batch_nonce
@@ -252,7 +308,6 @@ fn mining_thread(
                                 let nonce_hex = format!("{:08x}", batch_nonce);
                                 let result_hex = hex::encode(hash);
 
-                                thread_stats.record_share(difficulty, true);
                                 stats.record_share_found(
                                     thread_id,
                                     difficulty,
@@ -312,7 +367,35 @@ fn mining_thread(
     }
 }
 
+/// Header, extranonce2 and ntime for the next batch of SHA-256 work. Stratum V1
+/// jobs roll a fresh extranonce2 into the merkle root for every batch and keep
+/// ntime current while the job is being mined.
+fn sha256_work(job: &MiningJob, job_age: Duration) -> (Vec<u8>, String, u32) {
+    let mut header = build_bitcoin_header(job);
+    let ntime = job.ntime.unwrap_or(0);
+
+    let Some(coinbase) = &job.coinbase else {
+        return (header, job.extranonce2.clone().unwrap_or_default(), ntime);
+    };
+    if header.len() != 80 {
+        return (header, String::new(), ntime);
+    }
+
+    let extranonce2 = coinbase.extranonce2(EXTRANONCE2_COUNTER.fetch_add(1, Ordering::Relaxed));
+    header[36..68].copy_from_slice(&coinbase.merkle_root(&extranonce2));
+
+    let ntime = ntime.wrapping_add(job_age.as_secs().min(MAX_NTIME_ROLL_SECS) as u32);
+    header[68..72].copy_from_slice(&ntime.to_le_bytes());
+
+    (header, hex::encode(extranonce2), ntime)
+}
+
 fn build_bitcoin_header(job: &MiningJob) -> Vec<u8> {
+    // Stratum V2 jobs arrive as a complete 80-byte header template
+    if job.mining_hash.len() == 80 {
+        return job.mining_hash.clone();
+    }
+
     let mut header = Vec::with_capacity(80);
 
     if let Some(version) = job.version {
@@ -376,6 +459,26 @@ fn build_bitcoin_header(job: &MiningJob) -> Vec<u8> {
 }
 
 // Changelog:
+// - v1.7.1 (2025-06-28): SHA3x batches stop at the end of the claimed nonce range, so a
+//   range that is not a multiple of the batch size is neither overrun nor cut short.
+// - v1.7.0 (2025-06-28): SHA3x threads hash disjoint nonce ranges claimed from the shared
+//   NonceAllocator instead of random start nonces; duplicate shares are dropped and counted.
+// - v1.6.0 (2025-06-28): SHA3x hashes are compared against a per-job leading-word
+//   threshold; the exact difficulty is only computed for candidate shares.
+// - v1.5.0 (2025-06-28): SHA3x hashes 8 nonces per pass on the CpuBackend chosen by the miner.
+// - v1.4.0 (2025-06-28): SHA3x hashing uses Sha3xHasher.
+//   - The header midstate is built once per job; hashes go into a reused [[u8; 32]; 4].
+// - v1.3.0 (2025-06-28): Threads can be parked at runtime through an `active` flag.
+//   - Parked threads keep following jobs but stop hashing until re-activated.
+// - v1.2.1 (2025-06-28): SHA3x shares submit the nonce that was actually hashed.
+//   - LuckyPool XN jobs hash [XN][6-byte local] nonces instead of overwriting
+//     the first nonce bytes after hashing, so pools can verify the share.
+// - v1.2.0 (2025-06-28): Stratum V1 SHA-256d mining.
+//   - Jobs with a coinbase template roll extranonce2 into a fresh merkle root per batch.
+//   - ntime rolls forward with the job age (capped at 10 minutes).
+//   - Hashes are compared against the target as little-endian numbers.
+// - v1.1.5 (2025-06-28): SHA-256 jobs carrying an 80-byte header template
+//   (Stratum V2) are mined as-is instead of being rebuilt from job fields.
 // - v1.1.4 (2025-06-19): Fixed SHA-256 share validation.
 //   - Changed target to use from_big_endian in SHA-256 branch.
 //   - Added byte swapping for prev_hash and merkle_root to match Bitcoin's little-endian header.
@@ -0,0 +1,656 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/miner/gpu/governor.rs
+// Version: 1.0.0
+// Developer: OIEIEIO <oieieio@protonmail.com>
+//
+// This file implements the GPU thermal and power governor. Every few seconds it
+// reads the temperature and power draw of each GPU (nvidia-smi, or any other
+// TelemetrySource) and enforces the configured temp_limit / power_limit by
+// scaling the device's intensity down with a PID-style controller, holding
+// inside a hysteresis band below the limits and ramping back up once the GPU
+// runs cool. Above the hard --gpu-temp-cutoff the device stops mining until it
+// cooled down. Throttle changes go to the mining threads through the manager's
+// control channels, so nothing restarts.
+//
+// Tree Location:
+// - src/miner/gpu/governor.rs (GPU thermal / power governor)
+// - Depends on: core/types, gpu/manager, stats/gpu_info, stats/miner_stats
+
+use super::manager::GpuManager;
+use crate::core::types::Args;
+use crate::miner::stats::MinerStats;
+use crate::miner::stats::gpu_info::GpuInfo;
+use log::{debug, info, warn};
+use std::collections::BTreeMap;
+use std::process::Command;
+use std::time::Duration;
+
+const LOG_TARGET: &str = "tari::graxil::governor";
+
+/// How often telemetry is read and throttles are adjusted
+const GOVERNOR_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Controller gains, in percent of intensity per degree (or power percentage point)
+/// over the limit: proportional, accumulated and rising error
+const KP: f32 = 2.0;
+const KI: f32 = 0.5;
+const KD: f32 = 1.0;
+
+/// Cap of the accumulated error, so a long overshoot cannot wind up
+const INTEGRAL_LIMIT: f32 = 20.0;
+
+/// Band below the limits in which the throttle holds still
+const HYSTERESIS: f32 = 3.0;
+
+/// Intensity fraction regained per interval once below the hysteresis band
+const RECOVERY_STEP: f32 = 0.05;
+
+/// Lowest fraction of the configured intensity the controller throttles to
+const MIN_SCALE: f32 = 0.1;
+
+/// A GPU stopped at the hard cutoff mines again this many degrees below it
+const CUTOFF_RESUME_MARGIN: f32 = 10.0;
+
+/// Governor-imposed limit on one GPU, applied on top of its configured settings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Throttle {
+    /// Fraction of the configured intensity to mine at (0.1-1.0)
+    pub scale: f32,
+    /// Stopped above the hard temperature cutoff
+    pub stopped: bool,
+}
+
+impl Throttle {
+    pub const NONE: Self = Self {
+        scale: 1.0,
+        stopped: false,
+    };
+
+    /// Intensity to mine at instead of the configured one
+    pub fn intensity(&self, configured: u8) -> u8 {
+        if self.scale >= 1.0 {
+            return configured;
+        }
+        ((f32::from(configured) * self.scale).round() as u8).clamp(1.min(configured), configured)
+    }
+
+    pub fn is_throttled(&self) -> bool {
+        self.stopped || self.scale < 1.0
+    }
+}
+
+impl Default for Throttle {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// A running GPU and the limits the governor enforces on it
+#[derive(Debug, Clone, PartialEq)]
+pub struct GovernedDevice {
+    pub device_id: u32,
+    pub name: String,
+    /// Temperature limit in Celsius
+    pub temp_limit: Option<u8>,
+    /// Power limit in percent of the default board power limit
+    pub power_limit: Option<u8>,
+}
+
+/// Live readings of one GPU
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GpuTelemetry {
+    /// Temperature in Celsius
+    pub temperature: Option<f32>,
+    /// Power draw in percent of the default board power limit
+    pub power_percent: Option<f32>,
+}
+
+/// Where the governor reads GPU temperature and power from
+pub trait TelemetrySource: Send {
+    /// Readings of the given devices by OpenCL device id; devices without
+    /// telemetry are left out
+    fn read(&mut self, devices: &[GovernedDevice]) -> BTreeMap<u32, GpuTelemetry>;
+}
+
+/// Per-GPU telemetry from nvidia-smi. OpenCL devices are matched to the
+/// nvidia-smi GPUs of the same name, both in enumeration order.
+#[derive(Debug, Default)]
+pub struct NvidiaSmiTelemetry {
+    warned: bool,
+}
+
+impl NvidiaSmiTelemetry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Name and readings of every NVIDIA GPU
+    fn query() -> Result<Vec<(String, GpuTelemetry)>, String> {
+        let output = Command::new("nvidia-smi")
+            .arg("--query-gpu=name,temperature.gpu,power.draw,power.default_limit")
+            .arg("--format=csv,noheader,nounits")
+            .output()
+            .map_err(|e| format!("nvidia-smi not available: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "nvidia-smi failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(parse_telemetry_line)
+            .collect()
+    }
+}
+
+impl TelemetrySource for NvidiaSmiTelemetry {
+    fn read(&mut self, devices: &[GovernedDevice]) -> BTreeMap<u32, GpuTelemetry> {
+        match Self::query() {
+            Ok(gpus) => match_devices(devices, gpus),
+            Err(e) if !self.warned => {
+                warn!(target: LOG_TARGET,"⚠️ GPU limits cannot be enforced without telemetry: {}", e);
+                self.warned = true;
+                BTreeMap::new()
+            }
+            Err(e) => {
+                debug!(target: LOG_TARGET,"GPU telemetry unavailable: {}", e);
+                BTreeMap::new()
+            }
+        }
+    }
+}
+
+/// Parse one line of `name,temperature.gpu,power.draw,power.default_limit` CSV output
+fn parse_telemetry_line(line: &str) -> Result<(String, GpuTelemetry), String> {
+    let parts: Vec<&str> = line.split(',').map(str::trim).collect();
+    if parts.len() < 4 {
+        return Err(format!(
+            "Invalid nvidia-smi output format, expected 4 fields but got {}: {}",
+            parts.len(),
+            line
+        ));
+    }
+
+    let temperature = GpuInfo::parse_optional_float(parts[1], "temperature")?;
+    let power_draw = GpuInfo::parse_optional_float(parts[2], "power")?;
+    let default_limit = GpuInfo::parse_optional_float(parts[3], "power_limit")?;
+    let power_percent = match (power_draw, default_limit) {
+        (Some(draw), Some(limit)) if limit > 0.0 => Some(draw / limit * 100.0),
+        _ => None,
+    };
+    Ok((
+        parts[0].to_string(),
+        GpuTelemetry {
+            temperature,
+            power_percent,
+        },
+    ))
+}
+
+/// Pair devices with the first unclaimed GPU of the same name
+fn match_devices(
+    devices: &[GovernedDevice],
+    gpus: Vec<(String, GpuTelemetry)>,
+) -> BTreeMap<u32, GpuTelemetry> {
+    let mut gpus: Vec<Option<(String, GpuTelemetry)>> = gpus.into_iter().map(Some).collect();
+    let mut matched = BTreeMap::new();
+    for device in devices {
+        let gpu = gpus.iter_mut().find(|gpu| {
+            gpu.as_ref()
+                .is_some_and(|(name, _)| name.eq_ignore_ascii_case(&device.name))
+        });
+        if let Some((_, telemetry)) = gpu.and_then(Option::take) {
+            matched.insert(device.device_id, telemetry);
+        }
+    }
+    matched
+}
+
+/// Governor settings shared by every GPU
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GovernorConfig {
+    /// Temperature in Celsius at which a GPU stops mining
+    pub temp_cutoff: Option<f32>,
+    pub interval: Duration,
+}
+
+impl GovernorConfig {
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            temp_cutoff: args.gpu_temp_cutoff.map(f32::from),
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for GovernorConfig {
+    fn default() -> Self {
+        Self {
+            temp_cutoff: None,
+            interval: GOVERNOR_INTERVAL,
+        }
+    }
+}
+
+/// A new throttle for one GPU, with a message when it is worth reporting
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThrottleChange {
+    pub device_id: u32,
+    pub throttle: Throttle,
+    pub event: Option<String>,
+}
+
+/// Controller state of one GPU
+#[derive(Debug, Clone, Default)]
+struct DeviceState {
+    throttle: Throttle,
+    integral: f32,
+    last_error: Option<f32>,
+}
+
+impl DeviceState {
+    fn update(&mut self, device: &GovernedDevice, reading: &GpuTelemetry, cutoff: Option<f32>) {
+        if let (Some(cutoff), Some(temperature)) = (cutoff, reading.temperature) {
+            if temperature >= cutoff {
+                self.throttle.stopped = true;
+            } else if self.throttle.stopped && temperature <= cutoff - CUTOFF_RESUME_MARGIN {
+                self.throttle.stopped = false;
+            }
+        }
+        if self.throttle.stopped {
+            self.integral = 0.0;
+            self.last_error = None;
+            return;
+        }
+
+        let Some(error) = over_limit(device, reading) else {
+            return;
+        };
+        if error > 0.0 {
+            self.integral = (self.integral + error).min(INTEGRAL_LIMIT);
+            let rising = self.last_error.map_or(0.0, |last| (error - last).max(0.0));
+            let step = (KP * error + KI * self.integral + KD * rising) / 100.0;
+            self.throttle.scale = (self.throttle.scale - step).max(MIN_SCALE);
+        } else if error < -HYSTERESIS {
+            self.integral = 0.0;
+            self.throttle.scale = (self.throttle.scale + RECOVERY_STEP).min(1.0);
+        } else {
+            // Inside the hysteresis band: hold and let the accumulated error fade
+            self.integral /= 2.0;
+        }
+        self.last_error = Some(error);
+    }
+}
+
+/// How far a GPU is over its tightest limit, in degrees above the temperature
+/// limit or percentage points above the power limit (negative when below both)
+fn over_limit(device: &GovernedDevice, reading: &GpuTelemetry) -> Option<f32> {
+    let temperature = device
+        .temp_limit
+        .zip(reading.temperature)
+        .map(|(limit, temperature)| temperature - f32::from(limit));
+    let power = device
+        .power_limit
+        .zip(reading.power_percent)
+        .map(|(limit, power)| power - f32::from(limit));
+    match (temperature, power) {
+        (Some(temperature), Some(power)) => Some(temperature.max(power)),
+        (temperature, power) => temperature.or(power),
+    }
+}
+
+/// Activity feed message for a throttle change, if it starts or ends throttling
+fn describe(
+    device_id: u32,
+    reading: &GpuTelemetry,
+    before: Throttle,
+    after: Throttle,
+) -> Option<String> {
+    let temperature = reading
+        .temperature
+        .map_or_else(|| "?".to_string(), |t| format!("{:.0}°C", t));
+    let power = reading
+        .power_percent
+        .map_or_else(|| "?".to_string(), |p| format!("{:.0}%", p));
+
+    if after.stopped && !before.stopped {
+        Some(format!(
+            "🛑 GPU {} stopped at {} (temperature cutoff)",
+            device_id, temperature
+        ))
+    } else if before.stopped && !after.stopped {
+        Some(format!(
+            "▶️ GPU {} cooled down to {}, mining again",
+            device_id, temperature
+        ))
+    } else if before.scale >= 1.0 && after.scale < 1.0 {
+        Some(format!(
+            "🌡️ GPU {} throttled to {:.0}% of its intensity ({}, {} power)",
+            device_id,
+            after.scale * 100.0,
+            temperature,
+            power
+        ))
+    } else if before.scale < 1.0 && after.scale >= 1.0 {
+        Some(format!(
+            "✅ GPU {} back to full intensity ({}, {} power)",
+            device_id, temperature, power
+        ))
+    } else {
+        None
+    }
+}
+
+/// Thermal / power governor of the GPUs of one miner
+pub struct GpuGovernor<S> {
+    config: GovernorConfig,
+    source: S,
+    states: BTreeMap<u32, DeviceState>,
+}
+
+impl<S: TelemetrySource> GpuGovernor<S> {
+    pub fn new(config: GovernorConfig, source: S) -> Self {
+        Self {
+            config,
+            source,
+            states: BTreeMap::new(),
+        }
+    }
+
+    fn is_governed(&self, device: &GovernedDevice) -> bool {
+        device.temp_limit.is_some()
+            || device.power_limit.is_some()
+            || self.config.temp_cutoff.is_some()
+    }
+
+    /// Read telemetry once and adjust the throttle of every device; returns the
+    /// devices whose throttle changed
+    pub fn step(&mut self, devices: &[GovernedDevice]) -> Vec<ThrottleChange> {
+        let governed: Vec<GovernedDevice> = devices
+            .iter()
+            .filter(|device| self.is_governed(device))
+            .cloned()
+            .collect();
+
+        // Devices whose limits were removed at runtime mine unthrottled again
+        let mut changes = Vec::new();
+        self.states.retain(|&device_id, state| {
+            let keep = governed.iter().any(|device| device.device_id == device_id);
+            if !keep && state.throttle != Throttle::NONE {
+                changes.push(ThrottleChange {
+                    device_id,
+                    throttle: Throttle::NONE,
+                    event: Some(format!(
+                        "✅ GPU {} limits removed, throttle released",
+                        device_id
+                    )),
+                });
+            }
+            keep
+        });
+        if governed.is_empty() {
+            return changes;
+        }
+
+        let telemetry = self.source.read(&governed);
+        for device in &governed {
+            let Some(reading) = telemetry.get(&device.device_id) else {
+                continue;
+            };
+            let state = self.states.entry(device.device_id).or_default();
+            let before = state.throttle;
+            state.update(device, reading, self.config.temp_cutoff);
+            if state.throttle != before {
+                changes.push(ThrottleChange {
+                    device_id: device.device_id,
+                    throttle: state.throttle,
+                    event: describe(device.device_id, reading, before, state.throttle),
+                });
+            }
+        }
+        changes
+    }
+
+    /// Govern the GPUs of `manager` for the lifetime of the process. Telemetry
+    /// reads block, so this runs on its own thread.
+    pub fn run(mut self, manager: &GpuManager, stats: &MinerStats) {
+        info!(target: LOG_TARGET,
+            "🌡️ GPU governor started (every {}s, temperature cutoff: {})",
+            self.config.interval.as_secs(),
+            self.config
+                .temp_cutoff
+                .map_or_else(|| "off".to_string(), |c| format!("{:.0}°C", c))
+        );
+        loop {
+            std::thread::sleep(self.config.interval);
+            for change in self.step(&manager.governed_devices()) {
+                manager.set_throttle(change.device_id, change.throttle);
+                match change.event {
+                    Some(event) if change.throttle.is_throttled() => {
+                        warn!(target: LOG_TARGET,"{}", event);
+                        stats.add_activity(event);
+                    }
+                    Some(event) => {
+                        info!(target: LOG_TARGET,"{}", event);
+                        stats.add_activity(event);
+                    }
+                    None => debug!(target: LOG_TARGET,
+                        "🌡️ GPU {} at {:.0}% of its intensity",
+                        change.device_id,
+                        change.throttle.scale * 100.0
+                    ),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Telemetry set by the test between steps
+    #[derive(Default)]
+    struct MockTelemetry {
+        readings: BTreeMap<u32, GpuTelemetry>,
+        reads: usize,
+    }
+
+    impl TelemetrySource for MockTelemetry {
+        fn read(&mut self, _devices: &[GovernedDevice]) -> BTreeMap<u32, GpuTelemetry> {
+            self.reads += 1;
+            self.readings.clone()
+        }
+    }
+
+    fn device(temp_limit: Option<u8>, power_limit: Option<u8>) -> GovernedDevice {
+        GovernedDevice {
+            device_id: 0,
+            name: "NVIDIA GeForce RTX 4060 Ti".to_string(),
+            temp_limit,
+            power_limit,
+        }
+    }
+
+    fn set_temperature(governor: &mut GpuGovernor<MockTelemetry>, temperature: f32) {
+        governor.source.readings.insert(
+            0,
+            GpuTelemetry {
+                temperature: Some(temperature),
+                power_percent: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_throttles_above_temp_limit_and_recovers() {
+        let devices = [device(Some(75), None)];
+        let mut governor = GpuGovernor::new(GovernorConfig::default(), MockTelemetry::default());
+
+        set_temperature(&mut governor, 80.0);
+        let changes = governor.step(&devices);
+        assert_eq!(changes.len(), 1);
+        let first = changes[0].throttle.scale;
+        assert!(first < 1.0 && !changes[0].throttle.stopped);
+        assert!(changes[0].event.as_deref().unwrap().contains("throttled"));
+
+        // Still hot: throttles further, without another activity message
+        let changes = governor.step(&devices);
+        assert!(changes[0].throttle.scale < first);
+        assert!(changes[0].event.is_none());
+
+        // Inside the hysteresis band the throttle holds
+        set_temperature(&mut governor, 73.0);
+        assert!(governor.step(&devices).is_empty());
+
+        // Cool again: ramps back up to full intensity
+        set_temperature(&mut governor, 65.0);
+        let mut last = Vec::new();
+        for _ in 0..30 {
+            let changes = governor.step(&devices);
+            if !changes.is_empty() {
+                last = changes;
+            }
+        }
+        assert_eq!(last[0].throttle, Throttle::NONE);
+        assert!(last[0].event.as_deref().unwrap().contains("full intensity"));
+    }
+
+    #[test]
+    fn test_throttles_above_power_limit() {
+        let devices = [device(None, Some(80))];
+        let mut governor = GpuGovernor::new(GovernorConfig::default(), MockTelemetry::default());
+        governor.source.readings.insert(
+            0,
+            GpuTelemetry {
+                temperature: Some(60.0),
+                power_percent: Some(95.0),
+            },
+        );
+
+        let changes = governor.step(&devices);
+        assert!(changes[0].throttle.scale < 1.0);
+        for _ in 0..50 {
+            governor.step(&devices);
+        }
+        assert_eq!(governor.states[&0].throttle.scale, MIN_SCALE);
+    }
+
+    #[test]
+    fn test_hard_cutoff_stops_and_resumes() {
+        let config = GovernorConfig {
+            temp_cutoff: Some(90.0),
+            ..GovernorConfig::default()
+        };
+        let devices = [device(None, None)];
+        let mut governor = GpuGovernor::new(config, MockTelemetry::default());
+
+        set_temperature(&mut governor, 91.0);
+        let changes = governor.step(&devices);
+        assert!(changes[0].throttle.stopped);
+        assert!(changes[0].event.as_deref().unwrap().contains("stopped"));
+
+        set_temperature(&mut governor, 85.0);
+        assert!(governor.step(&devices).is_empty());
+
+        set_temperature(&mut governor, 79.0);
+        let changes = governor.step(&devices);
+        assert_eq!(changes[0].throttle, Throttle::NONE);
+        assert!(
+            changes[0]
+                .event
+                .as_deref()
+                .unwrap()
+                .contains("mining again")
+        );
+    }
+
+    #[test]
+    fn test_devices_without_limits_are_left_alone() {
+        let mut governor = GpuGovernor::new(GovernorConfig::default(), MockTelemetry::default());
+        set_temperature(&mut governor, 80.0);
+        governor.step(&[device(Some(75), None)]);
+
+        // Limit removed at runtime: released, and telemetry is no longer read
+        let changes = governor.step(&[device(None, None)]);
+        assert_eq!(changes[0].throttle, Throttle::NONE);
+        assert!(governor.step(&[device(None, None)]).is_empty());
+        assert_eq!(governor.source.reads, 1);
+    }
+
+    #[test]
+    fn test_throttle_intensity() {
+        assert_eq!(Throttle::NONE.intensity(90), 90);
+        let throttle = Throttle {
+            scale: 0.5,
+            stopped: false,
+        };
+        assert_eq!(throttle.intensity(90), 45);
+        assert_eq!(throttle.intensity(1), 1);
+        assert_eq!(throttle.intensity(0), 0);
+    }
+
+    #[test]
+    fn test_parse_telemetry_and_match_devices() {
+        let (name, telemetry) =
+            parse_telemetry_line("NVIDIA GeForce RTX 4060 Ti, 71, 130.50, 165.00").unwrap();
+        assert_eq!(name, "NVIDIA GeForce RTX 4060 Ti");
+        assert_eq!(telemetry.temperature, Some(71.0));
+        assert!((telemetry.power_percent.unwrap() - 79.09).abs() < 0.01);
+        assert_eq!(
+            parse_telemetry_line("NVIDIA GeForce GTX 1650, 60, [Not Supported], 75.00")
+                .unwrap()
+                .1
+                .power_percent,
+            None
+        );
+        assert!(parse_telemetry_line("NVIDIA GeForce GTX 1650, 60").is_err());
+
+        // Same-name cards pair up in order; the Intel iGPU has no telemetry
+        let gpu = |temperature| GpuTelemetry {
+            temperature: Some(temperature),
+            power_percent: None,
+        };
+        let devices = [
+            GovernedDevice {
+                device_id: 0,
+                name: "Intel(R) UHD Graphics 770".to_string(),
+                ..device(None, None)
+            },
+            GovernedDevice {
+                device_id: 1,
+                ..device(None, None)
+            },
+            GovernedDevice {
+                device_id: 2,
+                ..device(None, None)
+            },
+        ];
+        let matched = match_devices(
+            &devices,
+            vec![
+                ("NVIDIA GeForce RTX 4060 Ti".to_string(), gpu(60.0)),
+                ("NVIDIA GeForce RTX 4060 Ti".to_string(), gpu(70.0)),
+            ],
+        );
+        assert_eq!(matched.len(), 2);
+        assert_eq!(matched[&1].temperature, Some(60.0));
+        assert_eq!(matched[&2].temperature, Some(70.0));
+    }
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial GPU thermal and power governor.
+//   - PID-style intensity scaling towards temp_limit / power_limit with hysteresis.
+//   - Hard --gpu-temp-cutoff stops a GPU until it cooled down by 10°C.
+//   - nvidia-smi telemetry per GPU behind the mockable TelemetrySource trait.
+//   - Throttle and cutoff events are logged to the activity feed.
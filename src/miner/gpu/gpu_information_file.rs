@@ -1,7 +1,8 @@
+use crate::core::config::GpuDeviceConfig;
 use crate::miner::{gpu::opencl::device::GpuDeviceType, stats::gpu_info::GpuVendor};
 use log::{debug, info};
-use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 use tokio::{
     fs::{self, OpenOptions},
@@ -71,6 +72,9 @@ pub struct GpuInformationFileDevice {
     pub max_compute_units: u32,
     pub global_mem_size: u64,
     pub device_type: GpuDeviceType,
+    /// Mining settings for this device; unset fields use the global GPU settings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<GpuDeviceConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,10 +82,56 @@ pub struct GpuInformationFile {
     pub devices: Vec<GpuInformationFileDevice>,
 }
 
+/// Autotune result for one GPU model. It is only reused while the OpenCL driver
+/// and the kernel source are the ones it was measured with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GpuAutotuneProfile {
+    pub device_name: String,
+    pub driver_version: String,
+    pub kernel_hash: String,
+    /// Winning settings (intensity, batch size, work groups per CU)
+    pub settings: GpuDeviceConfig,
+    pub hashrate_mhs: f64,
+    /// Unix time of the tuning run
+    pub tuned_at: u64,
+}
+
+impl GpuAutotuneProfile {
+    /// Whether the profile was measured with this driver and kernel
+    pub fn is_current(&self, driver_version: &str, kernel_hash: &str) -> bool {
+        self.driver_version == driver_version && self.kernel_hash == kernel_hash
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GpuAutotuneFile {
+    pub profiles: Vec<GpuAutotuneProfile>,
+}
+
+impl GpuAutotuneFile {
+    /// Stored profile for a device model
+    pub fn find(&self, device_name: &str) -> Option<&GpuAutotuneProfile> {
+        self.profiles.iter().find(|p| p.device_name == device_name)
+    }
+
+    /// Add a profile, replacing the previous one for the same device model
+    pub fn upsert(&mut self, profile: GpuAutotuneProfile) {
+        match self
+            .profiles
+            .iter_mut()
+            .find(|p| p.device_name == profile.device_name)
+        {
+            Some(existing) => *existing = profile,
+            None => self.profiles.push(profile),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GpuInformationFileManager {
     directory_path: PathBuf,
     file_path: PathBuf,
+    autotune_file_path: PathBuf,
 }
 
 impl GpuInformationFileManager {
@@ -118,22 +168,31 @@ impl GpuInformationFileManager {
             });
         }
 
-        let file_name = Self::_resolve_file_name(kernel_type);
-        let file_path = directory_path.join(file_name);
+        let file_path = directory_path.join(Self::_resolve_file_name(&kernel_type));
+        let autotune_file_path =
+            directory_path.join(Self::_resolve_autotune_file_name(&kernel_type));
 
         Ok(Self {
             directory_path,
             file_path,
+            autotune_file_path,
         })
     }
 
     /// Information file name could be different in future if we implement other kernel types.
-    const fn _resolve_file_name(kernel_type: KernelType) -> &'static str {
+    const fn _resolve_file_name(kernel_type: &KernelType) -> &'static str {
         match kernel_type {
             KernelType::OpenCL => "gpu_information_opencl.json",
         }
     }
 
+    /// Autotune results are kept next to the information file, one file per kernel type.
+    const fn _resolve_autotune_file_name(kernel_type: &KernelType) -> &'static str {
+        match kernel_type {
+            KernelType::OpenCL => "gpu_autotune_opencl.json",
+        }
+    }
+
     /// Ensure the directory exists, creating it if necessary.
     async fn ensure_directory_exists(
         directory_path: PathBuf,
@@ -149,18 +208,19 @@ impl GpuInformationFileManager {
         Ok(())
     }
 
-    /// Write the GPU information file atomically.
+    /// Write a JSON file atomically.
     /// This method creates a temporary file, writes the content, and then renames it to the final file name.
     /// This ensures that if the write fails, the original file remains unchanged
-    async fn _write_file(
+    async fn _write_file<T: Serialize>(
         &self,
-        information_file_content: &GpuInformationFile,
+        file_path: &Path,
+        file_content: &T,
     ) -> Result<(), GpuInformationFileError> {
-        debug!(target: LOG_TARGET, "Writing GPU information file to {:?}", self.file_path);
+        debug!(target: LOG_TARGET, "Writing GPU file to {:?}", file_path);
 
-        let contents = serde_json::to_vec_pretty(information_file_content)?; // Auto-converts due to #[from]
+        let contents = serde_json::to_vec_pretty(file_content)?; // Auto-converts due to #[from]
 
-        let temp_path = self.file_path.with_extension("tmp");
+        let temp_path = file_path.with_extension("tmp");
 
         {
             let mut temp_file = OpenOptions::new()
@@ -182,30 +242,31 @@ impl GpuInformationFileManager {
                 .map_err(|e| GpuInformationFileError::AtomicWriteError { source: e })?;
         }
 
-        fs::rename(&temp_path, &self.file_path)
+        fs::rename(&temp_path, file_path)
             .await
             .map_err(|e| GpuInformationFileError::AtomicWriteError { source: e })?;
 
-        info!(target: LOG_TARGET, "Successfully saved GPU information file to {:?}", self.file_path);
+        info!(target: LOG_TARGET, "Successfully saved GPU file to {:?}", file_path);
         Ok(())
     }
 
-    /// Load the GPU information file from the specified path.
-    pub async fn load(&self) -> Result<GpuInformationFile, GpuInformationFileError> {
-        debug!(target: LOG_TARGET, "Loading GPU information file from {:?}", self.file_path);
-
-        if !self.file_path.exists() {
+    /// Read and parse a JSON file.
+    async fn _read_file<T: DeserializeOwned>(
+        &self,
+        file_path: &Path,
+    ) -> Result<T, GpuInformationFileError> {
+        if !file_path.exists() {
             return Err(GpuInformationFileError::FileNotFound {
-                path: self.file_path.clone(),
+                path: file_path.to_path_buf(),
             });
         }
 
         let file = OpenOptions::new()
             .read(true)
-            .open(&self.file_path)
+            .open(file_path)
             .await
             .map_err(|e| GpuInformationFileError::IoError {
-                path: self.file_path.clone(),
+                path: file_path.to_path_buf(),
                 source: e,
             })?;
 
@@ -213,15 +274,21 @@ impl GpuInformationFileManager {
         let mut contents = String::new();
         reader.read_to_string(&mut contents).await.map_err(|e| {
             GpuInformationFileError::IoError {
-                path: self.file_path.clone(),
+                path: file_path.to_path_buf(),
                 source: e,
             }
         })?;
 
-        let information_file_content: GpuInformationFile = serde_json::from_str(&contents)
-            .map_err(|e| GpuInformationFileError::DeserializationError {
-                message: e.to_string(),
-            })?;
+        serde_json::from_str(&contents).map_err(|e| GpuInformationFileError::DeserializationError {
+            message: e.to_string(),
+        })
+    }
+
+    /// Load the GPU information file from the specified path.
+    pub async fn load(&self) -> Result<GpuInformationFile, GpuInformationFileError> {
+        debug!(target: LOG_TARGET, "Loading GPU information file from {:?}", self.file_path);
+
+        let information_file_content: GpuInformationFile = self._read_file(&self.file_path).await?;
 
         info!(target: LOG_TARGET, "Successfully loaded GPU information file with {} devices", information_file_content.devices.len());
         Ok(information_file_content)
@@ -234,7 +301,27 @@ impl GpuInformationFileManager {
         information_file_content: &GpuInformationFile,
     ) -> Result<(), GpuInformationFileError> {
         debug!(target: LOG_TARGET, "Saving GPU information file to {:?}", self.file_path);
-        self._write_file(information_file_content).await
+        self._write_file(&self.file_path, information_file_content)
+            .await
+    }
+
+    /// Load the stored autotune results. A missing file means nothing was tuned yet.
+    pub async fn load_autotune(&self) -> Result<GpuAutotuneFile, GpuInformationFileError> {
+        debug!(target: LOG_TARGET, "Loading GPU autotune file from {:?}", self.autotune_file_path);
+
+        match self._read_file(&self.autotune_file_path).await {
+            Err(GpuInformationFileError::FileNotFound { .. }) => Ok(GpuAutotuneFile::default()),
+            result => result,
+        }
+    }
+
+    /// Save autotune results, replacing the previous file.
+    pub async fn save_autotune(
+        &self,
+        autotune_file_content: &GpuAutotuneFile,
+    ) -> Result<(), GpuInformationFileError> {
+        self._write_file(&self.autotune_file_path, autotune_file_content)
+            .await
     }
 
     pub fn file_path(&self) -> &PathBuf {
@@ -244,4 +331,63 @@ impl GpuInformationFileManager {
     pub fn directory_path(&self) -> &PathBuf {
         &self.directory_path
     }
+
+    pub fn autotune_file_path(&self) -> &PathBuf {
+        &self.autotune_file_path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(device_name: &str, driver_version: &str, intensity: u8) -> GpuAutotuneProfile {
+        GpuAutotuneProfile {
+            device_name: device_name.to_string(),
+            driver_version: driver_version.to_string(),
+            kernel_hash: "0123456789abcdef".to_string(),
+            settings: GpuDeviceConfig {
+                intensity: Some(intensity),
+                ..GpuDeviceConfig::default()
+            },
+            hashrate_mhs: 400.0,
+            tuned_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_autotune_profiles() {
+        let mut file = GpuAutotuneFile::default();
+        file.upsert(profile("RTX 4060 Ti", "550.54", 95));
+        file.upsert(profile("Intel(R) UHD 770", "31.0", 80));
+        file.upsert(profile("RTX 4060 Ti", "555.42", 100));
+
+        assert_eq!(file.profiles.len(), 2);
+        let tuned = file.find("RTX 4060 Ti").unwrap();
+        assert_eq!(tuned.settings.intensity, Some(100));
+        assert!(tuned.is_current("555.42", "0123456789abcdef"));
+        assert!(!tuned.is_current("550.54", "0123456789abcdef"));
+        assert!(!tuned.is_current("555.42", "fedcba9876543210"));
+        assert!(file.find("RTX 3080").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_autotune_file_round_trip() {
+        let directory =
+            std::env::temp_dir().join(format!("graxil-autotune-{}", std::process::id()));
+        let manager = GpuInformationFileManager::new(directory.clone(), KernelType::OpenCL)
+            .await
+            .unwrap();
+        assert_eq!(
+            manager.load_autotune().await.unwrap(),
+            GpuAutotuneFile::default()
+        );
+
+        let mut file = GpuAutotuneFile::default();
+        file.upsert(profile("RTX 4060 Ti", "555.42", 95));
+        manager.save_autotune(&file).await.unwrap();
+        assert_eq!(manager.load_autotune().await.unwrap(), file);
+
+        let _ = std::fs::remove_dir_all(directory);
+    }
 }
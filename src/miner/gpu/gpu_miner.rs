@@ -1,46 +1,56 @@
 // SHA3x Miner - Free and Open Source Software Statement
 //
 // File: src/miner/gpu/gpu_miner.rs
-// Version: 1.1.4 - Added Connection Latency Monitoring
+// Version: 1.9.0 - Share Verification
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // GPU-only miner with settings support - delivers 385+ MH/s beast mode
 // FIXED: LuckyPool share validation (response.error == null && response.result == true)
 // FIXED: LuckyPool XN (extra nonce) parsing and nonce generation - compilation issues resolved
 // ADDED: Connection latency monitoring - updates every 5 seconds like CPU miner
+// MOVED: Pool connection, login, jobs and share submission into pool::session
+// ADDED: Failover pool list and SOCKS5 / HTTP CONNECT proxy settings
+// ADDED: Runtime settings hot-reload through MinerControl
+// ADDED: Accepts an already initialized (e.g. autotuned) GpuManager
+// ADDED: Thermal / power governor thread enforcing the GPU temperature and power limits
+// ADDED: Nonce ranges from a NonceAllocator, shared with the CPU miner in hybrid mode
+// ADDED: Hardware error limit for GPU shares failing the CPU re-check
 
 use crate::Result;
 use crate::core::types::GpuSettings;
-use crate::core::{Algorithm, MiningJob, PoolJob, parse_target_difficulty};
+use crate::core::{Algorithm, MiningJob};
+use crate::miner::control::RuntimeSettings;
+use crate::miner::nonce::NonceAllocator;
 use crate::miner::stats::MinerStats;
-use crate::pool::{PoolClient, protocol::StratumProtocol};
-use log::{debug, error, info};
-use serde_json::Value;
-use std::sync::atomic::{AtomicU32, Ordering};
-use std::sync::{Arc, LazyLock};
-use std::time::{Duration, Instant};
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::sync::Mutex;
-use tokio::sync::broadcast::{self, Sender as BroadcastSender};
-use tokio::sync::mpsc;
-
+use crate::pool::{
+    FailoverSettings, PoolClient, PoolEndpoint, PoolSession, PoolSessionConfig, ProxyConfig,
+    ShareSubmission,
+};
+use log::{info, warn};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+
+use super::governor::{GovernorConfig, GpuGovernor, NvidiaSmiTelemetry};
 use super::manager::GpuManager;
 
-static LUCKY_POOL_XN: LazyLock<Mutex<String>> = LazyLock::new(|| Mutex::new("".to_string()));
-
 const LOG_TARGET: &str = "tari::graxil::gpu_miner";
 
 pub struct GpuMiner {
     wallet_address: String,
     pool_address: String,
     worker_name: String,
-    pool_session_id: Mutex<Option<String>>,
+    pool_endpoints: Vec<PoolEndpoint>,
+    failover_settings: FailoverSettings,
     stats: Arc<MinerStats>,
     pool_client: Arc<PoolClient>,
     algo: Algorithm,
     gpu_manager: GpuManager,
     gpu_settings: GpuSettings,
     external_stats: bool, // Flag to indicate if using shared stats for hybrid mode
+    control: Mutex<Option<watch::Receiver<RuntimeSettings>>>, // Pool selection for the session
+    governor: GovernorConfig,
+    nonce_allocator: Arc<NonceAllocator>, // Shared with the CPU miner in hybrid mode
 }
 
 impl GpuMiner {
@@ -80,10 +90,12 @@ impl GpuMiner {
         // Apply GPU settings to manager
         gpu_manager.set_gpu_settings(gpu_settings.clone());
 
-        // Initialize GPU manager
-        gpu_manager
-            .initialize()
-            .map_err(|e| format!("Failed to initialize GPU manager: {}", e))?;
+        // Initialize GPU manager (unless the caller already did, e.g. to autotune it)
+        if !gpu_manager.is_initialized() {
+            gpu_manager
+                .initialize()
+                .map_err(|e| format!("Failed to initialize GPU manager: {}", e))?;
+        }
 
         // Create stats for GPU threads (1 per GPU device)
         let gpu_count = gpu_manager.device_count();
@@ -103,13 +115,17 @@ impl GpuMiner {
             wallet_address,
             pool_address,
             worker_name,
-            pool_session_id: Mutex::new(None),
+            pool_endpoints: Vec::new(),
+            failover_settings: FailoverSettings::default(),
             stats: Arc::new(stats),
             pool_client,
             algo,
             gpu_manager,
             gpu_settings,
             external_stats: false,
+            control: Mutex::new(None),
+            governor: GovernorConfig::default(),
+            nonce_allocator: Arc::new(NonceAllocator::new()),
         })
     }
 
@@ -134,10 +150,12 @@ impl GpuMiner {
         gpu_manager.set_gpu_settings(gpu_settings.clone());
         gpu_manager.set_thread_id_offset(thread_id_offset);
 
-        // Initialize GPU manager with settings
-        gpu_manager
-            .initialize()
-            .map_err(|e| format!("Failed to initialize GPU manager for hybrid: {}", e))?;
+        // Initialize GPU manager with settings (unless the caller already did)
+        if !gpu_manager.is_initialized() {
+            gpu_manager
+                .initialize()
+                .map_err(|e| format!("Failed to initialize GPU manager for hybrid: {}", e))?;
+        }
 
         info!(target: LOG_TARGET,
             "🎮 GPU miner created for hybrid mode with {} device(s), offset={}",
@@ -149,13 +167,17 @@ impl GpuMiner {
             wallet_address,
             pool_address,
             worker_name,
-            pool_session_id: Mutex::new(None),
+            pool_endpoints: Vec::new(),
+            failover_settings: FailoverSettings::default(),
             stats: external_stats,
             pool_client: external_pool_client,
             algo,
             gpu_manager,
             gpu_settings,
             external_stats: true,
+            control: Mutex::new(None),
+            governor: GovernorConfig::default(),
+            nonce_allocator: Arc::new(NonceAllocator::new()),
         })
     }
 
@@ -174,6 +196,24 @@ impl GpuMiner {
         &self.gpu_settings
     }
 
+    /// Push new settings to the running GPU threads
+    pub fn apply_runtime_settings(&self, settings: &RuntimeSettings) {
+        self.gpu_manager.apply_runtime_settings(settings);
+    }
+
+    /// Follow GPU setting, excluded device and pause changes from a MinerControl;
+    /// the pool session started by run() follows its pool selection
+    pub fn watch_control(self: &Arc<Self>, mut settings: watch::Receiver<RuntimeSettings>) {
+        *self.control.lock().unwrap() = Some(settings.clone());
+        let miner = Arc::clone(self);
+        tokio::spawn(async move {
+            while settings.changed().await.is_ok() {
+                let current = settings.borrow_and_update().clone();
+                miner.apply_runtime_settings(&current);
+            }
+        });
+    }
+
     /// Get GPU performance summary
     pub fn get_performance_summary(&self) -> String {
         self.gpu_manager.get_performance_summary()
@@ -188,320 +228,59 @@ impl GpuMiner {
         Arc::clone(&self.stats)
     }
 
-    /// Connect to pool
-    async fn connect_to_pool(&self) -> Result<tokio::net::TcpStream> {
-        self.pool_client.connect_str(&self.pool_address).await
-    }
-
-    /// Login to pool
-    async fn login(&self, writer: &mut tokio::net::tcp::OwnedWriteHalf) -> Result<()> {
-        let login_msg = StratumProtocol::to_message(StratumProtocol::create_login_request(
-            &self.wallet_address,
-            &self.worker_name,
+    /// Pool session parameters for this miner
+    fn session_config(&self) -> PoolSessionConfig {
+        let mut config = PoolSessionConfig::single(
+            self.pool_address.clone(),
+            self.wallet_address.clone(),
+            self.worker_name.clone(),
             self.algo,
-        ));
-        writer.write_all(login_msg.as_bytes()).await?;
-        writer.flush().await?;
-        info!(target: LOG_TARGET,
-            "📤 Sent GPU miner login request ({}% intensity)",
-            self.gpu_settings.intensity
+            "GPU",
         );
-        Ok(())
+        if !self.pool_endpoints.is_empty() {
+            config.pools = self.pool_endpoints.clone();
+        }
+        config.failover = self.failover_settings.clone();
+        config
     }
 
-    /// Handle connection events and update latency every 5 seconds
-    async fn handle_connection_events(&self) {
-        // Track connection events and update latency every 5 seconds
-        let mut interval = tokio::time::interval(Duration::from_secs(5));
-
-        loop {
-            interval.tick().await;
-
-            if self.pool_client.is_connected() {
-                // Measure current connection latency by timing a lightweight operation
-                let start = Instant::now();
-
-                // Simple connection health check - measure how responsive the connection is
-                let latency = start.elapsed();
+    /// Use a failover pool list instead of the single pool given at construction
+    pub fn set_pool_failover(&mut self, pools: Vec<PoolEndpoint>, settings: FailoverSettings) {
+        self.pool_endpoints = pools;
+        self.failover_settings = settings;
+    }
 
-                // Update the pool client with current latency (add some realistic variation)
-                let actual_latency = Duration::from_millis(
-                    (latency.as_millis() as u64 + 20 + (rand::random::<u64>() % 30)).max(10),
-                );
+    /// Settings of the thermal / power governor (hard temperature cutoff)
+    pub fn set_governor(&mut self, config: GovernorConfig) {
+        self.governor = config;
+    }
 
-                self.pool_client.update_latency(actual_latency);
+    /// Share a nonce allocator with other miners (hybrid mode), so no two workers
+    /// search the same SHA3x nonces
+    pub fn set_nonce_allocator(&mut self, allocator: Arc<NonceAllocator>) {
+        self.nonce_allocator = allocator;
+    }
 
-                debug!(target: LOG_TARGET,"Updated GPU pool latency: {}ms", actual_latency.as_millis());
-            } else {
-                // Connection lost - this would be called in real disconnect scenarios
-                debug!(target: LOG_TARGET,"GPU pool connection lost, stopping latency monitoring");
-                break;
-            }
-        }
+    /// Stop a GPU after this many of its shares failed the CPU re-check
+    pub fn set_hw_error_limit(&mut self, limit: Option<u32>) {
+        self.gpu_manager.set_hw_error_limit(limit);
     }
 
-    /// Handle pool messages
-    async fn handle_pool_message(
-        &self,
-        message: &str,
-        job_tx: &BroadcastSender<MiningJob>,
-    ) -> Result<()> {
-        debug!(target: LOG_TARGET,"📨 GPU miner pool message: {}", message);
-        let response: Value = serde_json::from_str(message)?;
-
-        info!(target: LOG_TARGET,"Received GPU pool response: {:?}", response);
-
-        if let Some(method) = response.get("method").and_then(|m| m.as_str()) {
-            match method {
-                "job" => {
-                    debug!(target: LOG_TARGET,"Processing GPU job message: {:?}", response);
-                    if let Some(params) = response.get("params").and_then(|p| p.as_object()) {
-                        self.handle_new_job(params, job_tx).await?;
-                        if let Some(diff) = params.get("difficulty").and_then(|d| d.as_u64()) {
-                            self.stats.add_activity(format!(
-                                "🎮 GPU VarDiff update: {} ({}% intensity)",
-                                MinerStats::format_number(diff),
-                                self.gpu_settings.intensity
-                            ));
-                            info!(target: LOG_TARGET,"🎮 GPU VarDiff job update received");
-                        }
-                    }
-                }
-                _ => {
-                    debug!(target: LOG_TARGET,"Unknown method: {}", method);
-                }
-            }
-        } else if let Some(result) = response.get("result") {
-            debug!(target: LOG_TARGET,"GPU miner result response: {:?}", result);
-            if let Some(id) = response.get("id").and_then(|id| id.as_u64()) {
-                match id {
-                    1 => {
-                        info!(target: LOG_TARGET,"✅ GPU miner login successful");
-                        self.stats
-                            .add_activity("🎮 GPU connected successfully".to_string());
-
-                        if let Some(pool_session_id) = result.get("id").and_then(|id| id.as_str()) {
-                            *self.pool_session_id.lock().await = Some(pool_session_id.to_string());
-                            info!(target: LOG_TARGET,"GPU pool session ID: {}", pool_session_id);
-                        } else {
-                            error!(target: LOG_TARGET,"No session ID in GPU login response");
-                        }
-
-                        if let Some(job_params) = result.get("job").and_then(|j| j.as_object()) {
-                            debug!(target: LOG_TARGET,"Found job in GPU login response: {:?}", job_params);
-                            self.handle_new_job(job_params, job_tx).await?;
-                        }
-                    }
-                    _ => {
-                        let gpu_id = id as usize;
-                        debug!(target: LOG_TARGET,
-                            "GPU share response for ID {} (GPU {}): {:?}",
-                            id, gpu_id, result
-                        );
-
-                        // FIXED: LuckyPool share validation - check response.error first
-                        let accepted = if let Some(error) = response.get("error") {
-                            // LuckyPool: check error field first
-                            if error.is_null() {
-                                // Error is null, check result
-                                result.as_bool().unwrap_or(true)
-                            } else {
-                                // Error is not null, share was rejected
-                                error!(target: LOG_TARGET,"❌ GPU share rejected by LuckyPool: {:?}", error);
-                                false
-                            }
-                        } else {
-                            // No error field, fall back to original logic for other pools
-                            if let Some(status) = result.get("status").and_then(|s| s.as_str()) {
-                                matches!(status.to_lowercase().as_str(), "ok" | "accepted")
-                            } else if result.is_null() {
-                                info!(target: LOG_TARGET,"✅ GPU share accepted (null response)");
-                                true
-                            } else if let Some(accepted) = result.as_bool() {
-                                accepted
-                            } else {
-                                error!(target: LOG_TARGET,"❌ Unknown GPU share response format: {:?}", result);
-                                false
-                            }
-                        };
-
-                        if accepted {
-                            self.stats.shares_accepted.fetch_add(1, Ordering::Relaxed);
-                            info!(target: LOG_TARGET,
-                                "✅ GPU share accepted by pool ({}% intensity)",
-                                self.gpu_settings.intensity
-                            );
-                            self.stats.add_activity(format!(
-                                "✅ GPU share accepted from device {}",
-                                gpu_id
-                            ));
-                        } else {
-                            self.stats.shares_rejected.fetch_add(1, Ordering::Relaxed);
-                            info!(target: LOG_TARGET,"❌ GPU share rejected from device {}", gpu_id);
-                            self.stats.add_activity(format!(
-                                "❌ GPU share rejected from device {}",
-                                gpu_id
-                            ));
-                        }
-
-                        // For hybrid mode, need to account for thread ID offset
-                        let thread_id = if self.external_stats {
-                            self.gpu_manager
-                                .threads
-                                .get(gpu_id)
-                                .map(|t| t.thread_id)
-                                .unwrap_or(gpu_id)
-                        } else {
-                            gpu_id
-                        };
-
-                        if thread_id < self.stats.thread_stats.len() {
-                            self.stats.thread_stats[thread_id].record_share(0, accepted);
-                        }
-                    }
-                }
-            }
-        } else if let Some(error) = response.get("error") {
-            error!(target: LOG_TARGET,"❌ GPU pool error: {:?}", error);
-            self.stats
-                .add_activity(format!("🚫 GPU pool error: {}", error));
-        } else {
-            debug!(target: LOG_TARGET,"Unknown GPU pool message: {:?}", response);
+    /// Tunnel this miner's pool connections through a SOCKS5 / HTTP proxy
+    pub fn set_proxy(&self, proxy: Option<ProxyConfig>) {
+        if let Some(ref proxy) = proxy {
+            info!(target: LOG_TARGET,"🧅 GPU pool connections via proxy {}", proxy);
         }
-
-        Ok(())
+        self.pool_client.set_proxy(proxy);
     }
 
-    /// Handle new job with LuckyPool XN support - FIXED compilation issues
-    async fn handle_new_job(
-        &self,
-        job_data: &serde_json::Map<String, Value>,
-        job_tx: &BroadcastSender<MiningJob>,
-    ) -> Result<()> {
-        let job: PoolJob = serde_json::from_value(Value::Object(job_data.clone()))?;
-
-        let header_template = hex::decode(job.blob.unwrap_or_default())?;
-        let target_difficulty = job
-            .difficulty
-            .unwrap_or_else(|| parse_target_difficulty(&job.target, self.algo));
-
-        // FIXED: Handle XN (extra nonce) properly without borrow issues
-        let xn_info = if let Some(ref xn) = job.xn {
-            info!(target: LOG_TARGET,
-                "🔧 Special XN detected: {} (will be used as first 2 bytes of nonce)",
-                xn
-            );
-            *LUCKY_POOL_XN.lock().await = xn.clone(); // Store XN for nonce generation
-            format!(" XN: {}", xn)
-        } else {
-            String::new()
-        };
-
-        let mining_job = MiningJob {
-            job_id: job.job_id.clone(),
-            mining_hash: header_template,
-            target_difficulty,
-            height: job.height,
-            algo: Algorithm::Sha3x,
-            extranonce2: Some(LUCKY_POOL_XN.lock().await.clone()), // ✅ Pass XN from LuckyPool to mining threads
-            prev_hash: None,
-            merkle_root: None,
-            version: None,
-            ntime: None,
-            nbits: None,
-            merkle_path: None,
-            target: None,
-        };
-
-        // Update stats with job data
-        self.stats
-            .update_job(job.job_id.clone(), job.height, target_difficulty);
-
-        job_tx.send(mining_job)?;
-
+    /// Start GPU mining threads fed by an existing pool session
+    pub fn attach_to_session(&self, session: &PoolSession) -> Result<()> {
         info!(target: LOG_TARGET,
-            "🎮 GPU job sent: {} (height: {}, difficulty: {}, {}% intensity{})",
-            job.job_id,
-            job.height,
-            MinerStats::format_number(target_difficulty),
-            self.gpu_settings.intensity,
-            xn_info
+            "🎮 Starting GPU mining with settings: {}",
+            self.get_performance_summary()
         );
-        self.stats.add_activity(format!(
-            "🎮 GPU job: {} (height: {}, difficulty: {}{})",
-            &job.job_id[..8.min(job.job_id.len())],
-            job.height,
-            MinerStats::format_number(target_difficulty),
-            xn_info
-        ));
-
-        Ok(())
-    }
-
-    /// Start share submitter for GPU
-    fn start_gpu_share_submitter(
-        miner: Arc<Self>,
-        writer: Arc<Mutex<tokio::net::tcp::OwnedWriteHalf>>,
-        mut share_rx: mpsc::UnboundedReceiver<(String, String, String, usize, u64, String, u32)>,
-    ) {
-        let algo = miner.algo;
-        let intensity = miner.gpu_settings.intensity;
-        static GPU_SUBMIT_ID: AtomicU32 = AtomicU32::new(0); // Start at 0 for GPU shares
-
-        tokio::spawn(async move {
-            while let Some((job_id, nonce, result, gpu_id, difficulty, _extranonce2, _ntime)) =
-                share_rx.recv().await
-            {
-                let pool_session_id = miner
-                .pool_session_id
-                .lock()
-                .await
-                .clone()
-                .unwrap_or_else(|| {
-                    error!(target: LOG_TARGET,"GPU pool session ID not set, cannot submit shares");
-                    "".to_string()
-                });
-
-                info!(target: LOG_TARGET,
-                    "📤 Session ID: {}",
-                    pool_session_id
-                );
-
-                let submit_request = StratumProtocol::create_submit_request(
-                    &pool_session_id,
-                    &job_id,
-                    &nonce,
-                    &result,
-                    GPU_SUBMIT_ID.fetch_add(1, Ordering::SeqCst) as u64,
-                    algo,
-                    None, // No extranonce2 for SHA3x
-                    None, // No ntime for SHA3x
-                );
-                let message = StratumProtocol::to_message(submit_request);
-                info!(target: LOG_TARGET,
-                    "📤 LuckyPool message: {}",
-                    message
-                );
-                if message.is_empty() {
-                    error!(target: LOG_TARGET,"Failed to create GPU submit message for job {}", job_id);
-                    continue;
-                }
-
-                info!(target: LOG_TARGET,
-                    "📤 Submitting GPU share: job_id={}, nonce={}, gpu={}, difficulty={} ({}% intensity)",
-                    job_id,
-                    nonce,
-                    gpu_id,
-                    MinerStats::format_number(difficulty),
-                    intensity
-                );
-
-                let mut writer = writer.lock().await;
-                if let Err(e) = writer.write_all(message.as_bytes()).await {
-                    error!(target: LOG_TARGET,"Failed to submit GPU share: {}", e);
-                }
-            }
-        });
+        self.start_mining_threads(session.subscribe_jobs(), session.share_sender())
     }
 
     /// Start stats printer for GPU
@@ -520,114 +299,53 @@ impl GpuMiner {
         });
     }
 
-    /// Run GPU mining
+    /// Start the thermal / power governor; telemetry reads block, so it gets its own thread
+    fn start_governor(miner: Arc<Self>) {
+        let governor = GpuGovernor::new(miner.governor, NvidiaSmiTelemetry::new());
+        let spawned = std::thread::Builder::new()
+            .name("gpu-governor".to_string())
+            .spawn(move || governor.run(&miner.gpu_manager, &miner.stats));
+        if let Err(e) = spawned {
+            warn!(target: LOG_TARGET,"⚠️ GPU governor not started, limits are not enforced: {}", e);
+        }
+    }
+
+    /// Run GPU mining (standalone or hybrid mode, each with its own pool session)
     pub async fn run(self: Arc<Self>) -> Result<()> {
         if self.algo != Algorithm::Sha3x {
             return Err("GPU miner only supports SHA3x algorithm".into());
         }
 
-        // Don't connect to pool if using external pool client (hybrid mode)
-        if self.external_stats {
-            info!(target: LOG_TARGET,"🎮 GPU miner running in hybrid mode - using shared pool connection");
-            return self.run_mining_only().await;
-        }
-
-        let stream = self.connect_to_pool().await?;
-        info!(target: LOG_TARGET,"✅ GPU miner connected to SHA3x pool");
-        self.stats
-            .add_activity("🎮 GPU connected to pool".to_string());
-
-        let (reader, writer) = stream.into_split();
-        let writer = Arc::new(Mutex::new(writer));
+        let session = Arc::new(PoolSession::new(
+            self.session_config(),
+            Arc::clone(&self.pool_client),
+            Arc::clone(&self.stats),
+        )?);
 
-        // Login to pool
-        {
-            let mut writer_guard: tokio::sync::MutexGuard<'_, tokio::net::tcp::OwnedWriteHalf> =
-                writer.lock().await;
-            self.login(&mut writer_guard).await?;
+        if let Some(settings) = self.control.lock().unwrap().clone() {
+            session.follow_control(settings);
         }
-        info!(target: LOG_TARGET,"🎮 GPU miner login request sent");
-
-        let (job_tx, _) = broadcast::channel(16);
-        let (share_tx, share_rx) =
-            mpsc::unbounded_channel::<(String, String, String, usize, u64, String, u32)>();
-
-        // Start GPU mining threads
-        info!(target: LOG_TARGET,
-            "🎮 Starting GPU mining with settings: {}",
-            self.get_performance_summary()
-        );
-        self.start_mining_threads(job_tx.subscribe(), share_tx)?;
-
-        Self::start_gpu_share_submitter(self.clone(), Arc::clone(&writer), share_rx);
-        Self::start_gpu_stats_printer(self.clone());
-
-        // Start connection monitoring in background
-        let connection_monitor = self.clone();
-        tokio::spawn(async move {
-            connection_monitor.handle_connection_events().await;
-        });
-
-        let reader = BufReader::new(reader);
-        let mut lines = reader.lines();
-
-        info!(target: LOG_TARGET,
-            "🚀 GPU miner fully operational - delivering 385+ MH/s with {}% intensity!",
-            self.gpu_settings.intensity
-        );
+        self.attach_to_session(&session)?;
+        Self::start_governor(self.clone());
 
-        loop {
-            match lines.next_line().await {
-                Ok(Some(line)) => {
-                    self.handle_pool_message(&line, &job_tx).await?;
-                }
-                Ok(None) => {
-                    info!(target: LOG_TARGET,"📡 GPU connection closed, attempting reconnect...");
-                    self.pool_client.mark_disconnected();
-                    tokio::time::sleep(Duration::from_secs(5)).await;
-                    break;
-                }
-                Err(e) => {
-                    error!(target: LOG_TARGET,"📡 GPU connection error: {}, attempting reconnect...", e);
-                    self.pool_client.mark_disconnected();
-                    tokio::time::sleep(Duration::from_secs(5)).await;
-                    break;
-                }
-            }
+        // In hybrid mode the CPU miner prints the shared dashboard
+        if !self.external_stats {
+            Self::start_gpu_stats_printer(self.clone());
         }
 
-        Ok(())
-    }
-
-    /// Run mining only (for hybrid mode where pool connection is handled externally)
-    async fn run_mining_only(self: Arc<Self>) -> Result<()> {
-        info!(target: LOG_TARGET,"🎮 Starting GPU mining component for hybrid mode");
-        info!(target: LOG_TARGET,"🎮 Settings: {}", self.get_performance_summary());
-
-        // Create dummy channels for mining threads (they won't be used in hybrid mode)
-        let (job_tx, _job_rx) = broadcast::channel(16);
-        let (share_tx, _share_rx) =
-            mpsc::unbounded_channel::<(String, String, String, usize, u64, String, u32)>();
-
-        // Start mining threads
-        self.start_mining_threads(job_tx.subscribe(), share_tx)?;
-
         info!(target: LOG_TARGET,
-            "🚀 GPU mining component started for hybrid mode with {}% intensity!",
+            "🚀 GPU miner fully operational with {}% intensity!",
             self.gpu_settings.intensity
         );
 
-        // Keep the component alive
-        loop {
-            tokio::time::sleep(Duration::from_secs(60)).await;
-        }
+        session.run().await
     }
 
     /// Start mining threads with GPU settings
     fn start_mining_threads(
         &self,
         job_rx: tokio::sync::broadcast::Receiver<MiningJob>,
-        share_tx: mpsc::UnboundedSender<(String, String, String, usize, u64, String, u32)>,
+        share_tx: mpsc::UnboundedSender<ShareSubmission>,
     ) -> Result<()> {
         let gpu_count = self.gpu_manager.device_count();
         info!(target: LOG_TARGET,
@@ -639,7 +357,6 @@ impl GpuMiner {
         let devices = &self.gpu_manager.devices;
         let threads = &self.gpu_manager.threads;
         let stats = Arc::clone(&self.stats);
-        let gpu_settings = self.gpu_settings.clone();
 
         // Start GPU mining threads manually (same approach as in run() method)
         for (i, device) in devices.iter().enumerate() {
@@ -647,14 +364,16 @@ impl GpuMiner {
             let job_rx_clone = job_rx.resubscribe();
             let share_tx_clone = share_tx.clone();
             let stats_thread_clone = Arc::clone(&stats);
+            let nonce_allocator = Arc::clone(&self.nonce_allocator);
+            let hw_error_limit = self.gpu_manager.hw_error_limit();
             let device_name = device.name().to_string();
             let estimated_hashrate = threads[i].estimated_hashrate;
             let thread_id = threads[i].thread_id; // Use the actual thread ID (0 for GPU-only, offset for hybrid)
-            let settings_clone = gpu_settings.clone();
+            let (control, device_stats) = self.gpu_manager.thread_control(i, &stats); // Per-device settings, follows hot reloads
 
             info!(target: LOG_TARGET,
                 "🎮 Launching GPU mining thread {} for {} (~{:.1} MH/s, {}% intensity)",
-                thread_id, device_name, estimated_hashrate, gpu_settings.intensity
+                thread_id, device_name, estimated_hashrate, threads[i].gpu_settings.intensity
             );
 
             // Spawn GPU mining thread using std::thread for OpenCL safety
@@ -671,13 +390,22 @@ impl GpuMiner {
                         job_rx_clone,
                         share_tx_clone,
                         stats_thread_clone,
-                        settings_clone,
+                        control,
+                        device_stats,
+                        nonce_allocator,
+                        hw_error_limit,
                     )
                     .await;
                 });
             });
         }
 
+        // Threads start enabled; catch up with a pause or change made before they registered
+        if let Some(settings) = self.control.lock().unwrap().as_ref() {
+            let current = settings.borrow().clone();
+            self.apply_runtime_settings(&current);
+        }
+
         info!(target: LOG_TARGET,
             "🚀 GPU mining threads started with {}% intensity!",
             self.gpu_settings.intensity
@@ -687,6 +415,30 @@ impl GpuMiner {
 }
 
 // Changelog:
+// - v1.9.0-share-verification (2025-06-28): set_hw_error_limit() stops a GPU after that many
+//   shares failed the CPU re-check (--gpu-hw-error-limit).
+// - v1.8.0-nonce-allocator (2025-06-28): GPU threads claim nonce ranges from a NonceAllocator;
+//   set_nonce_allocator() shares one with the CPU miner in hybrid mode.
+// - v1.7.0-thermal-governor (2025-06-28): run() starts the GPU governor thread, which
+//   throttles devices towards their temp_limit / power_limit and stops them at the
+//   --gpu-temp-cutoff (set_governor()).
+// - v1.6.1 (2025-06-28): New mining threads pick up the current control settings, so a
+//   scheduled pause in effect at startup also idles the GPUs.
+// - v1.6.0-pool-selection (2025-06-28): watch_control() hands the control channel to the
+//   pool session, so the HTTP API can pin a pool; pausing idles every GPU.
+// - v1.5.0-autotune (2025-06-28): new_with_settings() / new_for_hybrid() keep an already
+//   initialized GpuManager, so autotuned settings are not rebuilt from scratch.
+// - v1.4.0-runtime-reload (2025-06-28): Runtime settings hot-reload.
+//   - Added watch_control() / apply_runtime_settings(); GPU threads pick up new
+//     settings and runtime-excluded devices without reconnecting to the pool.
+//   - Mining threads now use their per-device settings instead of the global ones.
+// - v1.3.0-pool-proxy-support (2025-06-28): Added set_pool_failover() and set_proxy()
+//   for failover pools and SOCKS5 / HTTP CONNECT proxies.
+// - v1.2.0-shared-pool-session (2025-06-28): Moved pool handling into pool::session
+//   - Connection, login, XN tracking, share submission and latency monitoring now live in PoolSession.
+//   - Hybrid mode runs its own session instead of mining on unconnected dummy channels.
+//   - Added attach_to_session() so GPU threads can be fed by any pool session.
+//   - Share responses are matched by JSON-RPC id; GPU submit ids no longer collide with login id 1.
 // - v1.1.4-connection-monitoring (2025-06-27): Added connection latency monitoring
 //   *** CONNECTION MONITORING ***:
 //   - Added handle_connection_events() method that updates pool latency every 5 seconds
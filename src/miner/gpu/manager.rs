@@ -1,30 +1,90 @@
 // SHA3x Miner - Free and Open Source Software Statement
 //
 // File: src/miner/gpu/manager.rs
-// Version: 3.2.2 - LuckyPool XN Nonce Generation Fix
+// Version: 3.8.1 - Share Verification
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // CRITICAL FIX: Removed the 1ms sleep that was destroying GPU performance
 // FIXED: LuckyPool 8-byte nonce + XN (extra nonce) proper generation
 // GPU mining manager with GPU settings support and hybrid thread coordination
+// Per-device settings: --gpu-device / [gpu.devices.KEY] overrides by index or name
+// pattern, layered over the settings stored in the GPU information file
+// Runtime settings changes pushed to running threads over watch channels
+// Autotune results stored per GPU model, re-tuned when the driver or kernel changes
+// Thermal / power governor throttles scale the configured intensity of running threads
+// Every kernel launch hashes a nonce range claimed from the shared NonceAllocator
+// GPU shares are re-hashed on the CPU before submission; mismatches are hardware errors
 
 use anyhow::{Error, Result};
 use log::{debug, error, info, warn};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::broadcast::Receiver;
 use tokio::sync::mpsc::UnboundedSender;
-
-use super::opencl::{OpenClDevice, OpenClEngine};
-use crate::core::types::{GpuSettings, MiningJob};
+use tokio::sync::watch;
+
+use super::governor::{GovernedDevice, Throttle};
+use super::opencl::engine::kernel_hash;
+use super::opencl::{AutotuneConfig, AutotuneResult, OpenClDevice, OpenClEngine};
+use crate::core::calculate_difficulty;
+use crate::core::config::{
+    GpuDeviceConfig, GpuDeviceOverrides, matching_override, resolve_device_settings,
+};
+use crate::core::sha3x::sha3x_hash_with_nonce;
+use crate::core::types::{Algorithm, GpuSettings, MiningJob};
+use crate::miner::control::RuntimeSettings;
 use crate::miner::gpu::gpu_information_file::GpuInformationFileDevice;
-use crate::miner::gpu::{GpuInformationFile, GpuInformationFileManager, KernelType};
-use crate::miner::stats::MinerStats;
+use crate::miner::gpu::{
+    GpuAutotuneFile, GpuAutotuneProfile, GpuInformationFile, GpuInformationFileError,
+    GpuInformationFileManager, KernelType,
+};
+use crate::miner::nonce::NonceAllocator;
 use crate::miner::stats::gpu_info::GpuVendor;
+use crate::miner::stats::{GpuDeviceStats, MinerStats};
 
 const LOG_TARGET: &str = "tari::graxil::manager";
 
+/// How often a disabled GPU thread checks whether it should mine again
+const DISABLED_POLL: Duration = Duration::from_millis(200);
+
+/// A running GPU mining thread as seen by apply_runtime_settings()
+struct ThreadHandle {
+    device_id: u32,
+    device_name: String,
+    control: watch::Sender<GpuThreadControl>,
+    stats: Arc<GpuDeviceStats>,
+    /// Settings from the configuration, before the governor's throttle
+    configured: GpuThreadControl,
+    throttle: Throttle,
+}
+
+impl ThreadHandle {
+    /// Send the configured settings with the throttle applied to the thread
+    fn push(&self) -> bool {
+        let mut update = self.configured.clone();
+        update.settings.intensity = self.throttle.intensity(update.settings.intensity);
+        update.enabled &= !self.throttle.stopped;
+        self.stats.update(update.settings.clone(), update.enabled);
+        self.control.send_if_modified(|current| {
+            if *current == update {
+                return false;
+            }
+            *current = update;
+            true
+        })
+    }
+}
+
+/// Live settings for one running GPU mining thread
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpuThreadControl {
+    pub settings: GpuSettings,
+    /// False while the device is excluded at runtime
+    pub enabled: bool,
+}
+
 /// GPU mining thread data
 #[derive(Clone)]
 pub struct GpuMiningThread {
@@ -41,8 +101,13 @@ pub struct GpuManager {
     pub threads: Vec<GpuMiningThread>,
     initialized: bool,
     gpu_settings: GpuSettings,
-    excluded_devices: Vec<u32>, // Excluded devices by ID
-    thread_id_offset: usize,    // For hybrid mode thread coordination
+    device_settings: GpuDeviceOverrides, // Per-device overrides by index or name pattern
+    stored_settings: BTreeMap<u32, (String, GpuDeviceConfig)>, // From the GPU information file
+    autotuned: BTreeMap<u32, (String, GpuDeviceConfig)>, // Current autotune results
+    excluded_devices: Vec<u32>,          // Excluded devices by ID
+    thread_id_offset: usize,             // For hybrid mode thread coordination
+    hw_error_limit: Option<u32>,         // Stop a GPU after this many hardware errors
+    controls: Mutex<Vec<ThreadHandle>>,  // Running threads
 }
 
 impl GpuManager {
@@ -54,8 +119,13 @@ impl GpuManager {
             threads: Vec::new(),
             initialized: false,
             gpu_settings: GpuSettings::default(),
+            device_settings: Vec::new(),
+            stored_settings: BTreeMap::new(),
+            autotuned: BTreeMap::new(),
             excluded_devices: Vec::new(), // No excluded devices by default
             thread_id_offset: 0,          // Default: GPU uses thread ID 0
+            hw_error_limit: None,
+            controls: Mutex::new(Vec::new()),
         }
     }
 
@@ -70,22 +140,252 @@ impl GpuManager {
             threads: Vec::new(),
             initialized: false,
             gpu_settings: settings,
+            device_settings: Vec::new(),
+            stored_settings: BTreeMap::new(),
+            autotuned: BTreeMap::new(),
             thread_id_offset: 0,
             excluded_devices,
+            hw_error_limit: None,
+            controls: Mutex::new(Vec::new()),
         }
     }
 
+    /// Stop a GPU once this many of its shares failed the CPU re-check (None = never)
+    pub fn set_hw_error_limit(&mut self, limit: Option<u32>) {
+        self.hw_error_limit = limit;
+    }
+
+    pub fn hw_error_limit(&self) -> Option<u32> {
+        self.hw_error_limit
+    }
+
     /// Set GPU settings after creation
     pub fn set_gpu_settings(&mut self, settings: GpuSettings) {
         info!(target: LOG_TARGET,
             "Setting GPU manager settings: intensity={}%, batch={:?}, power={:?}%, temp={:?}°C",
             settings.intensity, settings.batch_size, settings.power_limit, settings.temp_limit
         );
-        self.gpu_settings = settings.clone();
+        self.gpu_settings = settings;
+        self.refresh_thread_settings();
+    }
+
+    /// Override the GPU settings of individual devices, selected by index or name pattern
+    pub fn set_device_settings(&mut self, settings: GpuDeviceOverrides) {
+        for (selector, device_settings) in &settings {
+            info!(target: LOG_TARGET,
+                "Setting GPU {} settings: intensity={:?}%, batch={:?}, power={:?}%, temp={:?}°C, WG/CU={:?}",
+                selector,
+                device_settings.intensity,
+                device_settings.batch_size,
+                device_settings.power_limit,
+                device_settings.temp_limit,
+                device_settings.work_groups_per_cu
+            );
+        }
+        self.device_settings = settings;
+        self.refresh_thread_settings();
+    }
+
+    /// Use the per-device settings stored in the GPU information file (written by
+    /// --detect). A missing file is not an error; devices whose name changed are skipped.
+    pub async fn load_information_file(&mut self, directory_path: PathBuf) -> Result<()> {
+        let manager = GpuInformationFileManager::new(directory_path, KernelType::OpenCL).await?;
+        let information_file = match manager.load().await {
+            Ok(information_file) => information_file,
+            Err(GpuInformationFileError::FileNotFound { path }) => {
+                info!(target: LOG_TARGET,
+                    "💡 No GPU information file at {:?} - run with --detect to create one",
+                    path
+                );
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        for device in information_file.devices {
+            if let Some(settings) = device.settings {
+                info!(target: LOG_TARGET,
+                    "📄 Stored settings for GPU {} ({}): {:?}",
+                    device.device_id, device.name, settings
+                );
+                self.stored_settings
+                    .insert(device.device_id, (device.name, settings));
+            }
+        }
+        self.refresh_thread_settings();
+        Ok(())
+    }
+
+    /// Settings stored for a device, if it is still the same card: the information
+    /// file settings, with unset fields taken from the autotune results
+    fn stored_for(&self, device_id: u32, device_name: &str) -> Option<GpuDeviceConfig> {
+        let stored = match self.stored_settings.get(&device_id) {
+            Some((name, settings)) if name == device_name => Some(settings),
+            Some((name, _)) => {
+                warn!(target: LOG_TARGET,
+                    "⚠️ GPU {} is now {} (information file lists {}) - ignoring stored settings",
+                    device_id, device_name, name
+                );
+                None
+            }
+            None => None,
+        };
+        let autotuned = self
+            .autotuned
+            .get(&device_id)
+            .filter(|(name, _)| name == device_name)
+            .map(|(_, settings)| settings);
+
+        match (stored, autotuned) {
+            (Some(stored), Some(autotuned)) => Some(stored.or(autotuned)),
+            (stored, autotuned) => stored.or(autotuned).cloned(),
+        }
+    }
+
+    /// Settings for a device: global settings, stored settings, then its override
+    fn settings_for(&self, device: &OpenClDevice) -> GpuSettings {
+        resolve_device_settings(
+            &self.gpu_settings,
+            &self.device_settings,
+            self.stored_for(device.device_id(), device.name()).as_ref(),
+            device.device_id(),
+            device.name(),
+        )
+    }
+
+    /// Apply stored autotune results and tune the GPUs that need it. Results are
+    /// kept per GPU model in `directory_path` (next to the information file); a
+    /// model is tuned when `force` is set (--autotune) or when its stored result
+    /// was measured with another OpenCL driver or kernel. Requires initialize().
+    pub async fn autotune(
+        &mut self,
+        directory_path: Option<PathBuf>,
+        test_job: &MiningJob,
+        config: AutotuneConfig,
+        force: bool,
+    ) -> Result<()> {
+        if !self.initialized {
+            return Err(Error::msg(
+                "GPU manager must be initialized before autotuning",
+            ));
+        }
+
+        let file_manager = match directory_path {
+            Some(directory_path) => {
+                Some(GpuInformationFileManager::new(directory_path, KernelType::OpenCL).await?)
+            }
+            None => None,
+        };
+        let mut profiles = match &file_manager {
+            Some(file_manager) => file_manager.load_autotune().await?,
+            None => GpuAutotuneFile::default(),
+        };
+        let kernel = kernel_hash();
+        let mut tuned_models: Vec<String> = Vec::new(); // Identical cards are tuned once
+
+        for device in self.devices.clone() {
+            let driver = device.driver_version();
+            let stored = profiles.find(device.name()).cloned();
+
+            let needs_tuning = match &stored {
+                _ if tuned_models.iter().any(|m| m == device.name()) => false,
+                Some(profile) if !profile.is_current(&driver, &kernel) => {
+                    info!(target: LOG_TARGET,
+                        "🔄 GPU {} ({}): driver or kernel changed since autotuning (driver {} → {}) - re-tuning",
+                        device.device_id(), device.name(), profile.driver_version, driver
+                    );
+                    true
+                }
+                Some(_) => force,
+                None if force => true,
+                None => continue,
+            };
 
-        // Update existing thread settings
-        for thread in &mut self.threads {
-            thread.gpu_settings = settings.clone();
+            let profile = match stored {
+                Some(profile) if !needs_tuning => profile,
+                _ => match self.autotune_device(&device, test_job, &config).await {
+                    Ok(result) => {
+                        let profile = GpuAutotuneProfile {
+                            device_name: device.name().to_string(),
+                            driver_version: driver,
+                            kernel_hash: kernel.clone(),
+                            settings: GpuDeviceConfig {
+                                intensity: Some(result.settings.intensity),
+                                batch_size: result.settings.batch_size,
+                                work_groups_per_cu: result.settings.work_groups_per_cu,
+                                ..GpuDeviceConfig::default()
+                            },
+                            hashrate_mhs: result.tuned_mhs,
+                            tuned_at: SystemTime::now()
+                                .duration_since(UNIX_EPOCH)
+                                .map_or(0, |d| d.as_secs()),
+                        };
+                        profiles.upsert(profile.clone());
+                        tuned_models.push(profile.device_name.clone());
+                        profile
+                    }
+                    Err(e) => {
+                        error!(target: LOG_TARGET,
+                            "❌ Autotuning GPU {} ({}) failed: {}",
+                            device.device_id(), device.name(), e
+                        );
+                        continue;
+                    }
+                },
+            };
+
+            info!(target: LOG_TARGET,
+                "🎛️ GPU {} ({}): autotuned intensity={:?}%, batch={:?}, WG/CU={:?} ({:.1} MH/s)",
+                device.device_id(),
+                device.name(),
+                profile.settings.intensity,
+                profile.settings.batch_size,
+                profile.settings.work_groups_per_cu,
+                profile.hashrate_mhs
+            );
+            self.autotuned.insert(
+                device.device_id(),
+                (device.name().to_string(), profile.settings),
+            );
+        }
+
+        if !tuned_models.is_empty() {
+            match &file_manager {
+                Some(file_manager) => file_manager.save_autotune(&profiles).await?,
+                None => warn!(target: LOG_TARGET,
+                    "⚠️ No --information-file-dir given - autotune results are used for this run only"
+                ),
+            }
+        }
+        self.refresh_thread_settings();
+        Ok(())
+    }
+
+    /// Run the sequential autotuner on one device, starting from its current settings
+    async fn autotune_device(
+        &self,
+        device: &OpenClDevice,
+        test_job: &MiningJob,
+        config: &AutotuneConfig,
+    ) -> Result<AutotuneResult> {
+        info!(target: LOG_TARGET,
+            "🎛️ Autotuning GPU {} ({}) - {}s per measurement",
+            device.device_id(),
+            device.name(),
+            config.test_duration_secs
+        );
+        let mut engine = OpenClEngine::new_with_settings(device.clone(), self.settings_for(device));
+        engine.initialize()?;
+        engine.enable_autotuning(config.clone());
+        engine.run_sequential_autotune(test_job).await
+    }
+
+    /// Re-resolve the settings of already initialized threads
+    fn refresh_thread_settings(&mut self) {
+        let settings: Vec<GpuSettings> =
+            self.devices.iter().map(|d| self.settings_for(d)).collect();
+        for (thread, settings) in self.threads.iter_mut().zip(settings) {
+            thread.gpu_settings = settings;
         }
     }
 
@@ -165,8 +465,9 @@ impl GpuManager {
         let mut threads = Vec::new();
         for (device_id, device) in suitable_devices.iter().enumerate() {
             // Create engine with GPU settings
+            let gpu_settings = self.settings_for(device);
             let mut test_engine =
-                OpenClEngine::new_with_settings(device.clone(), self.gpu_settings.clone());
+                OpenClEngine::new_with_settings(device.clone(), gpu_settings.clone());
             test_engine.initialize().map_err(|e| {
                 Error::msg(format!(
                     "Failed to initialize engine for {}: {}",
@@ -177,10 +478,13 @@ impl GpuManager {
 
             let estimated_hashrate = test_engine.estimate_hashrate();
             info!(target: LOG_TARGET,
-                "🚀 GPU {} ready - estimated {:.1} MH/s with {}% intensity",
+                "🚀 GPU {} ready - estimated {:.1} MH/s with {}% intensity, batch={:?}, WG/CU={:?}, temp={:?}°C",
                 device.name(),
                 estimated_hashrate,
-                self.gpu_settings.intensity
+                gpu_settings.intensity,
+                gpu_settings.batch_size,
+                gpu_settings.work_groups_per_cu,
+                gpu_settings.temp_limit
             );
 
             let thread_info = GpuMiningThread {
@@ -188,7 +492,7 @@ impl GpuManager {
                 thread_id: self.thread_id_offset + device_id, // Apply thread ID offset for hybrid mode
                 device_name: device.name().to_string(),
                 estimated_hashrate,
-                gpu_settings: self.gpu_settings.clone(),
+                gpu_settings,
             };
 
             threads.push(thread_info);
@@ -256,12 +560,120 @@ impl GpuManager {
         )
     }
 
+    /// Control channel for the mining thread of device `index`; the sender is
+    /// kept so apply_runtime_settings() can reach the running thread, and the
+    /// device is registered with `stats` for per-device reporting. The device
+    /// stats are returned for the mining thread to count its hardware errors.
+    pub fn thread_control(
+        &self,
+        index: usize,
+        stats: &MinerStats,
+    ) -> (watch::Receiver<GpuThreadControl>, Arc<GpuDeviceStats>) {
+        let thread = &self.threads[index];
+        let device = &self.devices[index];
+        let configured = GpuThreadControl {
+            settings: thread.gpu_settings.clone(),
+            enabled: true,
+        };
+        let (sender, receiver) = watch::channel(configured.clone());
+
+        let device_stats = Arc::new(GpuDeviceStats::new(
+            device.device_id(),
+            thread.thread_id,
+            device.name().to_string(),
+            thread.gpu_settings.clone(),
+        ));
+        stats.add_gpu_device(Arc::clone(&device_stats));
+
+        self.controls.lock().unwrap().push(ThreadHandle {
+            device_id: device.device_id(),
+            device_name: device.name().to_string(),
+            control: sender,
+            stats: Arc::clone(&device_stats),
+            configured,
+            throttle: Throttle::NONE,
+        });
+        (receiver, device_stats)
+    }
+
+    /// Push new settings to the running GPU threads and enable / disable
+    /// devices, without restarting threads or the pool session
+    pub fn apply_runtime_settings(&self, settings: &RuntimeSettings) {
+        for device_id in &self.excluded_devices {
+            if !settings.excluded_devices.contains(device_id) {
+                warn!(target: LOG_TARGET,
+                    "⚠️ GPU {} was excluded at startup; restart the miner to mine on it",
+                    device_id
+                );
+            }
+        }
+
+        for thread in self.controls.lock().unwrap().iter_mut() {
+            let device_id = thread.device_id;
+            let (device_settings, enabled) = settings.gpu_device(
+                device_id,
+                &thread.device_name,
+                self.stored_for(device_id, &thread.device_name).as_ref(),
+            );
+            let update = GpuThreadControl {
+                settings: device_settings,
+                enabled,
+            };
+            if thread.configured != update {
+                info!(target: LOG_TARGET,
+                    "🔄 GPU {}: intensity={}%, batch={:?}, power={:?}%, temp={:?}°C, {}",
+                    device_id,
+                    update.settings.intensity,
+                    update.settings.batch_size,
+                    update.settings.power_limit,
+                    update.settings.temp_limit,
+                    if update.enabled { "enabled" } else { "disabled" }
+                );
+                thread.configured = update;
+            }
+            thread.push();
+        }
+    }
+
+    /// Running devices with their configured temperature and power limits
+    pub fn governed_devices(&self) -> Vec<GovernedDevice> {
+        self.controls
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|thread| GovernedDevice {
+                device_id: thread.device_id,
+                name: thread.device_name.clone(),
+                temp_limit: thread.configured.settings.temp_limit,
+                power_limit: thread.configured.settings.power_limit,
+            })
+            .collect()
+    }
+
+    /// Apply a governor throttle to the running thread of a device
+    pub fn set_throttle(&self, device_id: u32, throttle: Throttle) {
+        let mut controls = self.controls.lock().unwrap();
+        let Some(thread) = controls.iter_mut().find(|t| t.device_id == device_id) else {
+            return;
+        };
+        thread.throttle = throttle;
+        if thread.push() {
+            debug!(target: LOG_TARGET,
+                "🌡️ GPU {}: throttled to {}% intensity{}",
+                device_id,
+                throttle.intensity(thread.configured.settings.intensity),
+                if throttle.stopped { ", stopped" } else { "" }
+            );
+        }
+    }
+
     /// Start actual GPU mining threads with settings
     pub fn start_gpu_mining(
         &mut self,
         job_rx: Receiver<MiningJob>,
         share_tx: UnboundedSender<(String, String, String, usize, u64, String, u32)>,
         stats: Arc<MinerStats>,
+        nonce_allocator: Arc<NonceAllocator>,
     ) -> Result<()> {
         if !self.initialized {
             return Err(Error::msg("GPU manager not initialized"));
@@ -279,12 +691,15 @@ impl GpuManager {
             let job_rx_clone = job_rx.resubscribe();
             let share_tx_clone = share_tx.clone();
             let stats_clone = Arc::clone(&stats);
+            let nonce_allocator = Arc::clone(&nonce_allocator);
+            let hw_error_limit = self.hw_error_limit;
             let device_name = device.name().to_string();
             let estimated_hashrate = self.threads[i].estimated_hashrate;
-            let gpu_settings = self.gpu_settings.clone();
+            let gpu_settings = self.threads[i].gpu_settings.clone();
 
             // Update thread info with correct thread ID
             self.threads[i].thread_id = gpu_thread_id;
+            let (control, device_stats) = self.thread_control(i, &stats);
 
             info!(target: LOG_TARGET,
                 "🎮 Launching GPU mining thread {} for {} (~{:.1} MH/s, {}% intensity)",
@@ -312,7 +727,10 @@ impl GpuManager {
                         job_rx_clone,
                         share_tx_clone,
                         stats_clone,
-                        gpu_settings,
+                        control,
+                        device_stats,
+                        nonce_allocator,
+                        hw_error_limit,
                     )
                     .await;
                 });
@@ -324,15 +742,23 @@ impl GpuManager {
     }
 
     /// FIXED GPU mining loop - PERFORMANCE KILLER REMOVED! + LuckyPool XN nonce generation
-    /// The actual GPU mining loop with settings support (public for direct access)
+    /// The actual GPU mining loop with settings support (public for direct access).
+    /// `device_stats` counts the hardware errors checked against `hw_error_limit`.
     pub async fn gpu_mining_loop_with_settings(
         thread_id: usize,
         device: OpenClDevice,
         mut job_rx: Receiver<MiningJob>,
         share_tx: UnboundedSender<(String, String, String, usize, u64, String, u32)>,
         stats: Arc<MinerStats>,
-        gpu_settings: GpuSettings,
+        mut control: watch::Receiver<GpuThreadControl>,
+        device_stats: Arc<GpuDeviceStats>,
+        nonce_allocator: Arc<NonceAllocator>,
+        hw_error_limit: Option<u32>,
     ) {
+        let GpuThreadControl {
+            settings: mut gpu_settings,
+            mut enabled,
+        } = control.borrow_and_update().clone();
         info!(target: LOG_TARGET,
             "🎮 GPU mining thread {} starting for {} with {}% intensity",
             thread_id,
@@ -349,9 +775,10 @@ impl GpuManager {
 
         // let batch_size = engine.get_suggested_batch_size();
         let mut batch_size = 100;
-        let mut nonce_offset = thread_id as u64 * 1_000_000_000; // Unique nonce space per GPU
         let mut current_job: Option<MiningJob> = None;
         let mut last_stats_update = std::time::Instant::now();
+        // Stopped for too many hardware errors, until the miner restarts
+        let mut failed = false;
 
         info!(target: LOG_TARGET,
             "🎮 GPU thread {} initialized - starting CONTINUOUS mining with batch size {} ({}% intensity)",
@@ -366,46 +793,59 @@ impl GpuManager {
         );
 
         loop {
+            // Apply runtime setting changes without leaving the current job
+            if control.has_changed().unwrap_or(false) {
+                let update = control.borrow_and_update().clone();
+                if update.settings != gpu_settings {
+                    engine.set_gpu_settings(update.settings.clone());
+                    gpu_settings = update.settings;
+                }
+                if update.enabled != enabled {
+                    enabled = update.enabled;
+                    if enabled {
+                        info!(target: LOG_TARGET,"▶️ GPU thread {} re-enabled", thread_id);
+                    } else {
+                        info!(target: LOG_TARGET,"⏸️ GPU thread {} disabled", thread_id);
+                    }
+                }
+            }
+
             // Check for new jobs (non-blocking)
             if let Ok(job) = job_rx.try_recv() {
                 debug!(target: LOG_TARGET,"🎮 GPU {} got new job: {}", thread_id, job.job_id);
-                current_job = Some(job.clone());
                 info!(target: LOG_TARGET,"🎮 GPU {} got new job: {:?}", thread_id, job);
-                if job.extranonce2.is_some() {
+                if let Some(ref xn) = job.extranonce2 {
                     info!(target: LOG_TARGET,
                         "🎮 GPU {} received job with extranonce2 (XN): {}",
-                        thread_id, job.extranonce2.as_ref().unwrap()
-                    );
-                    nonce_offset = u16::from_le_bytes(
-                        hex::decode(job.extranonce2.as_ref().unwrap())
-                            .unwrap()
-                            .try_into()
-                            .unwrap(),
-                    ) as u64;
-                    info!(target: LOG_TARGET,
-                        "🎮 GPU {} nonce offset set to XN: {}",
-                        thread_id, nonce_offset
-                    );
-                } else {
-                    info!(target: LOG_TARGET,"🎮 GPU {} received job without extranonce2", thread_id);
-                    nonce_offset = thread_id as u64 * 1_000_000_000; // Reset nonce space
-                    info!(target: LOG_TARGET,
-                        "🎮 GPU {} nonce offset reset to: {}",
-                        thread_id, nonce_offset
+                        thread_id, xn
                     );
                 }
+                current_job = Some(job);
                 continue; // Immediately start mining the new job
             }
 
+            // Disabled GPUs keep following jobs but stop hashing
+            if !enabled || failed {
+                if let Some(thread_stats) = stats.thread_stats.get(thread_id) {
+                    thread_stats.update_hashrate(0);
+                }
+                tokio::time::sleep(DISABLED_POLL).await;
+                continue;
+            }
+
             // If we have a job, mine continuously at full speed!
             if let Some(ref job) = current_job {
+                // Each launch hashes its own range of the job's nonce space (XN kept in place)
+                let Some(range) = nonce_allocator.claim(job, engine.nonces_per_launch(batch_size))
+                else {
+                    tokio::time::sleep(DISABLED_POLL).await;
+                    continue;
+                };
+
                 // *** CRITICAL FIX: CONTINUOUS MINING - NO SLEEP! ***
-                match engine.mine(job, nonce_offset, batch_size).await {
+                match engine.mine(job, range.first_nonce(), batch_size).await {
                     Ok((found_nonce, hashes_processed, best_difficulty, new_batch_size)) => {
                         batch_size = new_batch_size;
-                        if let Some(found_nonce) = found_nonce {
-                            nonce_offset = found_nonce;
-                        }
                         // Update stats - FIXED to ensure thread_id is valid
                         if thread_id < stats.thread_stats.len() {
                             stats.thread_stats[thread_id].update_hashrate(hashes_processed);
@@ -432,27 +872,65 @@ impl GpuManager {
                             .hashes_computed
                             .fetch_add(hashes_processed, std::sync::atomic::Ordering::Relaxed);
 
-                        // Submit share if found
-                        if let Some(nonce) = found_nonce {
+                        // Re-hash found nonces on the CPU; a nonce missing the target is a hardware error
+                        let found_share = found_nonce.and_then(|nonce| {
+                            let verified = verify_gpu_share(job, nonce);
+                            if verified.is_none() {
+                                let errors = device_stats.record_hardware_error();
+                                error!(target: LOG_TARGET,
+                                    "❌ GPU {}: nonce {:016x} of job {} fails the CPU re-check, share dropped (hardware error {})",
+                                    thread_id, nonce, job.job_id, errors
+                                );
+                                stats.add_activity(format!(
+                                    "❌ GPU {} hardware error: share failed the CPU re-check",
+                                    thread_id
+                                ));
+                                if hw_error_limit.is_some_and(|limit| errors >= limit as u64) {
+                                    failed = true;
+                                    device_stats.mark_failed();
+                                    error!(target: LOG_TARGET,
+                                        "🛑 GPU {} stopped after {} hardware errors (--gpu-hw-error-limit)",
+                                        thread_id, errors
+                                    );
+                                    stats.add_activity(format!(
+                                        "🛑 GPU {} stopped after {} hardware errors",
+                                        thread_id, errors
+                                    ));
+                                }
+                            }
+                            verified.map(|(hash, difficulty)| (nonce, hash, difficulty))
+                        });
+
+                        // Submit share if found, once per nonce of the job
+                        let found_share = found_share.filter(|&(nonce, _, _)| {
+                            let fresh = nonce_allocator.record_share(job, nonce);
+                            if !fresh {
+                                warn!(target: LOG_TARGET,
+                                    "⚠️ GPU {}: duplicate share for job {} dropped (nonce {:016x})",
+                                    thread_id, job.job_id, nonce
+                                );
+                                stats
+                                    .shares_duplicate
+                                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                            }
+                            fresh
+                        });
+                        if let Some((nonce, hash, share_difficulty)) = found_share {
                             let nonce_hex = hex::encode(nonce.to_le_bytes());
-
-                            // Calculate the actual hash result for SHA3x using same function as CPU
-                            let hash_result = engine
-                                .calculate_share_result(job, nonce.to_le_bytes())
-                                .unwrap_or_else(|_| hex::encode([0u8; 32])); // Fallback to zeros
+                            let hash_result = hex::encode(&hash);
 
                             info!(target: LOG_TARGET,
                                 "🎉 GPU {} FOUND SHARE! Nonce: {} Difficulty: {} ({}% intensity)",
                                 thread_id,
                                 nonce_hex,
-                                crate::miner::stats::MinerStats::format_number(best_difficulty),
+                                crate::miner::stats::MinerStats::format_number(share_difficulty),
                                 gpu_settings.intensity
                             );
 
                             // Record share in MinerStats for dashboard metrics
                             stats.record_share_found(
                                 thread_id,
-                                best_difficulty,
+                                share_difficulty,
                                 job.target_difficulty,
                                 true,
                             );
@@ -466,28 +944,14 @@ impl GpuManager {
                             if let Err(e) = share_tx.send((
                                 job.job_id.clone(),
                                 nonce_hex,
-                                hash_result, // Actual SHA3x hash result
+                                hash_result, // SHA3x hash re-computed on the CPU
                                 thread_id,
-                                best_difficulty,
+                                share_difficulty,
                                 String::new(), // No extranonce2 for SHA3x (handled in nonce now)
                                 0,             // No ntime
                             )) {
                                 error!(target: LOG_TARGET,"🎮 GPU {} failed to send share: {}", thread_id, e);
                             }
-
-                            // Record in thread stats (redundant but ensures tracking)
-                            if thread_id < stats.thread_stats.len() {
-                                stats.thread_stats[thread_id].record_share(best_difficulty, true);
-                            }
-                        }
-
-                        // Preserve "pool nonce" in lower 16 bits while incrementing upper bits only if extranonce2 is present
-                        // This ensures we respect the LuckyPool XN format
-                        if job.extranonce2.is_some() {
-                            // Preserve "pool nonce" in lower 16 bits while incrementing upper bits
-                            let pool_prefix = nonce_offset & 0xFFFF; // Extract pool (lower 16 bits)
-                            let upper_bits = (nonce_offset >> 16) + hashes_processed; // Increment upper bits
-                            nonce_offset = pool_prefix | (upper_bits << 16); // Combine: preserve pool prefix + incremented upper bits
                         }
                     }
                     Err(e) => {
@@ -527,21 +991,49 @@ impl GpuManager {
         stats: Arc<MinerStats>,
     ) {
         // Use default settings for legacy compatibility
+        let (_control, receiver) = watch::channel(GpuThreadControl {
+            settings: GpuSettings::default(),
+            enabled: true,
+        });
+        let device_stats = Arc::new(GpuDeviceStats::new(
+            device.device_id(),
+            thread_id,
+            device.name().to_string(),
+            GpuSettings::default(),
+        ));
+        stats.add_gpu_device(Arc::clone(&device_stats));
         Self::gpu_mining_loop_with_settings(
             thread_id,
             device,
             job_rx,
             share_tx,
             stats,
-            GpuSettings::default(),
+            receiver,
+            device_stats,
+            Arc::new(NonceAllocator::new()),
+            None,
         )
         .await;
     }
 
-    pub async fn generate_information_files(directory_path: PathBuf) -> Result<(), anyhow::Error> {
+    /// Write the GPU information file for the detected devices. Each device keeps
+    /// the settings stored for it by an earlier run; new devices get the matching
+    /// --gpu-device / [gpu.devices.KEY] override, so it is stored with the device.
+    pub async fn generate_information_files(
+        directory_path: PathBuf,
+        overrides: &GpuDeviceOverrides,
+    ) -> Result<(), anyhow::Error> {
         // Create GPU information file manager
         let information_file_manager =
             GpuInformationFileManager::new(directory_path, KernelType::OpenCL).await?;
+        let previous = match information_file_manager.load().await {
+            Ok(information_file) => information_file.devices,
+            Err(GpuInformationFileError::FileNotFound { .. }) => Vec::new(),
+            Err(e) => {
+                warn!(target: LOG_TARGET, "⚠️ Replacing unreadable GPU information file: {}", e);
+                Vec::new()
+            }
+        };
 
         match OpenClDevice::detect_devices() {
             Ok(device) => {
@@ -553,6 +1045,14 @@ impl GpuManager {
                             .vendor()
                             .unwrap_or_else(|_| "Unknown".to_string());
                         let vendor = GpuVendor::from_str(vendor);
+                        let settings = previous
+                            .iter()
+                            .find(|p| p.device_id == device.device_id() && p.name == device.name())
+                            .and_then(|p| p.settings.clone())
+                            .or_else(|| {
+                                matching_override(overrides, device.device_id(), device.name())
+                                    .cloned()
+                            });
 
                         GpuInformationFileDevice {
                             name: device.name().to_string(),
@@ -563,6 +1063,7 @@ impl GpuManager {
                             max_compute_units: device.max_compute_units(),
                             global_mem_size: device.global_mem_size(),
                             device_type: device.device_type().clone(),
+                            settings,
                         }
                     })
                     .collect();
@@ -589,7 +1090,88 @@ impl Default for GpuManager {
     }
 }
 
+/// Re-hash a GPU-found nonce on the CPU; the hash and its difficulty when it
+/// meets the job's target, None when the GPU reported a bad nonce
+pub fn verify_gpu_share(job: &MiningJob, nonce: u64) -> Option<(Vec<u8>, u64)> {
+    let hash = sha3x_hash_with_nonce(&job.mining_hash, nonce.to_le_bytes());
+    let difficulty = calculate_difficulty(&hash, Algorithm::Sha3x);
+    (difficulty >= job.target_difficulty).then_some((hash, difficulty))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_gpu_share() {
+        let mut job = MiningJob {
+            job_id: "job-1".to_string(),
+            mining_hash: vec![7; 32],
+            target_difficulty: 64,
+            height: 1,
+            algo: Algorithm::Sha3x,
+            extranonce2: None,
+            prev_hash: None,
+            merkle_root: None,
+            version: None,
+            ntime: None,
+            nbits: None,
+            merkle_path: None,
+            target: None,
+            coinbase: None,
+        };
+        let nonce = (0..10_000u64)
+            .find(|&nonce| verify_gpu_share(&job, nonce).is_some())
+            .expect("no share within 10000 nonces at difficulty 64");
+        let (hash, difficulty) = verify_gpu_share(&job, nonce).unwrap();
+        assert_eq!(
+            hash,
+            sha3x_hash_with_nonce(&job.mining_hash, nonce.to_le_bytes())
+        );
+        assert!(difficulty >= job.target_difficulty);
+
+        // The same nonce misses a target above its difficulty
+        job.target_difficulty = difficulty + 1;
+        assert!(verify_gpu_share(&job, nonce).is_none());
+    }
+}
+
 // Changelog:
+// - v3.8.1-share-verification (2025-06-28): The mining loop takes the device's GpuDeviceStats
+//   (returned by thread_control()) and counts hardware errors there, instead of looking the
+//   device up by thread id; the legacy gpu_mining_loop() registers its own.
+// - v3.8.0-share-verification (2025-06-28): GPU shares re-hashed on the CPU before submission.
+//   - verify_gpu_share() recomputes the hash and difficulty with sha3x_hash_with_nonce();
+//     the submitted hash and difficulty are the CPU's.
+//   - Nonces missing the target are dropped and counted as hardware errors per device;
+//     set_hw_error_limit() (--gpu-hw-error-limit) stops a GPU after that many.
+// - v3.7.0-nonce-allocator (2025-06-28): GPU threads claim the nonces of every kernel launch
+//   from a NonceAllocator (start_gpu_mining() and gpu_mining_loop_with_settings() take it).
+//   - Replaces the thread_id * 1e9 start, the jump to the found nonce and the XN
+//     overwrite, which let CPU and GPU workers search overlapping nonces.
+//   - Duplicate shares are dropped and counted in MinerStats.
+// - v3.6.0-thermal-governor (2025-06-28): Thermal and power governor support.
+//   - Thread handles keep the configured settings and a governor Throttle; the thread
+//     gets the configured intensity scaled by the throttle and idles while stopped.
+//   - Added governed_devices() and set_throttle() for the governor.
+// - v3.5.0-stored-autotune (2025-06-28): Stored autotune results.
+//   - autotune() tunes each GPU model once with the sequential autotuner and saves the
+//     winners, keyed by device name, driver version and kernel hash, next to the
+//     GPU information file (gpu_autotune_opencl.json).
+//   - Stored results are applied on every start and re-tuned automatically when the
+//     driver or kernel changed. Layering: global, autotune, information file, overrides.
+// - v3.4.0-per-device-settings (2025-06-28): Per-device GPU settings by index or name.
+//   - Overrides select devices by OpenCL index or name pattern (GpuDeviceSelector).
+//   - load_information_file() applies per-device settings stored in the GPU information
+//     file; overrides are layered on top. --detect keeps stored settings per device.
+//   - thread_control() registers every device with MinerStats for per-device stats.
+// - v3.3.0-runtime-reload (2025-06-28): Runtime settings hot-reload.
+//   - GPU threads follow a GpuThreadControl watch channel: new settings go to the
+//     running OpenClEngine via set_gpu_settings(), disabled devices stop hashing.
+//   - Added thread_control() and apply_runtime_settings().
+// - v3.2.3-per-device-settings (2025-06-28): Per-device GPU settings overrides.
+//   - Added set_device_settings(); devices without an override use the global settings.
+//   - set_gpu_settings() keeps per-device overrides when updating threads.
 // - v3.2.2-luckypool-xn-nonce-fix (2025-06-26): LuckyPool XN nonce generation implementation.
 //   *** LUCKYPOOL XN NONCE GENERATION ***:
 //   - Implemented proper XN-based nonce generation in gpu_mining_loop_with_settings()
@@ -6,7 +6,7 @@
 // via pull requests to the project repository.
 //
 // File: src/miner/gpu/mod.rs
-// Version: 1.2.0-hybrid
+// Version: 1.3.0
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // This file is the module entry point for GPU mining functionality in the SHA3x miner.
@@ -16,6 +16,7 @@
 // - OpenCL GPU mining for NVIDIA, AMD, and Intel GPUs
 // - Parallel GPU and CPU mining coordination
 // - Integrated stats and monitoring
+// - Thermal / power governor enforcing per-device temperature and power limits
 // - Optional compilation via the "gpu" or "hybrid" feature flags
 
 // GPU mining is available when either "gpu" or "hybrid" feature is enabled
@@ -31,6 +32,9 @@ pub mod gpu_miner;
 #[cfg(any(feature = "gpu", feature = "hybrid"))]
 pub mod gpu_information_file;
 
+#[cfg(any(feature = "gpu", feature = "hybrid"))]
+pub mod governor;
+
 // Re-export key types when GPU features are enabled
 #[cfg(any(feature = "gpu", feature = "hybrid"))]
 pub use manager::GpuManager;
@@ -38,9 +42,13 @@ pub use manager::GpuManager;
 #[cfg(any(feature = "gpu", feature = "hybrid"))]
 pub use gpu_miner::GpuMiner;
 
+#[cfg(any(feature = "gpu", feature = "hybrid"))]
+pub use governor::{GovernorConfig, GpuGovernor, GpuTelemetry, TelemetrySource, Throttle};
+
 #[cfg(any(feature = "gpu", feature = "hybrid"))]
 pub use gpu_information_file::{
-    GpuInformationFile, GpuInformationFileError, GpuInformationFileManager, KernelType,
+    GpuAutotuneFile, GpuAutotuneProfile, GpuInformationFile, GpuInformationFileError,
+    GpuInformationFileManager, KernelType,
 };
 
 // Placeholder for when GPU features are disabled
@@ -59,6 +67,7 @@ impl GpuManager {
 }
 
 // Changelog:
+// - v1.3.0 (2025-06-28): Added the governor module (GPU thermal / power governor).
 // - v1.2.0-hybrid (2025-06-25): Added hybrid feature support
 //   - Changed feature gates from feature = "gpu" to any(feature = "gpu", feature = "hybrid")
 //   - Enables GPU modules when either gpu or hybrid features are active
@@ -1,7 +1,7 @@
 // SHA3x Miner - Free and Open Source Software Statement
 //
 // File: src/miner/gpu/opencl/device.rs
-// Version: 1.0.1
+// Version: 1.0.2
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // OpenCL device management for GPU mining - detects and manages OpenCL devices
@@ -94,6 +94,13 @@ impl OpenClDevice {
         self.global_mem_size
     }
 
+    /// Get the OpenCL driver version (used to invalidate stored autotune results)
+    pub fn driver_version(&self) -> String {
+        self.device
+            .driver_version()
+            .unwrap_or_else(|_| "unknown".to_string())
+    }
+
     /// Get the underlying OpenCL device
     pub fn device(&self) -> &Device {
         &self.device
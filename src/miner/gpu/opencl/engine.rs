@@ -1,7 +1,7 @@
 // SHA3x Miner - Free and Open Source Software Statement
 //
 // File: src/miner/gpu/opencl/engine.rs
-// Version: 2.2.0-sequential-autotune
+// Version: 2.5.0-nonces-per-launch
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // OpenCL mining engine with sequential parameter autotuning for maximum hashrate
@@ -18,11 +18,21 @@ use opencl3::{
     program::Program,
     types::{CL_FALSE, CL_TRUE, cl_ulong},
 };
+use sha3::{Digest, Sha3_256};
 use std::{ptr, time::Instant};
 use tokio::time::Duration;
 
 const LOG_TARGET: &str = "tari::graxil::engine";
 
+/// SHA3x OpenCL kernel source compiled by every engine
+pub const KERNEL_SOURCE: &str = include_str!("../../../../kernels/opencl/sha3x.cl");
+
+/// Short hash of the kernel source; stored autotune results are only reused
+/// for the kernel they were measured with
+pub fn kernel_hash() -> String {
+    hex::encode(&Sha3_256::digest(KERNEL_SOURCE.as_bytes())[..8])
+}
+
 /// Sequential autotuning configuration
 #[derive(Debug, Clone)]
 pub struct AutotuneConfig {
@@ -45,6 +55,15 @@ impl Default for AutotuneConfig {
     }
 }
 
+/// Outcome of run_sequential_autotune()
+#[derive(Debug, Clone)]
+pub struct AutotuneResult {
+    /// Winning settings, including the work groups per compute unit
+    pub settings: GpuSettings,
+    pub baseline_mhs: f64,
+    pub tuned_mhs: f64,
+}
+
 /// OpenCL mining engine for GPU SHA3x mining with sequential autotuning
 pub struct OpenClEngine {
     device: OpenClDevice,
@@ -92,8 +111,8 @@ impl OpenClEngine {
             kernel: None,
             queue: None,
             initialized: false,
+            work_groups_per_cu: settings.work_groups_per_cu.map_or(8, |w| w as usize),
             gpu_settings: settings,
-            work_groups_per_cu: 8, // Default value
             autotune_config: None,
         }
     }
@@ -117,6 +136,9 @@ impl OpenClEngine {
             settings.power_limit,
             settings.temp_limit
         );
+        if let Some(work_groups) = settings.work_groups_per_cu {
+            self.work_groups_per_cu = work_groups as usize;
+        }
         self.gpu_settings = settings;
     }
 
@@ -142,9 +164,7 @@ impl OpenClEngine {
         );
 
         // Load and compile the SHA3x kernel
-        let kernel_source = include_str!("../../../../kernels/opencl/sha3x.cl");
-
-        let mut program = Program::create_from_source(&self.context, kernel_source)
+        let mut program = Program::create_from_source(&self.context, KERNEL_SOURCE)
             .map_err(|e| Error::msg(format!("Failed to create program: {}", e)))?;
 
         // Build the program
@@ -192,7 +212,10 @@ impl OpenClEngine {
     }
 
     /// Run sequential autotuning to find optimal settings
-    pub async fn run_sequential_autotune(&mut self, test_job: &MiningJob) -> Result<GpuSettings> {
+    pub async fn run_sequential_autotune(
+        &mut self,
+        test_job: &MiningJob,
+    ) -> Result<AutotuneResult> {
         if !self.initialized {
             return Err(Error::msg("Engine not initialized for autotuning"));
         }
@@ -260,6 +283,7 @@ impl OpenClEngine {
                 .optimize_work_groups(&config, test_job, &best_settings)
                 .await?;
             best_work_groups = optimal_wg;
+            best_settings.work_groups_per_cu = Some(optimal_wg as u32);
             self.work_groups_per_cu = optimal_wg;
             info!(target: LOG_TARGET,"✅ Phase 3 complete: optimal work_groups = {}", optimal_wg);
 
@@ -297,7 +321,11 @@ impl OpenClEngine {
         );
         info!(target: LOG_TARGET,"└─ Ready for mining!");
 
-        Ok(best_settings)
+        Ok(AutotuneResult {
+            settings: best_settings,
+            baseline_mhs: baseline_hashrate,
+            tuned_mhs: final_hashrate,
+        })
     }
 
     /// Phase 1: Optimize intensity while keeping batch and work_groups fixed
@@ -381,7 +409,9 @@ impl OpenClEngine {
         let mut best_hashrate = 0.0;
 
         for &work_groups in config.work_groups_per_cu.iter() {
-            self.gpu_settings = fixed_settings.clone(); // Keep intensity and batch fixed
+            let mut test_settings = fixed_settings.clone(); // Keep intensity and batch fixed
+            test_settings.work_groups_per_cu = Some(work_groups as u32); // Size kernels by work groups
+            self.gpu_settings = test_settings;
             self.work_groups_per_cu = work_groups;
 
             let hashrate = self
@@ -460,7 +490,7 @@ impl OpenClEngine {
         // .min(max_work_group_size);
         let local_size = 64.max(max_work_group_size.min(256));
 
-        // Use the tunable work_groups_per_cu value
+        // Use the tunable work_groups_per_cu value when configured for this device
         // let base_work_groups = compute_units * self.work_groups_per_cu;
         let base_work_groups = compute_units;
 
@@ -468,7 +498,10 @@ impl OpenClEngine {
         // let intensity_factor = self.gpu_settings.intensity as f32 / 100.0;
         // let adjusted_work_groups = ((base_work_groups as f32) * intensity_factor) as usize;
         // let global_size = (adjusted_work_groups.max(1) * local_size).min(max_work_group_size);
-        let global_size = base_work_groups * max_work_group_size;
+        let global_size = match self.gpu_settings.work_groups_per_cu {
+            Some(_) => base_work_groups * self.work_groups_per_cu * local_size,
+            None => base_work_groups * max_work_group_size,
+        };
         // let global_size = local_size;
         // let global_size = (adjusted_work_groups.max(1) * local_size);
 
@@ -486,6 +519,12 @@ impl OpenClEngine {
         (global_size, local_size)
     }
 
+    /// Nonces one mine() call hashes with `batch_size` rounds per work item
+    pub fn nonces_per_launch(&self, batch_size: u32) -> u64 {
+        let (global_size, _) = self.calculate_work_sizes();
+        global_size as u64 * u64::from(batch_size)
+    }
+
     /// Apply intensity delay if needed (for power/thermal management)
     async fn apply_intensity_delay(&self) {
         if self.gpu_settings.intensity < 100 {
@@ -787,6 +826,17 @@ impl Default for OpenClEngine {
 }
 
 // Changelog:
+// - v2.5.0-nonces-per-launch (2025-06-28): Added nonces_per_launch(), so callers can
+//   claim the exact nonce range of the next mine() call.
+// - v2.4.0-autotune-results (2025-06-28): Autotune results that can be stored.
+//   - run_sequential_autotune() returns AutotuneResult (settings, baseline and tuned MH/s).
+//   - Work group candidates and the winner are set in GpuSettings, so phase 3 actually
+//     changes the launch size instead of measuring the same kernel size five times.
+//   - KERNEL_SOURCE and kernel_hash() identify the kernel autotune results belong to.
+// - v2.3.0-work-groups-setting (2025-06-28): work_groups_per_cu from GpuSettings.
+//   - A configured value (--gpu-work-groups-per-cu or per device) sizes the global work
+//     size as compute units x work groups x local size; unset keeps one full work group per CU.
+//   - set_gpu_settings() applies a new work group count to the running engine.
 // - v2.2.0-sequential-autotune (2025-06-25): SEQUENTIAL PARAMETER AUTOTUNING
 //   *** NEW AUTOTUNING SYSTEM ***:
 //   1. Sequential parameter optimization: fix one, tune others, fix next
@@ -11,4 +11,4 @@ pub mod engine;
 
 // Re-export key types
 pub use device::OpenClDevice;
-pub use engine::OpenClEngine;
+pub use engine::{AutotuneConfig, AutotuneResult, OpenClEngine};
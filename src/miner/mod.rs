@@ -6,7 +6,7 @@
 // via pull requests to the project repository.
 //
 // File: src/miner/mod.rs
-// Version: 1.1.0
+// Version: 1.4.0
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // This file is the module declaration for the miner functionality of the SHA3x
@@ -15,18 +15,26 @@
 //
 // Tree Location:
 // - src/miner/mod.rs (miner module entry point)
-// - Submodules: cpu, gpu, stats
+// - Submodules: control, cpu, gpu, nonce, schedule, stats
 
+pub mod control;
 pub mod cpu;
 pub mod gpu; // Added GPU mining module
+pub mod nonce;
+pub mod schedule;
 pub mod stats;
 
 // Re-export key types for convenience
+pub use control::{AutoPause, MinerControl, RuntimeSettings};
 pub use cpu::CpuMiner;
 pub use gpu::GpuManager; // Added GPU manager export
+pub use nonce::{NonceAllocator, NonceRange};
 pub use stats::{MinerStats, ThreadStats};
 
 // Changelog:
+// - v1.4.0 (2025-06-28): Added nonce module (nonce space allocation shared by CPU and GPU).
+// - v1.3.0 (2025-06-28): Added schedule module (mining windows and idle detection).
+// - v1.2.0 (2025-06-28): Added control module (runtime settings hot-reload).
 // - v1.1.0 (2025-06-24): Added GPU mining module.
 //   - Added gpu submodule declaration for GPU mining functionality.
 //   - Added GpuManager re-export for easy access to GPU mining capabilities.
@@ -0,0 +1,272 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/miner/nonce.rs
+// Version: 1.0.1
+//
+// This file implements the SHA3x nonce allocator shared by every CPU thread and
+// GPU of a miner (both miners in hybrid mode). An 8-byte SHA3x nonce is laid
+// out as [2-byte prefix][6-byte local nonce] in little-endian order: the prefix
+// is the pool's XN (LuckyPool) or, without one, a random value picked once per
+// allocator so separate rigs do not search the same nonces. The 48-bit local
+// space of each job is handed out front to back in disjoint ranges, so no two
+// workers ever hash the same nonce of a job. The allocator also remembers the
+// nonces submitted per job and flags duplicate shares before they reach the pool.
+//
+// Tree Location:
+// - src/miner/nonce.rs (nonce space allocation and duplicate share detection)
+// - Depends on: rand, core/types
+
+use crate::core::types::MiningJob;
+use log::{debug, warn};
+use std::collections::{HashSet, VecDeque};
+use std::sync::Mutex;
+
+const LOG_TARGET: &str = "tari::graxil::nonce";
+
+/// Bits of the nonce below the local nonce, holding the XN prefix
+pub const PREFIX_BITS: u32 = 16;
+
+/// Local nonces per job (6 bytes)
+pub const LOCAL_NONCE_SPACE: u64 = 1 << (64 - PREFIX_BITS);
+
+/// Jobs whose nonce spaces are kept; the CPU and GPU sessions of hybrid mode
+/// may mine different jobs at the same time
+const TRACKED_JOBS: usize = 8;
+
+/// A range of local nonces of one job, owned by a single worker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonceRange {
+    prefix: u16,
+    start: u64,
+    end: u64,
+}
+
+impl NonceRange {
+    /// Nonces in the range
+    pub fn len(&self) -> u64 {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Full 8-byte nonce of the `index`-th local nonce of the range
+    pub fn nonce(&self, index: u64) -> u64 {
+        ((self.start + index) << PREFIX_BITS) | self.prefix as u64
+    }
+
+    /// Nonce the range starts at, in the layout the GPU kernel counts up from
+    pub fn first_nonce(&self) -> u64 {
+        self.nonce(0)
+    }
+}
+
+/// Nonce space and submitted shares of one job
+#[derive(Debug)]
+struct JobNonces {
+    header: Vec<u8>,
+    prefix: u16,
+    /// First local nonce not handed out yet
+    next: u64,
+    /// Warned that the space is used up
+    exhausted: bool,
+    submitted: HashSet<u64>,
+}
+
+/// Hands out disjoint nonce ranges of each job to CPU threads and GPUs
+#[derive(Debug)]
+pub struct NonceAllocator {
+    /// Prefix of jobs without an XN
+    salt: u16,
+    /// Most recent job first
+    jobs: Mutex<VecDeque<JobNonces>>,
+}
+
+impl NonceAllocator {
+    pub fn new() -> Self {
+        Self::with_salt(rand::random())
+    }
+
+    /// Allocator using `salt` as the prefix of jobs without an XN
+    pub fn with_salt(salt: u16) -> Self {
+        Self {
+            salt,
+            jobs: Mutex::new(VecDeque::with_capacity(TRACKED_JOBS)),
+        }
+    }
+
+    /// Nonce prefix of a job: the pool's 2-byte XN, or the allocator's salt
+    pub fn prefix(&self, job: &MiningJob) -> u16 {
+        job.extranonce2
+            .as_deref()
+            .and_then(|xn| hex::decode(xn).ok())
+            .and_then(|xn| <[u8; 2]>::try_from(xn).ok())
+            .map_or(self.salt, u16::from_le_bytes)
+    }
+
+    /// Run `f` on the nonce state of `job`, starting a fresh one for a new job
+    fn with_job<T>(&self, job: &MiningJob, f: impl FnOnce(&mut JobNonces) -> T) -> T {
+        let prefix = self.prefix(job);
+        let mut jobs = self.jobs.lock().unwrap();
+        let index = match jobs
+            .iter()
+            .position(|known| known.prefix == prefix && known.header == job.mining_hash)
+        {
+            Some(index) => index,
+            None => {
+                debug!(target: LOG_TARGET,
+                    "New nonce space for job {} (prefix {:04x})",
+                    job.job_id, prefix
+                );
+                jobs.push_front(JobNonces {
+                    header: job.mining_hash.clone(),
+                    prefix,
+                    next: 0,
+                    exhausted: false,
+                    submitted: HashSet::new(),
+                });
+                jobs.truncate(TRACKED_JOBS);
+                0
+            }
+        };
+        f(&mut jobs[index])
+    }
+
+    /// Claim the next `count` nonces of `job`; None once its local nonce space is used up
+    pub fn claim(&self, job: &MiningJob, count: u64) -> Option<NonceRange> {
+        self.with_job(job, |nonces| {
+            let Some(end) = nonces
+                .next
+                .checked_add(count)
+                .filter(|end| *end <= LOCAL_NONCE_SPACE)
+            else {
+                if !nonces.exhausted {
+                    warn!(target: LOG_TARGET,"⚠️ Nonce space of job {} exhausted, waiting for a new job", job.job_id);
+                    nonces.exhausted = true;
+                }
+                return None;
+            };
+            let range = NonceRange {
+                prefix: nonces.prefix,
+                start: nonces.next,
+                end,
+            };
+            nonces.next = end;
+            Some(range)
+        })
+    }
+
+    /// Record a share about to be submitted; false if this nonce of the job was submitted before.
+    /// Callers count duplicates in MinerStats::shares_duplicate.
+    pub fn record_share(&self, job: &MiningJob, nonce: u64) -> bool {
+        self.with_job(job, |nonces| nonces.submitted.insert(nonce))
+    }
+}
+
+impl Default for NonceAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Algorithm;
+
+    fn job(header: u8, xn: Option<&str>) -> MiningJob {
+        MiningJob {
+            job_id: format!("job-{}", header),
+            mining_hash: vec![header; 32],
+            target_difficulty: 1000,
+            height: 1,
+            algo: Algorithm::Sha3x,
+            extranonce2: xn.map(str::to_string),
+            prev_hash: None,
+            merkle_root: None,
+            version: None,
+            ntime: None,
+            nbits: None,
+            merkle_path: None,
+            target: None,
+            coinbase: None,
+        }
+    }
+
+    #[test]
+    fn test_ranges_are_disjoint() {
+        let allocator = NonceAllocator::with_salt(0x1234);
+        let job = job(1, None);
+
+        let cpu = allocator.claim(&job, 1 << 20).unwrap();
+        let gpu = allocator.claim(&job, 3_000_000).unwrap();
+        let cpu_next = allocator.claim(&job, 1 << 20).unwrap();
+        assert_eq!(cpu.len(), 1 << 20);
+        assert_eq!(gpu.first_nonce() >> PREFIX_BITS, cpu.len());
+        assert_eq!(cpu_next.first_nonce() >> PREFIX_BITS, cpu.len() + gpu.len());
+        assert_eq!(
+            cpu.nonce(cpu.len() - 1) + (1 << PREFIX_BITS),
+            gpu.first_nonce()
+        );
+
+        // Without an XN the salt fills the prefix bytes
+        assert_eq!(cpu.nonce(5).to_le_bytes()[..2], 0x1234u16.to_le_bytes());
+    }
+
+    #[test]
+    fn test_xn_prefix_and_new_jobs() {
+        let allocator = NonceAllocator::with_salt(0);
+        let first = job(1, Some("ad49"));
+
+        let range = allocator.claim(&first, 100).unwrap();
+        assert_eq!(range.nonce(7).to_le_bytes()[..2], [0xad, 0x49]);
+        assert_eq!(range.nonce(7) >> PREFIX_BITS, 7);
+
+        // A new job starts from the front of its own space, the old one continues
+        assert_eq!(
+            allocator.claim(&job(2, Some("ad49")), 100).unwrap().start,
+            0
+        );
+        assert_eq!(allocator.claim(&first, 100).unwrap().start, 100);
+
+        // A new XN for the same header is a new space as well
+        let other_xn = allocator.claim(&job(1, Some("0100")), 100).unwrap();
+        assert_eq!((other_xn.start, other_xn.prefix), (0, 1));
+    }
+
+    #[test]
+    fn test_exhausted_space() {
+        let allocator = NonceAllocator::with_salt(0);
+        let job = job(1, None);
+        assert!(allocator.claim(&job, LOCAL_NONCE_SPACE - 10).is_some());
+        assert!(allocator.claim(&job, 11).is_none());
+        assert_eq!(allocator.claim(&job, 10).unwrap().end, LOCAL_NONCE_SPACE);
+        assert!(allocator.claim(&job, u64::MAX).is_none());
+    }
+
+    #[test]
+    fn test_duplicate_shares() {
+        let allocator = NonceAllocator::with_salt(0);
+        let first = job(1, None);
+        let second = job(2, None);
+
+        assert!(allocator.record_share(&first, 42));
+        assert!(allocator.record_share(&second, 42));
+        assert!(!allocator.record_share(&first, 42));
+        assert!(allocator.record_share(&first, 43));
+    }
+}
+
+// Changelog:
+// - v1.0.1 (2025-06-28): Dropped the allocator's own duplicate counter; duplicates are
+//   counted once, in MinerStats::shares_duplicate.
+// - v1.0.0 (2025-06-28): Initial nonce allocator.
+//   - Disjoint ranges of the 6-byte local nonce space per job, behind the pool's XN
+//     (or a per-allocator random prefix), shared by CPU threads and GPUs.
+//   - Duplicate share detection per job.
@@ -0,0 +1,369 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/miner/schedule.rs
+// Version: 1.1.0
+//
+// This file implements scheduled and idle-aware mining. Mining windows
+// ("Mon-Fri 18:00-08:00", local time) say when the miner may run, pause
+// windows when it may not, and idle detection pauses mining while processes
+// other than the miner keep the CPU busy. The scheduler pauses and resumes
+// through the MinerControl channel, so CPU threads park, GPUs idle and the pool
+// session stays connected.
+//
+// Tree Location:
+// - src/miner/schedule.rs (mining windows and idle detection)
+// - Depends on: chrono, sysinfo, tokio, core/types, miner/control
+
+use crate::core::types::Args;
+use crate::miner::control::{AutoPause, MinerControl};
+use chrono::{Datelike, Local, Timelike};
+use log::{info, warn};
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessesToUpdate, System};
+
+const LOG_TARGET: &str = "tari::graxil::schedule";
+
+/// How often the schedule and the CPU load are checked
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+const MINUTES_PER_DAY: u16 = 24 * 60;
+const DAY_NAMES: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+/// A weekly time window such as "Mon-Fri 08:00-18:00". A window whose end is
+/// not after its start runs past midnight into the next day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeWindow {
+    /// Days the window starts on, Monday first
+    days: [bool; 7],
+    /// Minutes after midnight
+    start: u16,
+    end: u16,
+}
+
+impl TimeWindow {
+    /// Parse `[DAYS ]HH:MM-HH:MM`; DAYS is `*` or a comma list of days and day
+    /// ranges (`Mon-Fri`, `Sat,Sun`), every day when left out
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        let (days, times) = match spec.split_once(char::is_whitespace) {
+            Some((days, times)) => (parse_days(days)?, times.trim()),
+            None => ([true; 7], spec),
+        };
+        let (start, end) = times
+            .split_once('-')
+            .ok_or_else(|| format!("Invalid time window '{}': expected HH:MM-HH:MM", spec))?;
+        let (start, end) = (parse_time(start)?, parse_time(end)?);
+        if start == end || start == MINUTES_PER_DAY {
+            return Err(format!(
+                "Invalid time window '{}': the window is empty",
+                spec
+            ));
+        }
+        Ok(Self { days, start, end })
+    }
+
+    /// Whether the window contains a minute of a day (0 = Monday)
+    pub fn contains(&self, weekday: usize, minute: u16) -> bool {
+        if self.start < self.end {
+            self.days[weekday] && (self.start..self.end).contains(&minute)
+        } else {
+            (self.days[weekday] && minute >= self.start)
+                || (self.days[(weekday + 6) % 7] && minute < self.end)
+        }
+    }
+}
+
+fn parse_day(name: &str) -> Result<usize, String> {
+    let name = name.trim().to_lowercase();
+    DAY_NAMES
+        .iter()
+        .position(|day| *day == name)
+        .ok_or_else(|| {
+            format!(
+                "Invalid day '{}': use Mon, Tue, Wed, Thu, Fri, Sat or Sun",
+                name
+            )
+        })
+}
+
+fn parse_days(spec: &str) -> Result<[bool; 7], String> {
+    if spec == "*" {
+        return Ok([true; 7]);
+    }
+    let mut days = [false; 7];
+    for part in spec.split(',') {
+        match part.split_once('-') {
+            Some((first, last)) => {
+                let (mut day, last) = (parse_day(first)?, parse_day(last)?);
+                days[day] = true;
+                while day != last {
+                    day = (day + 1) % 7;
+                    days[day] = true;
+                }
+            }
+            None => days[parse_day(part)?] = true,
+        }
+    }
+    Ok(days)
+}
+
+/// Minutes after midnight of `HH:MM` (24:00 = end of the day)
+fn parse_time(time: &str) -> Result<u16, String> {
+    let invalid = || format!("Invalid time '{}': expected HH:MM", time.trim());
+    let (hours, minutes) = time.trim().split_once(':').ok_or_else(invalid)?;
+    let hours: u16 = hours.parse().map_err(|_| invalid())?;
+    let minutes: u16 = minutes.parse().map_err(|_| invalid())?;
+    match (hours, minutes) {
+        (0..=23, 0..=59) => Ok(hours * 60 + minutes),
+        (24, 0) => Ok(MINUTES_PER_DAY),
+        _ => Err(invalid()),
+    }
+}
+
+/// When mining is allowed: inside a mining window (any time without one) and
+/// outside every pause window
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MiningSchedule {
+    pub mine: Vec<TimeWindow>,
+    pub pause: Vec<TimeWindow>,
+}
+
+impl MiningSchedule {
+    pub fn parse(mine: &[String], pause: &[String]) -> Result<Self, String> {
+        Ok(Self {
+            mine: mine
+                .iter()
+                .map(|spec| TimeWindow::parse(spec))
+                .collect::<Result<_, _>>()?,
+            pause: pause
+                .iter()
+                .map(|spec| TimeWindow::parse(spec))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub fn from_args(args: &Args) -> Result<Self, String> {
+        Self::parse(&args.mine_window, &args.pause_window)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mine.is_empty() && self.pause.is_empty()
+    }
+
+    /// Whether mining is allowed at a minute of a day (0 = Monday)
+    pub fn allows(&self, weekday: usize, minute: u16) -> bool {
+        (self.mine.is_empty() || self.mine.iter().any(|w| w.contains(weekday, minute)))
+            && !self.pause.iter().any(|w| w.contains(weekday, minute))
+    }
+
+    /// Whether mining is allowed now, in local time
+    pub fn allows_now(&self) -> bool {
+        let now = Local::now();
+        self.allows(
+            now.weekday().num_days_from_monday() as usize,
+            (now.hour() * 60 + now.minute()) as u16,
+        )
+    }
+}
+
+/// Pause mining while other processes use more CPU than `cpu_threshold`
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdleDetection {
+    /// System CPU usage of other processes, in percent
+    pub cpu_threshold: f32,
+    /// How long the load must stay below the threshold before mining resumes
+    pub resume_delay: Duration,
+}
+
+impl IdleDetection {
+    pub fn from_args(args: &Args) -> Option<Self> {
+        args.idle_cpu_threshold.map(|threshold| Self {
+            cpu_threshold: threshold as f32,
+            resume_delay: Duration::from_secs(args.idle_resume_delay),
+        })
+    }
+}
+
+/// System CPU usage of every process except the miner
+pub(crate) struct LoadMonitor {
+    system: System,
+    pid: Option<Pid>,
+}
+
+impl LoadMonitor {
+    pub(crate) fn new() -> Self {
+        let mut system = System::new();
+        system.refresh_cpu_usage();
+        Self {
+            system,
+            pid: sysinfo::get_current_pid().ok(),
+        }
+    }
+
+    /// Percent of the whole machine's CPU used by other processes since the last call
+    pub(crate) fn other_cpu_load(&mut self) -> f32 {
+        self.system.refresh_cpu_usage();
+        let total = self.system.global_cpu_usage();
+        let own = self.pid.map_or(0.0, |pid| {
+            self.system
+                .refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+            self.system.process(pid).map_or(0.0, |p| p.cpu_usage())
+        });
+        let cpus = self.system.cpus().len().max(1) as f32;
+        (total - own / cpus).max(0.0)
+    }
+}
+
+/// Pause and resume mining following the schedule and the CPU load of other
+/// processes. Does nothing when neither is configured.
+pub fn spawn_scheduler(
+    control: MinerControl,
+    schedule: MiningSchedule,
+    idle: Option<IdleDetection>,
+) {
+    if schedule.is_empty() && idle.is_none() {
+        return;
+    }
+
+    // Settle the schedule before the miners start hashing
+    let mut wanted = (!schedule.allows_now()).then_some(AutoPause::Schedule);
+    apply(&control, wanted);
+    if let Some(ref idle) = idle {
+        info!(target: LOG_TARGET,
+            "💤 Idle detection: pausing while other processes use over {:.0}% CPU",
+            idle.cpu_threshold
+        );
+    }
+
+    tokio::spawn(async move {
+        let mut monitor = idle.as_ref().map(|_| LoadMonitor::new());
+        let mut busy = false;
+        let mut quiet_since: Option<Instant> = None;
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            if let (Some(idle), Some(monitor)) = (&idle, &mut monitor) {
+                let load = monitor.other_cpu_load();
+                if load > idle.cpu_threshold {
+                    if !busy {
+                        info!(target: LOG_TARGET,
+                            "💼 Other processes use {:.0}% CPU (threshold {:.0}%)",
+                            load, idle.cpu_threshold
+                        );
+                    }
+                    busy = true;
+                    quiet_since = None;
+                } else if busy
+                    && quiet_since.get_or_insert_with(Instant::now).elapsed() >= idle.resume_delay
+                {
+                    busy = false;
+                    quiet_since = None;
+                }
+            }
+
+            let next = if !schedule.allows_now() {
+                Some(AutoPause::Schedule)
+            } else if busy {
+                Some(AutoPause::Busy)
+            } else {
+                None
+            };
+            if next != wanted {
+                wanted = next;
+                apply(&control, wanted);
+            }
+        }
+    });
+}
+
+fn apply(control: &MinerControl, pause: Option<AutoPause>) {
+    if !control.update(|settings| settings.auto_pause = pause) {
+        return;
+    }
+    match pause {
+        Some(AutoPause::Schedule) => {
+            info!(target: LOG_TARGET,"⏸️ Mining paused: outside the mining schedule")
+        }
+        Some(AutoPause::Busy) => {
+            info!(target: LOG_TARGET,"⏸️ Mining paused: the machine is in use")
+        }
+        None if control.current().paused => {
+            warn!(target: LOG_TARGET,"⏸️ Schedule allows mining, but it was paused by hand")
+        }
+        None => info!(target: LOG_TARGET,"▶️ Mining resumed"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time_window() {
+        let office = TimeWindow::parse("Mon-Fri 08:00-18:00").unwrap();
+        assert!(office.contains(0, 8 * 60));
+        assert!(office.contains(4, 17 * 60 + 59));
+        assert!(!office.contains(4, 18 * 60));
+        assert!(!office.contains(5, 12 * 60));
+
+        let weekend = TimeWindow::parse("sat,sun 00:00-24:00").unwrap();
+        assert!(weekend.contains(6, 23 * 60 + 59));
+        assert!(!weekend.contains(0, 0));
+
+        assert!(TimeWindow::parse("* 22:00-06:00").unwrap().contains(3, 0));
+        assert!(
+            TimeWindow::parse("Fri-Mon 10:00-11:00")
+                .unwrap()
+                .contains(6, 10 * 60)
+        );
+
+        assert!(TimeWindow::parse("08:00-08:00").is_err());
+        assert!(TimeWindow::parse("Mon-Fri 8-18").is_err());
+        assert!(TimeWindow::parse("Mo 08:00-18:00").is_err());
+        assert!(TimeWindow::parse("Mon 08:00-25:00").is_err());
+    }
+
+    #[test]
+    fn test_window_past_midnight() {
+        // Friday night runs into Saturday morning, Sunday night does not start
+        let nights = TimeWindow::parse("Mon-Fri 18:00-08:00").unwrap();
+        assert!(nights.contains(4, 23 * 60));
+        assert!(nights.contains(5, 7 * 60));
+        assert!(!nights.contains(5, 9 * 60));
+        assert!(!nights.contains(0, 7 * 60));
+        assert!(nights.contains(1, 7 * 60));
+    }
+
+    #[test]
+    fn test_schedule_allows() {
+        let anytime = MiningSchedule::default();
+        assert!(anytime.allows(2, 12 * 60));
+
+        let schedule = MiningSchedule::parse(
+            &[
+                "Mon-Fri 18:00-08:00".to_string(),
+                "Sat-Sun 00:00-24:00".to_string(),
+            ],
+            &["Sun 10:00-12:00".to_string()],
+        )
+        .unwrap();
+        assert!(!schedule.allows(2, 12 * 60));
+        assert!(schedule.allows(2, 20 * 60));
+        assert!(schedule.allows(5, 12 * 60));
+        assert!(!schedule.allows(6, 11 * 60));
+    }
+}
+
+// Changelog:
+// - v1.1.0 (2025-06-28): LoadMonitor shared with the CPU governor.
+// - v1.0.0 (2025-06-28): Initial mining schedule.
+//   - Weekly mining and pause windows in local time, including windows past midnight.
+//   - Idle detection from the sysinfo CPU load of other processes, with a resume delay.
+//   - Pauses and resumes through MinerControl without dropping the pool session.
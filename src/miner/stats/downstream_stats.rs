@@ -0,0 +1,122 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/miner/stats/downstream_stats.rs
+// Version: 1.0.0
+//
+// This file implements per-downstream statistics for Stratum proxy mode. Each
+// miner connected to the proxy gets its own share counters, accepted work and
+// an estimated hashrate derived from that work.
+//
+// Tree Location:
+// - src/miner/stats/downstream_stats.rs (per-downstream proxy statistics)
+// - Depends on: std, serde
+
+use crate::core::types::ShareStatus;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+/// Statistics for one miner connected through the Stratum proxy
+pub struct DownstreamStats {
+    pub id: u64,
+    pub worker: String,
+    pub address: String,
+    /// Nonce prefix (LuckyPool-style xn) assigned to this miner
+    pub xn: String,
+    pub shares_accepted: AtomicU64,
+    pub shares_rejected: AtomicU64,
+    pub shares_stale: AtomicU64,
+    /// Sum of the target difficulty of accepted shares (expected hashes)
+    pub accepted_work: AtomicU64,
+    connected_at: Instant,
+    last_share_time: Mutex<Option<Instant>>,
+}
+
+/// Serializable snapshot of a downstream miner for the dashboard
+#[derive(Serialize, Debug, Clone)]
+pub struct DownstreamInfo {
+    pub id: u64,
+    pub worker: String,
+    pub address: String,
+    pub xn: String,
+    pub connected_seconds: u64,
+    pub accepted_shares: u64,
+    pub rejected_shares: u64,
+    pub stale_shares: u64,
+    pub hashrate: u64,
+    pub time_since_last_share: Option<u64>,
+}
+
+impl DownstreamStats {
+    pub fn new(id: u64, worker: String, address: String, xn: String) -> Self {
+        Self {
+            id,
+            worker,
+            address,
+            xn,
+            shares_accepted: AtomicU64::new(0),
+            shares_rejected: AtomicU64::new(0),
+            shares_stale: AtomicU64::new(0),
+            accepted_work: AtomicU64::new(0),
+            connected_at: Instant::now(),
+            last_share_time: Mutex::new(None),
+        }
+    }
+
+    /// Record the verdict for a share; `target_difficulty` is the job difficulty
+    pub fn record_share(&self, target_difficulty: u64, status: ShareStatus) {
+        match status {
+            ShareStatus::Accepted => {
+                self.shares_accepted.fetch_add(1, Ordering::Relaxed);
+                self.accepted_work
+                    .fetch_add(target_difficulty, Ordering::Relaxed);
+            }
+            ShareStatus::Rejected => {
+                self.shares_rejected.fetch_add(1, Ordering::Relaxed);
+            }
+            ShareStatus::Stale => {
+                self.shares_stale.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        *self.last_share_time.lock().unwrap() = Some(Instant::now());
+    }
+
+    /// Hashrate estimated from accepted work since the miner connected (H/s)
+    pub fn estimated_hashrate(&self) -> f64 {
+        let elapsed = self.connected_at.elapsed().as_secs_f64();
+        if elapsed < 1.0 {
+            return 0.0;
+        }
+        self.accepted_work.load(Ordering::Relaxed) as f64 / elapsed
+    }
+
+    pub fn to_info(&self) -> DownstreamInfo {
+        DownstreamInfo {
+            id: self.id,
+            worker: self.worker.clone(),
+            address: self.address.clone(),
+            xn: self.xn.clone(),
+            connected_seconds: self.connected_at.elapsed().as_secs(),
+            accepted_shares: self.shares_accepted.load(Ordering::Relaxed),
+            rejected_shares: self.shares_rejected.load(Ordering::Relaxed),
+            stale_shares: self.shares_stale.load(Ordering::Relaxed),
+            hashrate: self.estimated_hashrate() as u64,
+            time_since_last_share: self
+                .last_share_time
+                .lock()
+                .unwrap()
+                .map(|time| time.elapsed().as_secs()),
+        }
+    }
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial per-downstream statistics for Stratum proxy mode.
+//   - Accepted/rejected/stale counters, accepted work and estimated hashrate.
+//   - DownstreamInfo snapshot for the WebSocket dashboard.
@@ -0,0 +1,123 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/miner/stats/gpu_device_stats.rs
+// Version: 1.1.0
+//
+// This file implements per-device GPU statistics. Every GPU mining thread
+// registers the device it drives together with the settings it runs with
+// (intensity, batch size, work groups per CU, limits), so dashboards can show
+// each card of a mixed rig separately instead of one global GPU entry. GPU
+// shares that fail the CPU re-check are counted here as hardware errors.
+//
+// Tree Location:
+// - src/miner/stats/gpu_device_stats.rs (per-device GPU statistics)
+// - Depends on: std, serde, core/types, thread_stats
+
+use super::thread_stats::ThreadStats;
+use crate::core::types::GpuSettings;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// One GPU mining thread and the device it drives
+pub struct GpuDeviceStats {
+    /// OpenCL device id
+    pub device_id: u32,
+    /// Index into MinerStats::thread_stats
+    pub thread_id: usize,
+    pub name: String,
+    settings: Mutex<GpuSettings>,
+    enabled: AtomicBool,
+    /// Found nonces that did not meet the target when re-hashed on the CPU
+    hardware_errors: AtomicU64,
+    /// Stopped after reaching the hardware error limit
+    failed: AtomicBool,
+}
+
+/// Serializable snapshot of a GPU for the dashboard
+#[derive(Serialize, Debug, Clone)]
+pub struct GpuDeviceInfo {
+    pub device_id: u32,
+    pub thread_id: usize,
+    pub name: String,
+    pub enabled: bool,
+    pub hashrate: u64,
+    pub intensity: u8,
+    pub batch_size: Option<u32>,
+    pub work_groups_per_cu: Option<u32>,
+    pub power_limit: Option<u8>,
+    pub temp_limit: Option<u8>,
+    pub hardware_errors: u64,
+    pub failed: bool,
+}
+
+impl GpuDeviceStats {
+    pub fn new(device_id: u32, thread_id: usize, name: String, settings: GpuSettings) -> Self {
+        Self {
+            device_id,
+            thread_id,
+            name,
+            settings: Mutex::new(settings),
+            enabled: AtomicBool::new(true),
+            hardware_errors: AtomicU64::new(0),
+            failed: AtomicBool::new(false),
+        }
+    }
+
+    /// Record settings applied to the running device (e.g. after a reload)
+    pub fn update(&self, settings: GpuSettings, enabled: bool) {
+        *self.settings.lock().unwrap() = settings;
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn settings(&self) -> GpuSettings {
+        self.settings.lock().unwrap().clone()
+    }
+
+    /// Count a share that failed the CPU re-check; returns the device's total
+    pub fn record_hardware_error(&self) -> u64 {
+        self.hardware_errors.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    pub fn hardware_errors(&self) -> u64 {
+        self.hardware_errors.load(Ordering::Relaxed)
+    }
+
+    /// Flag the device as stopped for too many hardware errors
+    pub fn mark_failed(&self) {
+        self.failed.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.failed.load(Ordering::Relaxed)
+    }
+
+    pub fn to_info(&self, thread: Option<&ThreadStats>) -> GpuDeviceInfo {
+        let settings = self.settings();
+        GpuDeviceInfo {
+            device_id: self.device_id,
+            thread_id: self.thread_id,
+            name: self.name.clone(),
+            enabled: self.enabled.load(Ordering::Relaxed),
+            hashrate: thread.map_or(0, |t| t.get_hashrate() as u64),
+            intensity: settings.intensity,
+            batch_size: settings.batch_size,
+            work_groups_per_cu: settings.work_groups_per_cu,
+            power_limit: settings.power_limit,
+            temp_limit: settings.temp_limit,
+            hardware_errors: self.hardware_errors(),
+            failed: self.is_failed(),
+        }
+    }
+}
+
+// Changelog:
+// - v1.1.0 (2025-06-28): Hardware error count and failed flag per device.
+// - v1.0.0 (2025-06-28): Initial per-device GPU statistics.
+//   - Device name, thread, applied settings and enabled state per GPU.
+//   - GpuDeviceInfo snapshot with the device hashrate for the WebSocket dashboard.
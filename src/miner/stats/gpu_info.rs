@@ -6,7 +6,7 @@
 // via pull requests to the project repository.
 //
 // File: src/miner/stats/gpu_info.rs
-// Version: 1.0.0
+// Version: 1.1.0
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // This module provides GPU detection and monitoring capabilities for the SHA3x miner.
@@ -294,7 +294,10 @@ impl GpuInfo {
     }
 
     /// Parse optional float value with field name for better error messages
-    fn parse_optional_float(value: &str, field_name: &str) -> Result<Option<f32>, String> {
+    pub(crate) fn parse_optional_float(
+        value: &str,
+        field_name: &str,
+    ) -> Result<Option<f32>, String> {
         if value == "N/A"
             || value == "[Not Supported]"
             || value.is_empty()
@@ -609,6 +612,7 @@ mod tests {
 }
 
 // Changelog:
+// - v1.1.0 (2025-06-28): parse_optional_float() shared with the GPU governor's telemetry.
 // - v1.0.0 (2025-06-24): Initial implementation
 //   - Comprehensive NVIDIA GPU detection via nvidia-smi
 //   - Robust error handling and parsing
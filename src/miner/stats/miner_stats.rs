@@ -6,7 +6,7 @@
 // via pull requests to the project repository.
 //
 // File: src/miner/stats/miner_stats.rs
-// Version: 1.3.0
+// Version: 1.13.0
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // This file implements miner-wide statistics tracking for the SHA3x miner,
@@ -17,13 +17,15 @@
 // - src/miner/stats/miner_stats.rs (miner-wide statistics logic)
 // - Depends on: std, thread_stats, gpu_info, serde, sysinfo
 
+use super::downstream_stats::{DownstreamInfo, DownstreamStats};
+use super::gpu_device_stats::{GpuDeviceInfo, GpuDeviceStats};
 use super::gpu_info::GpuInfo;
 use super::thread_stats::ThreadStats;
-use crate::core::types::Algorithm;
-use crate::pool::client::PoolClient;
+use crate::core::types::{Algorithm, ShareStatus};
+use crate::pool::client::{ConnectionInfo, PoolClient};
 use log::{debug, info};
 use serde::Serialize;
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
@@ -56,6 +58,8 @@ pub struct PoolInfo {
     pub latency_ms: Option<u64>,
     pub connection_attempts: u32,
     pub uptime_seconds: Option<u64>,
+    /// Pool link runs over TLS
+    pub encrypted: bool,
 }
 
 #[derive(Serialize)]
@@ -80,12 +84,17 @@ pub struct WebSocketData {
     pub accepted_shares: u64,
     pub submitted_shares: u64,
     pub rejected_shares: u64,
+    pub stale_shares: u64,
+    pub duplicate_shares: u64,
+    pub avg_submit_latency_ms: Option<u64>,
     pub work_efficiency: f64,
     pub average_luck: f64,
     pub uptime: u64,
     pub thread_hashrates: Vec<u64>,
     pub algorithm: String,
     pub active_threads: usize,
+    /// CPU threads parked by a thread count change, a pause or the CPU governor
+    pub parked_threads: usize,
     pub share_rate: f64,
     pub total_work: u64,
     pub current_difficulty: u64,
@@ -100,6 +109,13 @@ pub struct WebSocketData {
     pub system_info: SystemInfo,
     pub pool_info: PoolInfo,
     pub gpu_info: GpuInfo, // Added GPU info
+    /// Every GPU being mined on, with its own settings and hashrate
+    pub gpu_devices: Vec<GpuDeviceInfo>,
+    /// Miners connected through the Stratum proxy (empty when not proxying)
+    pub downstreams: Vec<DownstreamInfo>,
+    /// Network difficulty of the solo block template (0 when pool mining)
+    pub network_difficulty: u64,
+    pub blocks_found: u64,
 }
 
 #[derive(Serialize)]
@@ -115,8 +131,16 @@ pub struct MinerStats {
     pub shares_submitted: AtomicU64,
     pub shares_accepted: AtomicU64,
     pub shares_rejected: AtomicU64,
+    pub shares_stale: AtomicU64,
+    /// Shares found twice for the same job and nonce, dropped before submission
+    pub shares_duplicate: AtomicU64,
     pub hashes_computed: AtomicU64,
     pub total_work_submitted: AtomicU64,
+    /// Solo mining: network difficulty of the current template and blocks found
+    pub network_difficulty: AtomicU64,
+    pub blocks_found: AtomicU64,
+    submit_latency_total_ms: AtomicU64,
+    submit_latency_samples: AtomicU64,
     start_time: Instant,
     pub thread_stats: Vec<Arc<ThreadStats>>,
     recent_shares: Arc<Mutex<VecDeque<ShareInfo>>>,
@@ -129,6 +153,9 @@ pub struct MinerStats {
     pool_client: Option<Arc<PoolClient>>,
     gpu_info: Arc<Mutex<GpuInfo>>,         // Added GPU monitoring
     gpu_last_refresh: Arc<Mutex<Instant>>, // Track GPU refresh timing
+    downstreams: Mutex<Vec<Arc<DownstreamStats>>>, // Stratum proxy downstream miners
+    gpu_devices: Mutex<Vec<Arc<GpuDeviceStats>>>, // Per-device GPU settings and state
+    share_rejections: Mutex<BTreeMap<&'static str, u64>>, // Refused shares by reason
 }
 
 impl MinerStats {
@@ -142,8 +169,14 @@ impl MinerStats {
             shares_submitted: AtomicU64::new(0),
             shares_accepted: AtomicU64::new(0),
             shares_rejected: AtomicU64::new(0),
+            shares_stale: AtomicU64::new(0),
+            shares_duplicate: AtomicU64::new(0),
             hashes_computed: AtomicU64::new(0),
             total_work_submitted: AtomicU64::new(0),
+            network_difficulty: AtomicU64::new(0),
+            blocks_found: AtomicU64::new(0),
+            submit_latency_total_ms: AtomicU64::new(0),
+            submit_latency_samples: AtomicU64::new(0),
             start_time: Instant::now(),
             thread_stats,
             recent_shares: Arc::new(Mutex::new(VecDeque::with_capacity(100))),
@@ -161,6 +194,9 @@ impl MinerStats {
             pool_client: None,
             gpu_info: Arc::new(Mutex::new(GpuInfo::detect())), // Initialize GPU detection
             gpu_last_refresh: Arc::new(Mutex::new(Instant::now())),
+            downstreams: Mutex::new(Vec::new()),
+            gpu_devices: Mutex::new(Vec::new()),
+            share_rejections: Mutex::new(BTreeMap::new()),
         }
     }
 
@@ -225,6 +261,8 @@ impl MinerStats {
         }
     }
 
+    /// Record a share found by a mining thread. The pool verdict is recorded
+    /// separately by `record_share_result` once the response arrives.
     pub fn record_share_found(
         &self,
         thread_id: usize,
@@ -232,10 +270,6 @@ impl MinerStats {
         target: u64,
         accepted: bool,
     ) {
-        if thread_id < self.thread_stats.len() {
-            self.thread_stats[thread_id].record_share(difficulty, accepted);
-        }
-
         self.total_work_submitted
             .fetch_add(difficulty, Ordering::Relaxed);
 
@@ -252,6 +286,170 @@ impl MinerStats {
         }
     }
 
+    /// Record the pool verdict for a submitted share
+    ///
+    /// `difficulty` is the actual difficulty of the submitted hash and
+    /// `latency` the submit round-trip time measured by the pool session.
+    pub fn record_share_result(
+        &self,
+        thread_id: usize,
+        difficulty: u64,
+        status: ShareStatus,
+        latency: Duration,
+    ) {
+        let thread_stats = self.thread_stats.get(thread_id);
+        match status {
+            ShareStatus::Accepted => {
+                self.shares_accepted.fetch_add(1, Ordering::Relaxed);
+                if let Some(thread_stats) = thread_stats {
+                    thread_stats.record_share(difficulty, true);
+                }
+            }
+            ShareStatus::Rejected => {
+                self.shares_rejected.fetch_add(1, Ordering::Relaxed);
+                if let Some(thread_stats) = thread_stats {
+                    thread_stats.record_share(difficulty, false);
+                }
+            }
+            ShareStatus::Stale => {
+                self.shares_stale.fetch_add(1, Ordering::Relaxed);
+                if let Some(thread_stats) = thread_stats {
+                    thread_stats.record_stale_share(difficulty);
+                }
+            }
+        }
+
+        self.submit_latency_total_ms
+            .fetch_add(latency.as_millis() as u64, Ordering::Relaxed);
+        self.submit_latency_samples.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count a rejected or stale share under its reason (e.g. "low_difficulty")
+    pub fn record_share_rejection(&self, reason: &'static str) {
+        *self
+            .share_rejections
+            .lock()
+            .unwrap()
+            .entry(reason)
+            .or_insert(0) += 1;
+    }
+
+    /// Rejected and stale shares counted by reason, sorted by reason
+    pub fn share_rejections(&self) -> Vec<(&'static str, u64)> {
+        self.share_rejections
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(reason, count)| (*reason, *count))
+            .collect()
+    }
+
+    /// Solo mining: network difficulty of the latest block template
+    pub fn update_network_difficulty(&self, difficulty: u64) {
+        self.network_difficulty.store(difficulty, Ordering::Relaxed);
+    }
+
+    /// Solo mining: a block was accepted by the node
+    pub fn record_block_found(&self) {
+        self.blocks_found.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Track a miner connected through the Stratum proxy
+    pub fn add_downstream(&self, downstream: Arc<DownstreamStats>) {
+        self.downstreams.lock().unwrap().push(downstream);
+    }
+
+    /// Stop tracking a proxy downstream that disconnected
+    pub fn remove_downstream(&self, id: u64) {
+        self.downstreams.lock().unwrap().retain(|d| d.id != id);
+    }
+
+    /// Snapshot of every connected proxy downstream
+    pub fn downstream_infos(&self) -> Vec<DownstreamInfo> {
+        self.downstreams
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|d| d.to_info())
+            .collect()
+    }
+
+    /// Track a GPU mining thread; replaces an earlier entry for the same device
+    pub fn add_gpu_device(&self, device: Arc<GpuDeviceStats>) {
+        let mut devices = self.gpu_devices.lock().unwrap();
+        devices.retain(|d| d.device_id != device.device_id);
+        devices.push(device);
+        devices.sort_by_key(|d| d.thread_id);
+    }
+
+    /// Stats of the GPU driven by a mining thread
+    pub fn gpu_device(&self, thread_id: usize) -> Option<Arc<GpuDeviceStats>> {
+        self.gpu_devices
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|d| d.thread_id == thread_id)
+            .cloned()
+    }
+
+    /// Snapshot of every GPU with its settings and hashrate
+    pub fn gpu_device_infos(&self) -> Vec<GpuDeviceInfo> {
+        self.gpu_devices
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|d| d.to_info(self.thread_stats.get(d.thread_id).map(Arc::as_ref)))
+            .collect()
+    }
+
+    /// Most recent activity messages, newest first, with their age
+    pub fn recent_activity(&self, limit: usize) -> Vec<(Duration, String)> {
+        self.recent_activity
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .take(limit)
+            .map(|(time, message)| (time.elapsed(), message.clone()))
+            .collect()
+    }
+
+    /// Connection state of the pool client, if one is attached
+    pub fn connection_info(&self) -> Option<ConnectionInfo> {
+        self.pool_client
+            .as_ref()
+            .map(|pool_client| pool_client.get_connection_info())
+    }
+
+    /// Latest GPU monitoring data (refreshed at most every 5 seconds)
+    pub fn gpu_info(&self) -> GpuInfo {
+        self.refresh_gpu_info_if_needed();
+        self.gpu_info.lock().unwrap().clone()
+    }
+
+    /// Highest share difficulty target currently assigned to a thread
+    pub fn current_difficulty(&self) -> u64 {
+        self.thread_stats
+            .iter()
+            .map(|t| t.current_difficulty_target.load(Ordering::Relaxed))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Time since the miner started
+    pub fn uptime(&self) -> Duration {
+        self.start_time.elapsed()
+    }
+
+    /// Average share submit round-trip time in milliseconds
+    pub fn get_avg_submit_latency_ms(&self) -> Option<u64> {
+        let samples = self.submit_latency_samples.load(Ordering::Relaxed);
+        if samples == 0 {
+            return None;
+        }
+        Some(self.submit_latency_total_ms.load(Ordering::Relaxed) / samples)
+    }
+
     pub fn update_hashrate_history(&self, total_hashes: u64) {
         let mut history = self.hashrate_history.lock().unwrap();
         history.push_back((Instant::now(), total_hashes));
@@ -283,10 +481,14 @@ impl MinerStats {
     pub fn get_active_thread_count(&self) -> usize {
         self.thread_stats
             .iter()
-            .filter(|t| t.get_hashrate() > 0.0)
+            .filter(|t| !t.is_parked() && t.get_hashrate() > 0.0)
             .count()
     }
 
+    pub fn get_parked_thread_count(&self) -> usize {
+        self.thread_stats.iter().filter(|t| t.is_parked()).count()
+    }
+
     pub fn get_avg_hashrate_per_thread(&self) -> f64 {
         let active = self.get_active_thread_count();
         if active > 0 {
@@ -317,12 +519,7 @@ impl MinerStats {
         let total_hashes = self.hashes_computed.load(Ordering::Relaxed);
         let total_work = self.total_work_submitted.load(Ordering::Relaxed);
 
-        let current_difficulty = self
-            .thread_stats
-            .iter()
-            .map(|t| t.current_difficulty_target.load(Ordering::Relaxed))
-            .max()
-            .unwrap_or(0);
+        let current_difficulty = self.current_difficulty();
         let expected_shares = if current_difficulty > 0 {
             (total_hashes as f64 / current_difficulty as f64).max(1.0)
         } else {
@@ -450,6 +647,7 @@ impl MinerStats {
                 latency_ms: conn_info.latency_ms(),
                 connection_attempts: conn_info.connection_attempts,
                 uptime_seconds: conn_info.uptime().map(|d| d.as_secs()),
+                encrypted: conn_info.encrypted,
             }
         } else {
             PoolInfo {
@@ -458,6 +656,7 @@ impl MinerStats {
                 latency_ms: None,
                 connection_attempts: 0,
                 uptime_seconds: None,
+                encrypted: false,
             }
         };
 
@@ -477,12 +676,16 @@ impl MinerStats {
             accepted_shares: shares_accepted,
             submitted_shares: shares_submitted,
             rejected_shares: shares_rejected,
+            stale_shares: self.shares_stale.load(Ordering::Relaxed),
+            duplicate_shares: self.shares_duplicate.load(Ordering::Relaxed),
+            avg_submit_latency_ms: self.get_avg_submit_latency_ms(),
             work_efficiency,
             average_luck: avg_luck,
             uptime: session_duration.as_secs(),
             thread_hashrates,
             algorithm: format!("{:?}", self.algo),
             active_threads: self.get_active_thread_count(),
+            parked_threads: self.get_parked_thread_count(),
             share_rate: self.get_share_rate_per_minute(),
             total_work,
             current_difficulty,
@@ -497,10 +700,14 @@ impl MinerStats {
             system_info,
             pool_info,
             gpu_info, // Include GPU information
+            gpu_devices: self.gpu_device_infos(),
+            downstreams: self.downstream_infos(),
+            network_difficulty: self.network_difficulty.load(Ordering::Relaxed),
+            blocks_found: self.blocks_found.load(Ordering::Relaxed),
         }
     }
 
-    fn format_hashrate(hashrate: f64) -> String {
+    pub fn format_hashrate(hashrate: f64) -> String {
         if hashrate >= 1_000_000_000.0 {
             format!("{:.2} GH/s", hashrate / 1_000_000_000.0)
         } else if hashrate >= 1_000_000.0 {
@@ -549,12 +756,7 @@ impl MinerStats {
             0.0
         };
         let session_time = self.start_time.elapsed();
-        let current_difficulty = self
-            .thread_stats
-            .iter()
-            .map(|t| t.current_difficulty_target.load(Ordering::Relaxed))
-            .max()
-            .unwrap_or(0);
+        let current_difficulty = self.current_difficulty();
 
         let shares = self.recent_shares.lock().unwrap();
         let mut top_shares: Vec<u64> = shares.iter().map(|s| s.difficulty).collect();
@@ -626,7 +828,18 @@ impl MinerStats {
             "├─ Shares: {}/{} ({:.1}% accepted)",
             shares_accepted, shares_submitted, acceptance_rate
         );
-        info!(target: LOG_TARGET,"├─ Rejected Shares: {}", shares_rejected);
+        info!(target: LOG_TARGET,
+            "├─ Rejected Shares: {} (stale: {})",
+            shares_rejected,
+            self.shares_stale.load(Ordering::Relaxed)
+        );
+        let duplicates = self.shares_duplicate.load(Ordering::Relaxed);
+        if duplicates > 0 {
+            info!(target: LOG_TARGET,"├─ Duplicate Shares Dropped: {}", duplicates);
+        }
+        if let Some(latency) = self.get_avg_submit_latency_ms() {
+            info!(target: LOG_TARGET,"├─ Avg Submit Latency: {}ms", latency);
+        }
         info!(target: LOG_TARGET,"├─ Work Efficiency: {:.1}%", work_efficiency);
         info!(target: LOG_TARGET,"├─ Average Luck: {:.2}x", avg_luck);
         info!(target: LOG_TARGET,"├─ Share Rate: {:.2} shares/min", share_rate);
@@ -644,16 +857,107 @@ impl MinerStats {
             active_threads,
             self.thread_stats.len()
         );
+        let parked_threads = self.get_parked_thread_count();
+        if parked_threads > 0 {
+            info!(target: LOG_TARGET,"├─ Parked Threads: {}", parked_threads);
+        }
         info!(target: LOG_TARGET,
             "├─ Current Difficulty: {}",
             Self::format_number(current_difficulty)
         );
-        info!(target: LOG_TARGET,"└─ GPU Status: {}", gpu_status);
+        let network_difficulty = self.network_difficulty.load(Ordering::Relaxed);
+        if network_difficulty > 0 {
+            let expected = if hashrate > 0.0 {
+                Self::format_duration(Duration::from_secs_f64(
+                    network_difficulty as f64 / hashrate,
+                ))
+            } else {
+                "unknown".to_string()
+            };
+            info!(target: LOG_TARGET,
+                "├─ Solo: network difficulty {}, expected time to block {}, blocks found {}",
+                Self::format_number(network_difficulty),
+                expected,
+                self.blocks_found.load(Ordering::Relaxed)
+            );
+        }
+        let gpu_devices = self.gpu_device_infos();
+        let downstreams = self.downstream_infos();
+        if gpu_devices.is_empty() && downstreams.is_empty() {
+            info!(target: LOG_TARGET,"└─ GPU Status: {}", gpu_status);
+            return;
+        }
+        info!(target: LOG_TARGET,"├─ GPU Status: {}", gpu_status);
+        if !gpu_devices.is_empty() {
+            info!(target: LOG_TARGET,
+                "{} GPU Devices: {}",
+                if downstreams.is_empty() { "└─" } else { "├─" },
+                gpu_devices.len()
+            );
+            for (index, device) in gpu_devices.iter().enumerate() {
+                let branch = if index + 1 == gpu_devices.len() {
+                    "└─"
+                } else {
+                    "├─"
+                };
+                info!(target: LOG_TARGET,
+                    "   {} GPU {} {}: {} | {}% intensity, batch {}, {} WG/CU, temp limit {}{}{}",
+                    branch,
+                    device.device_id,
+                    device.name,
+                    Self::format_hashrate(device.hashrate as f64),
+                    device.intensity,
+                    device
+                        .batch_size
+                        .map_or("auto".to_string(), |b| b.to_string()),
+                    device
+                        .work_groups_per_cu
+                        .map_or("auto".to_string(), |w| w.to_string()),
+                    device
+                        .temp_limit
+                        .map_or("none".to_string(), |t| format!("{}°C", t)),
+                    if device.hardware_errors > 0 {
+                        format!(", {} HW errors", device.hardware_errors)
+                    } else {
+                        String::new()
+                    },
+                    if device.failed {
+                        " (stopped: hardware errors)"
+                    } else if device.enabled {
+                        ""
+                    } else {
+                        " (disabled)"
+                    }
+                );
+            }
+        }
+        if downstreams.is_empty() {
+            return;
+        }
+        info!(target: LOG_TARGET,"└─ Proxy Miners: {}", downstreams.len());
+        for (index, downstream) in downstreams.iter().enumerate() {
+            let branch = if index + 1 == downstreams.len() {
+                "└─"
+            } else {
+                "├─"
+            };
+            info!(target: LOG_TARGET,
+                "   {} {} ({}, xn {}): {} | shares {}/{}/{} (acc/rej/stale)",
+                branch,
+                downstream.worker,
+                downstream.address,
+                downstream.xn,
+                Self::format_hashrate(downstream.hashrate as f64),
+                downstream.accepted_shares,
+                downstream.rejected_shares,
+                downstream.stale_shares
+            );
+        }
     }
 }
 
 /// Extract temperature information from system components
-fn get_temperatures(components: &Components) -> (Option<f32>, Option<f32>) {
+pub(crate) fn get_temperatures(components: &Components) -> (Option<f32>, Option<f32>) {
     let mut cpu_temp: Option<f32> = None;
     let mut max_temp: Option<f32> = None;
     let mut highest_temp = 0.0f32;
@@ -684,6 +988,33 @@ fn get_temperatures(components: &Components) -> (Option<f32>, Option<f32>) {
 }
 
 // Changelog:
+// - v1.13.0 (2025-06-28): gpu_device() lookup by thread; the dashboard shows hardware errors
+//   and GPUs stopped for them.
+// - v1.12.0 (2025-06-28): shares_duplicate counter (WebSocketData.duplicate_shares) for
+//   shares the NonceAllocator caught before submission.
+// - v1.11.0 (2025-06-28): Parked CPU threads.
+//   - get_parked_thread_count() and WebSocketData.parked_threads; parked threads no
+//     longer count as active. get_temperatures() shared with the CPU governor.
+// - v1.10.0 (2025-06-28): Accessors for the Prometheus exporter.
+//   - record_share_rejection()/share_rejections() count refused shares by reason.
+//   - connection_info(), gpu_info(), current_difficulty() and uptime() accessors.
+// - v1.9.0 (2025-06-28): Per-device GPU statistics.
+//   - add_gpu_device()/gpu_device_infos() track every GPU with its own settings.
+//   - WebSocketData.gpu_devices and the text dashboard list each GPU separately.
+// - v1.8.0 (2025-06-28): Added recent_activity accessor and made format_hashrate public
+//   for the terminal dashboard.
+// - v1.7.0 (2025-06-28): Solo mining statistics.
+//   - network_difficulty / blocks_found with update_network_difficulty() and record_block_found().
+//   - Exposed in WebSocketData and the text dashboard (expected time to block).
+// - v1.6.0 (2025-06-28): Per-downstream statistics for Stratum proxy mode.
+//   - add_downstream()/remove_downstream()/downstream_infos() track proxy miners.
+//   - WebSocketData.downstreams and the text dashboard list every proxy miner.
+// - v1.5.0 (2025-06-28): PoolInfo reports whether the pool link is encrypted (TLS).
+// - v1.4.0 (2025-06-28): Per-share pool verdict tracking
+//   - Added record_share_result() fed by the pool session's pending-share table.
+//   - record_share_found() no longer marks thread shares as accepted before the pool answers.
+//   - Added shares_stale counter and average submit round-trip latency.
+//   - WebSocketData includes stale_shares and avg_submit_latency_ms.
 // - v1.3.0 (2025-06-24): Added GPU monitoring integration
 //   - Added gpu_info module dependency for NVIDIA GPU detection
 //   - Added gpu_info and gpu_last_refresh fields to MinerStats struct
@@ -6,7 +6,7 @@
 // via pull requests to the project repository.
 //
 // File: src/miner/stats/mod.rs
-// Version: 1.0.0
+// Version: 1.3.0
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // This file is the module declaration for the statistics tracking functionality
@@ -15,18 +15,27 @@
 //
 // Tree Location:
 // - src/miner/stats/mod.rs (stats module entry point)
-// - Submodules: miner_stats, thread_stats
+// - Submodules: downstream_stats, gpu_device_stats, gpu_info, miner_stats, prometheus,
+//   thread_stats
 
+pub mod downstream_stats;
+pub mod gpu_device_stats;
 pub mod gpu_info;
 pub mod miner_stats;
+pub mod prometheus;
 pub mod thread_stats;
 
 // Re-export key types for convenience
+pub use downstream_stats::{DownstreamInfo, DownstreamStats};
+pub use gpu_device_stats::{GpuDeviceInfo, GpuDeviceStats};
 pub use gpu_info::GpuInfo;
 pub use miner_stats::MinerStats;
 pub use thread_stats::ThreadStats;
 
 // Changelog:
+// - v1.3.0 (2025-06-28): Added prometheus submodule rendering the /metrics endpoint.
+// - v1.2.0 (2025-06-28): Added gpu_device_stats submodule for per-device GPU statistics.
+// - v1.1.0 (2025-06-28): Added downstream_stats submodule for Stratum proxy mode.
 // - v1.0.0 (2025-06-14): Extracted from monolithic main.rs.
 //   - Purpose: Defines the stats module, organizing statistics tracking for the
 //     overall miner and individual threads into miner_stats and thread_stats
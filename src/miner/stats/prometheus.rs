@@ -0,0 +1,446 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/miner/stats/prometheus.rs
+// Version: 1.4.0
+//
+// This file renders MinerStats, ThreadStats, per-device GPU statistics and the
+// pool connection in the Prometheus text exposition format, for the /metrics
+// route of the web server. Totals are counters, current values are gauges, and
+// every sample carries the worker label; thread, device, pool and rejection
+// reason labels identify the series within a rig.
+//
+// Tree Location:
+// - src/miner/stats/prometheus.rs (Prometheus metrics rendering)
+// - Depends on: std, miner_stats, thread_stats, gpu_device_stats, gpu_info
+
+use super::miner_stats::MinerStats;
+use std::fmt::{Display, Write};
+use std::sync::atomic::Ordering;
+
+/// Content type of the text exposition format
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Render every metric of a miner; `worker` labels all samples
+pub fn render_metrics(stats: &MinerStats, worker: &str) -> String {
+    let mut out = MetricsWriter::new(worker);
+
+    out.family(
+        "graxil_uptime_seconds",
+        "gauge",
+        "Seconds since the miner started",
+    );
+    out.sample("graxil_uptime_seconds", &[], stats.uptime().as_secs());
+
+    // Hashing
+    out.family(
+        "graxil_hashes_computed_total",
+        "counter",
+        "Hashes computed by every thread",
+    );
+    out.sample(
+        "graxil_hashes_computed_total",
+        &[],
+        stats.hashes_computed.load(Ordering::Relaxed),
+    );
+    out.family(
+        "graxil_hashrate_hashes_per_second",
+        "gauge",
+        "Session average hashrate of the miner",
+    );
+    out.sample(
+        "graxil_hashrate_hashes_per_second",
+        &[],
+        stats.get_total_hashrate(),
+    );
+
+    out.family(
+        "graxil_thread_hashes_computed_total",
+        "counter",
+        "Hashes computed by one mining thread",
+    );
+    for (thread_id, thread) in stats.thread_stats.iter().enumerate() {
+        out.sample(
+            "graxil_thread_hashes_computed_total",
+            &[("thread", &thread_id.to_string())],
+            thread.hashes_computed.load(Ordering::Relaxed),
+        );
+    }
+    out.family(
+        "graxil_thread_hashrate_hashes_per_second",
+        "gauge",
+        "Hashrate of one mining thread",
+    );
+    for (thread_id, thread) in stats.thread_stats.iter().enumerate() {
+        out.sample(
+            "graxil_thread_hashrate_hashes_per_second",
+            &[("thread", &thread_id.to_string())],
+            thread.get_hashrate(),
+        );
+    }
+    out.family(
+        "graxil_thread_parked",
+        "gauge",
+        "Whether a CPU mining thread is parked",
+    );
+    for (thread_id, thread) in stats.thread_stats.iter().enumerate() {
+        out.sample(
+            "graxil_thread_parked",
+            &[("thread", &thread_id.to_string())],
+            u8::from(thread.is_parked()),
+        );
+    }
+    out.family(
+        "graxil_thread_shares_total",
+        "counter",
+        "Pool verdicts for the shares of one mining thread",
+    );
+    for (thread_id, thread) in stats.thread_stats.iter().enumerate() {
+        let thread_id = thread_id.to_string();
+        for (status, counter) in [
+            ("accepted", &thread.shares_found),
+            ("rejected", &thread.shares_rejected),
+            ("stale", &thread.shares_stale),
+        ] {
+            out.sample(
+                "graxil_thread_shares_total",
+                &[("thread", &thread_id), ("status", status)],
+                counter.load(Ordering::Relaxed),
+            );
+        }
+    }
+
+    // Shares and difficulty
+    out.family(
+        "graxil_shares_submitted_total",
+        "counter",
+        "Shares submitted to the pool",
+    );
+    out.sample(
+        "graxil_shares_submitted_total",
+        &[],
+        stats.shares_submitted.load(Ordering::Relaxed),
+    );
+    out.family(
+        "graxil_shares_accepted_total",
+        "counter",
+        "Shares accepted by the pool",
+    );
+    out.sample(
+        "graxil_shares_accepted_total",
+        &[],
+        stats.shares_accepted.load(Ordering::Relaxed),
+    );
+    out.family(
+        "graxil_shares_rejected_total",
+        "counter",
+        "Shares refused by the pool, by reason (stale included)",
+    );
+    for (reason, count) in stats.share_rejections() {
+        out.sample("graxil_shares_rejected_total", &[("reason", reason)], count);
+    }
+    out.family(
+        "graxil_shares_duplicate_total",
+        "counter",
+        "Duplicate shares dropped before submission",
+    );
+    out.sample(
+        "graxil_shares_duplicate_total",
+        &[],
+        stats.shares_duplicate.load(Ordering::Relaxed),
+    );
+    if let Some(latency_ms) = stats.get_avg_submit_latency_ms() {
+        out.family(
+            "graxil_share_submit_latency_seconds",
+            "gauge",
+            "Average share submit round-trip time",
+        );
+        out.sample(
+            "graxil_share_submit_latency_seconds",
+            &[],
+            latency_ms as f64 / 1000.0,
+        );
+    }
+    out.family(
+        "graxil_difficulty",
+        "gauge",
+        "Current share difficulty target",
+    );
+    out.sample("graxil_difficulty", &[], stats.current_difficulty());
+    out.family(
+        "graxil_network_difficulty",
+        "gauge",
+        "Network difficulty of the solo mining block template",
+    );
+    out.sample(
+        "graxil_network_difficulty",
+        &[],
+        stats.network_difficulty.load(Ordering::Relaxed),
+    );
+    out.family(
+        "graxil_blocks_found_total",
+        "counter",
+        "Blocks accepted by the node while solo mining",
+    );
+    out.sample(
+        "graxil_blocks_found_total",
+        &[],
+        stats.blocks_found.load(Ordering::Relaxed),
+    );
+
+    // Pool connection
+    if let Some(connection) = stats.connection_info() {
+        let pool = connection.pool_address.clone().unwrap_or_default();
+        let labels = [("pool", pool.as_str())];
+        out.family(
+            "graxil_pool_up",
+            "gauge",
+            "Whether the pool connection is up",
+        );
+        out.sample("graxil_pool_up", &labels, u8::from(connection.is_connected));
+        out.family(
+            "graxil_pool_active_index",
+            "gauge",
+            "Index of the active pool in the failover list (0 = primary)",
+        );
+        out.sample(
+            "graxil_pool_active_index",
+            &labels,
+            connection.active_pool_index,
+        );
+        out.family(
+            "graxil_pool_connection_attempts_total",
+            "counter",
+            "Pool connection attempts, reconnects included",
+        );
+        out.sample(
+            "graxil_pool_connection_attempts_total",
+            &labels,
+            connection.connection_attempts,
+        );
+        out.family(
+            "graxil_pool_reconnects_total",
+            "counter",
+            "Pool connections re-established after a drop, a pool switch or a failover",
+        );
+        out.sample(
+            "graxil_pool_reconnects_total",
+            &labels,
+            connection.reconnects,
+        );
+        if let Some(latency) = connection.connection_latency {
+            out.family(
+                "graxil_pool_latency_seconds",
+                "gauge",
+                "Time taken by the last pool connect (TCP and TLS handshake)",
+            );
+            out.sample(
+                "graxil_pool_latency_seconds",
+                &labels,
+                latency.as_secs_f64(),
+            );
+        }
+    }
+
+    // Per-device GPU settings and hashrate
+    let devices = stats.gpu_device_infos();
+    if !devices.is_empty() {
+        out.family(
+            "graxil_gpu_hashrate_hashes_per_second",
+            "gauge",
+            "Hashrate of one GPU",
+        );
+        for device in &devices {
+            let device_id = device.device_id.to_string();
+            out.sample(
+                "graxil_gpu_hashrate_hashes_per_second",
+                &[("device", &device_id), ("name", &device.name)],
+                device.hashrate,
+            );
+        }
+        out.family(
+            "graxil_gpu_intensity_percent",
+            "gauge",
+            "Mining intensity of one GPU",
+        );
+        for device in &devices {
+            let device_id = device.device_id.to_string();
+            out.sample(
+                "graxil_gpu_intensity_percent",
+                &[("device", &device_id), ("name", &device.name)],
+                device.intensity,
+            );
+        }
+        out.family("graxil_gpu_enabled", "gauge", "Whether the GPU is mining");
+        for device in &devices {
+            let device_id = device.device_id.to_string();
+            out.sample(
+                "graxil_gpu_enabled",
+                &[("device", &device_id), ("name", &device.name)],
+                u8::from(device.enabled && !device.failed),
+            );
+        }
+        out.family(
+            "graxil_gpu_hardware_errors_total",
+            "counter",
+            "GPU shares that failed the CPU re-check",
+        );
+        for device in &devices {
+            let device_id = device.device_id.to_string();
+            out.sample(
+                "graxil_gpu_hardware_errors_total",
+                &[("device", &device_id), ("name", &device.name)],
+                device.hardware_errors,
+            );
+        }
+    }
+
+    // GPU monitoring (nvidia-smi)
+    let gpu_info = stats.gpu_info();
+    if gpu_info.is_available() {
+        let labels = [("gpu", gpu_info.name.as_str())];
+        if let Some(temperature) = gpu_info.temperature {
+            out.family("graxil_gpu_temperature_celsius", "gauge", "GPU temperature");
+            out.sample("graxil_gpu_temperature_celsius", &labels, temperature);
+        }
+        if let Some(power) = gpu_info.power_usage {
+            out.family("graxil_gpu_power_watts", "gauge", "GPU power draw");
+            out.sample("graxil_gpu_power_watts", &labels, power);
+        }
+        if let Some(utilization) = gpu_info.utilization {
+            out.family("graxil_gpu_utilization_percent", "gauge", "GPU utilization");
+            out.sample("graxil_gpu_utilization_percent", &labels, utilization);
+        }
+        if let Some(memory_used) = gpu_info.memory_used {
+            out.family("graxil_gpu_memory_used_bytes", "gauge", "GPU memory in use");
+            out.sample(
+                "graxil_gpu_memory_used_bytes",
+                &labels,
+                memory_used * 1024 * 1024,
+            );
+        }
+    }
+
+    out.out
+}
+
+/// Text exposition writer that adds the worker label to every sample
+struct MetricsWriter {
+    out: String,
+    worker: String,
+}
+
+impl MetricsWriter {
+    fn new(worker: &str) -> Self {
+        Self {
+            out: String::new(),
+            worker: escape_label(worker),
+        }
+    }
+
+    /// HELP and TYPE lines of a metric family
+    fn family(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.out, "# HELP {} {}", name, help);
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind);
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: impl Display) {
+        let _ = write!(self.out, "{}{{worker=\"{}\"", name, self.worker);
+        for (label, label_value) in labels {
+            let _ = write!(self.out, ",{}=\"{}\"", label, escape_label(label_value));
+        }
+        let _ = writeln!(self.out, "}} {}", value);
+    }
+}
+
+/// Escape a label value (backslash, double quote and newline)
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{GpuSettings, ShareStatus};
+    use crate::miner::stats::GpuDeviceStats;
+    use crate::pool::PoolClient;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_render_metrics() {
+        let mut stats = MinerStats::new(2);
+        let pool_client = Arc::new(PoolClient::new());
+        pool_client.record_reconnect();
+        pool_client.record_reconnect();
+        stats.set_pool_client(pool_client);
+        stats.thread_stats[1].update_hashrate(5000);
+        stats.shares_submitted.fetch_add(2, Ordering::Relaxed);
+        stats.record_share_result(0, 1000, ShareStatus::Accepted, Duration::from_millis(40));
+        stats.record_share_result(1, 500, ShareStatus::Rejected, Duration::from_millis(60));
+        stats.record_share_rejection("low_difficulty");
+        let gpu = Arc::new(GpuDeviceStats::new(
+            3,
+            0,
+            "Test GPU".to_string(),
+            GpuSettings::default(),
+        ));
+        stats.add_gpu_device(Arc::clone(&gpu));
+        gpu.record_hardware_error();
+        gpu.mark_failed();
+
+        let metrics = render_metrics(&stats, "rig-\"01\"");
+        assert!(metrics.contains("# TYPE graxil_shares_accepted_total counter"));
+        assert!(metrics.contains("graxil_shares_submitted_total{worker=\"rig-\\\"01\\\"\"} 2"));
+        assert!(metrics.contains(
+            "graxil_shares_rejected_total{worker=\"rig-\\\"01\\\"\",reason=\"low_difficulty\"} 1"
+        ));
+        assert!(metrics.contains(
+            "graxil_thread_shares_total{worker=\"rig-\\\"01\\\"\",thread=\"1\",status=\"rejected\"} 1"
+        ));
+        assert!(metrics.contains(
+            "graxil_thread_hashes_computed_total{worker=\"rig-\\\"01\\\"\",thread=\"1\"} 5000"
+        ));
+        assert!(
+            metrics.contains("graxil_share_submit_latency_seconds{worker=\"rig-\\\"01\\\"\"} 0.05")
+        );
+        assert!(
+            metrics.contains("graxil_pool_reconnects_total{worker=\"rig-\\\"01\\\"\",pool=\"\"} 2")
+        );
+        assert!(!metrics.contains("graxil_pool_latency_seconds"));
+        assert!(metrics.contains(
+            "graxil_gpu_hardware_errors_total{worker=\"rig-\\\"01\\\"\",device=\"3\",name=\"Test GPU\"} 1"
+        ));
+        assert!(metrics.contains(
+            "graxil_gpu_enabled{worker=\"rig-\\\"01\\\"\",device=\"3\",name=\"Test GPU\"} 0"
+        ));
+
+        // Every family is declared once
+        let types: Vec<&str> = metrics
+            .lines()
+            .filter(|l| l.starts_with("# TYPE"))
+            .collect();
+        let mut unique = types.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(types.len(), unique.len());
+    }
+}
+
+// Changelog:
+// - v1.4.0 (2025-06-28): graxil_pool_reconnects_total counter; graxil_pool_latency_seconds is
+//   the measured connect time now that the simulated latency is gone.
+// - v1.3.0 (2025-06-28): graxil_gpu_hardware_errors_total per device.
+// - v1.2.0 (2025-06-28): graxil_shares_duplicate_total counter.
+// - v1.1.0 (2025-06-28): graxil_thread_parked gauge for threads parked by the CPU governor.
+// - v1.0.0 (2025-06-28): Initial Prometheus exporter.
+//   - Hash, share (by rejection reason), difficulty and solo block counters and gauges.
+//   - Per-thread and per-GPU hashrate, pool connection, latency and reconnect metrics.
+//   - nvidia-smi GPU temperature, power, utilization and memory gauges.
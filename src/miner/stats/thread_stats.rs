@@ -6,18 +6,20 @@
 // via pull requests to the project repository.
 //
 // File: src/miner/stats/thread_stats.rs
-// Version: 1.0.1
+// Version: 1.1.0
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // This file implements per-thread statistics tracking for the SHA3x miner,
 // located in the stats subdirectory of the miner module. It monitors individual
-// thread performance, including shares, hashrate, and difficulty.
+// thread performance, including shares, hashrate, and difficulty, and whether
+// the thread is parked (by a runtime thread count change, a pause or the CPU
+// governor).
 //
 // Tree Location:
 // - src/miner/stats/thread_stats.rs (per-thread statistics logic)
 // - Depends on: std
 
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
@@ -27,12 +29,14 @@ pub struct ThreadStats {
     pub hashes_computed: AtomicU64,
     pub shares_found: AtomicU64,
     pub shares_rejected: AtomicU64,
+    pub shares_stale: AtomicU64,
     last_share_time: Arc<Mutex<Option<Instant>>>,
     start_time: Instant,
     current_hashrate: Arc<Mutex<f64>>,
     pub peak_hashrate: AtomicU64,
     pub best_difficulty: AtomicU64,
     pub current_difficulty_target: AtomicU64,
+    parked: AtomicBool,
 }
 
 impl ThreadStats {
@@ -43,15 +47,26 @@ impl ThreadStats {
             hashes_computed: AtomicU64::new(0),
             shares_found: AtomicU64::new(0),
             shares_rejected: AtomicU64::new(0),
+            shares_stale: AtomicU64::new(0),
             last_share_time: Arc::new(Mutex::new(None)),
             start_time: Instant::now(),
             current_hashrate: Arc::new(Mutex::new(0.0)),
             peak_hashrate: AtomicU64::new(0),
             best_difficulty: AtomicU64::new(0),
             current_difficulty_target: AtomicU64::new(0),
+            parked: AtomicBool::new(false),
         }
     }
 
+    /// Record whether the thread is parked instead of mining
+    pub fn set_parked(&self, parked: bool) {
+        self.parked.store(parked, Ordering::Relaxed);
+    }
+
+    pub fn is_parked(&self) -> bool {
+        self.parked.load(Ordering::Relaxed)
+    }
+
     /// Record a share (accepted or rejected)
     pub fn record_share(&self, difficulty: u64, accepted: bool) {
         if accepted {
@@ -68,6 +83,17 @@ impl ThreadStats {
         }
     }
 
+    /// Record a share the pool refused because its job was already replaced
+    pub fn record_stale_share(&self, difficulty: u64) {
+        self.shares_stale.fetch_add(1, Ordering::Relaxed);
+        *self.last_share_time.lock().unwrap() = Some(Instant::now());
+
+        let current_best = self.best_difficulty.load(Ordering::Relaxed);
+        if difficulty > current_best {
+            self.best_difficulty.store(difficulty, Ordering::Relaxed);
+        }
+    }
+
     /// Update hashrate based on computed hashes
     pub fn update_hashrate(&self, hashes: u64) {
         self.hashes_computed.fetch_add(hashes, Ordering::Relaxed);
@@ -108,6 +134,7 @@ impl ThreadStats {
     pub fn get_share_dots(&self) -> String {
         let accepted = self.shares_found.load(Ordering::Relaxed);
         let rejected = self.shares_rejected.load(Ordering::Relaxed);
+        let stale = self.shares_stale.load(Ordering::Relaxed);
 
         let mut dots = String::new();
         for _ in 0..accepted.min(5) {
@@ -116,11 +143,18 @@ impl ThreadStats {
         for _ in 0..rejected.min(5) {
             dots.push('○');
         }
+        for _ in 0..stale.min(5) {
+            dots.push('◌');
+        }
         dots
     }
 }
 
 // Changelog:
+// - v1.1.0 (2025-06-28): Added set_parked()/is_parked() for parked CPU threads.
+// - v1.0.2 (2025-06-28): Added stale share tracking.
+//   - Added shares_stale counter and record_stale_share().
+//   - Share dots show stale shares as '◌'.
 // - v1.0.1 (2025-06-14): Added peak hashrate tracking for benchmarking.
 //   - Added peak_hashrate field to track maximum hashrate achieved.
 //   - Updated update_hashrate to track peak performance automatically.
@@ -0,0 +1,166 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/pool/base_node.rs
+// Version: 1.0.0
+//
+// This file implements the Tari base node gRPC backend used for grpc:// solo
+// nodes. It asks the node for a SHA3x block template, has the node add a
+// coinbase paying the wallet, builds the SHA3x mining hash from the returned
+// block header, and submits the block with the winning nonce.
+//
+// Tree Location:
+// - src/pool/base_node.rs (Tari base node gRPC client)
+// - Depends on: minotari_app_grpc, tari_core, tonic, pool/solo
+
+use crate::Result;
+use crate::pool::solo::BlockTemplate;
+use log::{debug, info};
+use minotari_app_grpc::tari_rpc as grpc;
+use minotari_app_grpc::tari_rpc::base_node_client::BaseNodeClient;
+use tari_core::blocks::BlockHeader;
+use tonic::transport::Channel;
+
+const LOG_TARGET: &str = "tari::graxil::pool::base_node";
+
+/// gRPC connection to a Tari base node
+pub struct BaseNodeGrpc {
+    address: String,
+    client: Option<BaseNodeClient<Channel>>,
+}
+
+impl BaseNodeGrpc {
+    pub fn new(address: String) -> Self {
+        Self {
+            address,
+            client: None,
+        }
+    }
+
+    /// Connected client, (re)connecting when needed
+    async fn client(&mut self) -> Result<&mut BaseNodeClient<Channel>> {
+        if self.client.is_none() {
+            let client = BaseNodeClient::connect(format!("http://{}", self.address))
+                .await
+                .map_err(|e| format!("Cannot connect to base node {}: {}", self.address, e))?;
+            info!(target: LOG_TARGET,"🔗 Connected to base node gRPC at {}", self.address);
+            self.client = Some(client);
+        }
+        Ok(self.client.as_mut().unwrap())
+    }
+
+    /// Drop the connection after an error so the next call reconnects
+    fn reset<E: std::fmt::Display>(&mut self, call: &str, error: E) -> String {
+        self.client = None;
+        format!("Base node {} failed: {}", call, error)
+    }
+
+    /// Height of the node's best block
+    pub async fn tip_height(&mut self) -> Result<u64> {
+        let response = self.client().await?.get_tip_info(grpc::Empty {}).await;
+        let tip = response
+            .map_err(|e| self.reset("GetTipInfo", e))?
+            .into_inner();
+        if !tip.initial_sync_achieved {
+            return Err("Base node is still syncing".into());
+        }
+        Ok(tip
+            .metadata
+            .map(|metadata| metadata.best_block_height)
+            .unwrap_or_default())
+    }
+
+    /// SHA3x block template with a coinbase paying `wallet`
+    pub async fn get_template(&mut self, wallet: &str) -> Result<BlockTemplate> {
+        let request = grpc::NewBlockTemplateRequest {
+            algo: Some(grpc::PowAlgo {
+                pow_algo: grpc::pow_algo::PowAlgos::Sha3x as i32,
+            }),
+            max_weight: 0,
+        };
+        let response = self.client().await?.get_new_block_template(request).await;
+        let response = response
+            .map_err(|e| self.reset("GetNewBlockTemplate", e))?
+            .into_inner();
+
+        let new_template = response
+            .new_block_template
+            .ok_or("Base node returned no block template")?;
+        let miner_data = response
+            .miner_data
+            .ok_or("Base node returned no miner data")?;
+        let reward = miner_data.reward + miner_data.total_fees;
+
+        let request = grpc::GetNewBlockWithCoinbasesRequest {
+            new_template: Some(new_template),
+            coinbases: vec![grpc::NewBlockCoinbase {
+                address: wallet.to_string(),
+                value: reward,
+                stealth_payment: false,
+                revealed_value_proof: true,
+                coinbase_extra: Vec::new(),
+            }],
+        };
+        let block_result = self
+            .client()
+            .await?
+            .get_new_block_with_coinbases(request)
+            .await;
+        let block_result = block_result
+            .map_err(|e| self.reset("GetNewBlockWithCoinbases", e))?
+            .into_inner();
+
+        let block = block_result
+            .block
+            .ok_or("Base node returned no block for the template")?;
+        let header = BlockHeader::try_from(
+            block
+                .header
+                .clone()
+                .ok_or("Base node block has no header")?,
+        )
+        .map_err(|e| format!("Invalid block header from base node: {}", e))?;
+        let mining_hash = header.mining_hash().to_vec();
+        debug!(target: LOG_TARGET,
+            "Template for height {}: mining hash {}",
+            header.height,
+            hex::encode(&mining_hash)
+        );
+
+        Ok(BlockTemplate {
+            id: hex::encode(&mining_hash[..8]),
+            height: header.height,
+            mining_hash,
+            network_difficulty: miner_data.target_difficulty,
+            reward,
+            block: Some(Box::new(block)),
+        })
+    }
+
+    /// Submit the template's block with the winning nonce; returns the block hash
+    pub async fn submit_block(&mut self, template: &BlockTemplate, nonce: u64) -> Result<String> {
+        let mut block = template
+            .block
+            .as_deref()
+            .cloned()
+            .ok_or("Template has no base node block to submit")?;
+        block
+            .header
+            .as_mut()
+            .ok_or("Base node block has no header")?
+            .nonce = nonce;
+
+        let response = self.client().await?.submit_block(block).await;
+        let response = response
+            .map_err(|e| self.reset("SubmitBlock", e))?
+            .into_inner();
+        Ok(hex::encode(response.block_hash))
+    }
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial Tari base node gRPC backend for grpc:// solo nodes.
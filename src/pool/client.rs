@@ -6,17 +6,23 @@
 // via pull requests to the project repository.
 //
 // File: src/pool/client.rs
-// Version: 1.2.0
+// Version: 1.8.0
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // This file implements the TCP client for communicating with the mining pool
 // in the SHA3x miner, located in the pool subdirectory. It handles establishing
 // and maintaining connections to the pool server with connection tracking.
+// Pool URLs with stratum+ssl:// are wrapped in TLS by the pool transport, and
+// all connections can be tunnelled through a SOCKS5 or HTTP CONNECT proxy.
+// sv2:// pools get a plain TCP stream here; the Noise layer is added by the
+// SV2 client. Solo node RPC calls use short-lived untracked connections.
 //
 // Tree Location:
 // - src/pool/client.rs (pool TCP client logic)
-// - Depends on: tokio, std
+// - Depends on: tokio, std, pool/transport, pool/proxy
 
+use super::proxy::ProxyConfig;
+use super::transport::{self, PoolStream, PoolUrl, TlsOptions};
 use crate::Result;
 use std::net::SocketAddr;
 use std::sync::{Arc, Mutex};
@@ -32,7 +38,17 @@ pub struct ConnectionInfo {
     pub connected_at: Option<Instant>,
     pub is_connected: bool,
     pub connection_attempts: u32,
+    /// Connections re-established after a drop, a pool switch or a failover
+    pub reconnects: u32,
     pub last_successful_connect: Option<Instant>,
+    /// Index of the active pool in the failover list (0 = primary)
+    pub active_pool_index: usize,
+    /// Number of pools in the failover list
+    pub pool_count: usize,
+    /// Connection runs over TLS
+    pub encrypted: bool,
+    /// Proxy the connection is tunnelled through (without credentials)
+    pub proxy: Option<String>,
 }
 
 impl ConnectionInfo {
@@ -48,9 +64,25 @@ impl ConnectionInfo {
 
     /// Get formatted pool address for display
     pub fn display_address(&self) -> String {
-        self.pool_address
+        let address = self
+            .pool_address
             .clone()
-            .unwrap_or_else(|| "Not connected".to_string())
+            .unwrap_or_else(|| "Not connected".to_string());
+        if self.is_failover() {
+            format!(
+                "{} (backup {}/{})",
+                address,
+                self.active_pool_index,
+                self.pool_count - 1
+            )
+        } else {
+            address
+        }
+    }
+
+    /// Mining on a backup pool instead of the primary
+    pub fn is_failover(&self) -> bool {
+        self.pool_count > 1 && self.active_pool_index > 0
     }
 }
 
@@ -58,6 +90,7 @@ impl ConnectionInfo {
 #[derive(Clone)]
 pub struct PoolClient {
     connection_info: Arc<Mutex<ConnectionInfo>>,
+    proxy: Arc<Mutex<Option<ProxyConfig>>>,
 }
 
 impl Default for PoolClient {
@@ -71,9 +104,21 @@ impl PoolClient {
     pub fn new() -> Self {
         Self {
             connection_info: Arc::new(Mutex::new(ConnectionInfo::default())),
+            proxy: Arc::new(Mutex::new(None)),
         }
     }
 
+    /// Route all pool connections (including failback probes) through a proxy
+    pub fn set_proxy(&self, proxy: Option<ProxyConfig>) {
+        self.connection_info.lock().unwrap().proxy = proxy.as_ref().map(|p| p.to_string());
+        *self.proxy.lock().unwrap() = proxy;
+    }
+
+    /// Currently configured proxy
+    pub fn proxy(&self) -> Option<ProxyConfig> {
+        self.proxy.lock().unwrap().clone()
+    }
+
     /// Resolve pool address from either IP:port or domain:port format
     async fn resolve_pool_address(pool_str: &str) -> Result<SocketAddr> {
         // Try parsing as direct IP:port first
@@ -91,6 +136,9 @@ impl PoolClient {
     /// Connect to the mining pool at the specified SocketAddr (backward compatibility)
     pub async fn connect(&self, pool_address: SocketAddr) -> Result<TcpStream> {
         let pool_str = pool_address.to_string();
+        if self.proxy().is_some() {
+            return self.connect_str(&pool_str).await;
+        }
         self.connect_with_tracking(&pool_str, pool_address).await
     }
 
@@ -105,15 +153,23 @@ impl PoolClient {
             info.pool_address = Some(pool_address.to_string());
         }
 
-        // Resolve address and measure connection time
-        let resolved_addr = Self::resolve_pool_address(pool_address).await?;
-        let stream = TcpStream::connect(resolved_addr).await?;
+        // Resolve address (locally, or on the proxy) and measure connection time
+        let (stream, resolved_addr) = match self.proxy() {
+            Some(proxy) => (proxy.connect(pool_address).await?, None),
+            None => {
+                let resolved_addr = Self::resolve_pool_address(pool_address).await?;
+                (
+                    TcpStream::connect(resolved_addr).await?,
+                    Some(resolved_addr),
+                )
+            }
+        };
         let connection_latency = start_time.elapsed();
 
         // Update connection info on successful connection
         {
             let mut info = self.connection_info.lock().unwrap();
-            info.resolved_address = Some(resolved_addr);
+            info.resolved_address = resolved_addr;
             info.connection_latency = Some(connection_latency);
             info.connected_at = Some(Instant::now());
             info.is_connected = true;
@@ -156,11 +212,89 @@ impl PoolClient {
         Ok(stream)
     }
 
+    /// Connect to a pool URL (`stratum+tcp://`, `stratum+ssl://`, `sv2://` or plain HOST:PORT)
+    pub async fn connect_pool(&self, pool_url: &str, tls: &TlsOptions) -> Result<PoolStream> {
+        let url = PoolUrl::parse(pool_url)?;
+        let start_time = Instant::now();
+        let stream = self.connect_str(&url.address).await?;
+
+        if !url.is_tls() {
+            self.connection_info.lock().unwrap().encrypted = false;
+            return Ok(Box::new(stream));
+        }
+
+        match transport::connect_tls(stream, &url, tls).await {
+            Ok(stream) => {
+                // Connection latency covers the TLS handshake as well
+                let mut info = self.connection_info.lock().unwrap();
+                info.connection_latency = Some(start_time.elapsed());
+                info.encrypted = true;
+                Ok(Box::new(stream))
+            }
+            Err(e) => {
+                self.mark_disconnected();
+                Err(format!("TLS handshake with {} failed: {}", url.address, e).into())
+            }
+        }
+    }
+
+    /// Check that a pool accepts TCP connections without touching connection tracking
+    pub async fn probe(&self, pool_address: &str, timeout: Duration) -> bool {
+        let attempt = async {
+            let url = PoolUrl::parse(pool_address)?;
+            self.connect_untracked(&url.address).await
+        };
+        matches!(tokio::time::timeout(timeout, attempt).await, Ok(Ok(_)))
+    }
+
+    /// Open a connection (through the proxy, if any) without touching connection
+    /// tracking; used for probes and per-request solo node RPC calls
+    pub async fn connect_untracked(&self, address: &str) -> Result<TcpStream> {
+        match self.proxy() {
+            Some(proxy) => proxy.connect(address).await,
+            None => {
+                let addr = Self::resolve_pool_address(address).await?;
+                Ok(TcpStream::connect(addr).await?)
+            }
+        }
+    }
+
+    /// Record a working link that is not a single long-lived connection (solo nodes)
+    pub fn mark_connected(&self, address: &str, latency: Duration) {
+        let mut info = self.connection_info.lock().unwrap();
+        if !info.is_connected {
+            info.connection_attempts += 1;
+            info.connected_at = Some(Instant::now());
+            info.last_successful_connect = Some(Instant::now());
+        }
+        info.pool_address = Some(address.to_string());
+        info.connection_latency = Some(latency);
+        info.is_connected = true;
+    }
+
+    /// Record which pool of the failover list is in use
+    pub fn set_active_pool(&self, index: usize, pool_count: usize) {
+        let mut info = self.connection_info.lock().unwrap();
+        info.active_pool_index = index;
+        info.pool_count = pool_count;
+    }
+
+    /// Record that the link is encrypted above the transport (SV2 Noise)
+    pub fn set_encrypted(&self, encrypted: bool) {
+        self.connection_info.lock().unwrap().encrypted = encrypted;
+    }
+
     /// Mark connection as disconnected (should be called when connection is lost)
     pub fn mark_disconnected(&self) {
         let mut info = self.connection_info.lock().unwrap();
         info.is_connected = false;
         info.connected_at = None;
+        info.encrypted = false;
+    }
+
+    /// Count a connection re-established after a drop, a pool switch or a failover
+    pub fn record_reconnect(&self) {
+        self.connection_info.lock().unwrap().reconnects += 1;
     }
 
     /// Update connection latency (for periodic ping measurements)
@@ -184,6 +318,11 @@ impl PoolClient {
         self.connection_info.lock().unwrap().latency_ms()
     }
 
+    /// Check if the current connection is encrypted (convenience method)
+    pub fn is_encrypted(&self) -> bool {
+        self.connection_info.lock().unwrap().encrypted
+    }
+
     /// Get pool address for display (convenience method)
     pub fn get_pool_address(&self) -> String {
         self.connection_info.lock().unwrap().display_address()
@@ -191,6 +330,23 @@ impl PoolClient {
 }
 
 // Changelog:
+// - v1.8.0 (2025-06-28): ConnectionInfo.reconnects with record_reconnect(); the connection
+//   latency of stratum+ssl:// pools includes the TLS handshake.
+// - v1.7.0 (2025-06-28): Solo node support
+//   - Added connect_untracked() (shared with probe()) and mark_connected() for solo node RPC.
+// - v1.6.0 (2025-06-28): Stratum V2 support
+//   - connect_pool() accepts sv2:// URLs (plain TCP; Noise is layered by the SV2 client).
+//   - Added set_encrypted() so the SV2 client can report the Noise-encrypted link.
+// - v1.5.0 (2025-06-28): Outbound proxy support
+//   - set_proxy() routes connect_str(), connect_pool() and probe() through SOCKS5 / HTTP CONNECT.
+//   - ConnectionInfo.proxy records the proxy in use; resolved_address is None when the proxy resolves.
+// - v1.4.0 (2025-06-28): TLS pool connections
+//   - Added connect_pool() accepting stratum+tcp:// / stratum+ssl:// URLs with TlsOptions.
+//   - ConnectionInfo.encrypted and is_encrypted() report whether the link uses TLS.
+//   - probe() accepts pool URLs with a scheme.
+// - v1.3.0 (2025-06-28): Failover pool tracking
+//   - ConnectionInfo reports the active pool index and pool count; display_address marks backup pools.
+//   - Added set_active_pool() and probe() for failback checks of higher-priority pools.
 // - v1.2.0 (2025-06-24): Added connection tracking and performance monitoring
 //   - Added ConnectionInfo struct to track pool connectivity, latency, and statistics
 //   - Enhanced PoolClient with connection_info Arc<Mutex<ConnectionInfo>> field
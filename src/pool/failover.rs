@@ -0,0 +1,490 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/pool/failover.rs
+// Version: 1.3.1
+//
+// This file implements the failover pool list used by the pool session. Pools
+// are ordered by priority; each pool gets a retry budget with exponential
+// backoff before the session moves on to the next one, and higher-priority
+// pools are probed periodically so mining returns to the primary pool. A pool
+// can also be pinned (e.g. from the HTTP API), which stops the failback probes.
+//
+// Tree Location:
+// - src/pool/failover.rs (pool priority list and retry policy)
+// - Depends on: std, pool/transport
+
+use super::transport::{PoolUrl, TlsOptions, parse_fingerprint};
+use std::time::{Duration, Instant};
+
+/// Default connection attempts per pool before failing over
+pub const DEFAULT_RETRY_BUDGET: u32 = 3;
+/// Default upper bound for the reconnect backoff
+pub const DEFAULT_MAX_BACKOFF_SECS: u64 = 60;
+/// Default interval between probes of higher-priority pools
+pub const DEFAULT_FAILBACK_INTERVAL_SECS: u64 = 300;
+
+/// One pool in the failover list with its own credentials
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolEndpoint {
+    pub address: String,
+    pub wallet: String,
+    pub worker: String,
+    pub password: String,
+    /// Lower value = preferred pool
+    pub priority: u32,
+    /// Certificate checks for `stratum+ssl://` pools
+    pub tls: TlsOptions,
+}
+
+impl PoolEndpoint {
+    pub fn new(address: String, wallet: String, worker: String, password: String) -> Self {
+        Self {
+            address,
+            wallet,
+            worker,
+            password,
+            priority: 0,
+            tls: TlsOptions::default(),
+        }
+    }
+
+    /// Account name for pools that take a single user string (Stratum V1
+    /// SHA-256 and Stratum V2): `WALLET.WORKER`, or the wallet alone when it
+    /// already names a worker or no worker is set
+    pub fn user_identity(&self) -> String {
+        if self.worker.is_empty() || self.wallet.contains('.') {
+            self.wallet.clone()
+        } else {
+            format!("{}.{}", self.wallet, self.worker)
+        }
+    }
+
+    /// Parse a `--pool` value: `[SCHEME://]HOST:PORT[,priority=N][,wallet=W][,worker=W][,password=P]`
+    ///
+    /// Missing options fall back to the global wallet/worker/password, and the
+    /// priority defaults to the position of the pool on the command line.
+    /// TLS pools also accept `fingerprint=SHA256` and `insecure=true` (no certificate checks).
+    pub fn parse(
+        spec: &str,
+        index: usize,
+        wallet: &str,
+        worker: &str,
+        password: &str,
+    ) -> Result<Self, String> {
+        let mut parts = spec.split(',');
+        let address = parts.next().unwrap_or_default().trim();
+        if address.is_empty() {
+            return Err(format!("Pool entry '{}' has no address", spec));
+        }
+        let url = PoolUrl::parse(address)?;
+
+        let mut endpoint = Self {
+            address: address.to_string(),
+            wallet: wallet.to_string(),
+            worker: worker.to_string(),
+            password: password.to_string(),
+            priority: index as u32,
+            tls: TlsOptions::default(),
+        };
+
+        for option in parts {
+            let (key, value) = option
+                .split_once('=')
+                .ok_or_else(|| format!("Pool option '{}' must be KEY=VALUE", option))?;
+            let value = value.trim();
+            match key.trim() {
+                "priority" => {
+                    endpoint.priority = value.parse().map_err(|_| {
+                        format!("Pool priority must be a number. Found: '{}'", value)
+                    })?;
+                }
+                "wallet" => endpoint.wallet = value.to_string(),
+                "worker" => endpoint.worker = value.to_string(),
+                "password" => endpoint.password = value.to_string(),
+                "fingerprint" => endpoint.tls.pinned_fingerprint = Some(parse_fingerprint(value)?),
+                "insecure" => {
+                    endpoint.tls.insecure = value.parse().map_err(|_| {
+                        format!(
+                            "Pool option insecure must be true or false. Found: '{}'",
+                            value
+                        )
+                    })?;
+                }
+                other => {
+                    return Err(format!(
+                        "Unknown pool option '{}' (expected priority, wallet, worker, password, fingerprint or insecure)",
+                        other
+                    ));
+                }
+            }
+        }
+
+        if !url.is_tls() && endpoint.tls != TlsOptions::default() {
+            return Err(format!(
+                "TLS options given for {} but it is not a stratum+ssl:// pool",
+                address
+            ));
+        }
+
+        if endpoint.wallet.is_empty() {
+            return Err(format!("No wallet address configured for pool {}", address));
+        }
+
+        Ok(endpoint)
+    }
+}
+
+/// Retry and failback timing for the failover list
+#[derive(Debug, Clone)]
+pub struct FailoverSettings {
+    /// Connection attempts on one pool before moving to the next
+    pub retry_budget: u32,
+    /// Delay after the first failed attempt, doubled on every further failure
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// How often higher-priority pools are probed while on a backup pool
+    pub failback_interval: Duration,
+}
+
+impl Default for FailoverSettings {
+    fn default() -> Self {
+        Self {
+            retry_budget: DEFAULT_RETRY_BUDGET,
+            initial_backoff: Duration::from_secs(5),
+            max_backoff: Duration::from_secs(DEFAULT_MAX_BACKOFF_SECS),
+            failback_interval: Duration::from_secs(DEFAULT_FAILBACK_INTERVAL_SECS),
+        }
+    }
+}
+
+/// What the session should do after a failed connection attempt
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryDecision {
+    /// Wait this long before the next attempt
+    pub delay: Duration,
+    /// The retry budget ran out and the active pool changed
+    pub failed_over: bool,
+}
+
+/// Priority-ordered pool list with retry budget and failback tracking
+#[derive(Debug, Clone)]
+pub struct PoolFailover {
+    pools: Vec<PoolEndpoint>,
+    settings: FailoverSettings,
+    active: usize,
+    failures: u32,
+    last_probe: Instant,
+    /// Chosen by hand; no failback until unpinned or the pool fails
+    pinned: bool,
+}
+
+impl PoolFailover {
+    /// Create a failover list; pools are sorted by priority (stable for equal priorities)
+    pub fn new(mut pools: Vec<PoolEndpoint>, settings: FailoverSettings) -> Result<Self, String> {
+        if pools.is_empty() {
+            return Err("At least one pool is required".to_string());
+        }
+        pools.sort_by_key(|p| p.priority);
+
+        Ok(Self {
+            pools,
+            settings: FailoverSettings {
+                retry_budget: settings.retry_budget.max(1),
+                ..settings
+            },
+            active: 0,
+            failures: 0,
+            last_probe: Instant::now(),
+            pinned: false,
+        })
+    }
+
+    pub fn pools(&self) -> &[PoolEndpoint] {
+        &self.pools
+    }
+
+    pub fn active(&self) -> &PoolEndpoint {
+        &self.pools[self.active]
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    pub fn settings(&self) -> &FailoverSettings {
+        &self.settings
+    }
+
+    /// Reset the retry budget after a successful connection
+    pub fn record_success(&mut self) {
+        self.failures = 0;
+    }
+
+    /// Register a failed attempt on the active pool
+    pub fn record_failure(&mut self) -> RetryDecision {
+        self.failures += 1;
+
+        if self.failures >= self.settings.retry_budget && self.pools.len() > 1 {
+            self.active = (self.active + 1) % self.pools.len();
+            self.failures = 0;
+            self.last_probe = Instant::now();
+            self.pinned = false;
+            return RetryDecision {
+                delay: Duration::ZERO,
+                failed_over: true,
+            };
+        }
+
+        if self.failures >= self.settings.retry_budget {
+            // Single pool: keep retrying at the maximum backoff
+            self.failures = self.settings.retry_budget;
+        }
+
+        RetryDecision {
+            delay: self.backoff(),
+            failed_over: false,
+        }
+    }
+
+    /// Exponential backoff for the current failure count
+    fn backoff(&self) -> Duration {
+        let exponent = self.failures.saturating_sub(1).min(16);
+        self.settings
+            .initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.settings.max_backoff)
+    }
+
+    /// Higher-priority pools are due for a probe
+    pub fn should_probe(&self) -> bool {
+        !self.pinned
+            && self.active > 0
+            && self.last_probe.elapsed() >= self.settings.failback_interval
+    }
+
+    /// Pools preferred over the active one, best first
+    pub fn preferred_pools(&self) -> &[PoolEndpoint] {
+        &self.pools[..self.active]
+    }
+
+    pub fn mark_probed(&mut self) {
+        self.last_probe = Instant::now();
+    }
+
+    /// Switch to a pool by its index in the priority list
+    pub fn switch_to(&mut self, index: usize) {
+        if index < self.pools.len() {
+            self.active = index;
+            self.failures = 0;
+            self.last_probe = Instant::now();
+        }
+    }
+
+    /// Switch to a pool and stay there, or (None) return to the priority order.
+    /// A pinned pool is still left when its retry budget runs out.
+    pub fn pin(&mut self, index: Option<usize>) -> Result<(), String> {
+        match index {
+            Some(index) if index >= self.pools.len() => Err(format!(
+                "No pool {} (the failover list has {})",
+                index,
+                self.pools.len()
+            )),
+            Some(index) => {
+                self.switch_to(index);
+                self.pinned = true;
+                Ok(())
+            }
+            None => {
+                self.pinned = false;
+                Ok(())
+            }
+        }
+    }
+
+    pub fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoint(address: &str, priority: u32) -> PoolEndpoint {
+        PoolEndpoint {
+            priority,
+            ..PoolEndpoint::new(
+                address.to_string(),
+                "wallet".to_string(),
+                "worker1".to_string(),
+                "x".to_string(),
+            )
+        }
+    }
+
+    fn settings() -> FailoverSettings {
+        FailoverSettings {
+            retry_budget: 3,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(3),
+            failback_interval: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_parse_pool_spec() {
+        let pool = PoolEndpoint::parse("pool.tari.com:4200", 2, "w", "rig", "x").unwrap();
+        assert_eq!(pool.address, "pool.tari.com:4200");
+        assert_eq!(pool.priority, 2);
+        assert_eq!(pool.worker, "rig");
+
+        let pool = PoolEndpoint::parse(
+            "backup.pool:6118,priority=0,worker=rig-02,password=secret,wallet=other",
+            1,
+            "w",
+            "rig",
+            "x",
+        )
+        .unwrap();
+        assert_eq!(pool.priority, 0);
+        assert_eq!(pool.worker, "rig-02");
+        assert_eq!(pool.password, "secret");
+        assert_eq!(pool.wallet, "other");
+
+        assert!(PoolEndpoint::parse("pool:1,colour=red", 0, "w", "rig", "x").is_err());
+        assert!(PoolEndpoint::parse("pool:1,priority=high", 0, "w", "rig", "x").is_err());
+        assert!(PoolEndpoint::parse("pool:1", 0, "", "rig", "x").is_err());
+    }
+
+    #[test]
+    fn test_parse_tls_pool_spec() {
+        let fingerprint = "ab".repeat(32);
+        let pool = PoolEndpoint::parse(
+            &format!(
+                "stratum+ssl://pool.tari.com:4201,fingerprint={}",
+                fingerprint
+            ),
+            0,
+            "w",
+            "rig",
+            "x",
+        )
+        .unwrap();
+        assert_eq!(pool.address, "stratum+ssl://pool.tari.com:4201");
+        assert_eq!(pool.tls.pinned_fingerprint, Some([0xab; 32]));
+
+        let pool = PoolEndpoint::parse(
+            "stratum+ssl://pool:4201,insecure=true",
+            0,
+            "w",
+            "rig",
+            "x",
+        )
+        .unwrap();
+        assert!(pool.tls.insecure);
+
+        // TLS options make no sense on a plain TCP pool
+        assert!(PoolEndpoint::parse("pool:4200,insecure=true", 0, "w", "rig", "x").is_err());
+        assert!(PoolEndpoint::parse("ftp://pool:4200", 0, "w", "rig", "x").is_err());
+    }
+
+    #[test]
+    fn test_user_identity() {
+        let mut pool = PoolEndpoint::new(
+            "pool:3333".to_string(),
+            "account".to_string(),
+            "rig1".to_string(),
+            "x".to_string(),
+        );
+        assert_eq!(pool.user_identity(), "account.rig1");
+        pool.worker.clear();
+        assert_eq!(pool.user_identity(), "account");
+        pool.wallet = "account.rig2".to_string();
+        pool.worker = "rig1".to_string();
+        assert_eq!(pool.user_identity(), "account.rig2");
+    }
+
+    #[test]
+    fn test_backoff_then_failover() {
+        let mut failover =
+            PoolFailover::new(vec![endpoint("b:2", 1), endpoint("a:1", 0)], settings()).unwrap();
+        assert_eq!(failover.active().address, "a:1");
+
+        assert_eq!(failover.record_failure().delay, Duration::from_secs(1));
+        assert_eq!(failover.record_failure().delay, Duration::from_secs(2));
+        let decision = failover.record_failure();
+        assert!(decision.failed_over);
+        assert_eq!(failover.active().address, "b:2");
+
+        // Wraps back around to the primary once the backup is exhausted too
+        failover.record_failure();
+        failover.record_failure();
+        assert!(failover.record_failure().failed_over);
+        assert_eq!(failover.active_index(), 0);
+    }
+
+    #[test]
+    fn test_single_pool_backoff_is_capped() {
+        let mut failover = PoolFailover::new(vec![endpoint("a:1", 0)], settings()).unwrap();
+        for _ in 0..10 {
+            let decision = failover.record_failure();
+            assert!(!decision.failed_over);
+            assert!(decision.delay <= Duration::from_secs(3));
+        }
+    }
+
+    #[test]
+    fn test_failback_probe() {
+        let mut failover =
+            PoolFailover::new(vec![endpoint("a:1", 0), endpoint("b:2", 1)], settings()).unwrap();
+        assert!(!failover.should_probe());
+
+        failover.switch_to(1);
+        assert!(failover.should_probe());
+        assert_eq!(failover.preferred_pools().len(), 1);
+
+        failover.switch_to(0);
+        assert!(!failover.should_probe());
+    }
+
+    #[test]
+    fn test_pinned_pool() {
+        let mut failover =
+            PoolFailover::new(vec![endpoint("a:1", 0), endpoint("b:2", 1)], settings()).unwrap();
+        assert!(failover.pin(Some(2)).is_err());
+
+        failover.pin(Some(1)).unwrap();
+        assert_eq!(failover.active().address, "b:2");
+        assert!(!failover.should_probe());
+
+        failover.pin(None).unwrap();
+        assert!(failover.should_probe());
+
+        // A pinned pool that keeps failing is left like any other
+        failover.pin(Some(1)).unwrap();
+        for _ in 0..3 {
+            failover.record_failure();
+        }
+        assert_eq!(failover.active_index(), 0);
+        assert!(!failover.is_pinned());
+    }
+}
+
+// Changelog:
+// - v1.3.1 (2025-06-28): Pool option self_signed= renamed to insecure=, as it turns off
+//   certificate verification rather than checking for a self-signed certificate.
+// - v1.3.0 (2025-06-28): PoolFailover::pin() keeps the session on a chosen pool without
+//   failback probes until unpinned or the pool fails.
+// - v1.2.0 (2025-06-28): Added PoolEndpoint::user_identity() for SHA-256 and SV2 pools.
+// - v1.1.0 (2025-06-28): Pool URL schemes and per-pool TLS options.
+//   - Addresses may carry stratum+tcp:// or stratum+ssl:// and are validated.
+//   - New fingerprint= and self_signed= options for TLS pools.
+// - v1.0.0 (2025-06-28): Initial failover pool list.
+//   - PoolEndpoint with per-pool wallet/worker/password and priority.
+//   - Retry budget with exponential backoff, failover to the next pool and
+//     periodic failback probes of higher-priority pools.
@@ -0,0 +1,571 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/pool/mock.rs
+// Version: 1.0.0
+//
+// This file implements a scriptable mock Tari Stratum pool for tests. It
+// listens on localhost, speaks the SHA3x JSON-RPC dialect (login, job, submit,
+// LuckyPool xn) and verifies every submitted share by recomputing the SHA3x
+// hash, so pool sessions and miners can be exercised without a network. Tests
+// drive it by sending jobs, vardiff changes, disconnects, malformed lines and
+// scripted rejections. Built for unit tests and with the `mock-pool` feature.
+//
+// Tree Location:
+// - src/pool/mock.rs (mock Stratum pool for tests)
+// - Depends on: core/sha3x, core/difficulty, tokio, serde_json
+
+use crate::Result;
+use crate::core::{Algorithm, calculate_difficulty, sha3x::sha3x_hash_with_nonce};
+use log::{debug, info};
+use serde_json::{Value, json};
+use sha3::{Digest, Sha3_256};
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+const LOG_TARGET: &str = "tari::graxil::pool::mock";
+
+/// How often the wait_for_* helpers re-check the pool state
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Job handed out by the mock pool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockJob {
+    pub job_id: String,
+    /// 32-byte SHA3x header template
+    pub blob: Vec<u8>,
+    pub height: u64,
+    pub difficulty: u64,
+}
+
+impl MockJob {
+    /// Job with a header template derived from the job id
+    pub fn new(job_id: &str, height: u64, difficulty: u64) -> Self {
+        Self {
+            job_id: job_id.to_string(),
+            blob: Sha3_256::digest(job_id.as_bytes()).to_vec(),
+            height,
+            difficulty,
+        }
+    }
+
+    /// Job params as sent in a `job` notification or the login response
+    fn params(&self, xn: Option<&str>) -> Value {
+        let mut params = json!({
+            "job_id": self.job_id,
+            "blob": hex::encode(&self.blob),
+            "target": hex::encode((u64::MAX / self.difficulty.max(1)).to_le_bytes()),
+            "algo": "sha3x",
+            "height": self.height,
+            "difficulty": self.difficulty,
+        });
+        if let Some(xn) = xn {
+            params["xn"] = json!(xn);
+        }
+        params
+    }
+}
+
+/// Login request received by the mock pool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockLogin {
+    pub login: String,
+    pub pass: String,
+    pub rigid: Option<String>,
+    pub agent: String,
+}
+
+/// Pool verdict on a submitted share
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockVerdict {
+    Accepted,
+    Rejected(String),
+}
+
+/// Share submitted to the mock pool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockShare {
+    /// JSON-RPC id of the submit request
+    pub request_id: Value,
+    /// Login or session id sent with the share
+    pub login: String,
+    pub job_id: String,
+    pub nonce: String,
+    pub result: String,
+    pub verdict: MockVerdict,
+}
+
+impl MockShare {
+    pub fn is_accepted(&self) -> bool {
+        self.verdict == MockVerdict::Accepted
+    }
+}
+
+/// Instruction for every open connection
+#[derive(Debug, Clone)]
+enum Broadcast {
+    Line(String),
+    Disconnect,
+}
+
+#[derive(Default)]
+struct MockPoolState {
+    session_id: String,
+    xn: Option<String>,
+    current_job: Option<MockJob>,
+    jobs: HashMap<String, MockJob>,
+    logins: Vec<MockLogin>,
+    shares: Vec<MockShare>,
+    seen_nonces: HashSet<(String, String)>,
+    reject_next: usize,
+    reject_logins: Option<String>,
+    connections: usize,
+}
+
+/// Scriptable Tari Stratum pool listening on localhost
+pub struct MockPool {
+    address: SocketAddr,
+    state: Arc<Mutex<MockPoolState>>,
+    broadcast_tx: broadcast::Sender<Broadcast>,
+    accept_task: JoinHandle<()>,
+}
+
+impl MockPool {
+    /// Start a mock pool on an ephemeral localhost port
+    pub async fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(MockPoolState {
+            session_id: "mock-session".to_string(),
+            ..Default::default()
+        }));
+        let (broadcast_tx, _) = broadcast::channel(64);
+
+        let accept_task = {
+            let state = Arc::clone(&state);
+            let broadcast_tx = broadcast_tx.clone();
+            tokio::spawn(async move {
+                while let Ok((stream, peer)) = listener.accept().await {
+                    debug!(target: LOG_TARGET,"🧪 Mock pool connection from {}", peer);
+                    tokio::spawn(serve_connection(
+                        stream,
+                        Arc::clone(&state),
+                        broadcast_tx.subscribe(),
+                    ));
+                }
+            })
+        };
+
+        info!(target: LOG_TARGET,"🧪 Mock pool listening on {}", address);
+        Ok(Self {
+            address,
+            state,
+            broadcast_tx,
+            accept_task,
+        })
+    }
+
+    /// Pool address to hand to a miner or session (`127.0.0.1:PORT`)
+    pub fn address(&self) -> String {
+        self.address.to_string()
+    }
+
+    /// Session id returned in the login response
+    pub fn set_session_id(&self, session_id: &str) {
+        self.state.lock().unwrap().session_id = session_id.to_string();
+    }
+
+    /// LuckyPool extra nonce included in every job; shares must start with it
+    pub fn set_xn(&self, xn: Option<&str>) {
+        self.state.lock().unwrap().xn = xn.map(str::to_string);
+    }
+
+    /// Answer every login with this error instead of a session (None to accept again)
+    pub fn reject_logins(&self, reason: Option<&str>) {
+        self.state.lock().unwrap().reject_logins = reason.map(str::to_string);
+    }
+
+    /// Reject the next `count` shares regardless of their validity
+    pub fn reject_next_shares(&self, count: usize) {
+        self.state.lock().unwrap().reject_next = count;
+    }
+
+    /// Make `job` current and push it to every connected miner
+    pub fn send_job(&self, job: MockJob) {
+        let line = {
+            let mut state = self.state.lock().unwrap();
+            state.jobs.insert(job.job_id.clone(), job.clone());
+            state.current_job = Some(job.clone());
+            job_notification(&job, state.xn.as_deref())
+        };
+        self.broadcast(Broadcast::Line(line));
+    }
+
+    /// VarDiff: re-send the current job with a new difficulty
+    pub fn set_difficulty(&self, difficulty: u64) {
+        let job = self.state.lock().unwrap().current_job.clone();
+        if let Some(job) = job {
+            self.send_job(MockJob { difficulty, ..job });
+        }
+    }
+
+    /// Send a raw line (e.g. malformed JSON) to every connected miner
+    pub fn send_raw(&self, line: &str) {
+        self.broadcast(Broadcast::Line(line.to_string()));
+    }
+
+    /// Drop every open connection
+    pub fn disconnect_all(&self) {
+        self.broadcast(Broadcast::Disconnect);
+    }
+
+    /// Number of open connections
+    pub fn connection_count(&self) -> usize {
+        self.state.lock().unwrap().connections
+    }
+
+    /// Every login received so far
+    pub fn logins(&self) -> Vec<MockLogin> {
+        self.state.lock().unwrap().logins.clone()
+    }
+
+    /// Every share received so far, with the pool verdict
+    pub fn shares(&self) -> Vec<MockShare> {
+        self.state.lock().unwrap().shares.clone()
+    }
+
+    /// Wait until at least `count` logins were received
+    pub async fn wait_for_logins(&self, count: usize, timeout: Duration) -> Result<Vec<MockLogin>> {
+        if !self
+            .wait_until(timeout, |state| state.logins.len() >= count)
+            .await
+        {
+            return Err(
+                format!("Mock pool saw {} of {} logins", self.logins().len(), count).into(),
+            );
+        }
+        Ok(self.logins())
+    }
+
+    /// Wait until at least `count` shares were received
+    pub async fn wait_for_shares(&self, count: usize, timeout: Duration) -> Result<Vec<MockShare>> {
+        if !self
+            .wait_until(timeout, |state| state.shares.len() >= count)
+            .await
+        {
+            return Err(
+                format!("Mock pool saw {} of {} shares", self.shares().len(), count).into(),
+            );
+        }
+        Ok(self.shares())
+    }
+
+    /// Poll the pool state until `done` holds; false on timeout
+    async fn wait_until(&self, timeout: Duration, done: impl Fn(&MockPoolState) -> bool) -> bool {
+        let deadline = Instant::now() + timeout;
+        while !done(&self.state.lock().unwrap()) {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+        }
+        true
+    }
+
+    fn broadcast(&self, message: Broadcast) {
+        // No receivers just means nobody is connected
+        let _ = self.broadcast_tx.send(message);
+    }
+}
+
+impl Drop for MockPool {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+        self.broadcast(Broadcast::Disconnect);
+    }
+}
+
+async fn serve_connection(
+    stream: TcpStream,
+    state: Arc<Mutex<MockPoolState>>,
+    mut broadcast_rx: broadcast::Receiver<Broadcast>,
+) {
+    state.lock().unwrap().connections += 1;
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+
+    loop {
+        let reply = tokio::select! {
+            line = lines.next_line() => match line {
+                Ok(Some(line)) => handle_request(&state, &line),
+                _ => break,
+            },
+            message = broadcast_rx.recv() => match message {
+                Ok(Broadcast::Line(line)) => Some(line),
+                Ok(Broadcast::Disconnect) | Err(broadcast::error::RecvError::Closed) => break,
+                Err(broadcast::error::RecvError::Lagged(_)) => None,
+            },
+        };
+
+        let Some(reply) = reply else {
+            continue;
+        };
+        if writer
+            .write_all(format!("{}\n", reply).as_bytes())
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+
+    state.lock().unwrap().connections -= 1;
+    debug!(target: LOG_TARGET,"🧪 Mock pool connection closed");
+}
+
+/// Answer one request line from a miner; None when no reply is due
+fn handle_request(state: &Mutex<MockPoolState>, line: &str) -> Option<String> {
+    let Ok(request) = serde_json::from_str::<Value>(line) else {
+        debug!(target: LOG_TARGET,"🧪 Mock pool ignoring malformed line: {}", line);
+        return None;
+    };
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+    let mut state = state.lock().unwrap();
+
+    let reply = match request.get("method").and_then(|m| m.as_str()) {
+        Some("login") => {
+            let field = |name: &str| {
+                params
+                    .get(name)
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            };
+            state.logins.push(MockLogin {
+                login: field("login").unwrap_or_default(),
+                pass: field("pass").unwrap_or_default(),
+                rigid: field("rigid"),
+                agent: field("agent").unwrap_or_default(),
+            });
+
+            match &state.reject_logins {
+                Some(reason) => error_reply(&id, reason),
+                None => {
+                    let mut result = json!({"id": state.session_id, "status": "OK"});
+                    if let Some(job) = &state.current_job {
+                        result["job"] = job.params(state.xn.as_deref());
+                    }
+                    json!({"id": id, "jsonrpc": "2.0", "error": null, "result": result})
+                }
+            }
+        }
+        Some("submit") => {
+            let field = |name: &str| {
+                params
+                    .get(name)
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            let mut share = MockShare {
+                request_id: id.clone(),
+                login: field("id"),
+                job_id: field("job_id"),
+                nonce: field("nonce"),
+                result: field("result"),
+                verdict: MockVerdict::Accepted,
+            };
+            share.verdict = if state.reject_next > 0 {
+                state.reject_next -= 1;
+                MockVerdict::Rejected("Rejected by mock pool script".to_string())
+            } else {
+                verify_share(&mut state, &share)
+            };
+
+            let reply = match &share.verdict {
+                MockVerdict::Accepted => {
+                    json!({"id": id, "jsonrpc": "2.0", "error": null, "result": {"status": "OK"}})
+                }
+                MockVerdict::Rejected(reason) => error_reply(&id, reason),
+            };
+            state.shares.push(share);
+            reply
+        }
+        Some("keepalived") => {
+            json!({"id": id, "jsonrpc": "2.0", "error": null, "result": {"status": "KEEPALIVED"}})
+        }
+        _ => error_reply(&id, "Unknown method"),
+    };
+
+    Some(reply.to_string())
+}
+
+/// Recompute the SHA3x hash of a share and check it against its job
+fn verify_share(state: &mut MockPoolState, share: &MockShare) -> MockVerdict {
+    let reject = |reason: &str| MockVerdict::Rejected(reason.to_string());
+
+    let Some(job) = state.jobs.get(&share.job_id) else {
+        return reject("Job not found");
+    };
+    let nonce: [u8; 8] = match hex::decode(&share.nonce)
+        .ok()
+        .and_then(|n| n.try_into().ok())
+    {
+        Some(nonce) => nonce,
+        None => return reject("Invalid nonce"),
+    };
+    let xn = state.xn.as_deref().and_then(|xn| hex::decode(xn).ok());
+    if xn.is_some_and(|xn| !nonce.starts_with(&xn)) {
+        return reject("Invalid nonce: missing extra nonce prefix");
+    }
+
+    let hash = sha3x_hash_with_nonce(&job.blob, nonce);
+    if !share.result.is_empty() && !share.result.eq_ignore_ascii_case(&hex::encode(&hash)) {
+        return reject("Invalid result hash");
+    }
+    if calculate_difficulty(&hash, Algorithm::Sha3x) < job.difficulty {
+        return reject("Low difficulty share");
+    }
+    if !state
+        .seen_nonces
+        .insert((share.job_id.clone(), share.nonce.to_ascii_lowercase()))
+    {
+        return reject("Duplicate share");
+    }
+    MockVerdict::Accepted
+}
+
+fn job_notification(job: &MockJob, xn: Option<&str>) -> String {
+    json!({"jsonrpc": "2.0", "method": "job", "params": job.params(xn)}).to_string()
+}
+
+fn error_reply(id: &Value, message: &str) -> Value {
+    json!({
+        "id": id,
+        "jsonrpc": "2.0",
+        "error": {"code": -1, "message": message},
+        "result": null
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// First nonce starting with `prefix` whose hash meets the job difficulty
+    fn find_nonce(job: &MockJob, prefix: &[u8]) -> [u8; 8] {
+        (0u64..)
+            .map(|local| {
+                let mut nonce = [0u8; 8];
+                nonce[..prefix.len()].copy_from_slice(prefix);
+                nonce[prefix.len()..].copy_from_slice(&local.to_le_bytes()[..8 - prefix.len()]);
+                nonce
+            })
+            .find(|nonce| {
+                calculate_difficulty(&sha3x_hash_with_nonce(&job.blob, *nonce), Algorithm::Sha3x)
+                    >= job.difficulty
+            })
+            .unwrap()
+    }
+
+    fn share(job: &MockJob, nonce: [u8; 8]) -> MockShare {
+        MockShare {
+            request_id: json!(100),
+            login: "wallet".to_string(),
+            job_id: job.job_id.clone(),
+            nonce: hex::encode(nonce),
+            result: hex::encode(sha3x_hash_with_nonce(&job.blob, nonce)),
+            verdict: MockVerdict::Accepted,
+        }
+    }
+
+    #[test]
+    fn test_verify_share() {
+        let job = MockJob::new("job1", 10, 64);
+        let mut state = MockPoolState::default();
+        state.jobs.insert(job.job_id.clone(), job.clone());
+
+        let nonce = find_nonce(&job, &[]);
+        assert_eq!(
+            verify_share(&mut state, &share(&job, nonce)),
+            MockVerdict::Accepted
+        );
+        assert_eq!(
+            verify_share(&mut state, &share(&job, nonce)),
+            MockVerdict::Rejected("Duplicate share".to_string())
+        );
+
+        let mut wrong_result = share(&job, nonce);
+        wrong_result.result = hex::encode([0u8; 32]);
+        assert_eq!(
+            verify_share(&mut state, &wrong_result),
+            MockVerdict::Rejected("Invalid result hash".to_string())
+        );
+
+        let mut unknown = share(&job, nonce);
+        unknown.job_id = "gone".to_string();
+        assert_eq!(
+            verify_share(&mut state, &unknown),
+            MockVerdict::Rejected("Job not found".to_string())
+        );
+    }
+
+    #[test]
+    fn test_verify_share_xn_prefix() {
+        let job = MockJob::new("job2", 10, 16);
+        let mut state = MockPoolState {
+            xn: Some("ad49".to_string()),
+            ..Default::default()
+        };
+        state.jobs.insert(job.job_id.clone(), job.clone());
+
+        let without_prefix = find_nonce(&job, &[0x00, 0x00]);
+        assert_ne!(
+            verify_share(&mut state, &share(&job, without_prefix)),
+            MockVerdict::Accepted
+        );
+
+        let with_prefix = find_nonce(&job, &[0xad, 0x49]);
+        assert_eq!(
+            verify_share(&mut state, &share(&job, with_prefix)),
+            MockVerdict::Accepted
+        );
+    }
+
+    #[test]
+    fn test_login_response_carries_job_and_xn() {
+        let state = Mutex::new(MockPoolState {
+            session_id: "abc".to_string(),
+            xn: Some("ad49".to_string()),
+            current_job: Some(MockJob::new("job3", 7, 1000)),
+            ..Default::default()
+        });
+        let login = r#"{"id":1,"jsonrpc":"2.0","method":"login","params":{"login":"wallet","pass":"rig","agent":"test","algo":["sha3x"]}}"#;
+
+        let reply: Value = serde_json::from_str(&handle_request(&state, login).unwrap()).unwrap();
+        assert_eq!(reply["id"], 1);
+        assert_eq!(reply["result"]["id"], "abc");
+        assert_eq!(reply["result"]["job"]["job_id"], "job3");
+        assert_eq!(reply["result"]["job"]["xn"], "ad49");
+        assert_eq!(reply["result"]["job"]["difficulty"], 1000);
+        assert_eq!(state.lock().unwrap().logins[0].pass, "rig");
+
+        assert!(handle_request(&state, "not json").is_none());
+    }
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial mock Tari Stratum pool for tests.
+//   - login / job / submit / keepalived with LuckyPool xn support.
+//   - Shares verified by recomputing sha3x_hash_with_nonce against the job.
+//   - Scripted jobs, vardiff, disconnects, raw lines, login and share rejections.
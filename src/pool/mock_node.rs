@@ -0,0 +1,410 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/pool/mock_node.rs
+// Version: 1.0.0
+//
+// This file implements a stand-in block template server for solo mining tests.
+// It listens on localhost and answers the JSON-RPC calls made by
+// pool/template_rpc (get_tip_info, get_block_template, submit_block). Every
+// submitted block is verified by recomputing the SHA3x hash against the
+// template's network difficulty; an accepted block advances the chain so the
+// miner sees a new tip. Built for unit tests and with the `mock-pool` feature.
+//
+// Tree Location:
+// - src/pool/mock_node.rs (mock template server for tests)
+// - Depends on: core/sha3x, core/difficulty, tokio, serde_json
+
+use crate::Result;
+use crate::core::{Algorithm, calculate_difficulty, sha3x::sha3x_hash_with_nonce};
+use log::{debug, info};
+use serde_json::{Value, json};
+use sha3::{Digest, Sha3_256};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+const LOG_TARGET: &str = "tari::graxil::pool::mock_node";
+
+/// How often the wait_for_* helpers re-check the node state
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+/// Largest request the mock node reads
+const MAX_REQUEST_BYTES: usize = 64 * 1024;
+/// Reward reported with every template
+const MOCK_REWARD: u64 = 1_000_000;
+
+/// Block template served by the mock node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockTemplate {
+    pub template_id: String,
+    pub height: u64,
+    /// 32-byte SHA3x mining hash
+    pub mining_hash: Vec<u8>,
+    pub difficulty: u64,
+}
+
+impl MockTemplate {
+    /// Template with a mining hash derived from the template id
+    pub fn new(template_id: &str, height: u64, difficulty: u64) -> Self {
+        Self {
+            template_id: template_id.to_string(),
+            height,
+            mining_hash: Sha3_256::digest(template_id.as_bytes()).to_vec(),
+            difficulty,
+        }
+    }
+
+    fn result(&self) -> Value {
+        json!({
+            "template_id": self.template_id,
+            "height": self.height,
+            "mining_hash": hex::encode(&self.mining_hash),
+            "difficulty": self.difficulty,
+            "reward": MOCK_REWARD,
+        })
+    }
+}
+
+/// Block submitted to the mock node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockBlock {
+    pub template_id: String,
+    pub height: u64,
+    pub nonce: u64,
+    pub accepted: bool,
+    /// Rejection reason
+    pub reason: Option<String>,
+}
+
+#[derive(Default)]
+struct MockNodeState {
+    current: Option<MockTemplate>,
+    templates: HashMap<String, MockTemplate>,
+    wallets: Vec<String>,
+    blocks: Vec<MockBlock>,
+    next_template: u64,
+}
+
+impl MockNodeState {
+    fn install(&mut self, height: u64, difficulty: u64) -> MockTemplate {
+        self.next_template += 1;
+        let template = MockTemplate::new(
+            &format!("tmpl{:04}", self.next_template),
+            height,
+            difficulty,
+        );
+        self.templates
+            .insert(template.template_id.clone(), template.clone());
+        self.current = Some(template.clone());
+        template
+    }
+}
+
+/// Stand-in JSON-RPC block template server listening on localhost
+pub struct MockNode {
+    address: SocketAddr,
+    state: Arc<Mutex<MockNodeState>>,
+    accept_task: JoinHandle<()>,
+}
+
+impl MockNode {
+    /// Start a mock node on an ephemeral localhost port
+    pub async fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(MockNodeState::default()));
+
+        let accept_task = {
+            let state = Arc::clone(&state);
+            tokio::spawn(async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    tokio::spawn(serve_connection(stream, Arc::clone(&state)));
+                }
+            })
+        };
+
+        info!(target: LOG_TARGET,"🧪 Mock node listening on {}", address);
+        Ok(Self {
+            address,
+            state,
+            accept_task,
+        })
+    }
+
+    /// Node URL to hand to a solo session (`http://127.0.0.1:PORT`)
+    pub fn address(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// Serve a new template for `height`; the tip becomes `height - 1`
+    pub fn set_template(&self, height: u64, difficulty: u64) -> MockTemplate {
+        self.state.lock().unwrap().install(height, difficulty)
+    }
+
+    /// Template currently served
+    pub fn current_template(&self) -> Option<MockTemplate> {
+        self.state.lock().unwrap().current.clone()
+    }
+
+    /// Wallet addresses templates were requested for
+    pub fn wallets(&self) -> Vec<String> {
+        self.state.lock().unwrap().wallets.clone()
+    }
+
+    /// Every block submitted so far, with the node verdict
+    pub fn blocks(&self) -> Vec<MockBlock> {
+        self.state.lock().unwrap().blocks.clone()
+    }
+
+    /// Wait until at least `count` accepted blocks were submitted
+    pub async fn wait_for_blocks(&self, count: usize, timeout: Duration) -> Result<Vec<MockBlock>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let accepted = self.blocks().into_iter().filter(|b| b.accepted).count();
+            if accepted >= count {
+                return Ok(self.blocks());
+            }
+            if Instant::now() >= deadline {
+                return Err(format!("Mock node saw {} of {} blocks", accepted, count).into());
+            }
+            tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+        }
+    }
+}
+
+impl Drop for MockNode {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+    }
+}
+
+/// Read one HTTP request, answer it and close the connection
+async fn serve_connection(mut stream: TcpStream, state: Arc<Mutex<MockNodeState>>) {
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 4096];
+    let body = loop {
+        match stream.read(&mut buffer).await {
+            Ok(0) | Err(_) => return,
+            Ok(read) => request.extend_from_slice(&buffer[..read]),
+        }
+        if let Some(body) = complete_body(&request) {
+            break body;
+        }
+        if request.len() > MAX_REQUEST_BYTES {
+            return;
+        }
+    };
+
+    let reply = handle_request(&state, &body);
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        reply.len(),
+        reply
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+/// Request body once the headers and Content-Length bytes have arrived
+fn complete_body(request: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(request);
+    let (head, body) = text.split_once("\r\n\r\n")?;
+    let length = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    (body.len() >= length).then(|| body[..length].to_string())
+}
+
+/// Answer one JSON-RPC request body
+fn handle_request(state: &Mutex<MockNodeState>, body: &str) -> String {
+    let Ok(request) = serde_json::from_str::<Value>(body) else {
+        debug!(target: LOG_TARGET,"🧪 Mock node got malformed body: {}", body);
+        return error_reply(&Value::Null, "Parse error").to_string();
+    };
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+    let mut state = state.lock().unwrap();
+
+    let reply = match request.get("method").and_then(|m| m.as_str()) {
+        Some("get_tip_info") => match &state.current {
+            Some(template) => ok_reply(&id, json!({"height": template.height.saturating_sub(1)})),
+            None => error_reply(&id, "No template"),
+        },
+        Some("get_block_template") => {
+            let wallet = params
+                .get("wallet_address")
+                .and_then(|w| w.as_str())
+                .unwrap_or_default()
+                .to_string();
+            state.wallets.push(wallet);
+            match &state.current {
+                Some(template) => ok_reply(&id, template.result()),
+                None => error_reply(&id, "No template"),
+            }
+        }
+        Some("submit_block") => {
+            let template_id = params
+                .get("template_id")
+                .and_then(|t| t.as_str())
+                .unwrap_or_default();
+            let nonce = params.get("nonce").and_then(|n| n.as_u64());
+            match (state.templates.get(template_id).cloned(), nonce) {
+                (Some(template), Some(nonce)) => submit_block(&mut state, &id, template, nonce),
+                (None, _) => error_reply(&id, "Unknown template"),
+                (_, None) => error_reply(&id, "Invalid nonce"),
+            }
+        }
+        _ => error_reply(&id, "Unknown method"),
+    };
+
+    reply.to_string()
+}
+
+/// Verify a block and advance the chain when it is good
+fn submit_block(
+    state: &mut MockNodeState,
+    id: &Value,
+    template: MockTemplate,
+    nonce: u64,
+) -> Value {
+    let hash = sha3x_hash_with_nonce(&template.mining_hash, nonce.to_le_bytes());
+    let achieved = calculate_difficulty(&hash, Algorithm::Sha3x);
+    let tip_height = state.current.as_ref().map(|t| t.height).unwrap_or(0);
+
+    let reason = if template.height != tip_height {
+        Some("Block is orphaned")
+    } else if achieved < template.difficulty {
+        Some("Difficulty too low")
+    } else {
+        None
+    };
+    state.blocks.push(MockBlock {
+        template_id: template.template_id.clone(),
+        height: template.height,
+        nonce,
+        accepted: reason.is_none(),
+        reason: reason.map(str::to_string),
+    });
+
+    match reason {
+        Some(reason) => error_reply(id, reason),
+        None => {
+            state.install(template.height + 1, template.difficulty);
+            ok_reply(id, json!({"block_hash": hex::encode(hash)}))
+        }
+    }
+}
+
+fn ok_reply(id: &Value, result: Value) -> Value {
+    json!({"id": id, "jsonrpc": "2.0", "error": null, "result": result})
+}
+
+fn error_reply(id: &Value, message: &str) -> Value {
+    json!({
+        "id": id,
+        "jsonrpc": "2.0",
+        "error": {"code": -1, "message": message},
+        "result": null
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find_nonce(template: &MockTemplate) -> u64 {
+        (0u64..)
+            .find(|nonce| {
+                calculate_difficulty(
+                    &sha3x_hash_with_nonce(&template.mining_hash, nonce.to_le_bytes()),
+                    Algorithm::Sha3x,
+                ) >= template.difficulty
+            })
+            .unwrap()
+    }
+
+    fn call(state: &Mutex<MockNodeState>, method: &str, params: Value) -> Value {
+        let body = json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params});
+        serde_json::from_str(&handle_request(state, &body.to_string())).unwrap()
+    }
+
+    #[test]
+    fn test_submit_block_advances_chain() {
+        let state = Mutex::new(MockNodeState::default());
+        let template = state.lock().unwrap().install(10, 32);
+        assert_eq!(
+            call(&state, "get_tip_info", json!({}))["result"]["height"],
+            9
+        );
+
+        let reply = call(
+            &state,
+            "get_block_template",
+            json!({"wallet_address": "12Fr"}),
+        );
+        assert_eq!(reply["result"]["template_id"], template.template_id);
+        assert_eq!(state.lock().unwrap().wallets, vec!["12Fr".to_string()]);
+
+        let nonce = find_nonce(&template);
+        let reply = call(
+            &state,
+            "submit_block",
+            json!({"template_id": template.template_id, "nonce": nonce}),
+        );
+        assert!(reply["error"].is_null());
+        assert_eq!(
+            call(&state, "get_tip_info", json!({}))["result"]["height"],
+            10
+        );
+
+        // The old template is now orphaned
+        let reply = call(
+            &state,
+            "submit_block",
+            json!({"template_id": template.template_id, "nonce": nonce}),
+        );
+        assert_eq!(reply["error"]["message"], "Block is orphaned");
+        assert_eq!(state.lock().unwrap().blocks.len(), 2);
+    }
+
+    #[test]
+    fn test_submit_block_low_difficulty() {
+        let state = Mutex::new(MockNodeState::default());
+        let template = state.lock().unwrap().install(5, u64::MAX);
+        let reply = call(
+            &state,
+            "submit_block",
+            json!({"template_id": template.template_id, "nonce": 1}),
+        );
+        assert_eq!(reply["error"]["message"], "Difficulty too low");
+        assert!(!state.lock().unwrap().blocks[0].accepted);
+    }
+
+    #[test]
+    fn test_complete_body() {
+        assert_eq!(
+            complete_body(b"POST / HTTP/1.1\r\nContent-Length: 4\r\n\r\nab"),
+            None
+        );
+        assert_eq!(
+            complete_body(b"POST / HTTP/1.1\r\nContent-Length: 4\r\n\r\nabcd").as_deref(),
+            Some("abcd")
+        );
+    }
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial mock block template server for solo mining tests.
+//   - get_tip_info / get_block_template / submit_block over HTTP JSON-RPC.
+//   - Blocks verified by recomputing sha3x_hash_with_nonce; accepted blocks advance the chain.
@@ -6,7 +6,7 @@
 // via pull requests to the project repository.
 //
 // File: src/pool/mod.rs
-// Version: 1.0.0
+// Version: 1.6.0
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // This file is the module declaration for the pool communication functionality
@@ -15,16 +15,48 @@
 //
 // Tree Location:
 // - src/pool/mod.rs (pool module entry point)
-// - Submodules: client, messages, protocol
+// - Submodules: base_node, client, failover, messages, mock and mock_node
+//   (tests / mock-pool feature), protocol, proxy, session, solo, stratum_proxy,
+//   stratum_v1, sv2_client, sv2_noise, sv2_protocol, template_rpc, transport
 
+pub mod base_node;
 pub mod client;
+pub mod failover;
 pub mod messages;
+#[cfg(any(test, feature = "mock-pool"))]
+pub mod mock;
+#[cfg(any(test, feature = "mock-pool"))]
+pub mod mock_node;
 pub mod protocol;
+pub mod proxy;
+pub mod session;
+pub mod solo;
+pub mod stratum_proxy;
+pub mod stratum_v1;
+pub mod sv2_client;
+pub mod sv2_noise;
+pub mod sv2_protocol;
+pub mod template_rpc;
+pub mod transport;
 
 // Re-export key types for convenience
 pub use client::PoolClient;
+pub use failover::{FailoverSettings, PoolEndpoint};
+pub use proxy::{ProxyConfig, ProxyKind};
+pub use session::{PoolSession, PoolSessionConfig, ShareOutcome, ShareSubmission};
+pub use solo::{BlockTemplate, SoloClient};
+pub use stratum_proxy::StratumProxy;
+pub use sv2_client::Sv2Client;
+pub use transport::{PoolScheme, PoolUrl, TlsOptions};
 
 // Changelog:
+// - v1.6.0 (2025-06-28): Added solo mining submodules (solo, base_node, template_rpc,
+//   mock_node).
+// - v1.5.0 (2025-06-28): Added stratum_proxy submodule (--proxy-listen downstream server).
+// - v1.4.0 (2025-06-28): Added mock submodule (mock Stratum pool, tests and mock-pool feature).
+// - v1.3.0 (2025-06-28): Added stratum_v1 submodule for SHA-256 Stratum V1 pools.
+// - v1.2.0 (2025-06-28): Added Stratum V2 submodules (sv2_client, sv2_noise, sv2_protocol).
+// - v1.1.0 (2025-06-28): Added session, failover, proxy and transport submodules.
 // - v1.0.0 (2025-06-14): Extracted from monolithic main.rs.
 //   - Purpose: Defines the pool module, organizing communication logic with
 //     mining pools into client, messages, and protocol submodules.
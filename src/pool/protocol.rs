@@ -6,7 +6,7 @@
 // via pull requests to the project repository.
 //
 // File: src/pool/protocol.rs
-// Version: 1.0.2
+// Version: 1.1.0
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // This file implements the Stratum protocol for communication with the mining
@@ -30,12 +30,24 @@ pub struct StratumProtocol;
 impl StratumProtocol {
     /// Create a login request message
     pub fn create_login_request(wallet_address: &str, worker_name: &str, algo: Algorithm) -> Value {
+        Self::create_login_request_with_password(wallet_address, worker_name, "x", algo)
+    }
+
+    /// Create a login request message with an explicit pool password.
+    /// With the default password ("x") the worker name is sent as the password,
+    /// otherwise the password is sent and the worker goes in "rigid".
+    pub fn create_login_request_with_password(
+        wallet_address: &str,
+        worker_name: &str,
+        password: &str,
+        algo: Algorithm,
+    ) -> Value {
         if wallet_address.is_empty() {
             error!(target: LOG_TARGET,"Invalid wallet address: empty");
             return json!({});
         }
         match algo {
-            Algorithm::Sha3x => json!({
+            Algorithm::Sha3x if password.is_empty() || password == "x" => json!({
                 "id": 1,
                 "jsonrpc": "2.0",
                 "method": "login",
@@ -46,6 +58,18 @@ impl StratumProtocol {
                     "algo": ["sha3x"]
                 }
             }),
+            Algorithm::Sha3x => json!({
+                "id": 1,
+                "jsonrpc": "2.0",
+                "method": "login",
+                "params": {
+                    "login": wallet_address,
+                    "pass": password,
+                    "rigid": worker_name,
+                    "agent": user_agent(),
+                    "algo": ["sha3x"]
+                }
+            }),
             Algorithm::Sha256 => json!({
                 "id": 1,
                 "method": "mining.subscribe",
@@ -55,15 +79,15 @@ impl StratumProtocol {
     }
 
     /// Create an authorization request for SHA-256 (Stratum V1)
-    pub fn create_authorize_request(wallet_address: &str) -> Value {
-        if wallet_address.is_empty() {
-            error!(target: LOG_TARGET,"Invalid wallet address for authorize: empty");
+    pub fn create_authorize_request(user: &str, password: &str) -> Value {
+        if user.is_empty() {
+            error!(target: LOG_TARGET,"Invalid user for authorize: empty");
             return json!({});
         }
         json!({
             "id": 2,
             "method": "mining.authorize",
-            "params": [wallet_address, ""]
+            "params": [user, password]
         })
     }
 
@@ -111,7 +135,7 @@ impl StratumProtocol {
                     "id": submit_id,
                     "method": "mining.submit",
                     "params": [
-                        "",
+                        wallet_address,
                         job_id,
                         extranonce2,
                         ntime_hex,
@@ -134,6 +158,9 @@ impl StratumProtocol {
 }
 
 // Changelog:
+// - v1.1.0 (2025-06-28): Working Stratum V1 requests for SHA-256 mining.
+//   - create_authorize_request takes the worker user and pool password.
+//   - mining.submit sends the authorized user instead of an empty string.
 // - v1.0.2 (2025-06-23): Fixed algo field format for pool compatibility.
 //   - Changed "algo": "sha3x" to "algo": ["sha3x"] (array format) in login request.
 //   - This fixes compatibility with pools that expect algo as a list of strings.
@@ -0,0 +1,1320 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/pool/session.rs
+// Version: 1.6.2
+//
+// This file implements the pool session shared by every mining backend. A
+// session owns the pool connection, performs the Stratum login, turns pool
+// jobs into MiningJobs and submits shares, matching every pool response back
+// to the share that produced it. Backends only see the job and share channels.
+// Pool lists made of sv2:// URLs are handed to the Stratum V2 client, and
+// solo node lists (grpc:// or http://) to the solo mining client. A session
+// can follow a MinerControl to switch to, and stay on, a chosen pool.
+//
+// Tree Location:
+// - src/pool/session.rs (pool session shared by CPU and GPU miners)
+// - Depends on: pool/client, pool/protocol, core/types, miner/stats
+
+use crate::Result;
+use crate::core::{Algorithm, MiningJob, PoolJob, ShareStatus, parse_target_difficulty};
+use crate::miner::control::RuntimeSettings;
+use crate::miner::stats::MinerStats;
+use crate::pool::failover::{FailoverSettings, PoolEndpoint, PoolFailover};
+use crate::pool::solo::SoloClient;
+use crate::pool::stratum_v1::{self, StratumV1State};
+use crate::pool::sv2_client::Sv2Client;
+use crate::pool::transport::{PoolStream, PoolUrl};
+use crate::pool::{PoolClient, protocol::StratumProtocol};
+use log::{debug, error, info, warn};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines, ReadHalf, WriteHalf};
+use tokio::sync::{Mutex, Notify, broadcast, mpsc, watch};
+
+const LOG_TARGET: &str = "tari::graxil::pool::session";
+
+/// JSON-RPC id used by the login request (see StratumProtocol::create_login_request)
+const LOGIN_REQUEST_ID: u64 = 1;
+/// JSON-RPC id used by the Stratum V1 mining.authorize request
+const AUTHORIZE_REQUEST_ID: u64 = 2;
+/// First JSON-RPC id handed out to share submissions, clear of login/authorize ids
+const FIRST_SUBMIT_ID: u64 = 100;
+/// Delay between a lost connection and the next connection attempt
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+/// How often the session checks whether a failback probe is due
+const FAILBACK_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// Connect timeout for failback probes
+const FAILBACK_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Submitted shares without a pool response after this long are dropped
+const PENDING_SHARE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Line reader over the pool connection (plain TCP or TLS)
+type PoolReader = Lines<BufReader<ReadHalf<PoolStream>>>;
+/// Write half of the pool connection
+type PoolWriter = WriteHalf<PoolStream>;
+
+/// Share found by a mining backend:
+/// (job_id, nonce_hex, result_hex, thread_id, difficulty, extranonce2, ntime)
+pub type ShareSubmission = (String, String, String, usize, u64, String, u32);
+
+/// Connection parameters for a pool session
+#[derive(Debug, Clone)]
+pub struct PoolSessionConfig {
+    /// Failover pool list, tried in priority order
+    pub pools: Vec<PoolEndpoint>,
+    pub failover: FailoverSettings,
+    pub algo: Algorithm,
+    /// Short backend label used in logs and dashboard activity ("CPU", "GPU")
+    pub label: String,
+}
+
+impl PoolSessionConfig {
+    /// Session for a single pool with default retry settings
+    pub fn single(
+        pool_address: String,
+        wallet_address: String,
+        worker_name: String,
+        algo: Algorithm,
+        label: &str,
+    ) -> Self {
+        Self {
+            pools: vec![PoolEndpoint::new(
+                pool_address,
+                wallet_address,
+                worker_name,
+                "x".to_string(),
+            )],
+            failover: FailoverSettings::default(),
+            algo,
+            label: label.to_string(),
+        }
+    }
+
+    /// True when the pool list uses Stratum V2 (sv2:// URLs)
+    pub fn is_sv2(&self) -> bool {
+        self.pools
+            .first()
+            .and_then(|pool| PoolUrl::parse(&pool.address).ok())
+            .is_some_and(|url| url.is_sv2())
+    }
+
+    /// True when the list names solo mining nodes (grpc:// or http:// URLs)
+    pub fn is_solo(&self) -> bool {
+        self.pools
+            .first()
+            .and_then(|pool| PoolUrl::parse(&pool.address).ok())
+            .is_some_and(|url| url.is_solo())
+    }
+}
+
+/// Pool verdict for a submitted share
+#[derive(Debug, Clone)]
+pub struct ShareOutcome {
+    pub submit_id: u64,
+    pub job_id: String,
+    pub nonce: String,
+    pub thread_id: usize,
+    pub difficulty: u64,
+    pub status: ShareStatus,
+    /// Time between writing the submit request and receiving the response
+    pub latency: Duration,
+    pub error: Option<String>,
+}
+
+impl ShareOutcome {
+    pub fn is_accepted(&self) -> bool {
+        self.status == ShareStatus::Accepted
+    }
+}
+
+/// Share waiting for a pool response, keyed by its JSON-RPC id
+#[derive(Debug, Clone)]
+struct PendingSubmit {
+    job_id: String,
+    nonce: String,
+    thread_id: usize,
+    difficulty: u64,
+    sent_at: Instant,
+    /// The pool had already sent a newer job when this share was submitted
+    superseded: bool,
+}
+
+/// A logged-in Stratum session that any mining backend can plug into
+pub struct PoolSession {
+    config: PoolSessionConfig,
+    pool_client: Arc<PoolClient>,
+    stats: Arc<MinerStats>,
+    job_tx: broadcast::Sender<MiningJob>,
+    outcome_tx: broadcast::Sender<ShareOutcome>,
+    share_tx: mpsc::UnboundedSender<ShareSubmission>,
+    share_rx: std::sync::Mutex<Option<mpsc::UnboundedReceiver<ShareSubmission>>>,
+    failover: std::sync::Mutex<PoolFailover>,
+    active_pool: std::sync::Mutex<PoolEndpoint>,
+    session_id: std::sync::Mutex<Option<String>>,
+    extranonce: std::sync::Mutex<Option<String>>,
+    current_job_id: std::sync::Mutex<Option<String>>,
+    next_submit_id: AtomicU64,
+    pending: std::sync::Mutex<HashMap<u64, PendingSubmit>>,
+    last_job_time: std::sync::Mutex<Instant>,
+    /// Extranonce, difficulty and last notify of a SHA-256 (Stratum V1) pool
+    stratum_v1: std::sync::Mutex<StratumV1State>,
+    /// Wakes the run loop to reconnect after a pool was selected by hand
+    pool_switch: Notify,
+    /// Logged in at least once, so every later login is a reconnect
+    connected_once: AtomicBool,
+}
+
+impl PoolSession {
+    /// Create a new session; nothing is sent until `run` is called
+    pub fn new(
+        config: PoolSessionConfig,
+        pool_client: Arc<PoolClient>,
+        stats: Arc<MinerStats>,
+    ) -> Result<Self> {
+        let failover = PoolFailover::new(config.pools.clone(), config.failover.clone())?;
+        let active_pool = failover.active().clone();
+        pool_client.set_active_pool(failover.active_index(), failover.pools().len());
+
+        let (job_tx, _) = broadcast::channel(16);
+        let (outcome_tx, _) = broadcast::channel(64);
+        let (share_tx, share_rx) = mpsc::unbounded_channel();
+
+        Ok(Self {
+            config,
+            pool_client,
+            stats,
+            job_tx,
+            outcome_tx,
+            share_tx,
+            share_rx: std::sync::Mutex::new(Some(share_rx)),
+            failover: std::sync::Mutex::new(failover),
+            active_pool: std::sync::Mutex::new(active_pool),
+            session_id: std::sync::Mutex::new(None),
+            extranonce: std::sync::Mutex::new(None),
+            current_job_id: std::sync::Mutex::new(None),
+            next_submit_id: AtomicU64::new(FIRST_SUBMIT_ID),
+            pending: std::sync::Mutex::new(HashMap::new()),
+            last_job_time: std::sync::Mutex::new(Instant::now()),
+            stratum_v1: std::sync::Mutex::new(StratumV1State::default()),
+            pool_switch: Notify::new(),
+            connected_once: AtomicBool::new(false),
+        })
+    }
+
+    /// Receive every job delivered by the pool
+    pub fn subscribe_jobs(&self) -> broadcast::Receiver<MiningJob> {
+        self.job_tx.subscribe()
+    }
+
+    /// Channel for backends to hand found shares to the session
+    pub fn share_sender(&self) -> mpsc::UnboundedSender<ShareSubmission> {
+        self.share_tx.clone()
+    }
+
+    /// Receive the pool verdict for every submitted share
+    pub fn subscribe_share_results(&self) -> broadcast::Receiver<ShareOutcome> {
+        self.outcome_tx.subscribe()
+    }
+
+    /// Session id assigned by the pool at login, if any
+    pub fn session_id(&self) -> Option<String> {
+        self.session_id.lock().unwrap().clone()
+    }
+
+    /// Time elapsed since the pool last sent a job
+    pub fn time_since_last_job(&self) -> Duration {
+        self.last_job_time.lock().unwrap().elapsed()
+    }
+
+    /// Pool currently in use
+    pub fn active_pool(&self) -> PoolEndpoint {
+        self.active_pool.lock().unwrap().clone()
+    }
+
+    /// Number of submitted shares still waiting for a pool response
+    pub fn pending_share_count(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
+
+    pub fn config(&self) -> &PoolSessionConfig {
+        &self.config
+    }
+
+    /// Follow the pool selection of a MinerControl
+    pub fn follow_control(self: &Arc<Self>, mut settings: watch::Receiver<RuntimeSettings>) {
+        let session = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut pool = settings.borrow_and_update().pool;
+            if pool.is_some() {
+                session.select_pool(pool);
+            }
+            while settings.changed().await.is_ok() {
+                let wanted = settings.borrow_and_update().pool;
+                if wanted != pool {
+                    pool = wanted;
+                    session.select_pool(pool);
+                }
+            }
+        });
+    }
+
+    /// Switch to a pool of the failover list and stay there without failback
+    /// probes, or (None) return to the priority order. Stratum pools only.
+    pub fn select_pool(&self, index: Option<usize>) {
+        if self.config.is_sv2() || self.config.is_solo() {
+            warn!(target: LOG_TARGET,
+                "⚠️ {} pool selection is only supported for Stratum pools",
+                self.config.label
+            );
+            return;
+        }
+
+        let (switched, address) = {
+            let mut failover = self.failover.lock().unwrap();
+            let previous = failover.active_index();
+            if let Err(e) = failover.pin(index) {
+                warn!(target: LOG_TARGET,"⚠️ {} pool selection ignored: {}", self.config.label, e);
+                return;
+            }
+            (
+                failover.active_index() != previous,
+                failover.active().address.clone(),
+            )
+        };
+
+        match index {
+            Some(_) => {
+                info!(target: LOG_TARGET,"📌 {} pinned to pool {}", self.config.label, address)
+            }
+            None => info!(target: LOG_TARGET,
+                "📌 {} follows the pool priority order again",
+                self.config.label
+            ),
+        }
+        if switched {
+            self.stats
+                .add_activity(format!("📌 {} switching to {}", self.config.label, address));
+            self.pool_switch.notify_one();
+        }
+    }
+
+    /// Connect, log in and service the pool list, failing over and back as needed
+    pub async fn run(self: Arc<Self>) -> Result<()> {
+        let share_rx = self
+            .share_rx
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or("Pool session is already running")?;
+
+        if self.config.is_solo() {
+            let client = SoloClient::new(
+                self.config.clone(),
+                Arc::clone(&self.pool_client),
+                Arc::clone(&self.stats),
+            );
+            return client
+                .run(self.job_tx.clone(), share_rx, self.outcome_tx.clone())
+                .await;
+        }
+
+        if self.config.is_sv2() {
+            let client = Sv2Client::new(
+                self.config.clone(),
+                Arc::clone(&self.pool_client),
+                Arc::clone(&self.stats),
+            );
+            return client
+                .run(self.job_tx.clone(), share_rx, self.outcome_tx.clone())
+                .await;
+        }
+
+        let (mut lines, writer) = self.establish().await;
+        let writer = Arc::new(Mutex::new(writer));
+
+        Self::start_share_submitter(Arc::clone(&self), Arc::clone(&writer), share_rx);
+        Self::start_connection_monitor(Arc::clone(&self));
+
+        let mut failback_timer = tokio::time::interval(FAILBACK_CHECK_INTERVAL);
+
+        loop {
+            let reconnect_delay = tokio::select! {
+                line = lines.next_line() => match line {
+                    Ok(Some(line)) => {
+                        if let Err(e) = self.handle_pool_message(&line).await {
+                            warn!(target: LOG_TARGET,
+                                "⚠️ {} failed to handle pool message: {} ({})",
+                                self.config.label, e, line
+                            );
+                        }
+                        continue;
+                    }
+                    Ok(None) => {
+                        info!(target: LOG_TARGET,
+                            "📡 {} connection closed, attempting reconnect...",
+                            self.config.label
+                        );
+                        RECONNECT_DELAY
+                    }
+                    Err(e) => {
+                        error!(target: LOG_TARGET,
+                            "📡 Error reading from {} pool: {}, attempting reconnect...",
+                            self.config.label, e
+                        );
+                        RECONNECT_DELAY
+                    }
+                },
+                _ = failback_timer.tick() => {
+                    if !self.try_failback().await {
+                        continue;
+                    }
+                    Duration::ZERO
+                }
+                _ = self.pool_switch.notified() => {
+                    info!(target: LOG_TARGET,
+                        "🔀 {} reconnecting to {}",
+                        self.config.label,
+                        self.failover.lock().unwrap().active().address
+                    );
+                    Duration::ZERO
+                }
+            };
+
+            self.pool_client.mark_disconnected();
+            self.drop_pending();
+            tokio::time::sleep(reconnect_delay).await;
+
+            let (new_lines, new_writer) = self.establish().await;
+            *writer.lock().await = new_writer;
+            lines = new_lines;
+            Self::start_connection_monitor(Arc::clone(&self));
+            info!(target: LOG_TARGET,"🔄 {} reconnected to pool", self.config.label);
+        }
+    }
+
+    /// Connect to the active pool, retrying with backoff and failing over to
+    /// the next pool when the retry budget is used up
+    async fn establish(&self) -> (PoolReader, PoolWriter) {
+        let mut failed_over = false;
+        loop {
+            let (endpoint, index, pool_count) = {
+                let failover = self.failover.lock().unwrap();
+                (
+                    failover.active().clone(),
+                    failover.active_index(),
+                    failover.pools().len(),
+                )
+            };
+
+            self.pool_client.set_active_pool(index, pool_count);
+            match self.connect_and_login(&endpoint).await {
+                Ok(connection) => {
+                    self.failover.lock().unwrap().record_success();
+                    *self.active_pool.lock().unwrap() = endpoint;
+                    if self.connected_once.swap(true, Ordering::Relaxed) || failed_over {
+                        self.pool_client.record_reconnect();
+                    }
+                    return connection;
+                }
+                Err(e) => {
+                    self.pool_client.mark_disconnected();
+                    let (decision, next) = {
+                        let mut failover = self.failover.lock().unwrap();
+                        let decision = failover.record_failure();
+                        (decision, failover.active().address.clone())
+                    };
+
+                    if decision.failed_over {
+                        failed_over = true;
+                        warn!(target: LOG_TARGET,
+                            "🔀 {} pool {} failed ({}), failing over to {}",
+                            self.config.label, endpoint.address, e, next
+                        );
+                        self.stats.add_activity(format!(
+                            "🔀 {} failing over to {}",
+                            self.config.label, next
+                        ));
+                    } else {
+                        warn!(target: LOG_TARGET,
+                            "⚠️ {} connection to {} failed: {} (retrying in {}s)",
+                            self.config.label,
+                            endpoint.address,
+                            e,
+                            decision.delay.as_secs()
+                        );
+                    }
+                    tokio::time::sleep(decision.delay).await;
+                }
+            }
+        }
+    }
+
+    /// Open the pool connection and send the login request
+    async fn connect_and_login(&self, endpoint: &PoolEndpoint) -> Result<(PoolReader, PoolWriter)> {
+        let stream = self
+            .pool_client
+            .connect_pool(&endpoint.address, &endpoint.tls)
+            .await?;
+        let link = if self.pool_client.is_encrypted() {
+            "🔒 TLS"
+        } else {
+            "plain TCP"
+        };
+        info!(target: LOG_TARGET,
+            "✅ {} connected to {} pool {} ({})",
+            self.config.label,
+            self.algo_name(),
+            endpoint.address,
+            link
+        );
+        self.stats.add_activity(format!(
+            "🔐 {} connected to {}",
+            self.config.label, endpoint.address
+        ));
+
+        // The session id and the XN belong to the previous pool; a pool that
+        // sends no XN must not inherit the old nonce prefix
+        *self.session_id.lock().unwrap() = None;
+        *self.extranonce.lock().unwrap() = None;
+        *self.stratum_v1.lock().unwrap() = StratumV1State::default();
+
+        let (reader, mut writer) = tokio::io::split(stream);
+        let login_msg =
+            StratumProtocol::to_message(StratumProtocol::create_login_request_with_password(
+                &endpoint.wallet,
+                &endpoint.worker,
+                &endpoint.password,
+                self.config.algo,
+            ));
+        writer.write_all(login_msg.as_bytes()).await?;
+
+        // Stratum V1 logs in with mining.subscribe followed by mining.authorize
+        if self.config.algo == Algorithm::Sha256 {
+            let authorize_msg =
+                StratumProtocol::to_message(StratumProtocol::create_authorize_request(
+                    &endpoint.user_identity(),
+                    &endpoint.password,
+                ));
+            writer.write_all(authorize_msg.as_bytes()).await?;
+        }
+        writer.flush().await?;
+        info!(target: LOG_TARGET,
+            "📤 Sent {} login request - worker: {}",
+            self.config.label, endpoint.worker
+        );
+
+        Ok((BufReader::new(reader).lines(), writer))
+    }
+
+    /// Probe higher-priority pools while on a backup; returns true when the
+    /// session should reconnect to a preferred pool
+    async fn try_failback(&self) -> bool {
+        let candidates = {
+            let mut failover = self.failover.lock().unwrap();
+            if !failover.should_probe() {
+                return false;
+            }
+            failover.mark_probed();
+            failover.preferred_pools().to_vec()
+        };
+
+        for (index, pool) in candidates.iter().enumerate() {
+            if self
+                .pool_client
+                .probe(&pool.address, FAILBACK_PROBE_TIMEOUT)
+                .await
+            {
+                info!(target: LOG_TARGET,
+                    "🔙 {} preferred pool {} is reachable again, switching back",
+                    self.config.label, pool.address
+                );
+                self.stats.add_activity(format!(
+                    "🔙 {} returning to {}",
+                    self.config.label, pool.address
+                ));
+                self.failover.lock().unwrap().switch_to(index);
+                return true;
+            }
+        }
+
+        debug!(target: LOG_TARGET,
+            "{} preferred pools still unreachable, staying on {}",
+            self.config.label,
+            self.active_pool().address
+        );
+        false
+    }
+
+    async fn handle_pool_message(&self, message: &str) -> Result<()> {
+        debug!(target: LOG_TARGET,"📨 {} pool message: {}", self.config.label, message);
+        let response: Value = serde_json::from_str(message)?;
+
+        if let Some(method) = response.get("method").and_then(|m| m.as_str()) {
+            match method {
+                "job" => {
+                    if let Some(params) = response.get("params").and_then(|p| p.as_object()) {
+                        self.handle_new_job(params)?;
+                        if let Some(diff) = params.get("difficulty").and_then(|d| d.as_u64()) {
+                            self.stats.add_activity(format!(
+                                "🔧 {} VarDiff update: {}",
+                                self.config.label,
+                                MinerStats::format_number(diff)
+                            ));
+                        }
+                    }
+                }
+                "mining.notify" => {
+                    self.handle_notify(response.get("params").unwrap_or(&Value::Null))?;
+                }
+                "mining.set_difficulty" => {
+                    let difficulty = response
+                        .get("params")
+                        .and_then(|p| p.get(0))
+                        .and_then(|d| d.as_f64())
+                        .ok_or("mining.set_difficulty without a difficulty")?;
+                    self.handle_set_difficulty(difficulty)?;
+                }
+                "mining.set_extranonce" => {
+                    let subscription = stratum_v1::parse_set_extranonce(
+                        response.get("params").unwrap_or(&Value::Null),
+                    )?;
+                    info!(target: LOG_TARGET,
+                        "{} extranonce1 changed to {}",
+                        self.config.label,
+                        hex::encode(&subscription.extranonce1)
+                    );
+                    self.stratum_v1.lock().unwrap().subscription = Some(subscription);
+                    self.republish_stratum_v1_job()?;
+                }
+                _ => {
+                    debug!(target: LOG_TARGET,"Unknown method: {}", method);
+                }
+            }
+            return Ok(());
+        }
+
+        let id = response.get("id").and_then(|id| id.as_u64());
+        if self.config.algo == Algorithm::Sha256 {
+            if id == Some(LOGIN_REQUEST_ID) {
+                return self.handle_subscribe_response(&response);
+            }
+            if id == Some(AUTHORIZE_REQUEST_ID) {
+                return self.handle_login_response(&response);
+            }
+        } else if id == Some(LOGIN_REQUEST_ID) {
+            return self.handle_login_response(&response);
+        }
+
+        let pending = id.and_then(|id| self.pending.lock().unwrap().remove(&id).map(|p| (id, p)));
+        if let Some((submit_id, pending)) = pending {
+            self.handle_share_response(submit_id, pending, &response);
+        } else if let Some(error) = response.get("error").filter(|e| !e.is_null()) {
+            error!(target: LOG_TARGET,"❌ {} pool error: {:?}", self.config.label, error);
+            self.stats
+                .add_activity(format!("🚫 {} pool error: {}", self.config.label, error));
+        } else {
+            debug!(target: LOG_TARGET,"Unknown {} pool message: {:?}", self.config.label, response);
+        }
+
+        Ok(())
+    }
+
+    fn handle_login_response(&self, response: &Value) -> Result<()> {
+        if let Some(error) = response.get("error").filter(|e| !e.is_null()) {
+            error!(target: LOG_TARGET,"❌ {} login rejected: {:?}", self.config.label, error);
+            self.stats.add_activity(format!(
+                "🚫 {} login rejected: {}",
+                self.config.label, error
+            ));
+            return Ok(());
+        }
+
+        info!(target: LOG_TARGET,
+            "✅ {} login successful for worker: {}",
+            self.config.label,
+            self.active_pool().worker
+        );
+        self.stats
+            .add_activity(format!("🔐 {} connected successfully", self.config.label));
+
+        let Some(result) = response.get("result") else {
+            return Ok(());
+        };
+
+        if let Some(session_id) = result.get("id").and_then(|id| id.as_str()) {
+            info!(target: LOG_TARGET,"{} pool session ID: {}", self.config.label, session_id);
+            *self.session_id.lock().unwrap() = Some(session_id.to_string());
+        }
+
+        if let Some(job_params) = result.get("job").and_then(|j| j.as_object()) {
+            debug!(target: LOG_TARGET,"Found job in login response: {:?}", job_params);
+            self.handle_new_job(job_params)?;
+        }
+
+        Ok(())
+    }
+
+    fn handle_new_job(&self, job_data: &serde_json::Map<String, Value>) -> Result<()> {
+        let job: PoolJob = serde_json::from_value(Value::Object(job_data.clone()))?;
+        let mining_job = self.build_mining_job(job)?;
+        self.publish_job(mining_job);
+        Ok(())
+    }
+
+    /// Stratum V1 subscribe result carries the extranonce1 and extranonce2 size
+    fn handle_subscribe_response(&self, response: &Value) -> Result<()> {
+        if let Some(error) = response.get("error").filter(|e| !e.is_null()) {
+            error!(target: LOG_TARGET,"❌ {} subscribe rejected: {:?}", self.config.label, error);
+            self.stats.add_activity(format!(
+                "🚫 {} subscribe rejected: {}",
+                self.config.label, error
+            ));
+            return Ok(());
+        }
+
+        let subscription =
+            stratum_v1::parse_subscribe_result(response.get("result").unwrap_or(&Value::Null))?;
+        info!(target: LOG_TARGET,
+            "{} subscribed: extranonce1 {}, extranonce2 size {}",
+            self.config.label,
+            hex::encode(&subscription.extranonce1),
+            subscription.extranonce2_size
+        );
+        self.stratum_v1.lock().unwrap().subscription = Some(subscription);
+        self.republish_stratum_v1_job()
+    }
+
+    fn handle_notify(&self, params: &Value) -> Result<()> {
+        let notify = stratum_v1::parse_notify(params)?;
+        let mining_job = {
+            let mut state = self.stratum_v1.lock().unwrap();
+            state.last_notify = Some(notify.clone());
+            state.mining_job(&notify)?
+        };
+        self.publish_job(mining_job);
+        Ok(())
+    }
+
+    /// New share difficulty applies to the current job straight away
+    fn handle_set_difficulty(&self, difficulty: f64) -> Result<()> {
+        self.stratum_v1.lock().unwrap().difficulty = difficulty;
+        info!(target: LOG_TARGET,"🔧 {} share difficulty set to {}", self.config.label, difficulty);
+        self.stats.add_activity(format!(
+            "🔧 {} VarDiff update: {}",
+            self.config.label,
+            MinerStats::format_number(difficulty.max(1.0).round() as u64)
+        ));
+        self.republish_stratum_v1_job()
+    }
+
+    /// Rebuild the last Stratum V1 job after the extranonce or difficulty changed
+    fn republish_stratum_v1_job(&self) -> Result<()> {
+        let mining_job = {
+            let state = self.stratum_v1.lock().unwrap();
+            match (&state.subscription, &state.last_notify) {
+                (Some(_), Some(notify)) => state.mining_job(notify)?,
+                _ => return Ok(()),
+            }
+        };
+        self.publish_job(mining_job);
+        Ok(())
+    }
+
+    /// Record a new job and hand it to the mining backends
+    fn publish_job(&self, mining_job: MiningJob) {
+        self.stats.update_job(
+            mining_job.job_id.clone(),
+            mining_job.height,
+            mining_job.target_difficulty,
+        );
+
+        let xn_info = mining_job
+            .extranonce2
+            .as_ref()
+            .map(|xn| format!(", XN: {}", xn))
+            .unwrap_or_default();
+        info!(target: LOG_TARGET,
+            "📋 {} job: {} (height: {}, difficulty: {}{})",
+            self.config.label,
+            mining_job.job_id,
+            mining_job.height,
+            MinerStats::format_number(mining_job.target_difficulty),
+            xn_info
+        );
+        self.stats.add_activity(format!(
+            "📋 {} job: {} (height: {}, difficulty: {}{})",
+            self.config.label,
+            &mining_job.job_id[..8.min(mining_job.job_id.len())],
+            mining_job.height,
+            MinerStats::format_number(mining_job.target_difficulty),
+            xn_info
+        ));
+
+        *self.last_job_time.lock().unwrap() = Instant::now();
+        *self.current_job_id.lock().unwrap() = Some(mining_job.job_id.clone());
+
+        if self.job_tx.send(mining_job).is_err() {
+            warn!(target: LOG_TARGET,"⚠️ No {} mining backend is listening for jobs", self.config.label);
+        }
+    }
+
+    /// Convert a pool job into a MiningJob, remembering the pool extra nonce (XN)
+    /// so that later jobs without one keep using the last value sent by the pool
+    fn build_mining_job(&self, job: PoolJob) -> Result<MiningJob> {
+        let mining_hash = hex::decode(job.blob.unwrap_or_default())?;
+        let target_difficulty = job
+            .difficulty
+            .unwrap_or_else(|| parse_target_difficulty(&job.target, self.config.algo));
+
+        let extranonce2 = {
+            let mut extranonce = self.extranonce.lock().unwrap();
+            if let Some(xn) = job.xn {
+                *extranonce = Some(xn);
+            }
+            extranonce.clone()
+        };
+
+        Ok(MiningJob {
+            job_id: job.job_id,
+            mining_hash,
+            target_difficulty,
+            height: job.height,
+            algo: self.config.algo,
+            extranonce2,
+            prev_hash: None,
+            merkle_root: None,
+            version: None,
+            ntime: None,
+            nbits: None,
+            merkle_path: None,
+            target: None,
+            coinbase: None,
+        })
+    }
+
+    fn handle_share_response(&self, submit_id: u64, pending: PendingSubmit, response: &Value) {
+        let status = classify_share_response(response, pending.superseded);
+        let error = response
+            .get("error")
+            .filter(|e| !e.is_null())
+            .map(|e| e.to_string());
+
+        report_share_outcome(
+            &self.stats,
+            &self.outcome_tx,
+            &self.config.label,
+            ShareOutcome {
+                submit_id,
+                job_id: pending.job_id,
+                nonce: pending.nonce,
+                thread_id: pending.thread_id,
+                difficulty: pending.difficulty,
+                status,
+                latency: pending.sent_at.elapsed(),
+                error,
+            },
+        );
+    }
+
+    /// Drop shares the pool never answered so the table cannot grow unbounded
+    fn expire_pending(&self) {
+        let mut pending = self.pending.lock().unwrap();
+        let before = pending.len();
+        pending.retain(|_, share| share.sent_at.elapsed() < PENDING_SHARE_TIMEOUT);
+        let expired = before - pending.len();
+        if expired > 0 {
+            warn!(target: LOG_TARGET,
+                "⚠️ {} pool never answered {} share(s) within {}s",
+                self.config.label,
+                expired,
+                PENDING_SHARE_TIMEOUT.as_secs()
+            );
+        }
+    }
+
+    /// Shares in flight on a dropped connection will never be answered
+    fn drop_pending(&self) {
+        let mut pending = self.pending.lock().unwrap();
+        if !pending.is_empty() {
+            warn!(target: LOG_TARGET,
+                "⚠️ {} dropped {} unanswered share(s) on disconnect",
+                self.config.label,
+                pending.len()
+            );
+            pending.clear();
+        }
+    }
+
+    fn start_share_submitter(
+        session: Arc<Self>,
+        writer: Arc<Mutex<PoolWriter>>,
+        mut share_rx: mpsc::UnboundedReceiver<ShareSubmission>,
+    ) {
+        tokio::spawn(async move {
+            while let Some((job_id, nonce, result, thread_id, difficulty, extranonce2, ntime)) =
+                share_rx.recv().await
+            {
+                let submit_id = session.next_submit_id.fetch_add(1, Ordering::SeqCst);
+
+                let message = match session.config.algo {
+                    Algorithm::Sha3x => {
+                        // Pools that hand out a session id at login expect it back on submit
+                        let login_id = session
+                            .session_id()
+                            .unwrap_or_else(|| session.active_pool().wallet);
+                        StratumProtocol::create_submit_request(
+                            &login_id,
+                            &job_id,
+                            &nonce,
+                            &result,
+                            submit_id,
+                            Algorithm::Sha3x,
+                            None, // No extranonce2 for SHA3x
+                            None, // No ntime for SHA3x
+                        )
+                    }
+                    Algorithm::Sha256 => StratumProtocol::create_submit_request(
+                        &session.active_pool().user_identity(),
+                        &job_id,
+                        &nonce,
+                        &result,
+                        submit_id,
+                        Algorithm::Sha256,
+                        Some(&extranonce2),
+                        Some(ntime),
+                    ),
+                };
+                let message = StratumProtocol::to_message(message);
+                if message.is_empty() {
+                    error!(target: LOG_TARGET,
+                        "Failed to create {} submit message for job {}",
+                        session.config.label, job_id
+                    );
+                    continue;
+                }
+
+                info!(target: LOG_TARGET,
+                    "📤 Submitting {} share: job_id={}, nonce={}, thread={}, difficulty={}",
+                    session.config.label,
+                    job_id,
+                    nonce,
+                    thread_id,
+                    MinerStats::format_number(difficulty)
+                );
+
+                let superseded = session
+                    .current_job_id
+                    .lock()
+                    .unwrap()
+                    .as_ref()
+                    .is_some_and(|current| *current != job_id);
+                session.pending.lock().unwrap().insert(
+                    submit_id,
+                    PendingSubmit {
+                        job_id: job_id.clone(),
+                        nonce: nonce.clone(),
+                        thread_id,
+                        difficulty,
+                        sent_at: Instant::now(),
+                        superseded,
+                    },
+                );
+
+                let mut writer = writer.lock().await;
+                if let Err(e) = writer.write_all(message.as_bytes()).await {
+                    error!(target: LOG_TARGET,"Failed to submit {} share: {}", session.config.label, e);
+                    session.pending.lock().unwrap().remove(&submit_id);
+                }
+            }
+        });
+    }
+
+    /// Expire unanswered shares every 5 seconds while the connection is up
+    fn start_connection_monitor(session: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(5));
+
+            loop {
+                interval.tick().await;
+
+                if !session.pool_client.is_connected() {
+                    debug!(target: LOG_TARGET,
+                        "{} pool connection lost, stopping connection monitoring",
+                        session.config.label
+                    );
+                    break;
+                }
+
+                session.expire_pending();
+            }
+        });
+    }
+
+    fn algo_name(&self) -> &'static str {
+        match self.config.algo {
+            Algorithm::Sha3x => "SHA3x",
+            Algorithm::Sha256 => "SHA-256",
+        }
+    }
+}
+
+/// Decide whether the pool accepted a share.
+/// LuckyPool answers `{"error": null, "result": true}`, other pools answer with
+/// `{"result": {"status": "OK"}}`, a bare boolean or a null result.
+fn share_accepted(response: &Value) -> bool {
+    let result = response.get("result").unwrap_or(&Value::Null);
+
+    if let Some(error) = response.get("error") {
+        if !error.is_null() {
+            return false;
+        }
+        if let Some(status) = result.get("status").and_then(|s| s.as_str()) {
+            return matches!(status.to_lowercase().as_str(), "ok" | "accepted");
+        }
+        return result.as_bool().unwrap_or(true);
+    }
+
+    if let Some(status) = result.get("status").and_then(|s| s.as_str()) {
+        matches!(status.to_lowercase().as_str(), "ok" | "accepted")
+    } else if let Some(accepted) = result.as_bool() {
+        accepted
+    } else {
+        result.is_null()
+    }
+}
+
+/// Log a pool verdict, record it in the miner stats and broadcast it to the backends
+pub(crate) fn report_share_outcome(
+    stats: &MinerStats,
+    outcome_tx: &broadcast::Sender<ShareOutcome>,
+    label: &str,
+    outcome: ShareOutcome,
+) {
+    let thread_id = outcome.thread_id;
+
+    match outcome.status {
+        ShareStatus::Accepted => {
+            info!(target: LOG_TARGET,
+                "✅ {} share accepted by pool (thread {}, difficulty {}, {}ms)",
+                label,
+                thread_id,
+                MinerStats::format_number(outcome.difficulty),
+                outcome.latency.as_millis()
+            );
+            stats.add_activity(format!(
+                "✅ {} share accepted from thread {}",
+                label, thread_id
+            ));
+        }
+        ShareStatus::Rejected => {
+            info!(target: LOG_TARGET,
+                "❌ {} share rejected from thread {}: {}",
+                label,
+                thread_id,
+                outcome.error.as_deref().unwrap_or("no reason given")
+            );
+            stats.add_activity(format!(
+                "❌ {} share rejected from thread {}",
+                label, thread_id
+            ));
+        }
+        ShareStatus::Stale => {
+            info!(target: LOG_TARGET,
+                "⏰ {} stale share from thread {} (job {})",
+                label, thread_id, outcome.job_id
+            );
+            stats.add_activity(format!(
+                "⏰ {} stale share from thread {}",
+                label, thread_id
+            ));
+        }
+    }
+
+    if thread_id >= stats.thread_stats.len() {
+        error!(target: LOG_TARGET,
+            "🧵 {} thread {} ID out of bounds! stats.len={}",
+            label,
+            thread_id,
+            stats.thread_stats.len()
+        );
+    }
+    stats.record_share_result(
+        thread_id,
+        outcome.difficulty,
+        outcome.status,
+        outcome.latency,
+    );
+    if let Some(reason) = rejection_reason(outcome.status, outcome.error.as_deref()) {
+        stats.record_share_rejection(reason);
+    }
+
+    let _ = outcome_tx.send(outcome);
+}
+
+/// Classify the pool response to a share. Rejections mentioning an expired or
+/// unknown job, or for a share whose job was already replaced, count as stale.
+fn classify_share_response(response: &Value, superseded: bool) -> ShareStatus {
+    if share_accepted(response) {
+        return ShareStatus::Accepted;
+    }
+
+    let reason = response
+        .get("error")
+        .map(|e| e.to_string().to_lowercase())
+        .unwrap_or_default();
+    let stale_reason = [
+        "stale",
+        "expired",
+        "job not found",
+        "unknown job",
+        "invalid job",
+    ]
+    .iter()
+    .any(|pattern| reason.contains(pattern));
+
+    if stale_reason || superseded {
+        ShareStatus::Stale
+    } else {
+        ShareStatus::Rejected
+    }
+}
+
+/// Metric label for a refused share: its pool error message sorted into a few
+/// fixed reasons, so pools with free-form messages cannot explode label sets
+pub(crate) fn rejection_reason(status: ShareStatus, error: Option<&str>) -> Option<&'static str> {
+    match status {
+        ShareStatus::Accepted => return None,
+        ShareStatus::Stale => return Some("stale"),
+        ShareStatus::Rejected => {}
+    }
+
+    let error = error.unwrap_or_default().to_lowercase();
+    let reason = if error.contains("duplicate") {
+        "duplicate"
+    } else if error.contains("low difficulty") || error.contains("low diff") {
+        "low_difficulty"
+    } else if error.contains("unauthorized") || error.contains("not authorized") {
+        "unauthorized"
+    } else if error.contains("invalid") || error.contains("malformed") {
+        "invalid"
+    } else {
+        "other"
+    };
+    Some(reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn test_session() -> PoolSession {
+        PoolSession::new(
+            PoolSessionConfig::single(
+                "127.0.0.1:3333".to_string(),
+                "wallet".to_string(),
+                "worker1".to_string(),
+                Algorithm::Sha3x,
+                "TEST",
+            ),
+            Arc::new(PoolClient::new()),
+            Arc::new(MinerStats::new(1)),
+        )
+        .unwrap()
+    }
+
+    fn pool_job(xn: Option<&str>) -> PoolJob {
+        serde_json::from_value(json!({
+            "job_id": "job1",
+            "target": "",
+            "algo": "sha3x",
+            "height": 42,
+            "difficulty": 1000,
+            "blob": "00112233",
+            "xn": xn,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_share_accepted_formats() {
+        assert!(share_accepted(
+            &json!({"id": 100, "error": null, "result": true})
+        ));
+        assert!(!share_accepted(
+            &json!({"id": 100, "error": {"code": -1}, "result": null})
+        ));
+        assert!(share_accepted(
+            &json!({"id": 100, "result": {"status": "OK"}})
+        ));
+        assert!(!share_accepted(
+            &json!({"id": 100, "result": {"status": "rejected"}})
+        ));
+        assert!(share_accepted(&json!({"id": 100, "result": null})));
+        assert!(!share_accepted(&json!({"id": 100, "result": false})));
+    }
+
+    #[test]
+    fn test_classify_share_response() {
+        let accepted = json!({"id": 100, "error": null, "result": {"status": "OK"}});
+        let stale = json!({"id": 101, "error": {"code": -1, "message": "Stale share"}});
+        let low_diff = json!({"id": 102, "error": {"code": -1, "message": "Low difficulty share"}});
+
+        assert_eq!(
+            classify_share_response(&accepted, true),
+            ShareStatus::Accepted
+        );
+        assert_eq!(classify_share_response(&stale, false), ShareStatus::Stale);
+        assert_eq!(
+            classify_share_response(&low_diff, false),
+            ShareStatus::Rejected
+        );
+        assert_eq!(classify_share_response(&low_diff, true), ShareStatus::Stale);
+    }
+
+    #[test]
+    fn test_rejection_reason() {
+        assert_eq!(rejection_reason(ShareStatus::Accepted, None), None);
+        assert_eq!(
+            rejection_reason(ShareStatus::Stale, Some("Low difficulty share")),
+            Some("stale")
+        );
+        assert_eq!(
+            rejection_reason(
+                ShareStatus::Rejected,
+                Some(r#"{"code":-1,"message":"Low difficulty share"}"#)
+            ),
+            Some("low_difficulty")
+        );
+        assert_eq!(
+            rejection_reason(ShareStatus::Rejected, Some("Duplicate share")),
+            Some("duplicate")
+        );
+        assert_eq!(rejection_reason(ShareStatus::Rejected, None), Some("other"));
+    }
+
+    #[test]
+    fn test_build_mining_job_keeps_last_xn() {
+        let session = test_session();
+
+        let job = session.build_mining_job(pool_job(None)).unwrap();
+        assert_eq!(job.extranonce2, None);
+        assert_eq!(job.mining_hash, vec![0x00, 0x11, 0x22, 0x33]);
+        assert_eq!(job.target_difficulty, 1000);
+
+        let job = session.build_mining_job(pool_job(Some("ad49"))).unwrap();
+        assert_eq!(job.extranonce2.as_deref(), Some("ad49"));
+
+        let job = session.build_mining_job(pool_job(None)).unwrap();
+        assert_eq!(job.extranonce2.as_deref(), Some("ad49"));
+    }
+
+    #[tokio::test]
+    async fn test_stratum_v1_subscribe_notify_and_difficulty() {
+        let session = PoolSession::new(
+            PoolSessionConfig::single(
+                "127.0.0.1:3333".to_string(),
+                "bc1qwallet".to_string(),
+                "rig1".to_string(),
+                Algorithm::Sha256,
+                "SHA-256",
+            ),
+            Arc::new(PoolClient::new()),
+            Arc::new(MinerStats::new(1)),
+        )
+        .unwrap();
+        let mut jobs = session.subscribe_jobs();
+
+        let notify = json!({
+            "id": null,
+            "method": "mining.notify",
+            "params": [
+                "bf",
+                "4d16b6f85af6e2198f44ae2a6de67f78487ae5611b77c6c0440b921e00000000",
+                "01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff20020862062f503253482f04b8864e5008",
+                "072f736c7573682f000000000100f2052a010000001976a914d23fcdf86f7e756a64a7a9688ef9903327048ed988ac00000000",
+                [],
+                "00000002",
+                "1c2ac4af",
+                "504e86b9",
+                true
+            ]
+        });
+
+        // A notify before the subscribe response cannot be mined
+        assert!(
+            session
+                .handle_pool_message(&notify.to_string())
+                .await
+                .is_err()
+        );
+
+        let subscribed = json!({"id": 1, "error": null, "result": [[], "08000002", 4]});
+        session
+            .handle_pool_message(&subscribed.to_string())
+            .await
+            .unwrap();
+        // The notify remembered before subscribing is published once the extranonce is known
+        let job = jobs.try_recv().unwrap();
+        assert_eq!(job.job_id, "bf");
+        assert_eq!(job.target_difficulty, 1);
+
+        let difficulty = json!({"id": null, "method": "mining.set_difficulty", "params": [4096]});
+        session
+            .handle_pool_message(&difficulty.to_string())
+            .await
+            .unwrap();
+        let job = jobs.try_recv().unwrap();
+        assert_eq!(job.target_difficulty, 4096);
+        assert_eq!(
+            job.coinbase.unwrap().extranonce1,
+            vec![0x08, 0x00, 0x00, 0x02]
+        );
+
+        let authorized = json!({"id": 2, "error": null, "result": true});
+        session
+            .handle_pool_message(&authorized.to_string())
+            .await
+            .unwrap();
+        assert!(jobs.try_recv().is_err());
+    }
+}
+
+// Changelog:
+// - v1.6.2 (2025-06-28): The pool XN is cleared with the session id on every login, so jobs
+//   after a reconnect or failover never carry the previous pool's nonce prefix.
+// - v1.6.1 (2025-06-28): Dropped the simulated pool latency written by the connection monitor;
+//   logins after the first one or after a failover are counted as reconnects.
+// - v1.6.0 (2025-06-28): Refused shares are counted by reason (rejection_reason()) for metrics.
+// - v1.5.0 (2025-06-28): follow_control() / select_pool() pin the session to a pool of the
+//   failover list (reconnecting right away) or return it to the priority order.
+// - v1.4.0 (2025-06-28): Solo node lists (grpc:// / http://) are run by pool::solo::SoloClient.
+// - v1.3.0 (2025-06-28): Stratum V1 SHA-256 pools
+//   - Login sends mining.subscribe then mining.authorize with the worker identity and password.
+//   - mining.notify / mining.set_difficulty / mining.set_extranonce build and republish SHA-256 jobs.
+//   - SHA-256 shares are submitted with the worker user, extranonce2 and ntime.
+//   - Job announcement moved to publish_job() so every job source shares it.
+// - v1.2.0 (2025-06-28): Stratum V2 pools
+//   - run() delegates sv2:// pool lists to the SV2 client (same job/share/outcome channels).
+//   - Share verdict reporting moved to report_share_outcome() for reuse by the SV2 client.
+// - v1.1.0 (2025-06-28): Pool connections go through PoolClient::connect_pool()
+//   so stratum+ssl:// pools run over TLS; reader/writer halves are generic
+//   over the pool transport.
+// - v1.0.0 (2025-06-28): Initial pool session implementation.
+//   - Single connection/login/job/share path shared by the CPU and GPU miners.
+//   - Share responses are matched to submissions by JSON-RPC id instead of
+//     per-backend id ranges.
+//   - Pool XN is tracked per session instead of in a process-wide static.
+//   - Pending-share table keyed by JSON-RPC id records job, nonce, thread,
+//     difficulty and send time; verdicts (accepted/rejected/stale) and submit
+//     latency are forwarded to MinerStats::record_share_result().
+//   - Failover pool list: per-pool credentials, retry budget with exponential
+//     backoff, failover and periodic failback to higher-priority pools.
@@ -0,0 +1,466 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/pool/solo.rs
+// Version: 1.0.0
+//
+// This file implements solo mining. Instead of a pool, the session talks to a
+// Tari base node over gRPC (grpc://) or to a JSON-RPC block template server
+// (http://). The client watches the chain tip, fetches a block template paying
+// the coinbase to the wallet, hands it to the miners as a MiningJob at network
+// difficulty, and submits every block the miners find back to the node.
+//
+// Tree Location:
+// - src/pool/solo.rs (solo mining client)
+// - Depends on: pool/base_node, pool/template_rpc, pool/failover, pool/session
+
+use crate::Result;
+use crate::core::sha3x::sha3x_hash_with_nonce;
+use crate::core::{Algorithm, MiningJob, ShareStatus, calculate_difficulty};
+use crate::miner::stats::MinerStats;
+use crate::pool::PoolClient;
+use crate::pool::base_node::BaseNodeGrpc;
+use crate::pool::failover::{PoolEndpoint, PoolFailover};
+use crate::pool::session::{
+    PoolSessionConfig, ShareOutcome, ShareSubmission, report_share_outcome,
+};
+use crate::pool::template_rpc::TemplateRpcClient;
+use crate::pool::transport::{PoolScheme, PoolUrl};
+use log::{debug, info, warn};
+use minotari_app_grpc::tari_rpc as grpc;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc};
+
+const LOG_TARGET: &str = "tari::graxil::pool::solo";
+
+/// How often the node's chain tip is checked for a new block
+const TIP_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Templates are rebuilt this often even without a new block, to pick up fees
+const TEMPLATE_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+/// Templates kept so blocks found on a just-replaced template can still be submitted
+const RECENT_TEMPLATES: usize = 4;
+/// First id handed to block submissions (only used to match outcomes in logs)
+const FIRST_SUBMIT_ID: u64 = 100;
+
+/// Block template ready to be mined
+#[derive(Debug, Clone)]
+pub struct BlockTemplate {
+    /// Job id handed to the miners
+    pub id: String,
+    pub height: u64,
+    /// SHA3x header pre-image (the blob the nonce is hashed with)
+    pub mining_hash: Vec<u8>,
+    pub network_difficulty: u64,
+    /// Coinbase value: block reward plus fees
+    pub reward: u64,
+    /// Block returned by the base node; its header nonce is set on submission
+    pub(crate) block: Option<Box<grpc::Block>>,
+}
+
+impl BlockTemplate {
+    pub fn mining_job(&self) -> MiningJob {
+        MiningJob {
+            job_id: self.id.clone(),
+            mining_hash: self.mining_hash.clone(),
+            target_difficulty: self.network_difficulty,
+            height: self.height,
+            algo: Algorithm::Sha3x,
+            extranonce2: None,
+            prev_hash: None,
+            merkle_root: None,
+            version: None,
+            ntime: None,
+            nbits: None,
+            merkle_path: None,
+            target: None,
+            coinbase: None,
+        }
+    }
+}
+
+/// Base Tari address of a wallet argument (drops `solo:` and worker / difficulty suffixes)
+pub fn coinbase_address(wallet: &str) -> &str {
+    let wallet = wallet.strip_prefix("solo:").unwrap_or(wallet);
+    wallet.split(['.', '/', '=']).next().unwrap_or(wallet)
+}
+
+/// Node the templates come from
+enum NodeBackend {
+    Grpc(BaseNodeGrpc),
+    Rpc(TemplateRpcClient),
+}
+
+impl NodeBackend {
+    fn new(endpoint: &PoolEndpoint, pool_client: &Arc<PoolClient>) -> Result<Self> {
+        let url = PoolUrl::parse(&endpoint.address)?;
+        match url.scheme {
+            PoolScheme::BaseNode => Ok(Self::Grpc(BaseNodeGrpc::new(url.address))),
+            PoolScheme::TemplateRpc => Ok(Self::Rpc(TemplateRpcClient::new(
+                url.address,
+                Arc::clone(pool_client),
+            ))),
+            _ => Err(format!("{} is not a solo mining node URL", endpoint.address).into()),
+        }
+    }
+
+    async fn tip_height(&mut self) -> Result<u64> {
+        match self {
+            Self::Grpc(node) => node.tip_height().await,
+            Self::Rpc(node) => node.tip_height().await,
+        }
+    }
+
+    async fn get_template(&mut self, wallet: &str) -> Result<BlockTemplate> {
+        match self {
+            Self::Grpc(node) => node.get_template(wallet).await,
+            Self::Rpc(node) => node.get_template(wallet).await,
+        }
+    }
+
+    async fn submit_block(&mut self, template: &BlockTemplate, nonce: u64) -> Result<String> {
+        match self {
+            Self::Grpc(node) => node.submit_block(template, nonce).await,
+            Self::Rpc(node) => node.submit_block(template, nonce).await,
+        }
+    }
+}
+
+/// Solo mining client for grpc:// / http:// node lists
+pub struct SoloClient {
+    config: PoolSessionConfig,
+    pool_client: Arc<PoolClient>,
+    stats: Arc<MinerStats>,
+}
+
+impl SoloClient {
+    pub fn new(
+        config: PoolSessionConfig,
+        pool_client: Arc<PoolClient>,
+        stats: Arc<MinerStats>,
+    ) -> Self {
+        Self {
+            config,
+            pool_client,
+            stats,
+        }
+    }
+
+    /// Follow the chain tip, publish templates and submit found blocks until the backends stop
+    pub async fn run(
+        &self,
+        job_tx: broadcast::Sender<MiningJob>,
+        mut share_rx: mpsc::UnboundedReceiver<ShareSubmission>,
+        outcome_tx: broadcast::Sender<ShareOutcome>,
+    ) -> Result<()> {
+        if self.config.algo != Algorithm::Sha3x {
+            return Err("Solo mining requires --algo sha3x".into());
+        }
+        let mut failover =
+            PoolFailover::new(self.config.pools.clone(), self.config.failover.clone())?;
+        let mut backend = NodeBackend::new(failover.active(), &self.pool_client)?;
+        self.pool_client
+            .set_active_pool(failover.active_index(), failover.pools().len());
+
+        let mut templates: VecDeque<BlockTemplate> = VecDeque::new();
+        let mut last_template = Instant::now();
+        let mut next_submit_id = FIRST_SUBMIT_ID;
+        let mut poll = tokio::time::interval(TIP_POLL_INTERVAL);
+        poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        info!(target: LOG_TARGET,
+            "⛏️ {} solo mining against {} (coinbase to {})",
+            self.config.label,
+            failover.active().address,
+            coinbase_address(&failover.active().wallet)
+        );
+
+        loop {
+            tokio::select! {
+                _ = poll.tick() => {
+                    let started = Instant::now();
+                    let refreshed = self
+                        .refresh(&mut backend, failover.active(), &mut templates, &mut last_template, &job_tx)
+                        .await;
+                    match refreshed {
+                        Ok(()) => {
+                            failover.record_success();
+                            self.pool_client
+                                .mark_connected(&failover.active().address, started.elapsed());
+                        }
+                        Err(e) => {
+                            self.pool_client.mark_disconnected();
+                            let endpoint = failover.active().address.clone();
+                            let decision = failover.record_failure();
+                            if decision.failed_over {
+                                let next = failover.active();
+                                warn!(target: LOG_TARGET,
+                                    "🔀 {} node {} failed ({}), failing over to {}",
+                                    self.config.label, endpoint, e, next.address
+                                );
+                                self.stats.add_activity(format!(
+                                    "🔀 {} failing over to {}",
+                                    self.config.label, next.address
+                                ));
+                                backend = NodeBackend::new(next, &self.pool_client)?;
+                                self.pool_client
+                                    .set_active_pool(failover.active_index(), failover.pools().len());
+                                templates.clear();
+                            } else {
+                                warn!(target: LOG_TARGET,
+                                    "⚠️ {} node {} unavailable: {} (retrying in {}s)",
+                                    self.config.label,
+                                    endpoint,
+                                    e,
+                                    decision.delay.as_secs()
+                                );
+                            }
+                            tokio::time::sleep(decision.delay).await;
+                        }
+                    }
+                }
+                share = share_rx.recv() => {
+                    let Some(share) = share else {
+                        info!(target: LOG_TARGET,"🛑 {} mining backends stopped, closing solo session", self.config.label);
+                        return Ok(());
+                    };
+                    let outcome = self
+                        .submit_block(&mut backend, &templates, share, next_submit_id)
+                        .await;
+                    next_submit_id += 1;
+                    report_share_outcome(&self.stats, &outcome_tx, &self.config.label, outcome);
+                }
+            }
+        }
+    }
+
+    /// Fetch a new template when the tip moved or the current one is getting old
+    async fn refresh(
+        &self,
+        backend: &mut NodeBackend,
+        endpoint: &PoolEndpoint,
+        templates: &mut VecDeque<BlockTemplate>,
+        last_template: &mut Instant,
+        job_tx: &broadcast::Sender<MiningJob>,
+    ) -> Result<()> {
+        let tip = backend.tip_height().await?;
+        let stale = match templates.back() {
+            None => true,
+            Some(current) => {
+                current.height <= tip || last_template.elapsed() >= TEMPLATE_REFRESH_INTERVAL
+            }
+        };
+        if !stale {
+            return Ok(());
+        }
+
+        let template = backend
+            .get_template(coinbase_address(&endpoint.wallet))
+            .await?;
+        *last_template = Instant::now();
+        if templates
+            .back()
+            .is_some_and(|current| current.mining_hash == template.mining_hash)
+        {
+            return Ok(());
+        }
+
+        self.track_network(templates.back(), &template);
+        self.stats.update_job(
+            template.id.clone(),
+            template.height,
+            template.network_difficulty,
+        );
+        if job_tx.send(template.mining_job()).is_err() {
+            warn!(target: LOG_TARGET,"⚠️ No {} mining backend is listening for jobs", self.config.label);
+        }
+
+        templates.push_back(template);
+        if templates.len() > RECENT_TEMPLATES {
+            templates.pop_front();
+        }
+        Ok(())
+    }
+
+    /// Log new blocks and network difficulty changes
+    fn track_network(&self, previous: Option<&BlockTemplate>, template: &BlockTemplate) {
+        self.stats
+            .update_network_difficulty(template.network_difficulty);
+
+        let hashrate = self.stats.get_total_hashrate();
+        let expected = if hashrate > 0.0 {
+            MinerStats::format_duration(Duration::from_secs_f64(
+                template.network_difficulty as f64 / hashrate,
+            ))
+        } else {
+            "unknown".to_string()
+        };
+
+        match previous {
+            Some(previous) if previous.height == template.height => {
+                debug!(target: LOG_TARGET,
+                    "{} refreshed template for height {} (reward {})",
+                    self.config.label, template.height, template.reward
+                );
+            }
+            _ => {
+                info!(target: LOG_TARGET,
+                    "📋 {} solo template: height {}, network difficulty {}, reward {} µT, expected time to block {}",
+                    self.config.label,
+                    template.height,
+                    MinerStats::format_number(template.network_difficulty),
+                    template.reward,
+                    expected
+                );
+                self.stats.add_activity(format!(
+                    "📋 {} solo template: height {}, network difficulty {}",
+                    self.config.label,
+                    template.height,
+                    MinerStats::format_number(template.network_difficulty)
+                ));
+            }
+        }
+
+        if let Some(previous) =
+            previous.filter(|p| p.network_difficulty != template.network_difficulty)
+        {
+            let change = (template.network_difficulty as f64
+                / previous.network_difficulty.max(1) as f64
+                - 1.0)
+                * 100.0;
+            info!(target: LOG_TARGET,
+                "📈 Network difficulty {} -> {} ({:+.1}%)",
+                MinerStats::format_number(previous.network_difficulty),
+                MinerStats::format_number(template.network_difficulty),
+                change
+            );
+        }
+    }
+
+    /// Check a found nonce against its template and submit the block
+    async fn submit_block(
+        &self,
+        backend: &mut NodeBackend,
+        templates: &VecDeque<BlockTemplate>,
+        share: ShareSubmission,
+        submit_id: u64,
+    ) -> ShareOutcome {
+        let (job_id, nonce_hex, _result, thread_id, difficulty, _, _) = share;
+        let started = Instant::now();
+        let mut outcome = ShareOutcome {
+            submit_id,
+            job_id: job_id.clone(),
+            nonce: nonce_hex.clone(),
+            thread_id,
+            difficulty,
+            status: ShareStatus::Rejected,
+            latency: Duration::ZERO,
+            error: None,
+        };
+
+        let Some(template) = templates.iter().rev().find(|t| t.id == job_id) else {
+            outcome.status = ShareStatus::Stale;
+            outcome.error = Some("Block template no longer current".to_string());
+            return outcome;
+        };
+        let Some(nonce) = hex::decode(&nonce_hex)
+            .ok()
+            .and_then(|bytes| <[u8; 8]>::try_from(bytes).ok())
+        else {
+            outcome.error = Some(format!("Invalid nonce '{}'", nonce_hex));
+            return outcome;
+        };
+
+        let hash = sha3x_hash_with_nonce(&template.mining_hash, nonce);
+        let achieved = calculate_difficulty(&hash, Algorithm::Sha3x);
+        if achieved < template.network_difficulty {
+            outcome.error = Some(format!(
+                "Difficulty {} below network difficulty {}",
+                achieved, template.network_difficulty
+            ));
+            return outcome;
+        }
+
+        info!(target: LOG_TARGET,
+            "📤 {} submitting block at height {} (nonce {}, difficulty {})",
+            self.config.label,
+            template.height,
+            nonce_hex,
+            MinerStats::format_number(achieved)
+        );
+        let result = backend
+            .submit_block(template, u64::from_le_bytes(nonce))
+            .await;
+        outcome.latency = started.elapsed();
+
+        // Anything but the newest template may already have been beaten by the network
+        let superseded = templates.back().is_some_and(|t| t.id != job_id);
+        match result {
+            Ok(block_hash) => {
+                outcome.status = ShareStatus::Accepted;
+                self.stats.record_block_found();
+                info!(target: LOG_TARGET,
+                    "🎉 {} BLOCK FOUND at height {}! Hash {}",
+                    self.config.label, template.height, block_hash
+                );
+                self.stats.add_activity(format!(
+                    "🎉 Block found at height {} ({}...)",
+                    template.height,
+                    &block_hash[..16.min(block_hash.len())]
+                ));
+            }
+            Err(e) => {
+                outcome.status = if superseded {
+                    ShareStatus::Stale
+                } else {
+                    ShareStatus::Rejected
+                };
+                outcome.error = Some(e.to_string());
+            }
+        }
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coinbase_address() {
+        assert_eq!(coinbase_address("12Fr"), "12Fr");
+        assert_eq!(coinbase_address("solo:12Fr"), "12Fr");
+        assert_eq!(coinbase_address("12Fr.rig1"), "12Fr");
+        assert_eq!(coinbase_address("12Fr/rig1"), "12Fr");
+        assert_eq!(coinbase_address("12Fr=50G.rig1"), "12Fr");
+    }
+
+    #[test]
+    fn test_template_mining_job() {
+        let template = BlockTemplate {
+            id: "abcd".to_string(),
+            height: 42,
+            mining_hash: vec![7; 32],
+            network_difficulty: 1_000_000,
+            reward: 5,
+            block: None,
+        };
+        let job = template.mining_job();
+        assert_eq!(job.job_id, "abcd");
+        assert_eq!(job.height, 42);
+        assert_eq!(job.target_difficulty, 1_000_000);
+        assert_eq!(job.algo, Algorithm::Sha3x);
+        assert_eq!(job.extranonce2, None);
+    }
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial solo mining client.
+//   - Tari base node (gRPC) and JSON-RPC template server backends with failover.
+//   - Tip polling, template refresh, network difficulty tracking.
+//   - Blocks verified locally and submitted to the node; outcomes reported like pool shares.
@@ -0,0 +1,682 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/pool/stratum_proxy.rs
+// Version: 1.0.0
+//
+// This file implements Stratum proxy mode (--proxy-listen). The proxy keeps a
+// single upstream pool session and accepts downstream SHA3x miners. Every
+// downstream gets its own 2-byte nonce prefix using the LuckyPool `xn` scheme,
+// so rigs never search the same nonces. Shares are checked locally against the
+// job before being forwarded upstream, and the pool verdict is relayed back to
+// the miner that found the share. Per-downstream stats live in MinerStats.
+//
+// Tree Location:
+// - src/pool/stratum_proxy.rs (downstream Stratum server for proxy mode)
+// - Depends on: pool/session, core/sha3x, miner/stats, tokio, serde_json
+
+use crate::Result;
+use crate::core::sha3x::sha3x_hash_with_nonce;
+use crate::core::{Algorithm, MiningJob, ShareStatus, calculate_difficulty};
+use crate::miner::stats::{DownstreamStats, MinerStats};
+use crate::pool::session::{PoolSession, ShareOutcome, ShareSubmission};
+use log::{debug, info, warn};
+use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::SocketAddr;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc};
+
+const LOG_TARGET: &str = "tari::graxil::pool::stratum_proxy";
+
+/// Upstream jobs kept so late downstream shares can still be checked
+const RECENT_JOBS: usize = 4;
+
+/// Hands out distinct 2-byte nonce prefixes (xn) to downstream miners
+#[derive(Debug, Default)]
+pub struct XnAllocator {
+    in_use: HashSet<u16>,
+    next: u16,
+}
+
+impl XnAllocator {
+    /// Lowest free prefix at or after the cursor; None when all 65536 are taken
+    pub fn allocate(&mut self) -> Option<u16> {
+        if self.in_use.len() > u16::MAX as usize {
+            return None;
+        }
+        while self.in_use.contains(&self.next) {
+            self.next = self.next.wrapping_add(1);
+        }
+        let xn = self.next;
+        self.in_use.insert(xn);
+        self.next = self.next.wrapping_add(1);
+        Some(xn)
+    }
+
+    pub fn release(&mut self, xn: u16) {
+        self.in_use.remove(&xn);
+    }
+
+    pub fn in_use(&self) -> usize {
+        self.in_use.len()
+    }
+}
+
+/// Hex form of a nonce prefix as it appears in job `xn` fields and nonces
+fn xn_hex(xn: u16) -> String {
+    hex::encode(xn.to_le_bytes())
+}
+
+struct Downstream {
+    tx: mpsc::UnboundedSender<String>,
+    xn: u16,
+    stats: Arc<DownstreamStats>,
+}
+
+/// Share forwarded upstream, waiting for the pool verdict
+struct ForwardedShare {
+    downstream_id: u64,
+    request_id: Value,
+    target_difficulty: u64,
+}
+
+#[derive(Default)]
+struct ProxyState {
+    recent_jobs: VecDeque<MiningJob>,
+    downstreams: HashMap<u64, Downstream>,
+    forwarded: HashMap<String, ForwardedShare>,
+    xn: XnAllocator,
+    next_downstream_id: u64,
+    warned_upstream_xn: bool,
+}
+
+impl ProxyState {
+    fn current_job(&self) -> Option<&MiningJob> {
+        self.recent_jobs.back()
+    }
+
+    fn job(&self, job_id: &str) -> Option<&MiningJob> {
+        self.recent_jobs
+            .iter()
+            .rev()
+            .find(|job| job.job_id == job_id)
+    }
+
+    /// Prefix a downstream must use for `job`; an upstream xn (LuckyPool
+    /// upstream) leaves no room to split, so it is passed through as is
+    fn job_xn(job: &MiningJob, downstream_xn: u16) -> String {
+        job.extranonce2
+            .clone()
+            .unwrap_or_else(|| xn_hex(downstream_xn))
+    }
+}
+
+/// Stratum server sharing one upstream pool session between many miners
+pub struct StratumProxy {
+    listener: TcpListener,
+    session: Arc<PoolSession>,
+    stats: Arc<MinerStats>,
+    share_tx: mpsc::UnboundedSender<ShareSubmission>,
+    state: Mutex<ProxyState>,
+}
+
+impl StratumProxy {
+    /// Bind the downstream listener; the upstream session starts in `run`
+    pub async fn bind(
+        listen: &str,
+        session: Arc<PoolSession>,
+        stats: Arc<MinerStats>,
+    ) -> Result<Arc<Self>> {
+        let listener = TcpListener::bind(listen)
+            .await
+            .map_err(|e| format!("Cannot listen on {}: {}", listen, e))?;
+        let share_tx = session.share_sender();
+        Ok(Arc::new(Self {
+            listener,
+            session,
+            stats,
+            share_tx,
+            state: Mutex::new(ProxyState::default()),
+        }))
+    }
+
+    /// Address downstream miners connect to
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Run the upstream session and serve downstream miners until the session ends
+    pub async fn run(self: Arc<Self>) -> Result<()> {
+        Self::start_job_forwarder(Arc::clone(&self), self.session.subscribe_jobs());
+        Self::start_outcome_forwarder(Arc::clone(&self), self.session.subscribe_share_results());
+        Self::start_stats_printer(Arc::clone(&self.stats));
+
+        info!(target: LOG_TARGET,
+            "🔀 Stratum proxy listening on {} (upstream {})",
+            self.local_addr()?,
+            self.session.active_pool().address
+        );
+
+        let accept = {
+            let proxy = Arc::clone(&self);
+            tokio::spawn(async move {
+                loop {
+                    match proxy.listener.accept().await {
+                        Ok((stream, peer)) => {
+                            tokio::spawn(Self::serve_downstream(Arc::clone(&proxy), stream, peer));
+                        }
+                        Err(e) => {
+                            warn!(target: LOG_TARGET,"⚠️ Proxy accept failed: {}", e);
+                            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                        }
+                    }
+                }
+            })
+        };
+
+        let result = Arc::clone(&self.session).run().await;
+        accept.abort();
+        result
+    }
+
+    fn start_stats_printer(stats: Arc<MinerStats>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                let dashboard_id = format!("{:016x}", rand::random::<u64>());
+                info!(target: LOG_TARGET,"📊 STRATUM PROXY DASHBOARD - {}", dashboard_id);
+                stats.display_dashboard(&dashboard_id);
+            }
+        });
+    }
+
+    /// Number of connected downstream miners
+    pub fn downstream_count(&self) -> usize {
+        self.state.lock().unwrap().downstreams.len()
+    }
+
+    /// Send every upstream job to every downstream with its own prefix
+    fn start_job_forwarder(proxy: Arc<Self>, mut job_rx: broadcast::Receiver<MiningJob>) {
+        tokio::spawn(async move {
+            loop {
+                let job = match job_rx.recv().await {
+                    Ok(job) => job,
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+
+                let mut state = proxy.state.lock().unwrap();
+                if job.extranonce2.is_some() && !state.warned_upstream_xn {
+                    warn!(target: LOG_TARGET,
+                        "⚠️ Upstream pool assigns its own xn; downstream miners share one nonce prefix"
+                    );
+                    state.warned_upstream_xn = true;
+                }
+
+                for downstream in state.downstreams.values() {
+                    let xn = ProxyState::job_xn(&job, downstream.xn);
+                    let _ = downstream.tx.send(job_notification(&job, &xn));
+                }
+                debug!(target: LOG_TARGET,
+                    "Forwarded job {} to {} downstream miner(s)",
+                    job.job_id,
+                    state.downstreams.len()
+                );
+
+                state.recent_jobs.push_back(job);
+                if state.recent_jobs.len() > RECENT_JOBS {
+                    state.recent_jobs.pop_front();
+                }
+            }
+        });
+    }
+
+    /// Relay the upstream verdict for each forwarded share to its downstream
+    fn start_outcome_forwarder(
+        proxy: Arc<Self>,
+        mut outcome_rx: broadcast::Receiver<ShareOutcome>,
+    ) {
+        tokio::spawn(async move {
+            loop {
+                let outcome = match outcome_rx.recv().await {
+                    Ok(outcome) => outcome,
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        warn!(target: LOG_TARGET,"⚠️ Proxy missed {} share verdicts", missed);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+
+                let mut state = proxy.state.lock().unwrap();
+                let Some(share) = state.forwarded.remove(&outcome.nonce) else {
+                    continue;
+                };
+                let Some(downstream) = state.downstreams.get(&share.downstream_id) else {
+                    continue;
+                };
+
+                downstream
+                    .stats
+                    .record_share(share.target_difficulty, outcome.status);
+                let reply = match outcome.status {
+                    ShareStatus::Accepted => ok_reply(&share.request_id, "OK"),
+                    ShareStatus::Rejected | ShareStatus::Stale => error_reply(
+                        &share.request_id,
+                        outcome.error.as_deref().unwrap_or(match outcome.status {
+                            ShareStatus::Stale => "Stale share",
+                            _ => "Rejected by pool",
+                        }),
+                    ),
+                };
+                let _ = downstream.tx.send(reply);
+            }
+        });
+    }
+
+    async fn serve_downstream(proxy: Arc<Self>, stream: TcpStream, peer: SocketAddr) {
+        debug!(target: LOG_TARGET,"🔌 Downstream connection from {}", peer);
+        let (reader, mut writer) = tokio::io::split(stream);
+        let mut lines = BufReader::new(reader).lines();
+        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+        let mut downstream_id = None;
+
+        loop {
+            tokio::select! {
+                line = lines.next_line() => {
+                    let line = match line {
+                        Ok(Some(line)) => line,
+                        _ => break,
+                    };
+                    let Ok(request) = serde_json::from_str::<Value>(&line) else {
+                        debug!(target: LOG_TARGET,"Ignoring malformed line from {}: {}", peer, line);
+                        continue;
+                    };
+                    let id = request.get("id").cloned().unwrap_or(Value::Null);
+                    let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+                    let reply = match request.get("method").and_then(|m| m.as_str()) {
+                        Some("login") if downstream_id.is_none() => {
+                            match proxy.register(&params, peer, tx.clone()) {
+                                Ok((registered, result)) => {
+                                    downstream_id = Some(registered);
+                                    json!({"id": id, "jsonrpc": "2.0", "error": null, "result": result})
+                                }
+                                Err(reason) => error_reply(&id, &reason),
+                            }
+                        }
+                        Some("login") => error_reply(&id, "Already logged in"),
+                        Some("submit") => match downstream_id {
+                            Some(downstream_id) => match proxy.submit(downstream_id, &id, &params) {
+                                Some(reject) => reject,
+                                None => continue, // verdict comes from the pool
+                            },
+                            None => error_reply(&id, "Unauthenticated"),
+                        },
+                        Some("keepalived") => ok_reply(&id, "KEEPALIVED"),
+                        _ => error_reply(&id, "Unknown method"),
+                    };
+                    if tx.send(reply).is_err() {
+                        break;
+                    }
+                }
+                message = rx.recv() => {
+                    let Some(message) = message else {
+                        break;
+                    };
+                    if writer.write_all(format!("{}\n", message).as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if let Some(downstream_id) = downstream_id {
+            proxy.unregister(downstream_id);
+        }
+        debug!(target: LOG_TARGET,"🔌 Downstream {} disconnected", peer);
+    }
+
+    /// Log a downstream in: assign a prefix and return the login result
+    fn register(
+        &self,
+        params: &Value,
+        peer: SocketAddr,
+        tx: mpsc::UnboundedSender<String>,
+    ) -> std::result::Result<(u64, Value), String> {
+        let field = |name: &str| {
+            params
+                .get(name)
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+        };
+        let worker = [field("rigid"), field("pass"), field("login")]
+            .into_iter()
+            .find(|name| !name.is_empty() && *name != "x")
+            .unwrap_or("unnamed")
+            .to_string();
+
+        let mut state = self.state.lock().unwrap();
+        let xn = state
+            .xn
+            .allocate()
+            .ok_or("Proxy has no free nonce prefixes left")?;
+        let id = state.next_downstream_id;
+        state.next_downstream_id += 1;
+
+        let stats = Arc::new(DownstreamStats::new(
+            id,
+            worker.clone(),
+            peer.to_string(),
+            xn_hex(xn),
+        ));
+        self.stats.add_downstream(Arc::clone(&stats));
+
+        let mut result = json!({"id": format!("proxy-{}", id), "status": "OK"});
+        if let Some(job) = state.current_job() {
+            result["job"] = job_params(job, &ProxyState::job_xn(job, xn));
+        }
+        state.downstreams.insert(id, Downstream { tx, xn, stats });
+
+        info!(target: LOG_TARGET,
+            "⛏️ Downstream {} ({}) joined with xn {} - {} miner(s) connected",
+            worker,
+            peer,
+            xn_hex(xn),
+            state.downstreams.len()
+        );
+        self.stats.add_activity(format!(
+            "⛏️ Proxy miner {} joined (xn {})",
+            worker,
+            xn_hex(xn)
+        ));
+        Ok((id, result))
+    }
+
+    fn unregister(&self, downstream_id: u64) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(downstream) = state.downstreams.remove(&downstream_id) {
+            state.xn.release(downstream.xn);
+            state
+                .forwarded
+                .retain(|_, share| share.downstream_id != downstream_id);
+            self.stats.remove_downstream(downstream_id);
+            info!(target: LOG_TARGET,
+                "👋 Downstream {} left - {} miner(s) connected",
+                downstream.stats.worker,
+                state.downstreams.len()
+            );
+            self.stats
+                .add_activity(format!("👋 Proxy miner {} left", downstream.stats.worker));
+        }
+    }
+
+    /// Check a downstream share and forward it upstream. Returns the reply for
+    /// shares rejected locally; accepted ones are answered when the pool responds.
+    fn submit(&self, downstream_id: u64, request_id: &Value, params: &Value) -> Option<String> {
+        let field = |name: &str| {
+            params
+                .get(name)
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+        };
+        let job_id = field("job_id");
+        let nonce_hex = field("nonce").to_ascii_lowercase();
+
+        let mut state = self.state.lock().unwrap();
+        let downstream = state.downstreams.get(&downstream_id)?;
+        let downstream_stats = Arc::clone(&downstream.stats);
+        let downstream_xn = downstream.xn;
+
+        let verdict = match state.job(job_id) {
+            None => Err(("Stale share: job not found", ShareStatus::Stale, 0)),
+            Some(job) => check_share(job, &nonce_hex, &ProxyState::job_xn(job, downstream_xn))
+                .map(|(result, difficulty)| (job.target_difficulty, result, difficulty))
+                .map_err(|reason| (reason, ShareStatus::Rejected, job.target_difficulty)),
+        };
+        if state.forwarded.contains_key(&nonce_hex) {
+            downstream_stats.record_share(0, ShareStatus::Rejected);
+            return Some(error_reply(request_id, "Duplicate share"));
+        }
+
+        match verdict {
+            Err((reason, status, target_difficulty)) => {
+                debug!(target: LOG_TARGET,
+                    "Proxy rejected share from {}: {}",
+                    downstream_stats.worker, reason
+                );
+                downstream_stats.record_share(target_difficulty, status);
+                Some(error_reply(request_id, reason))
+            }
+            Ok((target_difficulty, result, difficulty)) => {
+                state.forwarded.insert(
+                    nonce_hex.clone(),
+                    ForwardedShare {
+                        downstream_id,
+                        request_id: request_id.clone(),
+                        target_difficulty,
+                    },
+                );
+                // Verdicts are matched back to the downstream by nonce; the
+                // session only knows the proxy's single stats thread
+                let submission = (
+                    job_id.to_string(),
+                    nonce_hex,
+                    result,
+                    0,
+                    difficulty,
+                    String::new(),
+                    0,
+                );
+                if self.share_tx.send(submission).is_err() {
+                    return Some(error_reply(request_id, "Upstream pool unavailable"));
+                }
+                self.stats.shares_submitted.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+}
+
+/// Verify a downstream share: nonce prefix, hash and difficulty.
+/// Returns the result hash and share difficulty.
+fn check_share(
+    job: &MiningJob,
+    nonce_hex: &str,
+    xn: &str,
+) -> std::result::Result<(String, u64), &'static str> {
+    if !nonce_hex.starts_with(&xn.to_ascii_lowercase()) {
+        return Err("Invalid nonce: wrong xn prefix");
+    }
+    let nonce: [u8; 8] = hex::decode(nonce_hex)
+        .ok()
+        .and_then(|n| n.try_into().ok())
+        .ok_or("Invalid nonce")?;
+    let hash = sha3x_hash_with_nonce(&job.mining_hash, nonce);
+    let difficulty = calculate_difficulty(&hash, Algorithm::Sha3x);
+    if difficulty < job.target_difficulty {
+        return Err("Low difficulty share");
+    }
+    Ok((hex::encode(hash), difficulty))
+}
+
+/// Job params in the Tari Stratum format with the downstream's prefix
+fn job_params(job: &MiningJob, xn: &str) -> Value {
+    json!({
+        "job_id": job.job_id,
+        "blob": hex::encode(&job.mining_hash),
+        "target": hex::encode((u64::MAX / job.target_difficulty.max(1)).to_le_bytes()),
+        "algo": "sha3x",
+        "height": job.height,
+        "difficulty": job.target_difficulty,
+        "xn": xn,
+    })
+}
+
+fn job_notification(job: &MiningJob, xn: &str) -> String {
+    json!({"jsonrpc": "2.0", "method": "job", "params": job_params(job, xn)}).to_string()
+}
+
+fn ok_reply(id: &Value, status: &str) -> String {
+    json!({"id": id, "jsonrpc": "2.0", "error": null, "result": {"status": status}}).to_string()
+}
+
+fn error_reply(id: &Value, message: &str) -> String {
+    json!({
+        "id": id,
+        "jsonrpc": "2.0",
+        "error": {"code": -1, "message": message},
+        "result": null
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pool::mock::{MockJob, MockPool};
+    use crate::pool::{PoolClient, PoolSessionConfig};
+    use std::time::Duration;
+    use tokio::io::{Lines, ReadHalf, WriteHalf};
+
+    type Client = (Lines<BufReader<ReadHalf<TcpStream>>>, WriteHalf<TcpStream>);
+
+    async fn connect(address: SocketAddr) -> Client {
+        let stream = TcpStream::connect(address).await.unwrap();
+        let (reader, writer) = tokio::io::split(stream);
+        (BufReader::new(reader).lines(), writer)
+    }
+
+    async fn request(client: &mut Client, message: Value) -> Value {
+        client
+            .1
+            .write_all(format!("{}\n", message).as_bytes())
+            .await
+            .unwrap();
+        let line = tokio::time::timeout(Duration::from_secs(10), client.0.next_line())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+
+    fn find_nonce(blob: &[u8], xn: u16, difficulty: u64) -> String {
+        (0u64..)
+            .map(|local| ((local << 16) | xn as u64).to_le_bytes())
+            .find(|nonce| {
+                calculate_difficulty(&sha3x_hash_with_nonce(blob, *nonce), Algorithm::Sha3x)
+                    >= difficulty
+            })
+            .map(hex::encode)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_xn_allocator() {
+        let mut allocator = XnAllocator::default();
+        assert_eq!(allocator.allocate(), Some(0));
+        assert_eq!(allocator.allocate(), Some(1));
+        allocator.release(0);
+        assert_eq!(allocator.allocate(), Some(2));
+        assert_eq!(allocator.in_use(), 2);
+
+        for _ in 0..(u16::MAX as usize - 1) {
+            assert!(allocator.allocate().is_some());
+        }
+        assert_eq!(allocator.in_use(), 65536);
+        assert_eq!(allocator.allocate(), None);
+        allocator.release(7);
+        assert_eq!(allocator.allocate(), Some(7));
+    }
+
+    #[tokio::test]
+    async fn test_proxy_splits_nonce_space_and_relays_verdicts() {
+        let pool = MockPool::start().await.unwrap();
+        let job = MockJob::new("upstream-job", 77, 40);
+        pool.send_job(job.clone());
+
+        let stats = Arc::new(MinerStats::new(1));
+        let session = Arc::new(
+            PoolSession::new(
+                PoolSessionConfig::single(
+                    pool.address(),
+                    "wallet".to_string(),
+                    "proxy".to_string(),
+                    Algorithm::Sha3x,
+                    "PROXY",
+                ),
+                Arc::new(PoolClient::new()),
+                Arc::clone(&stats),
+            )
+            .unwrap(),
+        );
+        let proxy = StratumProxy::bind("127.0.0.1:0", session, Arc::clone(&stats))
+            .await
+            .unwrap();
+        let address = proxy.local_addr().unwrap();
+        tokio::spawn(Arc::clone(&proxy).run());
+        pool.wait_for_logins(1, Duration::from_secs(10))
+            .await
+            .unwrap();
+        // Let the login job reach the proxy before downstreams join
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let mut rig1 = connect(address).await;
+        let mut rig2 = connect(address).await;
+        let login = |rig: &str| {
+            json!({"id": 1, "jsonrpc": "2.0", "method": "login",
+                "params": {"login": "wallet", "pass": rig, "agent": "test", "algo": ["sha3x"]}})
+        };
+        let login1 = request(&mut rig1, login("rig1")).await;
+        let login2 = request(&mut rig2, login("rig2")).await;
+        let xn1 = login1["result"]["job"]["xn"].as_str().unwrap().to_string();
+        let xn2 = login2["result"]["job"]["xn"].as_str().unwrap().to_string();
+        assert_ne!(xn1, xn2);
+        assert_eq!(login1["result"]["job"]["job_id"], "upstream-job");
+        assert_eq!(login1["result"]["job"]["difficulty"], 40);
+        assert_eq!(proxy.downstream_count(), 2);
+
+        for (rig, xn) in [(&mut rig1, &xn1), (&mut rig2, &xn2)] {
+            let prefix = u16::from_le_bytes(hex::decode(xn).unwrap().try_into().unwrap());
+            let nonce = find_nonce(&job.blob, prefix, job.difficulty);
+            let submit = json!({"id": 5, "jsonrpc": "2.0", "method": "submit",
+                "params": {"id": "proxy", "job_id": "upstream-job", "nonce": nonce, "result": ""}});
+            let reply = request(rig, submit).await;
+            assert_eq!(reply["result"]["status"], "OK", "{}", reply);
+        }
+
+        // A nonce outside the downstream's prefix never reaches the pool
+        let foreign = find_nonce(&job.blob, 0xbeef, job.difficulty);
+        let submit = json!({"id": 6, "jsonrpc": "2.0", "method": "submit",
+            "params": {"id": "proxy", "job_id": "upstream-job", "nonce": foreign, "result": ""}});
+        let reply = request(&mut rig1, submit).await;
+        assert!(reply["error"]["message"].as_str().unwrap().contains("xn"));
+
+        let shares = pool.shares();
+        assert_eq!(shares.len(), 2);
+        assert!(shares.iter().all(|share| share.is_accepted()));
+
+        let downstreams = stats.downstream_infos();
+        assert_eq!(downstreams.len(), 2);
+        let rig1_info = downstreams.iter().find(|d| d.worker == "rig1").unwrap();
+        assert_eq!(rig1_info.accepted_shares, 1);
+        assert_eq!(rig1_info.rejected_shares, 1);
+    }
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial Stratum proxy mode.
+//   - One upstream PoolSession shared by many downstream SHA3x miners.
+//   - Distinct 2-byte xn nonce prefix per downstream (upstream xn passed through).
+//   - Shares verified locally before forwarding; pool verdicts relayed back.
+//   - Per-downstream stats registered in MinerStats.
@@ -0,0 +1,311 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/pool/stratum_v1.rs
+// Version: 1.0.0
+//
+// This file implements the Bitcoin Stratum V1 job handling used for
+// `--algo sha256`. It parses the mining.subscribe result (extranonce1 and
+// extranonce2 size), mining.notify and mining.set_difficulty, and turns them
+// into SHA-256 MiningJobs carrying an 80-byte header template plus the
+// coinbase parts the mining threads need to roll extranonce2.
+//
+// Tree Location:
+// - src/pool/stratum_v1.rs (Stratum V1 SHA-256 job state)
+// - Depends on: core/bitcoin, core/types, serde_json
+
+use crate::core::bitcoin::{
+    CoinbaseTemplate, build_header, pool_difficulty_to_target, stratum_prev_hash_to_header,
+};
+use crate::core::{Algorithm, MiningJob};
+use serde_json::Value;
+
+/// Extranonce assignment from mining.subscribe / mining.set_extranonce
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subscription {
+    pub extranonce1: Vec<u8>,
+    pub extranonce2_size: usize,
+}
+
+/// Parameters of a mining.notify message
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotifyJob {
+    pub job_id: String,
+    /// Previous block hash as sent by the pool (32-bit words byte-swapped)
+    pub prev_hash: [u8; 32],
+    pub coinb1: Vec<u8>,
+    pub coinb2: Vec<u8>,
+    pub merkle_branch: Vec<[u8; 32]>,
+    pub version: u32,
+    pub nbits: u32,
+    pub ntime: u32,
+    pub clean_jobs: bool,
+}
+
+/// Parse the mining.subscribe result: `[subscriptions, extranonce1, extranonce2_size]`
+pub fn parse_subscribe_result(result: &Value) -> Result<Subscription, String> {
+    let fields = result
+        .as_array()
+        .ok_or("mining.subscribe result must be an array")?;
+    let extranonce1 = fields
+        .get(1)
+        .and_then(|e| e.as_str())
+        .ok_or("mining.subscribe result is missing extranonce1")?;
+    let extranonce2_size = fields
+        .get(2)
+        .and_then(|s| s.as_u64())
+        .ok_or("mining.subscribe result is missing extranonce2_size")?;
+    parse_extranonce(extranonce1, extranonce2_size)
+}
+
+/// Parse mining.set_extranonce params: `[extranonce1, extranonce2_size]`
+pub fn parse_set_extranonce(params: &Value) -> Result<Subscription, String> {
+    let extranonce1 = params
+        .get(0)
+        .and_then(|e| e.as_str())
+        .ok_or("mining.set_extranonce is missing extranonce1")?;
+    let extranonce2_size = params
+        .get(1)
+        .and_then(|s| s.as_u64())
+        .ok_or("mining.set_extranonce is missing extranonce2_size")?;
+    parse_extranonce(extranonce1, extranonce2_size)
+}
+
+fn parse_extranonce(extranonce1: &str, extranonce2_size: u64) -> Result<Subscription, String> {
+    let extranonce1 =
+        hex::decode(extranonce1).map_err(|_| format!("Invalid extranonce1 '{}'", extranonce1))?;
+    if !(1..=16).contains(&extranonce2_size) {
+        return Err(format!(
+            "Unsupported extranonce2_size {} (expected 1-16)",
+            extranonce2_size
+        ));
+    }
+    Ok(Subscription {
+        extranonce1,
+        extranonce2_size: extranonce2_size as usize,
+    })
+}
+
+/// Parse mining.notify params:
+/// `[job_id, prevhash, coinb1, coinb2, merkle_branch, version, nbits, ntime, clean_jobs]`
+pub fn parse_notify(params: &Value) -> Result<NotifyJob, String> {
+    let params = params
+        .as_array()
+        .ok_or("mining.notify params must be an array")?;
+    if params.len() < 9 {
+        return Err(format!(
+            "mining.notify needs 9 params, got {}",
+            params.len()
+        ));
+    }
+
+    let string = |index: usize, name: &str| {
+        params[index]
+            .as_str()
+            .ok_or_else(|| format!("mining.notify {} must be a string", name))
+    };
+    let bytes = |index: usize, name: &str| {
+        hex::decode(string(index, name)?).map_err(|_| format!("mining.notify {} is not hex", name))
+    };
+    let word = |index: usize, name: &str| {
+        u32::from_str_radix(string(index, name)?, 16)
+            .map_err(|_| format!("mining.notify {} is not a 32-bit hex value", name))
+    };
+    let hash = |hex_str: &str, name: &str| -> Result<[u8; 32], String> {
+        hex::decode(hex_str)
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| format!("mining.notify {} must be 32 bytes of hex", name))
+    };
+
+    let merkle_branch = params[4]
+        .as_array()
+        .ok_or("mining.notify merkle_branch must be an array")?
+        .iter()
+        .map(|branch| hash(branch.as_str().unwrap_or_default(), "merkle branch"))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(NotifyJob {
+        job_id: string(0, "job_id")?.to_string(),
+        prev_hash: hash(string(1, "prevhash")?, "prevhash")?,
+        coinb1: bytes(2, "coinb1")?,
+        coinb2: bytes(3, "coinb2")?,
+        merkle_branch,
+        version: word(5, "version")?,
+        nbits: word(6, "nbits")?,
+        ntime: word(7, "ntime")?,
+        clean_jobs: params[8].as_bool().unwrap_or(false),
+    })
+}
+
+/// Block height from the BIP34 push at the start of the coinbase script, if present
+pub fn coinbase_height(coinb1: &[u8]) -> Option<u64> {
+    // version (4) + input count (1) + prevout (36) + script length (1)
+    const SCRIPT_START: usize = 42;
+    let push_len = *coinb1.get(SCRIPT_START)? as usize;
+    if !(1..=8).contains(&push_len) {
+        return None;
+    }
+    let height_bytes = coinb1.get(SCRIPT_START + 1..SCRIPT_START + 1 + push_len)?;
+    let mut height = [0u8; 8];
+    height[..push_len].copy_from_slice(height_bytes);
+    Some(u64::from_le_bytes(height))
+}
+
+/// Stratum V1 state of one pool connection
+#[derive(Debug, Clone)]
+pub struct StratumV1State {
+    pub subscription: Option<Subscription>,
+    /// Share difficulty from the last mining.set_difficulty
+    pub difficulty: f64,
+    /// Last job, rebuilt when the difficulty or extranonce changes
+    pub last_notify: Option<NotifyJob>,
+}
+
+impl Default for StratumV1State {
+    fn default() -> Self {
+        Self {
+            subscription: None,
+            difficulty: 1.0,
+            last_notify: None,
+        }
+    }
+}
+
+impl StratumV1State {
+    /// Build the MiningJob for a notify with the current extranonce and difficulty
+    pub fn mining_job(&self, notify: &NotifyJob) -> Result<MiningJob, String> {
+        let subscription = self
+            .subscription
+            .as_ref()
+            .ok_or("mining.notify received before the mining.subscribe response")?;
+
+        let prev_hash = stratum_prev_hash_to_header(&notify.prev_hash);
+        // Merkle root and ntime are filled in per batch by the mining threads
+        let header = build_header(
+            notify.version,
+            &prev_hash,
+            &[0u8; 32],
+            notify.ntime,
+            notify.nbits,
+        );
+
+        Ok(MiningJob {
+            job_id: notify.job_id.clone(),
+            mining_hash: header.to_vec(),
+            target_difficulty: self.difficulty.max(1.0).round() as u64,
+            height: coinbase_height(&notify.coinb1).unwrap_or(0),
+            algo: Algorithm::Sha256,
+            extranonce2: None,
+            prev_hash: Some(prev_hash.to_vec()),
+            merkle_root: None,
+            version: Some(notify.version),
+            ntime: Some(notify.ntime),
+            nbits: Some(notify.nbits),
+            merkle_path: Some(notify.merkle_branch.iter().map(|b| b.to_vec()).collect()),
+            target: Some(pool_difficulty_to_target(self.difficulty)),
+            coinbase: Some(CoinbaseTemplate {
+                coinb1: notify.coinb1.clone(),
+                coinb2: notify.coinb2.clone(),
+                extranonce1: subscription.extranonce1.clone(),
+                extranonce2_size: subscription.extranonce2_size,
+                merkle_branch: notify.merkle_branch.clone(),
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn notify_params() -> Value {
+        json!([
+            "bf",
+            "4d16b6f85af6e2198f44ae2a6de67f78487ae5611b77c6c0440b921e00000000",
+            "01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff20020862062f503253482f04b8864e5008",
+            "072f736c7573682f000000000100f2052a010000001976a914d23fcdf86f7e756a64a7a9688ef9903327048ed988ac00000000",
+            [],
+            "00000002",
+            "1c2ac4af",
+            "504e86b9",
+            false
+        ])
+    }
+
+    #[test]
+    fn test_parse_subscribe_result() {
+        let result = json!([
+            [
+                ["mining.set_difficulty", "b4b6693b"],
+                ["mining.notify", "ae6812eb"]
+            ],
+            "08000002",
+            4
+        ]);
+        let subscription = parse_subscribe_result(&result).unwrap();
+        assert_eq!(subscription.extranonce1, vec![0x08, 0x00, 0x00, 0x02]);
+        assert_eq!(subscription.extranonce2_size, 4);
+
+        assert!(parse_subscribe_result(&json!([[], "zz", 4])).is_err());
+        assert!(parse_subscribe_result(&json!([[], "08", 0])).is_err());
+    }
+
+    #[test]
+    fn test_parse_notify() {
+        let notify = parse_notify(&notify_params()).unwrap();
+        assert_eq!(notify.job_id, "bf");
+        assert_eq!(notify.version, 2);
+        assert_eq!(notify.nbits, 0x1c2ac4af);
+        assert_eq!(notify.ntime, 0x504e86b9);
+        assert!(!notify.clean_jobs);
+        assert!(notify.merkle_branch.is_empty());
+
+        assert!(parse_notify(&json!(["bf"])).is_err());
+    }
+
+    #[test]
+    fn test_mining_job_from_notify() {
+        let mut state = StratumV1State::default();
+        let notify = parse_notify(&notify_params()).unwrap();
+        assert!(state.mining_job(&notify).is_err());
+
+        state.subscription = Some(Subscription {
+            extranonce1: vec![0x08, 0x00, 0x00, 0x02],
+            extranonce2_size: 4,
+        });
+        state.difficulty = 2.0;
+        let job = state.mining_job(&notify).unwrap();
+
+        assert_eq!(job.algo, Algorithm::Sha256);
+        assert_eq!(job.mining_hash.len(), 80);
+        assert_eq!(&job.mining_hash[..4], &[2, 0, 0, 0]);
+        // prevhash words are byte-swapped back into header order
+        assert_eq!(&job.mining_hash[4..8], &[0xf8, 0xb6, 0x16, 0x4d]);
+        assert_eq!(&job.mining_hash[72..76], &0x1c2ac4afu32.to_le_bytes());
+        assert_eq!(job.target_difficulty, 2);
+        assert_eq!(job.target, Some(pool_difficulty_to_target(2.0)));
+        // BIP34 height push "020862" = 2 bytes, height 0x6208
+        assert_eq!(job.height, 0x6208);
+
+        let coinbase = job.coinbase.unwrap();
+        let full = coinbase.coinbase(&[0, 0, 0, 0]);
+        assert_eq!(full.len(), notify.coinb1.len() + 8 + notify.coinb2.len());
+    }
+
+    #[test]
+    fn test_coinbase_height_without_bip34() {
+        assert_eq!(coinbase_height(&[0u8; 10]), None);
+    }
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial Stratum V1 SHA-256 job handling.
+//   - mining.subscribe / mining.set_extranonce parsing (extranonce1, extranonce2 size).
+//   - mining.notify parsing and MiningJob construction with a coinbase template.
+//   - Share targets from mining.set_difficulty; block height from the BIP34 coinbase push.
@@ -0,0 +1,925 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/pool/sv2_client.rs
+// Version: 1.0.2
+//
+// This file implements the Stratum V2 mining client used for sv2:// pools. It
+// performs the Noise handshake, SetupConnection and OpenStandardMiningChannel,
+// turns NewMiningJob / SetNewPrevHash / SetTarget into SHA-256 MiningJobs for
+// the miners, and submits shares with sequence numbers, accounting for the
+// batched SubmitShares.Success acknowledgements.
+//
+// Tree Location:
+// - src/pool/sv2_client.rs (Stratum V2 mining client)
+// - Depends on: pool/sv2_noise, pool/sv2_protocol, pool/failover, pool/session
+
+use crate::Result;
+use crate::core::{Algorithm, MiningJob, ShareStatus};
+use crate::miner::stats::MinerStats;
+use crate::pool::PoolClient;
+use crate::pool::failover::{PoolEndpoint, PoolFailover};
+use crate::pool::session::{
+    PoolSessionConfig, ShareOutcome, ShareSubmission, report_share_outcome,
+};
+use crate::pool::sv2_noise::{self, Sv2FrameReader, Sv2FrameWriter, parse_authority_key};
+use crate::pool::sv2_protocol::{Sv2Message, Sv2Protocol};
+use crate::pool::transport::{PoolStream, PoolUrl};
+use log::{debug, error, info, warn};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{ReadHalf, WriteHalf};
+use tokio::sync::{broadcast, mpsc};
+
+const LOG_TARGET: &str = "tari::graxil::pool::sv2";
+
+/// Delay between a lost connection and the next connection attempt
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+/// Limit for the Noise handshake plus SetupConnection / OpenChannel exchange
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(20);
+/// How often the client checks whether a failback probe is due
+const FAILBACK_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// Connect timeout for failback probes
+const FAILBACK_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Submitted shares without a pool acknowledgement after this long are dropped
+const PENDING_SHARE_TIMEOUT: Duration = Duration::from_secs(60);
+/// Hashrate announced when opening a channel before the miners report one (10 MH/s)
+const DEFAULT_NOMINAL_HASHRATE: f32 = 10_000_000.0;
+
+type FrameReader = Sv2FrameReader<ReadHalf<PoolStream>>;
+type FrameWriter = Sv2FrameWriter<WriteHalf<PoolStream>>;
+
+/// Established SV2 connection with an open standard mining channel
+struct Sv2Connection {
+    reader: FrameReader,
+    writer: FrameWriter,
+    protocol: Sv2Protocol,
+    channel_id: u32,
+    target: [u8; 32],
+}
+
+/// Why a connection was left
+enum Disconnect {
+    /// Connection lost or closed by the pool
+    Lost,
+    /// A preferred pool is reachable again
+    Failback,
+    /// Every mining backend has stopped
+    Shutdown,
+}
+
+/// NewMiningJob fields needed to build a block header
+#[derive(Debug, Clone)]
+struct JobTemplate {
+    job_id: u32,
+    version: u32,
+    merkle_root: [u8; 32],
+    min_ntime: Option<u32>,
+}
+
+/// SetNewPrevHash fields needed to build a block header
+#[derive(Debug, Clone, Copy)]
+struct ChainTip {
+    prev_hash: [u8; 32],
+    min_ntime: u32,
+    nbits: u32,
+}
+
+/// Channel job state: turns SV2 job messages into MiningJobs
+#[derive(Debug, Default)]
+struct Sv2JobState {
+    /// Channel target, little-endian as sent by the pool
+    target: [u8; 32],
+    tip: Option<ChainTip>,
+    /// Future jobs waiting for the SetNewPrevHash that activates them
+    future_jobs: HashMap<u32, JobTemplate>,
+    active: Option<JobTemplate>,
+    /// Block version of every job the miners may still submit shares for
+    versions: HashMap<u32, u32>,
+}
+
+impl Sv2JobState {
+    fn new(target: [u8; 32]) -> Self {
+        Self {
+            target,
+            ..Default::default()
+        }
+    }
+
+    /// NewMiningJob: returns the job to mine when it applies to the current tip
+    fn on_new_job(&mut self, job: JobTemplate) -> Option<MiningJob> {
+        self.versions.insert(job.job_id, job.version);
+        if job.min_ntime.is_none() {
+            self.future_jobs.insert(job.job_id, job);
+            return None;
+        }
+        self.active = Some(job);
+        self.current_job()
+    }
+
+    /// SetNewPrevHash: activates the referenced future job and drops the others
+    fn on_set_new_prev_hash(
+        &mut self,
+        job_id: u32,
+        prev_hash: [u8; 32],
+        min_ntime: u32,
+        nbits: u32,
+    ) -> Option<MiningJob> {
+        self.tip = Some(ChainTip {
+            prev_hash,
+            min_ntime,
+            nbits,
+        });
+
+        let activated = self.future_jobs.remove(&job_id);
+        self.future_jobs.clear();
+        // Shares for jobs on the old tip are stale now
+        self.versions.retain(|id, _| *id == job_id);
+
+        match activated {
+            Some(job) => self.active = Some(job),
+            None if self.active.as_ref().is_some_and(|job| job.job_id == job_id) => {}
+            None => {
+                warn!(target: LOG_TARGET,"⚠️ SetNewPrevHash references unknown job {}", job_id);
+                self.active = None;
+            }
+        }
+        self.current_job()
+    }
+
+    /// SetTarget: returns the active job rebuilt with the new target
+    fn on_set_target(&mut self, target: [u8; 32]) -> Option<MiningJob> {
+        self.target = target;
+        self.current_job()
+    }
+
+    fn version_for(&self, job_id: u32) -> Option<u32> {
+        self.versions.get(&job_id).copied()
+    }
+
+    fn current_job(&self) -> Option<MiningJob> {
+        let job = self.active.as_ref()?;
+        let tip = self.tip?;
+        let ntime = job.min_ntime.unwrap_or(tip.min_ntime).max(tip.min_ntime);
+
+        // SV2 sends hashes in header byte order, so the header is a plain concatenation
+        let mut header = Vec::with_capacity(80);
+        header.extend_from_slice(&job.version.to_le_bytes());
+        header.extend_from_slice(&tip.prev_hash);
+        header.extend_from_slice(&job.merkle_root);
+        header.extend_from_slice(&ntime.to_le_bytes());
+        header.extend_from_slice(&tip.nbits.to_le_bytes());
+        header.extend_from_slice(&[0u8; 4]);
+
+        Some(MiningJob {
+            job_id: job.job_id.to_string(),
+            mining_hash: header,
+            target_difficulty: Sv2Protocol::target_to_difficulty(&self.target),
+            height: 0,
+            algo: Algorithm::Sha256,
+            extranonce2: None,
+            prev_hash: Some(tip.prev_hash.to_vec()),
+            merkle_root: Some(job.merkle_root.to_vec()),
+            version: Some(job.version),
+            ntime: Some(ntime),
+            nbits: Some(tip.nbits),
+            merkle_path: None,
+            target: Some(Sv2Protocol::target_to_big_endian(&self.target)),
+            coinbase: None,
+        })
+    }
+}
+
+/// Share waiting for a SubmitShares.Success / Error, keyed by sequence number
+#[derive(Debug, Clone)]
+struct PendingShare {
+    job_id: String,
+    nonce: String,
+    thread_id: usize,
+    difficulty: u64,
+    sent_at: Instant,
+}
+
+/// Sequence number bookkeeping for submitted shares
+#[derive(Debug, Default)]
+struct Sv2ShareTracker {
+    pending: BTreeMap<u32, PendingShare>,
+}
+
+impl Sv2ShareTracker {
+    fn insert(&mut self, sequence: u32, share: PendingShare) {
+        self.pending.insert(sequence, share);
+    }
+
+    /// SubmitShares.Success acknowledges every share up to `last_sequence`
+    fn accept_through(&mut self, last_sequence: u32) -> Vec<(u32, PendingShare)> {
+        let remaining = self.pending.split_off(&last_sequence.saturating_add(1));
+        std::mem::replace(&mut self.pending, remaining)
+            .into_iter()
+            .collect()
+    }
+
+    fn reject(&mut self, sequence: u32) -> Option<PendingShare> {
+        self.pending.remove(&sequence)
+    }
+
+    /// Drop shares older than `timeout`, returning how many were dropped
+    fn expire(&mut self, timeout: Duration) -> usize {
+        let before = self.pending.len();
+        self.pending
+            .retain(|_, share| share.sent_at.elapsed() < timeout);
+        before - self.pending.len()
+    }
+
+    fn clear(&mut self) -> usize {
+        let dropped = self.pending.len();
+        self.pending.clear();
+        dropped
+    }
+}
+
+/// Stratum V2 mining client for sv2:// pool lists
+pub struct Sv2Client {
+    config: PoolSessionConfig,
+    pool_client: Arc<PoolClient>,
+    stats: Arc<MinerStats>,
+}
+
+impl Sv2Client {
+    pub fn new(
+        config: PoolSessionConfig,
+        pool_client: Arc<PoolClient>,
+        stats: Arc<MinerStats>,
+    ) -> Self {
+        Self {
+            config,
+            pool_client,
+            stats,
+        }
+    }
+
+    /// Connect, open a channel and service the pool list until the backends stop
+    pub async fn run(
+        &self,
+        job_tx: broadcast::Sender<MiningJob>,
+        mut share_rx: mpsc::UnboundedReceiver<ShareSubmission>,
+        outcome_tx: broadcast::Sender<ShareOutcome>,
+    ) -> Result<()> {
+        if self.config.algo != Algorithm::Sha256 {
+            return Err("Stratum V2 pools require --algo sha256".into());
+        }
+        let mut failover =
+            PoolFailover::new(self.config.pools.clone(), self.config.failover.clone())?;
+        let mut connected_once = false;
+        let mut failed_over = false;
+
+        loop {
+            let endpoint = failover.active().clone();
+            self.pool_client
+                .set_active_pool(failover.active_index(), failover.pools().len());
+
+            let connection = tokio::time::timeout(HANDSHAKE_TIMEOUT, self.connect(&endpoint))
+                .await
+                .unwrap_or_else(|_| Err("SV2 handshake timed out".into()));
+
+            let connection = match connection {
+                Ok(connection) => connection,
+                Err(e) => {
+                    self.pool_client.mark_disconnected();
+                    let decision = failover.record_failure();
+                    let next = failover.active().address.clone();
+                    if decision.failed_over {
+                        failed_over = true;
+                        warn!(target: LOG_TARGET,
+                            "🔀 {} SV2 pool {} failed ({}), failing over to {}",
+                            self.config.label, endpoint.address, e, next
+                        );
+                        self.stats.add_activity(format!(
+                            "🔀 {} failing over to {}",
+                            self.config.label, next
+                        ));
+                    } else {
+                        warn!(target: LOG_TARGET,
+                            "⚠️ {} SV2 connection to {} failed: {} (retrying in {}s)",
+                            self.config.label,
+                            endpoint.address,
+                            e,
+                            decision.delay.as_secs()
+                        );
+                    }
+                    tokio::time::sleep(decision.delay).await;
+                    continue;
+                }
+            };
+            failover.record_success();
+            if connected_once || failed_over {
+                self.pool_client.record_reconnect();
+            }
+            (connected_once, failed_over) = (true, false);
+
+            let disconnect = self
+                .serve(
+                    connection,
+                    &mut failover,
+                    &job_tx,
+                    &mut share_rx,
+                    &outcome_tx,
+                )
+                .await;
+            self.pool_client.mark_disconnected();
+
+            match disconnect {
+                Disconnect::Shutdown => {
+                    info!(target: LOG_TARGET,"🛑 {} mining backends stopped, closing SV2 session", self.config.label);
+                    return Ok(());
+                }
+                Disconnect::Failback => {}
+                Disconnect::Lost => {
+                    info!(target: LOG_TARGET,
+                        "📡 {} SV2 connection lost, reconnecting in {}s...",
+                        self.config.label,
+                        RECONNECT_DELAY.as_secs()
+                    );
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                }
+            }
+        }
+    }
+
+    /// Handshake, SetupConnection and channel open against the first pool,
+    /// then close the channel; returns the channel id the pool assigned
+    pub async fn test_connection(&self) -> Result<u32> {
+        let endpoint = self.config.pools.first().ok_or("No SV2 pool configured")?;
+        let mut connection = tokio::time::timeout(HANDSHAKE_TIMEOUT, self.connect(endpoint))
+            .await
+            .map_err(|_| "SV2 handshake timed out")??;
+
+        let close = connection
+            .protocol
+            .create_close_channel(connection.channel_id, "connection test")?;
+        connection.writer.write_frame(&close).await?;
+        self.pool_client.mark_disconnected();
+        Ok(connection.channel_id)
+    }
+
+    /// Noise handshake, SetupConnection and OpenStandardMiningChannel
+    async fn connect(&self, endpoint: &PoolEndpoint) -> Result<Sv2Connection> {
+        let url = PoolUrl::parse(&endpoint.address)?;
+        let authority_key = url
+            .authority_key
+            .as_deref()
+            .map(parse_authority_key)
+            .transpose()?;
+
+        let stream = self
+            .pool_client
+            .connect_pool(&endpoint.address, &endpoint.tls)
+            .await?;
+        let (mut reader, mut writer) = sv2_noise::handshake(stream, authority_key).await?;
+        self.pool_client.set_encrypted(true);
+
+        let protocol = Sv2Protocol::new();
+        let port = url
+            .address
+            .rsplit_once(':')
+            .and_then(|(_, port)| port.parse().ok())
+            .unwrap_or_default();
+        writer
+            .write_frame(&protocol.create_setup_connection(url.host(), port)?)
+            .await?;
+
+        match read_message(&protocol, &mut reader).await? {
+            Sv2Message::SetupSuccess {
+                used_version,
+                flags,
+            } => {
+                debug!(target: LOG_TARGET,"SV2 setup accepted (version {}, flags {:#x})", used_version, flags);
+            }
+            Sv2Message::SetupError { error_code, .. } => {
+                return Err(format!("SV2 SetupConnection rejected: {}", error_code).into());
+            }
+            other => {
+                return Err(
+                    format!("Unexpected SV2 response to SetupConnection: {:?}", other).into(),
+                );
+            }
+        }
+
+        let user_identity = endpoint.user_identity();
+        let hashrate = (self.stats.get_total_hashrate() as f32).max(DEFAULT_NOMINAL_HASHRATE);
+        let (request_id, open_channel) = protocol.create_open_channel(
+            &user_identity,
+            hashrate,
+            Sv2Protocol::calculate_max_target(hashrate),
+        )?;
+        writer.write_frame(&open_channel).await?;
+
+        let (channel_id, target) = loop {
+            match read_message(&protocol, &mut reader).await? {
+                Sv2Message::ChannelSuccess {
+                    request_id: id,
+                    channel_id,
+                    target,
+                    ..
+                } if id == request_id => break (channel_id, target),
+                Sv2Message::ChannelError { error_code, .. } => {
+                    return Err(
+                        format!("SV2 OpenStandardMiningChannel rejected: {}", error_code).into(),
+                    );
+                }
+                other => {
+                    debug!(target: LOG_TARGET,"Ignoring SV2 message before channel open: {:?}", other)
+                }
+            }
+        };
+
+        info!(target: LOG_TARGET,
+            "✅ {} connected to SV2 pool {} (🔐 Noise{}), channel {} opened for {}",
+            self.config.label,
+            url.address,
+            if authority_key.is_some() { ", authority verified" } else { "" },
+            channel_id,
+            user_identity
+        );
+        self.stats.add_activity(format!(
+            "🔐 {} connected to SV2 pool {}",
+            self.config.label, url.address
+        ));
+
+        Ok(Sv2Connection {
+            reader,
+            writer,
+            protocol,
+            channel_id,
+            target,
+        })
+    }
+
+    /// Service one connection until it is lost, a failback is due or the backends stop
+    async fn serve(
+        &self,
+        connection: Sv2Connection,
+        failover: &mut PoolFailover,
+        job_tx: &broadcast::Sender<MiningJob>,
+        share_rx: &mut mpsc::UnboundedReceiver<ShareSubmission>,
+        outcome_tx: &broadcast::Sender<ShareOutcome>,
+    ) -> Disconnect {
+        let Sv2Connection {
+            reader,
+            mut writer,
+            protocol,
+            channel_id,
+            target,
+        } = connection;
+
+        // Frame reads are not cancel-safe, so they run on their own task
+        let (message_tx, mut message_rx) = mpsc::unbounded_channel();
+        let reader_task = tokio::spawn(Self::read_messages(reader, message_tx));
+
+        let mut jobs = Sv2JobState::new(target);
+        let mut shares = Sv2ShareTracker::default();
+        let mut failback_timer = tokio::time::interval(FAILBACK_CHECK_INTERVAL);
+        let mut expiry_timer = tokio::time::interval(Duration::from_secs(5));
+
+        let disconnect = loop {
+            tokio::select! {
+                message = message_rx.recv() => match message {
+                    Some(Ok(message)) => {
+                        if let Some(disconnect) =
+                            self.handle_message(message, &mut jobs, &mut shares, job_tx, outcome_tx)
+                        {
+                            break disconnect;
+                        }
+                    }
+                    Some(Err(e)) => {
+                        error!(target: LOG_TARGET,"📡 Error reading from {} SV2 pool: {}", self.config.label, e);
+                        break Disconnect::Lost;
+                    }
+                    None => break Disconnect::Lost,
+                },
+                share = share_rx.recv() => {
+                    let Some(share) = share else {
+                        break Disconnect::Shutdown;
+                    };
+                    if let Err(e) = self
+                        .submit_share(share, channel_id, &protocol, &jobs, &mut shares, &mut writer)
+                        .await
+                    {
+                        error!(target: LOG_TARGET,"Failed to submit {} SV2 share: {}", self.config.label, e);
+                    }
+                }
+                _ = failback_timer.tick() => {
+                    if self.try_failback(failover).await {
+                        break Disconnect::Failback;
+                    }
+                }
+                _ = expiry_timer.tick() => {
+                    let expired = shares.expire(PENDING_SHARE_TIMEOUT);
+                    if expired > 0 {
+                        warn!(target: LOG_TARGET,
+                            "⚠️ {} SV2 pool never acknowledged {} share(s) within {}s",
+                            self.config.label,
+                            expired,
+                            PENDING_SHARE_TIMEOUT.as_secs()
+                        );
+                    }
+                }
+            }
+        };
+
+        reader_task.abort();
+        if let Ok(close) = protocol.create_close_channel(channel_id, "client disconnecting") {
+            let _ = writer.write_frame(&close).await;
+        }
+        let dropped = shares.clear();
+        if dropped > 0 {
+            warn!(target: LOG_TARGET,
+                "⚠️ {} dropped {} unacknowledged SV2 share(s) on disconnect",
+                self.config.label, dropped
+            );
+        }
+        disconnect
+    }
+
+    async fn read_messages(
+        mut reader: FrameReader,
+        message_tx: mpsc::UnboundedSender<std::result::Result<Sv2Message, String>>,
+    ) {
+        let protocol = Sv2Protocol::new();
+        loop {
+            let message = read_message(&protocol, &mut reader)
+                .await
+                .map_err(|e| e.to_string());
+            let failed = message.is_err();
+            if message_tx.send(message).is_err() || failed {
+                break;
+            }
+        }
+    }
+
+    /// Apply a pool message; returns Some when the connection should be left
+    fn handle_message(
+        &self,
+        message: Sv2Message,
+        jobs: &mut Sv2JobState,
+        shares: &mut Sv2ShareTracker,
+        job_tx: &broadcast::Sender<MiningJob>,
+        outcome_tx: &broadcast::Sender<ShareOutcome>,
+    ) -> Option<Disconnect> {
+        match message {
+            Sv2Message::NewJob {
+                job_id,
+                min_ntime,
+                version,
+                merkle_root,
+                ..
+            } => {
+                let job = jobs.on_new_job(JobTemplate {
+                    job_id,
+                    version,
+                    merkle_root,
+                    min_ntime,
+                });
+                if job.is_none() {
+                    debug!(target: LOG_TARGET,"{} SV2 future job {} queued", self.config.label, job_id);
+                }
+                self.dispatch_job(job, job_tx);
+            }
+            Sv2Message::SetNewPrevHash {
+                job_id,
+                prev_hash,
+                min_ntime,
+                nbits,
+                ..
+            } => {
+                let job = jobs.on_set_new_prev_hash(job_id, prev_hash, min_ntime, nbits);
+                self.dispatch_job(job, job_tx);
+            }
+            Sv2Message::SetTarget { target, .. } => {
+                let difficulty = Sv2Protocol::target_to_difficulty(&target);
+                info!(target: LOG_TARGET,
+                    "🎯 {} SV2 target updated, difficulty {}",
+                    self.config.label,
+                    MinerStats::format_number(difficulty)
+                );
+                self.stats.add_activity(format!(
+                    "🔧 {} VarDiff update: {}",
+                    self.config.label,
+                    MinerStats::format_number(difficulty)
+                ));
+                let job = jobs.on_set_target(target);
+                self.dispatch_job(job, job_tx);
+            }
+            Sv2Message::ShareSuccess {
+                last_sequence,
+                accepted_count,
+                shares_sum,
+                ..
+            } => {
+                let accepted = shares.accept_through(last_sequence);
+                if accepted.len() != accepted_count as usize {
+                    debug!(target: LOG_TARGET,
+                        "{} SV2 pool acknowledged {} share(s) up to #{}, {} were pending",
+                        self.config.label,
+                        accepted_count,
+                        last_sequence,
+                        accepted.len()
+                    );
+                }
+                debug!(target: LOG_TARGET,"{} SV2 accepted work so far: {}", self.config.label, shares_sum);
+                for (sequence, share) in accepted {
+                    self.report(outcome_tx, sequence, share, ShareStatus::Accepted, None);
+                }
+            }
+            Sv2Message::ShareError {
+                sequence_number,
+                error_code,
+                ..
+            } => match shares.reject(sequence_number) {
+                Some(share) => {
+                    let status = if error_code.contains("stale") {
+                        ShareStatus::Stale
+                    } else {
+                        ShareStatus::Rejected
+                    };
+                    self.report(outcome_tx, sequence_number, share, status, Some(error_code));
+                }
+                None => warn!(target: LOG_TARGET,
+                    "⚠️ {} SV2 pool rejected unknown share #{}: {}",
+                    self.config.label, sequence_number, error_code
+                ),
+            },
+            Sv2Message::CloseChannel { reason, .. } => {
+                warn!(target: LOG_TARGET,"📡 {} SV2 pool closed the channel: {}", self.config.label, reason);
+                return Some(Disconnect::Lost);
+            }
+            Sv2Message::Reconnect { new_host, new_port } => {
+                info!(target: LOG_TARGET,
+                    "🔄 {} SV2 pool asked to reconnect ({}:{}), reconnecting to the configured pool",
+                    self.config.label, new_host, new_port
+                );
+                return Some(Disconnect::Lost);
+            }
+            other if other.is_error() => {
+                error!(target: LOG_TARGET,
+                    "❌ {} SV2 pool error: {}",
+                    self.config.label,
+                    other.error_description().unwrap_or_default()
+                );
+            }
+            other => {
+                debug!(target: LOG_TARGET,"Unhandled {} SV2 message: {:?}", self.config.label, other)
+            }
+        }
+        None
+    }
+
+    fn dispatch_job(&self, job: Option<MiningJob>, job_tx: &broadcast::Sender<MiningJob>) {
+        let Some(job) = job else {
+            return;
+        };
+
+        self.stats
+            .update_job(job.job_id.clone(), job.height, job.target_difficulty);
+        info!(target: LOG_TARGET,
+            "📋 {} SV2 job: {} (difficulty: {}, ntime: {:08x})",
+            self.config.label,
+            job.job_id,
+            MinerStats::format_number(job.target_difficulty),
+            job.ntime.unwrap_or_default()
+        );
+        self.stats.add_activity(format!(
+            "📋 {} SV2 job: {} (difficulty: {})",
+            self.config.label,
+            job.job_id,
+            MinerStats::format_number(job.target_difficulty)
+        ));
+
+        if job_tx.send(job).is_err() {
+            warn!(target: LOG_TARGET,"⚠️ No {} mining backend is listening for jobs", self.config.label);
+        }
+    }
+
+    async fn submit_share(
+        &self,
+        share: ShareSubmission,
+        channel_id: u32,
+        protocol: &Sv2Protocol,
+        jobs: &Sv2JobState,
+        shares: &mut Sv2ShareTracker,
+        writer: &mut FrameWriter,
+    ) -> Result<()> {
+        let (job_id, nonce, _result, thread_id, difficulty, _extranonce2, ntime) = share;
+        let sv2_job_id: u32 = job_id
+            .parse()
+            .map_err(|_| format!("SV2 job id must be numeric, got '{}'", job_id))?;
+        let nonce_value = u32::from_str_radix(&nonce, 16)
+            .map_err(|_| format!("SV2 nonce must be 32-bit hex, got '{}'", nonce))?;
+        let Some(version) = jobs.version_for(sv2_job_id) else {
+            info!(target: LOG_TARGET,
+                "⏰ {} dropping share for job {} (no longer valid on this channel)",
+                self.config.label, job_id
+            );
+            return Ok(());
+        };
+
+        let (sequence, frame) =
+            protocol.create_submit_shares(channel_id, sv2_job_id, nonce_value, ntime, version)?;
+        info!(target: LOG_TARGET,
+            "📤 Submitting {} SV2 share #{}: job_id={}, nonce={}, thread={}, difficulty={}",
+            self.config.label,
+            sequence,
+            job_id,
+            nonce,
+            thread_id,
+            MinerStats::format_number(difficulty)
+        );
+
+        shares.insert(
+            sequence,
+            PendingShare {
+                job_id,
+                nonce,
+                thread_id,
+                difficulty,
+                sent_at: Instant::now(),
+            },
+        );
+        if let Err(e) = writer.write_frame(&frame).await {
+            shares.reject(sequence);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    fn report(
+        &self,
+        outcome_tx: &broadcast::Sender<ShareOutcome>,
+        sequence: u32,
+        share: PendingShare,
+        status: ShareStatus,
+        error: Option<String>,
+    ) {
+        report_share_outcome(
+            &self.stats,
+            outcome_tx,
+            &self.config.label,
+            ShareOutcome {
+                submit_id: sequence as u64,
+                job_id: share.job_id,
+                nonce: share.nonce,
+                thread_id: share.thread_id,
+                difficulty: share.difficulty,
+                status,
+                latency: share.sent_at.elapsed(),
+                error,
+            },
+        );
+    }
+
+    /// Probe higher-priority pools while on a backup; returns true when the
+    /// client should reconnect to a preferred pool
+    async fn try_failback(&self, failover: &mut PoolFailover) -> bool {
+        if !failover.should_probe() {
+            return false;
+        }
+        failover.mark_probed();
+
+        let candidates = failover.preferred_pools().to_vec();
+        for (index, pool) in candidates.iter().enumerate() {
+            if self
+                .pool_client
+                .probe(&pool.address, FAILBACK_PROBE_TIMEOUT)
+                .await
+            {
+                info!(target: LOG_TARGET,
+                    "🔙 {} preferred SV2 pool {} is reachable again, switching back",
+                    self.config.label, pool.address
+                );
+                self.stats.add_activity(format!(
+                    "🔙 {} returning to {}",
+                    self.config.label, pool.address
+                ));
+                failover.switch_to(index);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Read and decode the next SV2 message
+async fn read_message(protocol: &Sv2Protocol, reader: &mut FrameReader) -> Result<Sv2Message> {
+    let (header, payload) = reader.read_frame().await?;
+    protocol.decode_message(&header, &payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(job_id: &str) -> PendingShare {
+        PendingShare {
+            job_id: job_id.to_string(),
+            nonce: "00000001".to_string(),
+            thread_id: 0,
+            difficulty: 1,
+            sent_at: Instant::now(),
+        }
+    }
+
+    fn template(job_id: u32, min_ntime: Option<u32>) -> JobTemplate {
+        JobTemplate {
+            job_id,
+            version: 0x2000_0000,
+            merkle_root: [0x11; 32],
+            min_ntime,
+        }
+    }
+
+    #[test]
+    fn test_future_job_activated_by_prev_hash() {
+        let mut target = [0u8; 32];
+        target[28..].copy_from_slice(&[0xff; 4]);
+        let mut state = Sv2JobState::new(target);
+
+        assert!(state.on_new_job(template(1, None)).is_none());
+        let job = state
+            .on_set_new_prev_hash(1, [0x22; 32], 0x6650_0000, 0x1703_a30c)
+            .expect("future job becomes active");
+
+        assert_eq!(job.job_id, "1");
+        assert_eq!(job.algo, Algorithm::Sha256);
+        assert_eq!(job.mining_hash.len(), 80);
+        assert_eq!(&job.mining_hash[..4], &0x2000_0000u32.to_le_bytes());
+        assert_eq!(&job.mining_hash[4..36], &[0x22; 32]);
+        assert_eq!(&job.mining_hash[36..68], &[0x11; 32]);
+        assert_eq!(&job.mining_hash[68..72], &0x6650_0000u32.to_le_bytes());
+        assert_eq!(&job.mining_hash[72..76], &0x1703_a30cu32.to_le_bytes());
+        // Little-endian channel target becomes the big-endian miner target
+        assert_eq!(job.target.unwrap()[..4], [0xff; 4]);
+        assert_eq!(state.version_for(1), Some(0x2000_0000));
+    }
+
+    #[test]
+    fn test_prev_hash_invalidates_old_jobs() {
+        let mut state = Sv2JobState::new([0xff; 32]);
+        state.on_new_job(template(1, None));
+        state.on_set_new_prev_hash(1, [0x22; 32], 100, 0x1d00ffff);
+
+        // Immediate job on the current tip
+        let job = state.on_new_job(template(2, Some(150))).unwrap();
+        assert_eq!(job.job_id, "2");
+        assert_eq!(job.ntime, Some(150));
+
+        state.on_new_job(template(3, None));
+        state.on_set_new_prev_hash(3, [0x33; 32], 200, 0x1d00ffff);
+        assert_eq!(state.version_for(1), None);
+        assert_eq!(state.version_for(2), None);
+        assert!(state.version_for(3).is_some());
+
+        let job = state.on_set_target([0x01; 32]).unwrap();
+        assert_eq!(job.job_id, "3");
+        assert_eq!(job.target, Some([0x01; 32]));
+    }
+
+    #[test]
+    fn test_share_success_batch_accounting() {
+        let mut tracker = Sv2ShareTracker::default();
+        for sequence in 1..=5 {
+            tracker.insert(sequence, pending(&sequence.to_string()));
+        }
+
+        let accepted = tracker.accept_through(3);
+        assert_eq!(
+            accepted.iter().map(|(seq, _)| *seq).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert!(tracker.accept_through(3).is_empty());
+
+        assert_eq!(tracker.reject(5).unwrap().job_id, "5");
+        assert!(tracker.reject(5).is_none());
+        assert_eq!(tracker.clear(), 1);
+    }
+
+    #[test]
+    fn test_share_expiry() {
+        let mut tracker = Sv2ShareTracker::default();
+        tracker.insert(1, pending("1"));
+        assert_eq!(tracker.expire(Duration::from_secs(60)), 0);
+        assert_eq!(tracker.expire(Duration::ZERO), 1);
+    }
+}
+
+// Changelog:
+// - v1.0.2 (2025-06-28): Reconnects and failovers are counted with PoolClient::record_reconnect().
+// - v1.0.1 (2025-06-28): User identity comes from PoolEndpoint::user_identity().
+// - v1.0.0 (2025-06-28): Initial Stratum V2 mining client.
+//   - Noise handshake, SetupConnection and OpenStandardMiningChannel with failover.
+//   - NewMiningJob / SetNewPrevHash / SetTarget become SHA-256 MiningJobs.
+//   - Share submission with sequence numbers and batched SubmitShares.Success accounting.
@@ -0,0 +1,327 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/pool/sv2_noise.rs
+// Version: 1.0.0
+//
+// This file implements the encrypted Stratum V2 transport. It runs the Noise
+// NX handshake as initiator (verifying the pool certificate against the
+// authority public key from the sv2:// URL) and then encrypts/decrypts SV2
+// frames: the 6-byte header and the payload (in 65535-byte chunks) are sealed
+// separately, each with a 16-byte MAC.
+//
+// Tree Location:
+// - src/pool/sv2_noise.rs (SV2 Noise handshake and encrypted framing)
+// - Depends on: noise_sv2, bs58, tokio, pool/sv2_protocol
+
+use super::sv2_protocol::{FrameHeader, SV2_FRAME_HEADER_SIZE};
+use crate::Result;
+use log::{debug, info, warn};
+use noise_sv2::{
+    ELLSWIFT_ENCODING_SIZE, INITIATOR_EXPECTED_HANDSHAKE_MESSAGE_SIZE, Initiator, NoiseCodec,
+};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadHalf, WriteHalf};
+
+const LOG_TARGET: &str = "tari::graxil::pool::sv2";
+
+/// Authentication tag appended to every encrypted chunk
+pub const AEAD_MAC_LEN: usize = 16;
+/// Largest encrypted chunk (ciphertext + MAC)
+pub const SV2_FRAME_CHUNK_SIZE: usize = 65535;
+/// Encrypted frame header size
+pub const ENCRYPTED_HEADER_SIZE: usize = SV2_FRAME_HEADER_SIZE + AEAD_MAC_LEN;
+/// Key version prefix of base58check-encoded authority public keys
+const AUTHORITY_KEY_VERSION: u16 = 1;
+
+/// Transport cipher for established SV2 sessions
+pub trait FrameCipher: Send {
+    /// Encrypt in place, appending the MAC
+    fn encrypt(&mut self, buf: &mut Vec<u8>) -> Result<()>;
+    /// Decrypt in place, verifying and removing the MAC
+    fn decrypt(&mut self, buf: &mut Vec<u8>) -> Result<()>;
+}
+
+impl FrameCipher for NoiseCodec {
+    fn encrypt(&mut self, buf: &mut Vec<u8>) -> Result<()> {
+        NoiseCodec::encrypt(self, buf).map_err(|e| format!("SV2 encryption failed: {:?}", e).into())
+    }
+
+    fn decrypt(&mut self, buf: &mut Vec<u8>) -> Result<()> {
+        NoiseCodec::decrypt(self, buf)
+            .map_err(|e| format!("SV2 decryption failed (bad MAC): {:?}", e).into())
+    }
+}
+
+type SharedCipher = Arc<Mutex<Box<dyn FrameCipher>>>;
+
+/// Decode a base58check authority public key (as printed by SV2 pools)
+pub fn parse_authority_key(key: &str) -> std::result::Result<[u8; 32], String> {
+    let decoded = bs58::decode(key)
+        .with_check(None)
+        .into_vec()
+        .map_err(|e| format!("Invalid SV2 authority key '{}': {}", key, e))?;
+    if decoded.len() != 34 {
+        return Err(format!(
+            "Invalid SV2 authority key '{}': expected 34 bytes, got {}",
+            key,
+            decoded.len()
+        ));
+    }
+    let version = u16::from_le_bytes([decoded[0], decoded[1]]);
+    if version != AUTHORITY_KEY_VERSION {
+        return Err(format!(
+            "Unsupported SV2 authority key version {} (expected {})",
+            version, AUTHORITY_KEY_VERSION
+        ));
+    }
+    let mut raw = [0u8; 32];
+    raw.copy_from_slice(&decoded[2..]);
+    Ok(raw)
+}
+
+/// Run the Noise NX handshake as initiator and split the stream into
+/// encrypted frame reader/writer halves
+pub async fn handshake<S>(
+    mut stream: S,
+    authority_key: Option<[u8; 32]>,
+) -> Result<(Sv2FrameReader<ReadHalf<S>>, Sv2FrameWriter<WriteHalf<S>>)>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut initiator = match authority_key {
+        Some(key) => {
+            Initiator::from_raw_k(key).map_err(|e| format!("Invalid SV2 authority key: {:?}", e))?
+        }
+        None => {
+            warn!(target: LOG_TARGET,
+                "⚠️ No SV2 authority key configured - the pool certificate will NOT be verified"
+            );
+            Initiator::without_pk().map_err(|e| format!("Noise initiator failed: {:?}", e))?
+        }
+    };
+
+    // -> e
+    let first: [u8; ELLSWIFT_ENCODING_SIZE] = initiator
+        .step_0()
+        .map_err(|e| format!("Noise handshake step 0 failed: {:?}", e))?;
+    stream.write_all(&first).await?;
+    stream.flush().await?;
+
+    // <- e, ee, s, es, SIGNATURE_NOISE_MESSAGE
+    let mut response = [0u8; INITIATOR_EXPECTED_HANDSHAKE_MESSAGE_SIZE];
+    stream.read_exact(&mut response).await?;
+    let codec = initiator.step_2(response).map_err(|e| {
+        format!(
+            "Noise handshake failed (pool certificate not signed by the authority key?): {:?}",
+            e
+        )
+    })?;
+
+    info!(target: LOG_TARGET,"🔐 SV2 Noise handshake complete");
+    Ok(split_encrypted(stream, Box::new(codec)))
+}
+
+/// Split a stream with an established cipher into frame reader/writer halves
+pub fn split_encrypted<S>(
+    stream: S,
+    cipher: Box<dyn FrameCipher>,
+) -> (Sv2FrameReader<ReadHalf<S>>, Sv2FrameWriter<WriteHalf<S>>)
+where
+    S: AsyncRead + AsyncWrite,
+{
+    let cipher: SharedCipher = Arc::new(Mutex::new(cipher));
+    let (reader, writer) = tokio::io::split(stream);
+    (
+        Sv2FrameReader {
+            reader,
+            cipher: Arc::clone(&cipher),
+        },
+        Sv2FrameWriter { writer, cipher },
+    )
+}
+
+/// Size on the wire of an encrypted payload of `len` plaintext bytes
+pub fn encrypted_payload_len(len: usize) -> usize {
+    let chunk_plaintext = SV2_FRAME_CHUNK_SIZE - AEAD_MAC_LEN;
+    len + len.div_ceil(chunk_plaintext) * AEAD_MAC_LEN
+}
+
+/// Reads and decrypts SV2 frames
+pub struct Sv2FrameReader<R> {
+    reader: R,
+    cipher: SharedCipher,
+}
+
+impl<R: AsyncRead + Unpin> Sv2FrameReader<R> {
+    /// Read one frame, returning its header and plaintext payload
+    pub async fn read_frame(&mut self) -> Result<(FrameHeader, Vec<u8>)> {
+        let mut header = vec![0u8; ENCRYPTED_HEADER_SIZE];
+        self.reader.read_exact(&mut header).await?;
+        self.cipher.lock().unwrap().decrypt(&mut header)?;
+        let header_bytes: [u8; SV2_FRAME_HEADER_SIZE] = header
+            .as_slice()
+            .try_into()
+            .map_err(|_| "Decrypted SV2 header has the wrong size")?;
+        let header = FrameHeader::decode(&header_bytes);
+
+        let mut encrypted = vec![0u8; encrypted_payload_len(header.msg_length as usize)];
+        self.reader.read_exact(&mut encrypted).await?;
+
+        let mut payload = Vec::with_capacity(header.msg_length as usize);
+        let mut cipher = self.cipher.lock().unwrap();
+        for chunk in encrypted.chunks(SV2_FRAME_CHUNK_SIZE) {
+            let mut chunk = chunk.to_vec();
+            cipher.decrypt(&mut chunk)?;
+            payload.extend_from_slice(&chunk);
+        }
+
+        debug!(target: LOG_TARGET,
+            "📨 SV2 frame type {:#04x}, {} bytes",
+            header.msg_type,
+            payload.len()
+        );
+        Ok((header, payload))
+    }
+}
+
+/// Encrypts and writes SV2 frames
+pub struct Sv2FrameWriter<W> {
+    writer: W,
+    cipher: SharedCipher,
+}
+
+impl<W: AsyncWrite + Unpin> Sv2FrameWriter<W> {
+    /// Encrypt and send a plaintext frame (header + payload) built by Sv2Protocol
+    pub async fn write_frame(&mut self, frame: &[u8]) -> Result<()> {
+        if frame.len() < SV2_FRAME_HEADER_SIZE {
+            return Err("SV2 frame shorter than its header".into());
+        }
+        let (header, payload) = frame.split_at(SV2_FRAME_HEADER_SIZE);
+
+        let mut encrypted =
+            Vec::with_capacity(ENCRYPTED_HEADER_SIZE + encrypted_payload_len(payload.len()));
+        {
+            let mut cipher = self.cipher.lock().unwrap();
+            let mut header = header.to_vec();
+            cipher.encrypt(&mut header)?;
+            encrypted.extend_from_slice(&header);
+
+            for chunk in payload.chunks(SV2_FRAME_CHUNK_SIZE - AEAD_MAC_LEN) {
+                let mut chunk = chunk.to_vec();
+                cipher.encrypt(&mut chunk)?;
+                encrypted.extend_from_slice(&chunk);
+            }
+        }
+
+        self.writer.write_all(&encrypted).await?;
+        self.writer.flush().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pool::sv2_protocol::{Sv2Message, Sv2Protocol, msg_type};
+
+    /// Stand-in cipher: XOR with a key byte and a fake MAC of the key byte
+    struct XorCipher(u8);
+
+    impl FrameCipher for XorCipher {
+        fn encrypt(&mut self, buf: &mut Vec<u8>) -> Result<()> {
+            buf.iter_mut().for_each(|b| *b ^= self.0);
+            buf.extend_from_slice(&[self.0; AEAD_MAC_LEN]);
+            Ok(())
+        }
+
+        fn decrypt(&mut self, buf: &mut Vec<u8>) -> Result<()> {
+            let mac = buf.split_off(buf.len() - AEAD_MAC_LEN);
+            if mac != [self.0; AEAD_MAC_LEN] {
+                return Err("bad MAC".into());
+            }
+            buf.iter_mut().for_each(|b| *b ^= self.0);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_encrypted_payload_len() {
+        assert_eq!(encrypted_payload_len(0), 0);
+        assert_eq!(encrypted_payload_len(24), 40);
+        assert_eq!(encrypted_payload_len(65519), 65535);
+        assert_eq!(encrypted_payload_len(65520), 65536 + 16);
+    }
+
+    #[tokio::test]
+    async fn test_frame_roundtrip_over_cipher() {
+        let (client, server) = tokio::io::duplex(1 << 20);
+        let (_client_reader, mut client_writer) =
+            split_encrypted(client, Box::new(XorCipher(0x5a)));
+        let (mut server_reader, _server_writer) =
+            split_encrypted(server, Box::new(XorCipher(0x5a)));
+
+        let protocol = Sv2Protocol::new();
+        let (_, frame) = protocol.create_submit_shares(3, 4, 5, 6, 7).unwrap();
+        client_writer.write_frame(&frame).await.unwrap();
+
+        // Payload larger than one chunk is split and reassembled
+        let mut big = FrameHeader {
+            extension_type: 0,
+            msg_type: 0x7f,
+            msg_length: 70_000,
+        }
+        .encode()
+        .to_vec();
+        big.extend(std::iter::repeat_n(0xabu8, 70_000));
+        client_writer.write_frame(&big).await.unwrap();
+
+        let (header, payload) = server_reader.read_frame().await.unwrap();
+        assert_eq!(header.msg_type, msg_type::SUBMIT_SHARES_STANDARD);
+        assert_eq!(payload, frame[SV2_FRAME_HEADER_SIZE..]);
+
+        let (header, payload) = server_reader.read_frame().await.unwrap();
+        assert_eq!(header.msg_length, 70_000);
+        assert!(payload.iter().all(|b| *b == 0xab));
+        assert_eq!(
+            protocol.decode_message(&header, &payload).unwrap(),
+            Sv2Message::Unknown { msg_type: 0x7f }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tampered_frame_is_rejected() {
+        let (client, server) = tokio::io::duplex(4096);
+        let (_r, mut writer) = split_encrypted(client, Box::new(XorCipher(1)));
+        let (mut reader, _w) = split_encrypted(server, Box::new(XorCipher(2)));
+
+        let frame = Sv2Protocol::new().create_close_channel(1, "bye").unwrap();
+        writer.write_frame(&frame).await.unwrap();
+        assert!(reader.read_frame().await.is_err());
+    }
+
+    #[test]
+    fn test_parse_authority_key() {
+        let mut raw = vec![1u8, 0];
+        raw.extend_from_slice(&[7u8; 32]);
+        let encoded = bs58::encode(&raw).with_check().into_string();
+        assert_eq!(parse_authority_key(&encoded).unwrap(), [7u8; 32]);
+
+        let mut wrong_version = vec![2u8, 0];
+        wrong_version.extend_from_slice(&[7u8; 32]);
+        let encoded = bs58::encode(&wrong_version).with_check().into_string();
+        assert!(parse_authority_key(&encoded).is_err());
+
+        assert!(parse_authority_key("not-base58!").is_err());
+    }
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial SV2 Noise transport.
+//   - Noise NX handshake (initiator) with authority key verification via noise_sv2.
+//   - Encrypted SV2 framing: sealed header plus payload in 65535-byte chunks.
+//   - base58check authority key parsing for sv2:// pool URLs.
@@ -0,0 +1,732 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/pool/sv2_protocol.rs
+// Version: 3.0.0
+// Developer: OIEIEIO <oieieio@protonmail.com>
+//
+// This file implements the Stratum V2 protocol for communication with Bitcoin
+// mining pools in the SHA3x miner. It handles SV2 frame headers and the binary
+// encoding/decoding of the Common and Mining protocol messages used by a
+// standard-channel mining device.
+//
+// Tree Location:
+// - src/pool/sv2_protocol.rs (SV2 protocol logic)
+// - Depends on: core/difficulty
+
+use crate::Result;
+use crate::core::difficulty::U256;
+use crate::core::{Algorithm, parse_target_difficulty};
+use log::{debug, error, info, warn};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+const LOG_TARGET: &str = "tari::graxil::pool::sv2";
+
+/// Plaintext SV2 frame header: extension_type (U16), msg_type (U8), msg_length (U24)
+pub const SV2_FRAME_HEADER_SIZE: usize = 6;
+/// Extension type bit marking channel messages
+pub const CHANNEL_MSG_BIT: u16 = 0x8000;
+
+/// SV2 message types (Common and Mining protocols)
+pub mod msg_type {
+    pub const SETUP_CONNECTION: u8 = 0x00;
+    pub const SETUP_CONNECTION_SUCCESS: u8 = 0x01;
+    pub const SETUP_CONNECTION_ERROR: u8 = 0x02;
+    pub const CHANNEL_ENDPOINT_CHANGED: u8 = 0x03;
+    pub const OPEN_STANDARD_MINING_CHANNEL: u8 = 0x10;
+    pub const OPEN_STANDARD_MINING_CHANNEL_SUCCESS: u8 = 0x11;
+    pub const OPEN_MINING_CHANNEL_ERROR: u8 = 0x12;
+    pub const NEW_MINING_JOB: u8 = 0x15;
+    pub const UPDATE_CHANNEL: u8 = 0x16;
+    pub const UPDATE_CHANNEL_ERROR: u8 = 0x17;
+    pub const CLOSE_CHANNEL: u8 = 0x18;
+    pub const SUBMIT_SHARES_STANDARD: u8 = 0x1a;
+    pub const SUBMIT_SHARES_SUCCESS: u8 = 0x1c;
+    pub const SUBMIT_SHARES_ERROR: u8 = 0x1d;
+    pub const SET_NEW_PREV_HASH: u8 = 0x20;
+    pub const SET_TARGET: u8 = 0x21;
+    pub const RECONNECT: u8 = 0x25;
+}
+
+/// Decoded SV2 frame header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameHeader {
+    pub extension_type: u16,
+    pub msg_type: u8,
+    pub msg_length: u32,
+}
+
+impl FrameHeader {
+    pub fn encode(&self) -> [u8; SV2_FRAME_HEADER_SIZE] {
+        let ext = self.extension_type.to_le_bytes();
+        let len = self.msg_length.to_le_bytes();
+        [ext[0], ext[1], self.msg_type, len[0], len[1], len[2]]
+    }
+
+    pub fn decode(bytes: &[u8; SV2_FRAME_HEADER_SIZE]) -> Self {
+        Self {
+            extension_type: u16::from_le_bytes([bytes[0], bytes[1]]),
+            msg_type: bytes[2],
+            msg_length: u32::from_le_bytes([bytes[3], bytes[4], bytes[5], 0]),
+        }
+    }
+
+    pub fn is_channel_message(&self) -> bool {
+        self.extension_type & CHANNEL_MSG_BIT != 0
+    }
+}
+
+/// Build a plaintext frame (header + payload)
+fn frame(msg_type: u8, channel_msg: bool, payload: Vec<u8>) -> Result<Vec<u8>> {
+    if payload.len() >= 1 << 24 {
+        return Err("SV2 payload too large".into());
+    }
+    let header = FrameHeader {
+        extension_type: if channel_msg { CHANNEL_MSG_BIT } else { 0 },
+        msg_type,
+        msg_length: payload.len() as u32,
+    };
+    let mut frame = Vec::with_capacity(SV2_FRAME_HEADER_SIZE + payload.len());
+    frame.extend_from_slice(&header.encode());
+    frame.extend_from_slice(&payload);
+    Ok(frame)
+}
+
+/// Serializer for SV2 binary data types (all little-endian)
+#[derive(Default)]
+struct Sv2Writer(Vec<u8>);
+
+impl Sv2Writer {
+    fn u8(mut self, v: u8) -> Self {
+        self.0.push(v);
+        self
+    }
+    fn u16(mut self, v: u16) -> Self {
+        self.0.extend_from_slice(&v.to_le_bytes());
+        self
+    }
+    fn u32(mut self, v: u32) -> Self {
+        self.0.extend_from_slice(&v.to_le_bytes());
+        self
+    }
+    fn f32(mut self, v: f32) -> Self {
+        self.0.extend_from_slice(&v.to_le_bytes());
+        self
+    }
+    fn u256(mut self, v: &[u8; 32]) -> Self {
+        self.0.extend_from_slice(v);
+        self
+    }
+    /// STR0_255: one length byte followed by UTF-8 bytes
+    fn str0_255(mut self, v: &str) -> Result<Self> {
+        if v.len() > 255 {
+            return Err(format!("SV2 string too long ({} bytes): {}", v.len(), v).into());
+        }
+        self.0.push(v.len() as u8);
+        self.0.extend_from_slice(v.as_bytes());
+        Ok(self)
+    }
+    fn finish(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+/// Deserializer for SV2 binary data types
+struct Sv2Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Sv2Reader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        if self.buf.len() < n {
+            return Err(format!(
+                "SV2 message truncated (needed {} more bytes, have {})",
+                n,
+                self.buf.len()
+            )
+            .into());
+        }
+        let (head, tail) = self.buf.split_at(n);
+        self.buf = tail;
+        Ok(head)
+    }
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into()?))
+    }
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into()?))
+    }
+    fn u256(&mut self) -> Result<[u8; 32]> {
+        Ok(self.take(32)?.try_into()?)
+    }
+    fn str0_255(&mut self) -> Result<String> {
+        let len = self.u8()? as usize;
+        Ok(String::from_utf8_lossy(self.take(len)?).into_owned())
+    }
+    /// B0_32: one length byte followed by up to 32 bytes
+    fn b0_32(&mut self) -> Result<Vec<u8>> {
+        let len = self.u8()? as usize;
+        if len > 32 {
+            return Err(format!("SV2 B0_32 field too long ({} bytes)", len).into());
+        }
+        Ok(self.take(len)?.to_vec())
+    }
+    /// OPTION[U32]: one count byte (0 or 1) followed by the value
+    fn option_u32(&mut self) -> Result<Option<u32>> {
+        match self.u8()? {
+            0 => Ok(None),
+            1 => Ok(Some(self.u32()?)),
+            n => Err(format!("Invalid SV2 option length {}", n).into()),
+        }
+    }
+}
+
+/// SV2 Protocol handler for Bitcoin mining
+pub struct Sv2Protocol {
+    /// Current sequence number for requests
+    request_id_counter: AtomicU32,
+    /// Current sequence number for share submissions
+    sequence_counter: AtomicU32,
+}
+
+impl Default for Sv2Protocol {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sv2Protocol {
+    /// Create a new SV2 protocol handler
+    pub fn new() -> Self {
+        Self {
+            request_id_counter: AtomicU32::new(1),
+            sequence_counter: AtomicU32::new(1),
+        }
+    }
+
+    /// Get the next request ID
+    fn next_request_id(&self) -> u32 {
+        self.request_id_counter.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Get the next sequence number
+    fn next_sequence(&self) -> u32 {
+        self.sequence_counter.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Create a SetupConnection message for Bitcoin mining
+    pub fn create_setup_connection(
+        &self,
+        endpoint_host: &str,
+        endpoint_port: u16,
+    ) -> Result<Vec<u8>> {
+        let payload = Sv2Writer::default()
+            .u8(Self::MINING_PROTOCOL)
+            .u16(Self::PROTOCOL_VERSION)
+            .u16(Self::PROTOCOL_VERSION)
+            .u32(Self::REQUIRES_STANDARD_JOBS)
+            .str0_255(endpoint_host)?
+            .u16(endpoint_port)
+            .str0_255(Self::VENDOR)?
+            .str0_255(Self::HARDWARE_VERSION)?
+            .str0_255(Self::FIRMWARE_VERSION)?
+            .str0_255(Self::DEVICE_ID)?
+            .finish();
+
+        let encoded = frame(msg_type::SETUP_CONNECTION, false, payload)?;
+        debug!(target: LOG_TARGET,"Created SetupConnection message: {} bytes", encoded.len());
+        Ok(encoded)
+    }
+
+    /// Create an OpenStandardMiningChannel message; returns the request id with the frame
+    pub fn create_open_channel(
+        &self,
+        user_identity: &str,
+        nominal_hashrate: f32,
+        max_target: [u8; 32],
+    ) -> Result<(u32, Vec<u8>)> {
+        let request_id = self.next_request_id();
+        let payload = Sv2Writer::default()
+            .u32(request_id)
+            .str0_255(user_identity)?
+            .f32(nominal_hashrate)
+            .u256(&max_target)
+            .finish();
+
+        let encoded = frame(msg_type::OPEN_STANDARD_MINING_CHANNEL, false, payload)?;
+        info!(target: LOG_TARGET,
+            "Created OpenStandardMiningChannel for user: {}, hashrate: {} H/s",
+            user_identity, nominal_hashrate
+        );
+        Ok((request_id, encoded))
+    }
+
+    /// Create a SubmitSharesStandard message; returns the sequence number with the frame
+    pub fn create_submit_shares(
+        &self,
+        channel_id: u32,
+        job_id: u32,
+        nonce: u32,
+        ntime: u32,
+        version: u32,
+    ) -> Result<(u32, Vec<u8>)> {
+        let sequence_number = self.next_sequence();
+        let payload = Sv2Writer::default()
+            .u32(channel_id)
+            .u32(sequence_number)
+            .u32(job_id)
+            .u32(nonce)
+            .u32(ntime)
+            .u32(version)
+            .finish();
+
+        let encoded = frame(msg_type::SUBMIT_SHARES_STANDARD, true, payload)?;
+        debug!(target: LOG_TARGET,
+            "Created SubmitSharesStandard: channel={}, seq={}, job={}, nonce={:08x}, ntime={:08x}",
+            channel_id, sequence_number, job_id, nonce, ntime
+        );
+        Ok((sequence_number, encoded))
+    }
+
+    /// Create an UpdateChannel message to report hashrate changes
+    pub fn create_update_channel(
+        &self,
+        channel_id: u32,
+        nominal_hashrate: f32,
+        max_target: [u8; 32],
+    ) -> Result<Vec<u8>> {
+        let payload = Sv2Writer::default()
+            .u32(channel_id)
+            .f32(nominal_hashrate)
+            .u256(&max_target)
+            .finish();
+
+        let encoded = frame(msg_type::UPDATE_CHANNEL, true, payload)?;
+        debug!(target: LOG_TARGET,
+            "Created UpdateChannel: channel={}, hashrate={} H/s",
+            channel_id, nominal_hashrate
+        );
+        Ok(encoded)
+    }
+
+    /// Create a CloseChannel message
+    pub fn create_close_channel(&self, channel_id: u32, reason: &str) -> Result<Vec<u8>> {
+        let payload = Sv2Writer::default()
+            .u32(channel_id)
+            .str0_255(reason)?
+            .finish();
+
+        let encoded = frame(msg_type::CLOSE_CHANNEL, true, payload)?;
+        info!(target: LOG_TARGET,"Created CloseChannel: channel={}, reason={}", channel_id, reason);
+        Ok(encoded)
+    }
+
+    /// Decode a pool message from its frame header and plaintext payload
+    pub fn decode_message(&self, header: &FrameHeader, payload: &[u8]) -> Result<Sv2Message> {
+        let mut r = Sv2Reader::new(payload);
+        let message = match header.msg_type {
+            msg_type::SETUP_CONNECTION_SUCCESS => Sv2Message::SetupSuccess {
+                used_version: r.u16()?,
+                flags: r.u32()?,
+            },
+            msg_type::SETUP_CONNECTION_ERROR => Sv2Message::SetupError {
+                flags: r.u32()?,
+                error_code: r.str0_255()?,
+            },
+            msg_type::OPEN_STANDARD_MINING_CHANNEL_SUCCESS => Sv2Message::ChannelSuccess {
+                request_id: r.u32()?,
+                channel_id: r.u32()?,
+                target: r.u256()?,
+                extranonce_prefix: r.b0_32()?,
+                group_channel_id: r.u32()?,
+            },
+            msg_type::OPEN_MINING_CHANNEL_ERROR => Sv2Message::ChannelError {
+                request_id: r.u32()?,
+                error_code: r.str0_255()?,
+            },
+            msg_type::NEW_MINING_JOB => Sv2Message::NewJob {
+                channel_id: r.u32()?,
+                job_id: r.u32()?,
+                min_ntime: r.option_u32()?,
+                version: r.u32()?,
+                merkle_root: r.u256()?,
+            },
+            msg_type::SET_NEW_PREV_HASH => Sv2Message::SetNewPrevHash {
+                channel_id: r.u32()?,
+                job_id: r.u32()?,
+                prev_hash: r.u256()?,
+                min_ntime: r.u32()?,
+                nbits: r.u32()?,
+            },
+            msg_type::SET_TARGET => Sv2Message::SetTarget {
+                channel_id: r.u32()?,
+                target: r.u256()?,
+            },
+            msg_type::SUBMIT_SHARES_SUCCESS => Sv2Message::ShareSuccess {
+                channel_id: r.u32()?,
+                last_sequence: r.u32()?,
+                accepted_count: r.u32()?,
+                shares_sum: r.u64()?,
+            },
+            msg_type::SUBMIT_SHARES_ERROR => Sv2Message::ShareError {
+                channel_id: r.u32()?,
+                sequence_number: r.u32()?,
+                error_code: r.str0_255()?,
+            },
+            msg_type::UPDATE_CHANNEL_ERROR => Sv2Message::ChannelError {
+                request_id: r.u32()?,
+                error_code: r.str0_255()?,
+            },
+            msg_type::CLOSE_CHANNEL => Sv2Message::CloseChannel {
+                channel_id: r.u32()?,
+                reason: r.str0_255()?,
+            },
+            msg_type::RECONNECT => Sv2Message::Reconnect {
+                new_host: r.str0_255()?,
+                new_port: r.u16()?,
+            },
+            other => {
+                warn!(target: LOG_TARGET,"Received unhandled SV2 message type {:#04x}", other);
+                Sv2Message::Unknown { msg_type: other }
+            }
+        };
+
+        if let Some(description) = message.error_description() {
+            error!(target: LOG_TARGET,"❌ SV2 error from pool: {}", description);
+        }
+        Ok(message)
+    }
+
+    /// Convert our internal U256 to an SV2 U256 (32 bytes, little-endian)
+    pub fn convert_target_to_sv2(target: &U256) -> [u8; 32] {
+        target.to_little_endian()
+    }
+
+    /// Convert an SV2 U256 (32 bytes, little-endian) to our internal U256
+    pub fn convert_target_from_sv2(sv2_target: &[u8; 32]) -> U256 {
+        U256::from_little_endian(sv2_target)
+    }
+
+    /// SV2 target as big-endian bytes, the order the mining threads compare against
+    pub fn target_to_big_endian(sv2_target: &[u8; 32]) -> [u8; 32] {
+        let mut bytes = *sv2_target;
+        bytes.reverse();
+        bytes
+    }
+
+    /// Share difficulty (relative to difficulty 1) of an SV2 target
+    pub fn target_to_difficulty(sv2_target: &[u8; 32]) -> u64 {
+        let big_endian = Self::target_to_big_endian(sv2_target);
+        parse_target_difficulty(&hex::encode(big_endian), Algorithm::Sha256)
+    }
+
+    /// Calculate maximum target for given hashrate (helper function)
+    pub fn calculate_max_target(hashrate: f32) -> [u8; 32] {
+        // Set a reasonable max target based on hashrate
+        // Higher hashrate = can handle higher difficulty (lower target)
+        let leading = if hashrate > 1_000_000.0 {
+            // > 1 MH/s - can handle higher difficulty
+            [0x00, 0x00, 0x00, 0x00, 0x0F, 0xFF]
+        } else if hashrate > 100_000.0 {
+            // 100 KH/s - 1 MH/s
+            [0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF]
+        } else {
+            // < 100 KH/s - very easy target
+            [0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF]
+        };
+        let mut big_endian = [0u8; 32];
+        big_endian[..6].copy_from_slice(&leading);
+        let target = Self::target_to_big_endian(&big_endian);
+
+        debug!(target: LOG_TARGET,
+            "Calculated max target for hashrate {} H/s: {}",
+            hashrate,
+            hex::encode(big_endian)
+        );
+        target
+    }
+}
+
+/// Internal representation of SV2 messages
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sv2Message {
+    SetupSuccess {
+        used_version: u16,
+        flags: u32,
+    },
+    SetupError {
+        flags: u32,
+        error_code: String,
+    },
+    ChannelSuccess {
+        request_id: u32,
+        channel_id: u32,
+        target: [u8; 32],
+        extranonce_prefix: Vec<u8>,
+        group_channel_id: u32,
+    },
+    ChannelError {
+        request_id: u32,
+        error_code: String,
+    },
+    NewJob {
+        channel_id: u32,
+        job_id: u32,
+        /// None = future job, activated by a later SetNewPrevHash
+        min_ntime: Option<u32>,
+        version: u32,
+        merkle_root: [u8; 32],
+    },
+    SetNewPrevHash {
+        channel_id: u32,
+        job_id: u32,
+        prev_hash: [u8; 32],
+        min_ntime: u32,
+        nbits: u32,
+    },
+    SetTarget {
+        channel_id: u32,
+        target: [u8; 32],
+    },
+    ShareSuccess {
+        channel_id: u32,
+        last_sequence: u32,
+        accepted_count: u32,
+        shares_sum: u64,
+    },
+    ShareError {
+        channel_id: u32,
+        sequence_number: u32,
+        error_code: String,
+    },
+    CloseChannel {
+        channel_id: u32,
+        reason: String,
+    },
+    Reconnect {
+        new_host: String,
+        new_port: u16,
+    },
+    Unknown {
+        msg_type: u8,
+    },
+}
+
+impl Sv2Message {
+    /// Check if this is an error message
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self,
+            Sv2Message::SetupError { .. }
+                | Sv2Message::ChannelError { .. }
+                | Sv2Message::ShareError { .. }
+        )
+    }
+
+    /// Get error description if this is an error message
+    pub fn error_description(&self) -> Option<String> {
+        match self {
+            Sv2Message::SetupError { error_code, .. }
+            | Sv2Message::ChannelError { error_code, .. }
+            | Sv2Message::ShareError { error_code, .. } => Some(error_code.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Helper functions for SV2 protocol constants
+impl Sv2Protocol {
+    /// Get the protocol version we support
+    pub const PROTOCOL_VERSION: u16 = 2;
+
+    /// Get our vendor string
+    pub const VENDOR: &'static str = "sha3x-miner";
+
+    /// Get our hardware version
+    pub const HARDWARE_VERSION: &'static str = "2.0.0";
+
+    /// Get our firmware version
+    pub const FIRMWARE_VERSION: &'static str = "sha3x-miner-sv2";
+
+    /// Get our device ID
+    pub const DEVICE_ID: &'static str = "sha3x-cpu-miner";
+
+    /// Standard mining protocol ID
+    pub const MINING_PROTOCOL: u8 = 0;
+
+    /// Flags for setup connection
+    pub const REQUIRES_STANDARD_JOBS: u32 = 0b001;
+    pub const REQUIRES_WORK_SELECTION: u32 = 0b010;
+    pub const REQUIRES_VERSION_ROLLING: u32 = 0b100;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(frame: &[u8]) -> (FrameHeader, &[u8]) {
+        let header = FrameHeader::decode(frame[..SV2_FRAME_HEADER_SIZE].try_into().unwrap());
+        (header, &frame[SV2_FRAME_HEADER_SIZE..])
+    }
+
+    #[test]
+    fn test_frame_header_roundtrip() {
+        let header = FrameHeader {
+            extension_type: CHANNEL_MSG_BIT,
+            msg_type: msg_type::SUBMIT_SHARES_STANDARD,
+            msg_length: 0x01_02_03,
+        };
+        let bytes = header.encode();
+        assert_eq!(bytes, [0x00, 0x80, 0x1a, 0x03, 0x02, 0x01]);
+        assert_eq!(FrameHeader::decode(&bytes), header);
+        assert!(header.is_channel_message());
+    }
+
+    #[test]
+    fn test_submit_shares_encoding() {
+        let protocol = Sv2Protocol::new();
+        let (sequence, encoded) = protocol
+            .create_submit_shares(7, 42, 0xdeadbeef, 0x6650_0000, 0x2000_0000)
+            .unwrap();
+        assert_eq!(sequence, 1);
+
+        let (header, payload) = split(&encoded);
+        assert_eq!(header.msg_type, msg_type::SUBMIT_SHARES_STANDARD);
+        assert!(header.is_channel_message());
+        assert_eq!(header.msg_length as usize, payload.len());
+        assert_eq!(payload.len(), 24);
+        assert_eq!(&payload[0..4], &7u32.to_le_bytes());
+        assert_eq!(&payload[12..16], &0xdeadbeefu32.to_le_bytes());
+
+        let (next, _) = protocol.create_submit_shares(7, 42, 1, 1, 1).unwrap();
+        assert_eq!(next, 2);
+    }
+
+    #[test]
+    fn test_setup_connection_encoding() {
+        let protocol = Sv2Protocol::new();
+        let encoded = protocol
+            .create_setup_connection("pool.example", 3333)
+            .unwrap();
+        let (header, payload) = split(&encoded);
+        assert_eq!(header.msg_type, msg_type::SETUP_CONNECTION);
+        assert!(!header.is_channel_message());
+        // protocol, min/max version, flags, then the endpoint host string
+        assert_eq!(payload[0], 0);
+        assert_eq!(&payload[1..5], &[2, 0, 2, 0]);
+        assert_eq!(payload[9] as usize, "pool.example".len());
+    }
+
+    #[test]
+    fn test_decode_pool_messages() {
+        let protocol = Sv2Protocol::new();
+        let decode = |msg_type: u8, payload: Vec<u8>| {
+            let header = FrameHeader {
+                extension_type: 0,
+                msg_type,
+                msg_length: payload.len() as u32,
+            };
+            protocol.decode_message(&header, &payload).unwrap()
+        };
+
+        let mut target = [0u8; 32];
+        target[28] = 0xff;
+        let payload = Sv2Writer::default()
+            .u32(1)
+            .u32(9)
+            .u256(&target)
+            .u8(2)
+            .u16(0xabcd)
+            .u32(0)
+            .finish();
+        assert_eq!(
+            decode(msg_type::OPEN_STANDARD_MINING_CHANNEL_SUCCESS, payload),
+            Sv2Message::ChannelSuccess {
+                request_id: 1,
+                channel_id: 9,
+                target,
+                extranonce_prefix: vec![0xcd, 0xab],
+                group_channel_id: 0,
+            }
+        );
+
+        let payload = Sv2Writer::default()
+            .u32(9)
+            .u32(5)
+            .u8(0)
+            .u32(0x2000_0000)
+            .u256(&[7u8; 32])
+            .finish();
+        assert_eq!(
+            decode(msg_type::NEW_MINING_JOB, payload),
+            Sv2Message::NewJob {
+                channel_id: 9,
+                job_id: 5,
+                min_ntime: None,
+                version: 0x2000_0000,
+                merkle_root: [7u8; 32],
+            }
+        );
+
+        let payload = Sv2Writer::default()
+            .u32(9)
+            .u32(12)
+            .str0_255("stale-share")
+            .unwrap()
+            .finish();
+        let message = decode(msg_type::SUBMIT_SHARES_ERROR, payload);
+        assert!(message.is_error());
+        assert_eq!(message.error_description().as_deref(), Some("stale-share"));
+
+        // Truncated payloads are errors, not panics
+        let header = FrameHeader {
+            extension_type: 0,
+            msg_type: msg_type::SET_TARGET,
+            msg_length: 3,
+        };
+        assert!(protocol.decode_message(&header, &[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_target_conversions() {
+        // Difficulty 1 target 0x00000000ffff0000... in SV2 little-endian order
+        let mut diff1 = [0u8; 32];
+        diff1[26] = 0xff;
+        diff1[27] = 0xff;
+        assert_eq!(Sv2Protocol::target_to_difficulty(&diff1), 1);
+        assert_eq!(Sv2Protocol::target_to_big_endian(&diff1)[4], 0xff);
+
+        let target = Sv2Protocol::convert_target_from_sv2(&diff1);
+        assert_eq!(Sv2Protocol::convert_target_to_sv2(&target), diff1);
+    }
+}
+
+// Changelog:
+// - v3.0.0 (2025-06-28): Working SV2 codec for the Stratum V2 mining client.
+//   - Moved into the pool module and declared in pool/mod.rs.
+//   - Messages are encoded/decoded directly from the SV2 binary format instead
+//     of the unbuildable binary_sv2/codec_sv2 message imports.
+//   - Added FrameHeader, SetNewPrevHash, CloseChannel and Reconnect decoding.
+//   - create_open_channel/create_submit_shares return the request id / sequence number.
+//   - Targets are plain 32-byte little-endian arrays with difficulty helpers.
+// - v2.0.0 (2025-06-19): Complete SV2 protocol implementation.
+//   - Replaces old JSON-RPC Stratum V1 protocol with binary SV2.
+//   - Implements all core SV2 messages: SetupConnection, OpenStandardMiningChannel, SubmitSharesStandard.
+//   - Provides message encoding/decoding with proper error handling.
+//   - Includes helper functions for target conversion and channel management.
+//   - Supports standard channel mining for Bitcoin SHA-256d algorithm.
+//   - Compatible with binary_sv2, codec_sv2, and roles_logic_sv2 crates.
+//   - Clean separation from SHA3x protocol (which remains JSON-based).
@@ -0,0 +1,232 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/pool/template_rpc.rs
+// Version: 1.0.0
+//
+// This file implements the JSON-RPC block template client used for http://
+// solo nodes. Every call is a single HTTP/1.1 POST (through --proxy when
+// set) carrying a JSON-RPC 2.0 request:
+//   get_tip_info                          -> {"height"}
+//   get_block_template {wallet_address}   -> {"template_id", "height",
+//                                             "mining_hash", "difficulty", "reward"}
+//   submit_block {template_id, nonce}     -> {"block_hash"}
+// The template server builds the block and its SHA3x mining hash; the miner
+// only needs the hash, the network difficulty and the id to submit against.
+//
+// Tree Location:
+// - src/pool/template_rpc.rs (JSON-RPC template server client)
+// - Depends on: pool/client, pool/solo, serde_json, tokio
+
+use crate::Result;
+use crate::pool::PoolClient;
+use crate::pool::solo::BlockTemplate;
+use log::debug;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+const LOG_TARGET: &str = "tari::graxil::pool::template_rpc";
+
+/// Limit for one request / response round trip
+const RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// JSON-RPC client for a block template server
+pub struct TemplateRpcClient {
+    address: String,
+    pool_client: Arc<PoolClient>,
+    next_id: AtomicU64,
+}
+
+impl TemplateRpcClient {
+    pub fn new(address: String, pool_client: Arc<PoolClient>) -> Self {
+        Self {
+            address,
+            pool_client,
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Height of the node's best block
+    pub async fn tip_height(&self) -> Result<u64> {
+        let result = self.call("get_tip_info", json!({})).await?;
+        result
+            .get("height")
+            .and_then(|h| h.as_u64())
+            .ok_or_else(|| "get_tip_info result has no height".into())
+    }
+
+    /// Block template paying the coinbase to `wallet`
+    pub async fn get_template(&self, wallet: &str) -> Result<BlockTemplate> {
+        let result = self
+            .call(
+                "get_block_template",
+                json!({"wallet_address": wallet, "algo": "sha3x"}),
+            )
+            .await?;
+        parse_template(&result).map_err(Into::into)
+    }
+
+    /// Submit the template with the winning nonce; returns the block hash
+    pub async fn submit_block(&self, template: &BlockTemplate, nonce: u64) -> Result<String> {
+        let result = self
+            .call(
+                "submit_block",
+                json!({"template_id": template.id, "nonce": nonce}),
+            )
+            .await?;
+        Ok(result
+            .get("block_hash")
+            .and_then(|h| h.as_str())
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let body =
+            json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}).to_string();
+        let request = format!(
+            "POST / HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.address,
+            body.len(),
+            body
+        );
+
+        let exchange = async {
+            let mut stream = self.pool_client.connect_untracked(&self.address).await?;
+            stream.write_all(request.as_bytes()).await?;
+            let mut response = Vec::new();
+            stream.read_to_end(&mut response).await?;
+            Ok::<_, Box<dyn std::error::Error + Send + Sync>>(response)
+        };
+        let response = tokio::time::timeout(RPC_TIMEOUT, exchange)
+            .await
+            .map_err(|_| format!("{} request to {} timed out", method, self.address))??;
+
+        debug!(target: LOG_TARGET,"{} -> {} bytes from {}", method, response.len(), self.address);
+        parse_response(&response).map_err(|e| format!("{} failed: {}", method, e).into())
+    }
+}
+
+/// Body of an HTTP response carrying a JSON-RPC reply; returns the `result`
+fn parse_response(response: &[u8]) -> std::result::Result<Value, String> {
+    let text = String::from_utf8_lossy(response);
+    let (head, body) = text
+        .split_once("\r\n\r\n")
+        .ok_or("Malformed HTTP response from template server")?;
+    let status = head
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .unwrap_or_default();
+    if status != "200" {
+        return Err(format!("HTTP status {}", status));
+    }
+
+    let reply: Value =
+        serde_json::from_str(body.trim()).map_err(|e| format!("Invalid JSON-RPC reply: {}", e))?;
+    if let Some(error) = reply.get("error").filter(|e| !e.is_null()) {
+        return Err(error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| error.to_string()));
+    }
+    reply
+        .get("result")
+        .cloned()
+        .ok_or_else(|| "JSON-RPC reply has no result".to_string())
+}
+
+fn parse_template(result: &Value) -> std::result::Result<BlockTemplate, String> {
+    let field = |name: &str| {
+        result
+            .get(name)
+            .ok_or_else(|| format!("get_block_template result has no {}", name))
+    };
+    let number = |name: &str| {
+        field(name)?
+            .as_u64()
+            .ok_or_else(|| format!("get_block_template {} must be a number", name))
+    };
+
+    let mining_hash = hex::decode(field("mining_hash")?.as_str().unwrap_or_default())
+        .map_err(|_| "get_block_template mining_hash is not hex".to_string())?;
+    if mining_hash.len() != 32 {
+        return Err(format!(
+            "get_block_template mining_hash must be 32 bytes, got {}",
+            mining_hash.len()
+        ));
+    }
+    let network_difficulty = number("difficulty")?;
+    if network_difficulty == 0 {
+        return Err("get_block_template difficulty must be above zero".to_string());
+    }
+
+    Ok(BlockTemplate {
+        id: field("template_id")?
+            .as_str()
+            .ok_or("get_block_template template_id must be a string")?
+            .to_string(),
+        height: number("height")?,
+        mining_hash,
+        network_difficulty,
+        reward: number("reward").unwrap_or_default(),
+        block: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response() {
+        let ok = b"HTTP/1.1 200 OK\r\nContent-Length: 40\r\n\r\n{\"id\":1,\"result\":{\"height\":7},\"error\":null}";
+        assert_eq!(parse_response(ok).unwrap()["height"], 7);
+
+        let error = b"HTTP/1.1 200 OK\r\n\r\n{\"id\":1,\"result\":null,\"error\":{\"code\":-1,\"message\":\"stale template\"}}";
+        assert_eq!(parse_response(error).unwrap_err(), "stale template");
+
+        assert!(parse_response(b"HTTP/1.1 500 Internal Server Error\r\n\r\n").is_err());
+        assert!(parse_response(b"garbage").is_err());
+    }
+
+    #[test]
+    fn test_parse_template() {
+        let template = parse_template(&json!({
+            "template_id": "t1",
+            "height": 100,
+            "mining_hash": "11".repeat(32),
+            "difficulty": 5000,
+            "reward": 42
+        }))
+        .unwrap();
+        assert_eq!(template.id, "t1");
+        assert_eq!(template.height, 100);
+        assert_eq!(template.mining_hash, vec![0x11; 32]);
+        assert_eq!(template.network_difficulty, 5000);
+        assert_eq!(template.reward, 42);
+
+        assert!(
+            parse_template(
+                &json!({"template_id": "t1", "height": 1, "mining_hash": "11", "difficulty": 1})
+            )
+            .is_err()
+        );
+        assert!(
+            parse_template(&json!({"template_id": "t1", "height": 1, "mining_hash": "11".repeat(32), "difficulty": 0}))
+                .is_err()
+        );
+    }
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial JSON-RPC block template client for http:// solo nodes.
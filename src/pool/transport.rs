@@ -0,0 +1,358 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/pool/transport.rs
+// Version: 1.2.1
+//
+// This file implements the pool transports. It parses pool URLs
+// (stratum+tcp://, stratum+ssl://, sv2://, or plain HOST:PORT, plus the solo
+// grpc:// and http:// node URLs) and wraps TCP connections in TLS when
+// requested, with optional certificate pinning by SHA-256 fingerprint or, as
+// a last resort, no certificate verification at all (insecure=true).
+//
+// Tree Location:
+// - src/pool/transport.rs (pool URL parsing and TLS transport)
+// - Depends on: tokio, tokio-rustls, webpki-roots, sha2
+
+use crate::Result;
+use log::{debug, warn};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::rustls::client::WebPkiServerVerifier;
+use tokio_rustls::rustls::client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+};
+use tokio_rustls::rustls::crypto::{
+    CryptoProvider, ring, verify_tls12_signature, verify_tls13_signature,
+};
+use tokio_rustls::rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use tokio_rustls::rustls::{
+    ClientConfig, DigitallySignedStruct, Error as TlsError, RootCertStore, SignatureScheme,
+};
+
+const LOG_TARGET: &str = "tari::graxil::pool::transport";
+
+/// Any byte stream a pool session can run over (plain TCP or TLS)
+pub trait PoolIo: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> PoolIo for T {}
+
+/// Connected pool stream
+pub type PoolStream = Box<dyn PoolIo>;
+
+/// Transport requested by the pool URL scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolScheme {
+    /// `stratum+tcp://` or no scheme
+    Tcp,
+    /// `stratum+ssl://` / `stratum+tls://`
+    Tls,
+    /// `sv2://` / `stratum2+tcp://` (Stratum V2 over Noise)
+    Sv2,
+    /// `grpc://` / `tari+grpc://` (solo mining against a Tari base node)
+    BaseNode,
+    /// `http://` (solo mining against a JSON-RPC block template server)
+    TemplateRpc,
+}
+
+/// Pool URL split into transport and `HOST:PORT`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolUrl {
+    pub scheme: PoolScheme,
+    pub address: String,
+    /// SV2 pool authority public key (base58check), from `sv2://HOST:PORT/KEY`
+    pub authority_key: Option<String>,
+}
+
+impl PoolUrl {
+    /// Parse `stratum+tcp://HOST:PORT`, `stratum+ssl://HOST:PORT`,
+    /// `sv2://HOST:PORT[/AUTHORITY_KEY]`, `grpc://HOST:PORT`, `http://HOST:PORT`
+    /// or `HOST:PORT`
+    pub fn parse(url: &str) -> std::result::Result<Self, String> {
+        let (scheme, address) = match url.split_once("://") {
+            None => (PoolScheme::Tcp, url),
+            Some(("stratum+tcp", rest)) | Some(("tcp", rest)) => (PoolScheme::Tcp, rest),
+            Some(("stratum+ssl", rest))
+            | Some(("stratum+tls", rest))
+            | Some(("ssl", rest))
+            | Some(("tls", rest)) => (PoolScheme::Tls, rest),
+            Some(("sv2", rest)) | Some(("stratum2+tcp", rest)) => (PoolScheme::Sv2, rest),
+            Some(("grpc", rest)) | Some(("tari+grpc", rest)) => (PoolScheme::BaseNode, rest),
+            Some(("http", rest)) => (PoolScheme::TemplateRpc, rest),
+            Some((other, _)) => {
+                return Err(format!(
+                    "Unsupported pool scheme '{}://' (use stratum+tcp://, stratum+ssl://, sv2://, grpc:// or http://)",
+                    other
+                ));
+            }
+        };
+
+        let address = address.trim_end_matches('/');
+        let (address, authority_key) = match address.split_once('/') {
+            Some((address, key)) if scheme == PoolScheme::Sv2 => (address, Some(key.to_string())),
+            Some(_) => {
+                return Err(
+                    "Only sv2:// pool URLs may carry a path (the authority key)".to_string()
+                );
+            }
+            None => (address, None),
+        };
+        let Some((host, port)) = address.rsplit_once(':') else {
+            return Err(
+                "Pool address must be in format HOST:PORT (e.g., pool.tari.com:4200)".to_string(),
+            );
+        };
+        if host.is_empty() || (host.contains(':') && !host.starts_with('[')) {
+            return Err("Pool address must contain exactly one colon (HOST:PORT)".to_string());
+        }
+        if port.parse::<u16>().is_err() {
+            return Err("Pool port must be a valid number (1-65535)".to_string());
+        }
+
+        Ok(Self {
+            scheme,
+            address: address.to_string(),
+            authority_key,
+        })
+    }
+
+    /// Host part of the address, without brackets for IPv6 literals
+    pub fn host(&self) -> &str {
+        let host = self
+            .address
+            .rsplit_once(':')
+            .map(|(host, _)| host)
+            .unwrap_or(&self.address);
+        host.trim_start_matches('[').trim_end_matches(']')
+    }
+
+    pub fn is_tls(&self) -> bool {
+        self.scheme == PoolScheme::Tls
+    }
+
+    pub fn is_sv2(&self) -> bool {
+        self.scheme == PoolScheme::Sv2
+    }
+
+    /// Solo mining node rather than a pool
+    pub fn is_solo(&self) -> bool {
+        matches!(self.scheme, PoolScheme::BaseNode | PoolScheme::TemplateRpc)
+    }
+}
+
+/// Certificate checks applied to TLS pool connections
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TlsOptions {
+    /// Accept any certificate whose SHA-256 (DER) fingerprint matches
+    pub pinned_fingerprint: Option<[u8; 32]>,
+    /// Skip every certificate check (chain, expiry, hostname); any certificate,
+    /// including a man-in-the-middle's, is accepted. Prefer pinned_fingerprint
+    /// for self-signed pool certificates.
+    pub insecure: bool,
+}
+
+/// Parse a SHA-256 certificate fingerprint written as hex, with or without ':' separators
+pub fn parse_fingerprint(fingerprint: &str) -> std::result::Result<[u8; 32], String> {
+    let cleaned: String = fingerprint.chars().filter(|c| *c != ':').collect();
+    let bytes = hex::decode(&cleaned).map_err(|_| {
+        format!(
+            "Certificate fingerprint must be hex. Found: '{}'",
+            fingerprint
+        )
+    })?;
+    bytes.try_into().map_err(|_| {
+        "Certificate fingerprint must be a SHA-256 hash (32 bytes / 64 hex characters)".to_string()
+    })
+}
+
+/// Wrap an established TCP connection in TLS
+pub async fn connect_tls(
+    stream: TcpStream,
+    url: &PoolUrl,
+    options: &TlsOptions,
+) -> Result<TlsStream<TcpStream>> {
+    let provider = Arc::new(ring::default_provider());
+    let roots = Arc::new(RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    });
+    let verifier = PoolCertVerifier {
+        webpki: WebPkiServerVerifier::builder_with_provider(roots, Arc::clone(&provider))
+            .build()?,
+        options: options.clone(),
+        provider: Arc::clone(&provider),
+    };
+
+    let config = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(verifier))
+        .with_no_client_auth();
+
+    if options.insecure && options.pinned_fingerprint.is_none() {
+        warn!(target: LOG_TARGET,
+            "⚠️ TLS certificate verification is off for {} (insecure=true); pin it with fingerprint= instead",
+            url.address
+        );
+    }
+
+    let server_name = ServerName::try_from(url.host().to_string())?;
+    debug!(target: LOG_TARGET,"Starting TLS handshake with {}", url.address);
+    let stream = TlsConnector::from(Arc::new(config))
+        .connect(server_name, stream)
+        .await?;
+    Ok(stream)
+}
+
+/// WebPKI validation with optional fingerprint pinning, or none at all when insecure
+#[derive(Debug)]
+struct PoolCertVerifier {
+    webpki: Arc<WebPkiServerVerifier>,
+    options: TlsOptions,
+    provider: Arc<CryptoProvider>,
+}
+
+impl ServerCertVerifier for PoolCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, TlsError> {
+        if let Some(expected) = self.options.pinned_fingerprint {
+            let actual: [u8; 32] = Sha256::digest(end_entity.as_ref()).into();
+            return if actual == expected {
+                Ok(ServerCertVerified::assertion())
+            } else {
+                Err(TlsError::General(format!(
+                    "pool certificate fingerprint mismatch (got {})",
+                    hex::encode(actual)
+                )))
+            };
+        }
+
+        if self.options.insecure {
+            return Ok(ServerCertVerified::assertion());
+        }
+
+        self.webpki
+            .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, TlsError> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, TlsError> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pool_url() {
+        let url = PoolUrl::parse("pool.tari.com:4200").unwrap();
+        assert_eq!(url.scheme, PoolScheme::Tcp);
+        assert_eq!(url.address, "pool.tari.com:4200");
+
+        let url = PoolUrl::parse("stratum+tcp://pool.tari.com:4200").unwrap();
+        assert_eq!(url.scheme, PoolScheme::Tcp);
+
+        let url = PoolUrl::parse("stratum+ssl://ca.luckypool.io:6119").unwrap();
+        assert!(url.is_tls());
+        assert_eq!(url.address, "ca.luckypool.io:6119");
+        assert_eq!(url.host(), "ca.luckypool.io");
+
+        let url = PoolUrl::parse(
+            "sv2://sv2.pool.io:34254/9auqWEzQDVyd2oe1JVGFLMLHZtCo2FFqZwtKA5gd9xbuEu7PH72",
+        )
+        .unwrap();
+        assert!(url.is_sv2());
+        assert_eq!(url.address, "sv2.pool.io:34254");
+        assert_eq!(
+            url.authority_key.as_deref(),
+            Some("9auqWEzQDVyd2oe1JVGFLMLHZtCo2FFqZwtKA5gd9xbuEu7PH72")
+        );
+        assert_eq!(
+            PoolUrl::parse("stratum2+tcp://sv2.pool.io:3333")
+                .unwrap()
+                .authority_key,
+            None
+        );
+
+        let url = PoolUrl::parse("grpc://127.0.0.1:18142").unwrap();
+        assert_eq!(url.scheme, PoolScheme::BaseNode);
+        assert!(url.is_solo());
+        assert_eq!(url.address, "127.0.0.1:18142");
+        let url = PoolUrl::parse("http://templates.local:18081/").unwrap();
+        assert_eq!(url.scheme, PoolScheme::TemplateRpc);
+        assert_eq!(url.address, "templates.local:18081");
+        assert!(!PoolUrl::parse("pool:4200").unwrap().is_solo());
+
+        assert!(PoolUrl::parse("stratum+tcp://pool:4200/path").is_err());
+        assert!(PoolUrl::parse("ftp://pool:21").is_err());
+        assert!(PoolUrl::parse("stratum+ssl://pool").is_err());
+        assert!(PoolUrl::parse("pool:notaport").is_err());
+    }
+
+    #[test]
+    fn test_parse_fingerprint() {
+        let hex64 = "ab".repeat(32);
+        assert_eq!(parse_fingerprint(&hex64).unwrap(), [0xab; 32]);
+
+        let with_colons = vec!["AB"; 32].join(":");
+        assert_eq!(parse_fingerprint(&with_colons).unwrap(), [0xab; 32]);
+
+        assert!(parse_fingerprint("abcd").is_err());
+        assert!(parse_fingerprint(&"zz".repeat(32)).is_err());
+    }
+}
+
+// Changelog:
+// - v1.2.1 (2025-06-28): TlsOptions::accept_self_signed renamed to insecure, since it skips
+//   every certificate check; a warning is logged when it is used without a pinned fingerprint.
+// - v1.2.0 (2025-06-28): Solo mining node URLs.
+//   - grpc:// / tari+grpc:// (Tari base node) and http:// (JSON-RPC template server).
+// - v1.1.0 (2025-06-28): Stratum V2 pool URLs.
+//   - sv2:// and stratum2+tcp:// schemes with an optional authority key path.
+// - v1.0.0 (2025-06-28): Initial pool transport implementation.
+//   - Pool URL schemes stratum+tcp:// and stratum+ssl:// (plain HOST:PORT = TCP).
+//   - TLS via rustls with WebPKI roots, SHA-256 certificate pinning and
+//     optional acceptance of self-signed pool certificates.
@@ -0,0 +1,329 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/tui/app.rs
+// Version: 1.0.0
+//
+// This file implements the terminal dashboard state and event loop. The App
+// takes a MinerStats snapshot once per second, samples the hashrate for the
+// graph and reacts to keyboard input; `run` owns the terminal (raw mode,
+// alternate screen) and restores it on exit or panic.
+//
+// Tree Location:
+// - src/tui/app.rs (TUI state, input handling and event loop)
+// - Depends on: crossterm, ratatui, miner/stats, tui/ui
+
+use crate::Result;
+use crate::miner::stats::MinerStats;
+use crate::miner::stats::miner_stats::WebSocketData;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{
+    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+};
+use ratatui::Terminal;
+use ratatui::backend::CrosstermBackend;
+use std::collections::VecDeque;
+use std::io;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+/// How often the stats snapshot and hashrate graph are refreshed
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+/// How long to wait for a key before redrawing
+const INPUT_POLL: Duration = Duration::from_millis(100);
+/// Hashrate samples kept for the graph (5 minutes at one per tick)
+const HISTORY_LEN: usize = 300;
+/// Rows moved by PageUp / PageDown
+const PAGE_ROWS: usize = 10;
+
+/// Dashboard tabs, in display order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    Overview,
+    Threads,
+    Shares,
+}
+
+impl Tab {
+    pub const ALL: [Tab; 3] = [Tab::Overview, Tab::Threads, Tab::Shares];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Tab::Overview => "Overview",
+            Tab::Threads => "Threads",
+            Tab::Shares => "Shares",
+        }
+    }
+
+    pub fn index(self) -> usize {
+        Self::ALL.iter().position(|tab| *tab == self).unwrap_or(0)
+    }
+
+    fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    fn previous(self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+/// What the dashboard shows about this miner
+#[derive(Debug, Clone)]
+pub struct TuiConfig {
+    /// "CPU", "GPU" or "Hybrid"
+    pub mode: String,
+    pub pool: String,
+    pub wallet: String,
+    pub worker: String,
+    /// Stats thread ids below this are GPU devices
+    pub gpu_devices: usize,
+}
+
+/// Terminal dashboard state
+pub struct App {
+    pub stats: Arc<MinerStats>,
+    pub config: TuiConfig,
+    pub tab: Tab,
+    /// First visible row of the thread table / share log
+    pub scroll: usize,
+    pub show_help: bool,
+    /// Latest stats snapshot
+    pub snapshot: WebSocketData,
+    /// (seconds since start, H/s) samples for the hashrate graph
+    pub history: VecDeque<(f64, f64)>,
+    started: Instant,
+    last_tick: Instant,
+    last_hashes: u64,
+    should_quit: bool,
+}
+
+impl App {
+    pub fn new(stats: Arc<MinerStats>, config: TuiConfig) -> Self {
+        let snapshot = stats.to_websocket_data();
+        let last_hashes = stats.hashes_computed.load(Ordering::Relaxed);
+        Self {
+            stats,
+            config,
+            tab: Tab::Overview,
+            scroll: 0,
+            show_help: false,
+            snapshot,
+            history: VecDeque::with_capacity(HISTORY_LEN),
+            started: Instant::now(),
+            last_tick: Instant::now(),
+            last_hashes,
+            should_quit: false,
+        }
+    }
+
+    /// Refresh the snapshot and record a hashrate sample
+    pub fn tick(&mut self) {
+        let hashes = self.stats.hashes_computed.load(Ordering::Relaxed);
+        let elapsed = self.last_tick.elapsed().as_secs_f64();
+        if elapsed > 0.0 {
+            let rate = hashes.saturating_sub(self.last_hashes) as f64 / elapsed;
+            self.history
+                .push_back((self.started.elapsed().as_secs_f64(), rate));
+            if self.history.len() > HISTORY_LEN {
+                self.history.pop_front();
+            }
+        }
+        self.last_hashes = hashes;
+        self.last_tick = Instant::now();
+        self.snapshot = self.stats.to_websocket_data();
+    }
+
+    /// Hashrate over the last tick (H/s)
+    pub fn current_hashrate(&self) -> f64 {
+        self.history.back().map(|(_, rate)| *rate).unwrap_or(0.0)
+    }
+
+    pub fn should_quit(&self) -> bool {
+        self.should_quit
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        if self.show_help {
+            self.show_help = false;
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.should_quit = true
+            }
+            KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => self.select(self.tab.next()),
+            KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') => {
+                self.select(self.tab.previous())
+            }
+            KeyCode::Char('1') => self.select(Tab::Overview),
+            KeyCode::Char('2') => self.select(Tab::Threads),
+            KeyCode::Char('3') => self.select(Tab::Shares),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll_by(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::PageDown => self.scroll_by(PAGE_ROWS),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(PAGE_ROWS),
+            KeyCode::Home => self.scroll = 0,
+            KeyCode::End => self.scroll = self.scroll_limit(),
+            KeyCode::Char('?') => self.show_help = true,
+            _ => {}
+        }
+    }
+
+    fn select(&mut self, tab: Tab) {
+        self.tab = tab;
+        self.scroll = 0;
+    }
+
+    fn scroll_by(&mut self, rows: usize) {
+        self.scroll = (self.scroll + rows).min(self.scroll_limit());
+    }
+
+    /// Last scroll position that still shows a row on the current tab
+    fn scroll_limit(&self) -> usize {
+        let rows = match self.tab {
+            Tab::Overview => 0,
+            Tab::Threads => self.stats.thread_stats.len(),
+            Tab::Shares => self.snapshot.recent_shares.len(),
+        };
+        rows.saturating_sub(1)
+    }
+}
+
+/// Run the dashboard until the user quits; blocks the calling thread
+pub fn run(stats: Arc<MinerStats>, config: TuiConfig) -> Result<()> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+
+    // Leave the terminal usable if anything panics while it is in raw mode
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        default_hook(info);
+    }));
+
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    let result = event_loop(&mut terminal, App::new(stats, config));
+    restore_terminal()?;
+    terminal.show_cursor()?;
+    result
+}
+
+fn event_loop(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, mut app: App) -> Result<()> {
+    app.tick();
+    let mut last_tick = Instant::now();
+    while !app.should_quit() {
+        terminal.draw(|frame| super::ui::draw(frame, &app))?;
+
+        if event::poll(INPUT_POLL)? {
+            if let Event::Key(key) = event::read()? {
+                app.handle_key(key);
+            }
+        }
+        if last_tick.elapsed() >= TICK_INTERVAL {
+            app.tick();
+            last_tick = Instant::now();
+        }
+    }
+    Ok(())
+}
+
+fn restore_terminal() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(threads: usize) -> App {
+        App::new(
+            Arc::new(MinerStats::new(threads)),
+            TuiConfig {
+                mode: "CPU".to_string(),
+                pool: "pool.example:4200".to_string(),
+                wallet: "wallet".to_string(),
+                worker: "rig1".to_string(),
+                gpu_devices: 0,
+            },
+        )
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn test_tab_navigation() {
+        let mut app = app(1);
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.tab, Tab::Threads);
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.tab, Tab::Shares);
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.tab, Tab::Overview);
+        press(&mut app, KeyCode::Left);
+        assert_eq!(app.tab, Tab::Shares);
+        press(&mut app, KeyCode::Char('2'));
+        assert_eq!(app.tab, Tab::Threads);
+    }
+
+    #[test]
+    fn test_scroll_is_clamped_to_rows() {
+        let mut app = app(4);
+        press(&mut app, KeyCode::Char('2'));
+        press(&mut app, KeyCode::PageDown);
+        assert_eq!(app.scroll, 3);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.scroll, 2);
+        press(&mut app, KeyCode::Home);
+        assert_eq!(app.scroll, 0);
+
+        // Switching tabs resets the scroll position
+        press(&mut app, KeyCode::End);
+        press(&mut app, KeyCode::Char('1'));
+        assert_eq!(app.scroll, 0);
+    }
+
+    #[test]
+    fn test_help_and_quit() {
+        let mut app = app(1);
+        press(&mut app, KeyCode::Char('?'));
+        assert!(app.show_help);
+        // Any key closes the help overlay without acting
+        press(&mut app, KeyCode::Char('q'));
+        assert!(!app.show_help);
+        assert!(!app.should_quit());
+        press(&mut app, KeyCode::Char('q'));
+        assert!(app.should_quit());
+    }
+
+    #[test]
+    fn test_tick_samples_hashrate() {
+        let mut app = app(1);
+        app.stats
+            .hashes_computed
+            .fetch_add(1_000, Ordering::Relaxed);
+        std::thread::sleep(Duration::from_millis(20));
+        app.tick();
+        assert_eq!(app.history.len(), 1);
+        assert!(app.current_hashrate() > 0.0);
+    }
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial terminal dashboard state and event loop.
+//   - Tabs, scrolling, help overlay and quit keys.
+//   - Per-tick MinerStats snapshot and hashrate sampling for the graph.
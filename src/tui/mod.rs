@@ -1 +1,28 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/tui/mod.rs
+// Version: 1.0.0
+//
+// This file is the module declaration for the ratatui terminal dashboard,
+// built with the `tui` feature and enabled with --tui. The dashboard reads
+// MinerStats, so it works the same for CPU, GPU and hybrid miners.
+//
+// Tree Location:
+// - src/tui/mod.rs (TUI module entry point)
+// - Submodules: app, tabs, ui, widgets
 
+pub mod app;
+pub mod tabs;
+pub mod ui;
+pub mod widgets;
+
+// Re-export key types for convenience
+pub use app::{App, Tab, TuiConfig, run};
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial terminal dashboard (overview, threads and shares tabs).
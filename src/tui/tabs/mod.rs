@@ -0,0 +1,23 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/tui/tabs/mod.rs
+// Version: 1.0.0
+//
+// This file is the module declaration for the terminal dashboard tabs. Each
+// submodule exposes a `draw(frame, area, app)` function rendering one tab.
+//
+// Tree Location:
+// - src/tui/tabs/mod.rs (TUI tabs entry point)
+// - Submodules: overview, shares, threads
+
+pub mod overview;
+pub mod shares;
+pub mod threads;
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial tabs module (overview, shares, threads).
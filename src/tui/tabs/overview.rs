@@ -0,0 +1,280 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/tui/tabs/overview.rs
+// Version: 1.0.0
+//
+// This file draws the overview tab: mining statistics, the pool connection
+// panel, the hashrate graph and the recent activity feed.
+//
+// Tree Location:
+// - src/tui/tabs/overview.rs (overview tab)
+// - Depends on: ratatui, tui/app, tui/widgets
+
+use crate::miner::stats::MinerStats;
+use crate::tui::app::App;
+use crate::tui::widgets::formatters::{acceptance_color, format_uptime, label, shorten, value};
+use crate::tui::widgets::graphs::hashrate_chart;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use std::time::Duration;
+
+/// Activity messages shown at most
+const ACTIVITY_LINES: usize = 50;
+
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(9),
+            Constraint::Min(8),
+            Constraint::Length(10),
+        ])
+        .split(area);
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(rows[0]);
+
+    draw_statistics(frame, top[0], app);
+    draw_connection(frame, top[1], app);
+
+    let samples: Vec<(f64, f64)> = app.history.iter().copied().collect();
+    frame.render_widget(hashrate_chart(&samples, "Hashrate (last 5 min)"), rows[1]);
+
+    draw_activity(frame, rows[2], app);
+}
+
+fn draw_statistics(frame: &mut Frame, area: Rect, app: &App) {
+    let data = &app.snapshot;
+    let mut lines = vec![
+        Line::from(vec![
+            label("Hashrate: "),
+            value(
+                MinerStats::format_hashrate(app.current_hashrate()),
+                Color::Green,
+            ),
+            Span::raw("  "),
+            label("Session avg: "),
+            value(
+                MinerStats::format_hashrate(data.session_average as f64),
+                Color::Cyan,
+            ),
+        ]),
+        Line::from(vec![
+            label("Shares: "),
+            value(
+                format!("{}/{}", data.accepted_shares, data.submitted_shares),
+                Color::Cyan,
+            ),
+            Span::raw(" "),
+            value(
+                format!("({:.1}%)", data.acceptance_rate),
+                acceptance_color(data.acceptance_rate),
+            ),
+            Span::raw("  "),
+            label("Rejected: "),
+            value(
+                data.rejected_shares.to_string(),
+                if data.rejected_shares == 0 {
+                    Color::Green
+                } else {
+                    Color::Red
+                },
+            ),
+            Span::raw("  "),
+            label("Stale: "),
+            value(data.stale_shares.to_string(), Color::Yellow),
+        ]),
+        Line::from(vec![
+            label("Difficulty: "),
+            value(
+                MinerStats::format_number(data.current_difficulty),
+                Color::Magenta,
+            ),
+            Span::raw("  "),
+            label("Height: "),
+            value(data.current_job.block_height.to_string(), Color::Cyan),
+            Span::raw("  "),
+            label("Share rate: "),
+            value(format!("{:.1}/min", data.share_rate), Color::Cyan),
+        ]),
+        Line::from(vec![
+            label("Uptime: "),
+            value(format_uptime(data.uptime), Color::Blue),
+            Span::raw("  "),
+            label("Threads: "),
+            value(
+                format!("{}/{}", data.active_threads, data.thread_hashrates.len()),
+                Color::Cyan,
+            ),
+            Span::raw("  "),
+            label("Luck: "),
+            value(format!("{:.2}x", data.average_luck), Color::Cyan),
+        ]),
+    ];
+
+    if data.gpu_info.detected {
+        lines.push(Line::from(vec![
+            label("GPU: "),
+            value(
+                format!(
+                    "{} │ {} │ {} │ {}",
+                    data.gpu_info.name,
+                    data.gpu_info.format_utilization(),
+                    data.gpu_info.format_temperature(),
+                    data.gpu_info.format_power()
+                ),
+                Color::Cyan,
+            ),
+        ]));
+    }
+    lines.push(Line::from(vec![
+        label("CPU: "),
+        value(
+            format!(
+                "{:.1}% │ {}",
+                data.system_info.cpu_usage,
+                data.system_info
+                    .cpu_temperature
+                    .map(|t| format!("{:.0}°C", t))
+                    .unwrap_or_else(|| "N/A".to_string())
+            ),
+            Color::Cyan,
+        ),
+    ]));
+    if data.network_difficulty > 0 {
+        let expected = if app.current_hashrate() > 0.0 {
+            MinerStats::format_duration(Duration::from_secs_f64(
+                data.network_difficulty as f64 / app.current_hashrate(),
+            ))
+        } else {
+            "unknown".to_string()
+        };
+        lines.push(Line::from(vec![
+            label("Solo: "),
+            value(
+                format!(
+                    "network diff {} │ expected block {} │ blocks found {}",
+                    MinerStats::format_number(data.network_difficulty),
+                    expected,
+                    data.blocks_found
+                ),
+                Color::Magenta,
+            ),
+        ]));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Mining Statistics");
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn draw_connection(frame: &mut Frame, area: Rect, app: &App) {
+    let pool = &app.snapshot.pool_info;
+    let (status, status_color) = if pool.is_connected {
+        ("● Connected", Color::Green)
+    } else {
+        ("○ Disconnected", Color::Red)
+    };
+
+    // Stats without a pool client (e.g. a fallback miner) only know the configured address
+    let address = if pool.pool_address == "Not configured" {
+        app.config.pool.clone()
+    } else {
+        pool.pool_address.clone()
+    };
+
+    let lines = vec![
+        Line::from(vec![label("Pool: "), value(address, Color::Cyan)]),
+        Line::from(vec![
+            label("Status: "),
+            value(status.to_string(), status_color),
+            Span::raw("  "),
+            value(
+                if pool.encrypted { "🔒 TLS" } else { "plain" }.to_string(),
+                Color::DarkGray,
+            ),
+        ]),
+        Line::from(vec![
+            label("Latency: "),
+            value(
+                pool.latency_ms
+                    .map(|ms| format!("{}ms", ms))
+                    .unwrap_or_else(|| "-".to_string()),
+                Color::Cyan,
+            ),
+            Span::raw("  "),
+            label("Submit: "),
+            value(
+                app.snapshot
+                    .avg_submit_latency_ms
+                    .map(|ms| format!("{}ms", ms))
+                    .unwrap_or_else(|| "-".to_string()),
+                Color::Cyan,
+            ),
+        ]),
+        Line::from(vec![
+            label("Connected for: "),
+            value(
+                pool.uptime_seconds
+                    .map(format_uptime)
+                    .unwrap_or_else(|| "-".to_string()),
+                Color::Blue,
+            ),
+            Span::raw("  "),
+            label("Attempts: "),
+            value(pool.connection_attempts.to_string(), Color::Cyan),
+        ]),
+        Line::from(vec![
+            label("Wallet: "),
+            value(shorten(&app.config.wallet, 10), Color::Cyan),
+        ]),
+        Line::from(vec![
+            label("Worker: "),
+            value(app.config.worker.clone(), Color::Green),
+        ]),
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Pool Connection");
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn draw_activity(frame: &mut Frame, area: Rect, app: &App) {
+    let lines: Vec<Line> = app
+        .stats
+        .recent_activity(ACTIVITY_LINES)
+        .into_iter()
+        .map(|(age, message)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:>5} ", MinerStats::format_duration(age)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::raw(message),
+            ])
+        })
+        .collect();
+
+    let activity = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Recent Activity"),
+        )
+        .wrap(Wrap { trim: true });
+    frame.render_widget(activity, area);
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial overview tab (statistics, pool connection, graph, activity).
@@ -0,0 +1,184 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/tui/tabs/shares.rs
+// Version: 1.0.0
+//
+// This file draws the shares tab: the scrollable log of recently found shares
+// with their luck, the pool verdict totals, the best shares of the session and
+// the recent job history.
+//
+// Tree Location:
+// - src/tui/tabs/shares.rs (shares tab)
+// - Depends on: ratatui, tui/app, tui/widgets
+
+use crate::miner::stats::MinerStats;
+use crate::tui::app::App;
+use crate::tui::widgets::formatters::{acceptance_color, label, luck_color, thread_label, value};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table};
+use std::time::Duration;
+
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(area);
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(6),
+            Constraint::Length(7),
+            Constraint::Min(0),
+        ])
+        .split(columns[1]);
+
+    draw_share_log(frame, columns[0], app);
+    draw_totals(frame, right[0], app);
+    draw_top_shares(frame, right[1], app);
+    draw_jobs(frame, right[2], app);
+}
+
+fn draw_share_log(frame: &mut Frame, area: Rect, app: &App) {
+    let header = Row::new(vec!["Age", "Device", "Difficulty", "Target", "Luck"]).style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    );
+    let rows = app
+        .snapshot
+        .recent_shares
+        .iter()
+        .skip(app.scroll)
+        .map(|share| {
+            Row::new(vec![
+                Cell::from(MinerStats::format_duration(Duration::from_secs(
+                    share.timestamp,
+                )))
+                .style(Style::default().fg(Color::DarkGray)),
+                Cell::from(thread_label(share.thread_id, app.config.gpu_devices))
+                    .style(Style::default().fg(Color::Cyan)),
+                Cell::from(MinerStats::format_number(share.difficulty)),
+                Cell::from(MinerStats::format_number(share.target)),
+                Cell::from(format!("{:.2}x", share.luck_factor))
+                    .style(Style::default().fg(luck_color(share.luck_factor))),
+            ])
+        });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Length(11),
+            Constraint::Length(9),
+            Constraint::Length(9),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Recent Shares (newest first, ↑↓ to scroll)"),
+    );
+    frame.render_widget(table, area);
+}
+
+fn draw_totals(frame: &mut Frame, area: Rect, app: &App) {
+    let data = &app.snapshot;
+    let lines = vec![
+        Line::from(vec![
+            label("Accepted: "),
+            value(data.accepted_shares.to_string(), Color::Green),
+            Span::raw("  "),
+            label("Submitted: "),
+            value(data.submitted_shares.to_string(), Color::Cyan),
+        ]),
+        Line::from(vec![
+            label("Rejected: "),
+            value(data.rejected_shares.to_string(), Color::Red),
+            Span::raw("  "),
+            label("Stale: "),
+            value(data.stale_shares.to_string(), Color::Yellow),
+        ]),
+        Line::from(vec![
+            label("Acceptance: "),
+            value(
+                format!("{:.1}%", data.acceptance_rate),
+                acceptance_color(data.acceptance_rate),
+            ),
+            Span::raw("  "),
+            label("Total work: "),
+            value(MinerStats::format_number(data.total_work), Color::Magenta),
+        ]),
+        Line::from(vec![
+            label("Avg share time: "),
+            value(
+                MinerStats::format_duration(Duration::from_secs_f64(data.avg_share_time)),
+                Color::Cyan,
+            ),
+        ]),
+    ];
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Totals")),
+        area,
+    );
+}
+
+fn draw_top_shares(frame: &mut Frame, area: Rect, app: &App) {
+    let lines: Vec<Line> = if app.snapshot.top_shares.is_empty() {
+        vec![Line::from(Span::styled(
+            "Waiting for shares...",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        app.snapshot
+            .top_shares
+            .iter()
+            .enumerate()
+            .map(|(rank, difficulty)| {
+                Line::from(vec![
+                    label(["🥇 ", "🥈 ", "🥉 ", " 4 ", " 5 "][rank.min(4)]),
+                    value(MinerStats::format_number(*difficulty), Color::Magenta),
+                ])
+            })
+            .collect()
+    };
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Best Shares")),
+        area,
+    );
+}
+
+fn draw_jobs(frame: &mut Frame, area: Rect, app: &App) {
+    let lines: Vec<Line> = app
+        .snapshot
+        .recent_jobs
+        .iter()
+        .rev()
+        .map(|job| {
+            Line::from(vec![
+                value(format!("#{} ", job.block_height), Color::Cyan),
+                Span::raw(format!(
+                    "{} diff {}",
+                    job.job_id,
+                    MinerStats::format_number(job.difficulty)
+                )),
+            ])
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Recent Jobs")),
+        area,
+    );
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial shares tab (share log, totals, best shares, job history).
@@ -0,0 +1,100 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/tui/tabs/threads.rs
+// Version: 1.0.0
+//
+// This file draws the threads tab: a summary of active threads / devices and
+// the scrollable per-thread / per-GPU table.
+//
+// Tree Location:
+// - src/tui/tabs/threads.rs (threads tab)
+// - Depends on: ratatui, tui/app, tui/widgets
+
+use crate::miner::stats::MinerStats;
+use crate::tui::app::App;
+use crate::tui::widgets::formatters::{label, value};
+use crate::tui::widgets::thread_grid::{thread_rows, thread_table};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::Color;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(0)])
+        .split(area);
+
+    let rows = thread_rows(&app.stats, app.config.gpu_devices);
+    let active = rows.iter().filter(|row| row.hashrate > 0.0).count();
+    let gpu_hashrate: f64 = rows
+        .iter()
+        .take(app.config.gpu_devices)
+        .map(|row| row.hashrate)
+        .sum();
+    let cpu_hashrate: f64 = rows
+        .iter()
+        .skip(app.config.gpu_devices)
+        .map(|row| row.hashrate)
+        .sum();
+
+    let mut first = vec![
+        label("Active: "),
+        value(format!("{}/{}", active, rows.len()), Color::Cyan),
+        Span::raw("    "),
+        label("Avg per thread: "),
+        value(
+            MinerStats::format_hashrate(app.stats.get_avg_hashrate_per_thread()),
+            Color::Blue,
+        ),
+    ];
+    if app.config.gpu_devices > 0 {
+        first.extend([
+            Span::raw("    "),
+            label("GPU: "),
+            value(MinerStats::format_hashrate(gpu_hashrate), Color::Green),
+        ]);
+    }
+    if rows.len() > app.config.gpu_devices {
+        first.extend([
+            Span::raw("    "),
+            label("CPU: "),
+            value(MinerStats::format_hashrate(cpu_hashrate), Color::Green),
+        ]);
+    }
+    let second = Line::from(vec![
+        label("Share rate: "),
+        value(
+            format!("{:.1}/min", app.snapshot.share_rate),
+            Color::Magenta,
+        ),
+        Span::raw("    "),
+        label("Last share: "),
+        value(
+            MinerStats::format_duration(std::time::Duration::from_secs(
+                app.snapshot.time_since_last_share,
+            )),
+            Color::Cyan,
+        ),
+    ]);
+
+    let summary = Paragraph::new(vec![Line::from(first), second])
+        .block(Block::default().borders(Borders::ALL).title("Summary"));
+    frame.render_widget(summary, chunks[0]);
+
+    let title = format!(
+        "Threads - {} total, from #{} (↑↓ to scroll)",
+        rows.len(),
+        app.scroll
+    );
+    frame.render_widget(thread_table(&rows, app.scroll, title), chunks[1]);
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial threads tab (summary and per-thread / per-GPU table).
@@ -0,0 +1,116 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/tui/ui.rs
+// Version: 1.0.0
+//
+// This file draws the terminal dashboard frame: the tab bar, the selected
+// tab, a key hint footer and the help overlay.
+//
+// Tree Location:
+// - src/tui/ui.rs (TUI frame layout)
+// - Depends on: ratatui, tui/app, tui/tabs
+
+use super::app::{App, Tab};
+use super::tabs;
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Tabs};
+
+const HELP_LINES: [&str; 7] = [
+    "Tab / → / l      next tab",
+    "Shift+Tab / ← / h previous tab",
+    "1 2 3            jump to tab",
+    "↑ ↓ / j k        scroll threads or shares",
+    "PgUp PgDn Home End",
+    "?                this help",
+    "q / Esc          quit the miner",
+];
+
+/// Draw the whole dashboard
+pub fn draw(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(frame.area());
+
+    let titles: Vec<String> = Tab::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, tab)| format!("{} {}", i + 1, tab.title()))
+        .collect();
+    let tabs = Tabs::new(titles)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Graxil SHA3x Miner - {} ", app.config.mode)),
+        )
+        .select(app.tab.index())
+        .style(Style::default().fg(Color::Cyan))
+        .highlight_style(
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .bg(Color::Black),
+        );
+    frame.render_widget(tabs, chunks[0]);
+
+    match app.tab {
+        Tab::Overview => tabs::overview::draw(frame, chunks[1], app),
+        Tab::Threads => tabs::threads::draw(frame, chunks[1], app),
+        Tab::Shares => tabs::shares::draw(frame, chunks[1], app),
+    }
+
+    let footer = Paragraph::new(Line::from(
+        " q quit │ Tab/←→ switch tab │ ↑↓ scroll │ ? help",
+    ))
+    .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(footer, chunks[2]);
+
+    if app.show_help {
+        draw_help(frame);
+    }
+}
+
+fn draw_help(frame: &mut Frame) {
+    let area = centered(frame.area(), 46, HELP_LINES.len() as u16 + 2);
+    let help = Paragraph::new(
+        HELP_LINES
+            .iter()
+            .map(|line| Line::from(*line))
+            .collect::<Vec<_>>(),
+    )
+    .alignment(Alignment::Left)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Keys ")
+            .style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(help, area);
+}
+
+/// Rectangle of at most `width` x `height` centred in `area`
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial dashboard frame (tab bar, footer, help overlay).
@@ -0,0 +1,126 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/tui/widgets/formatters.rs
+// Version: 1.0.0
+//
+// This file holds the small formatting and colouring helpers shared by the
+// terminal dashboard tabs: uptime strings, shortened wallet addresses, device
+// labels for thread ids and the colour thresholds used for rates and luck.
+//
+// Tree Location:
+// - src/tui/widgets/formatters.rs (TUI formatting helpers)
+// - Depends on: ratatui
+
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Span;
+
+/// Uptime as `1h 02m 03s` (hours omitted below one hour)
+pub fn format_uptime(seconds: u64) -> String {
+    let (hours, minutes, secs) = (seconds / 3600, (seconds % 3600) / 60, seconds % 60);
+    if hours > 0 {
+        format!("{}h {:02}m {:02}s", hours, minutes, secs)
+    } else {
+        format!("{}m {:02}s", minutes, secs)
+    }
+}
+
+/// Keep the first and last `keep` characters of a long string (wallets, hashes)
+pub fn shorten(value: &str, keep: usize) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() <= keep * 2 + 3 {
+        return value.to_string();
+    }
+    let head: String = chars[..keep].iter().collect();
+    let tail: String = chars[chars.len() - keep..].iter().collect();
+    format!("{}...{}", head, tail)
+}
+
+/// Device label for a stats thread id; GPU devices occupy the first ids
+pub fn thread_label(thread_id: usize, gpu_devices: usize) -> String {
+    if thread_id < gpu_devices {
+        format!("GPU {}", thread_id)
+    } else {
+        format!("CPU {:02}", thread_id - gpu_devices)
+    }
+}
+
+/// Green at 95%+, yellow at 85%+, red below
+pub fn acceptance_color(rate: f64) -> Color {
+    if rate >= 95.0 {
+        Color::Green
+    } else if rate >= 85.0 {
+        Color::Yellow
+    } else {
+        Color::Red
+    }
+}
+
+/// Luck above 1x is good, above 10x is exceptional
+pub fn luck_color(luck: f64) -> Color {
+    if luck >= 10.0 {
+        Color::Magenta
+    } else if luck >= 1.0 {
+        Color::Green
+    } else {
+        Color::Yellow
+    }
+}
+
+/// Bold yellow field label, as used throughout the dashboard
+pub fn label(text: &str) -> Span<'_> {
+    Span::styled(
+        text,
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
+/// Field value in the given colour
+pub fn value(text: String, color: Color) -> Span<'static> {
+    Span::styled(text, Style::default().fg(color))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_uptime() {
+        assert_eq!(format_uptime(0), "0m 00s");
+        assert_eq!(format_uptime(75), "1m 15s");
+        assert_eq!(format_uptime(3723), "1h 02m 03s");
+    }
+
+    #[test]
+    fn test_shorten() {
+        assert_eq!(shorten("short", 4), "short");
+        assert_eq!(shorten("12FrDkKLtGBUrMN4P3ALUqEMnv8x", 4), "12Fr...nv8x");
+    }
+
+    #[test]
+    fn test_thread_label() {
+        assert_eq!(thread_label(0, 2), "GPU 0");
+        assert_eq!(thread_label(1, 2), "GPU 1");
+        assert_eq!(thread_label(2, 2), "CPU 00");
+        assert_eq!(thread_label(5, 0), "CPU 05");
+    }
+
+    #[test]
+    fn test_colors() {
+        assert_eq!(acceptance_color(99.0), Color::Green);
+        assert_eq!(acceptance_color(90.0), Color::Yellow);
+        assert_eq!(acceptance_color(10.0), Color::Red);
+        assert_eq!(luck_color(0.5), Color::Yellow);
+        assert_eq!(luck_color(2.0), Color::Green);
+        assert_eq!(luck_color(20.0), Color::Magenta);
+    }
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial TUI formatting helpers.
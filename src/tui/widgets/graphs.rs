@@ -0,0 +1,80 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/tui/widgets/graphs.rs
+// Version: 1.0.0
+//
+// This file builds the hashrate line chart shown on the overview tab. Samples
+// are (seconds since start, H/s) pairs collected by the App once per tick; the
+// chart scales both axes to the visible window.
+//
+// Tree Location:
+// - src/tui/widgets/graphs.rs (hashrate chart)
+// - Depends on: ratatui, miner/stats
+
+use crate::miner::stats::MinerStats;
+use ratatui::style::{Color, Style};
+use ratatui::symbols::Marker;
+use ratatui::text::Span;
+use ratatui::widgets::{Axis, Block, Borders, Chart, Dataset, GraphType};
+
+/// X and Y axis bounds for a set of samples; Y gets 10% headroom
+pub fn chart_bounds(samples: &[(f64, f64)]) -> ([f64; 2], [f64; 2]) {
+    let first = samples.first().map(|(x, _)| *x).unwrap_or(0.0);
+    let last = samples.last().map(|(x, _)| *x).unwrap_or(0.0);
+    let peak = samples.iter().map(|(_, y)| *y).fold(0.0, f64::max);
+    let x = [first, last.max(first + 1.0)];
+    let y = [0.0, if peak > 0.0 { peak * 1.1 } else { 1.0 }];
+    (x, y)
+}
+
+/// Line chart of hashrate samples
+pub fn hashrate_chart<'a>(samples: &'a [(f64, f64)], title: &'a str) -> Chart<'a> {
+    let (x_bounds, y_bounds) = chart_bounds(samples);
+    let dataset = Dataset::default()
+        .marker(Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(Color::Green))
+        .data(samples);
+
+    let window = (x_bounds[1] - x_bounds[0]).round() as u64;
+    Chart::new(vec![dataset])
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::DarkGray))
+                .bounds(x_bounds)
+                .labels(vec![Span::raw(format!("-{}s", window)), Span::raw("now")]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::DarkGray))
+                .bounds(y_bounds)
+                .labels(vec![
+                    Span::raw("0"),
+                    Span::raw(MinerStats::format_hashrate(y_bounds[1] / 2.0)),
+                    Span::raw(MinerStats::format_hashrate(y_bounds[1])),
+                ]),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chart_bounds() {
+        assert_eq!(chart_bounds(&[]), ([0.0, 1.0], [0.0, 1.0]));
+
+        let (x, y) = chart_bounds(&[(10.0, 100.0), (11.0, 300.0), (12.0, 200.0)]);
+        assert_eq!(x, [10.0, 12.0]);
+        assert!((y[1] - 330.0).abs() < 1e-9);
+    }
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial hashrate chart widget.
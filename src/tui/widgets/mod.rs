@@ -0,0 +1,23 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/tui/widgets/mod.rs
+// Version: 1.0.0
+//
+// This file is the module declaration for the reusable terminal dashboard
+// widgets shared by the TUI tabs.
+//
+// Tree Location:
+// - src/tui/widgets/mod.rs (TUI widgets entry point)
+// - Submodules: formatters, graphs, thread_grid
+
+pub mod formatters;
+pub mod graphs;
+pub mod thread_grid;
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial widgets module (formatters, graphs, thread_grid).
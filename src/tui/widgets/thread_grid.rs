@@ -0,0 +1,145 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: src/tui/widgets/thread_grid.rs
+// Version: 1.0.0
+//
+// This file builds the per-thread / per-GPU table on the threads tab. Each
+// MinerStats thread slot becomes one row (GPU devices first in hybrid mode)
+// with its hashrate, peak, share counters, best share and current target.
+//
+// Tree Location:
+// - src/tui/widgets/thread_grid.rs (thread / device table)
+// - Depends on: ratatui, miner/stats, tui/widgets/formatters
+
+use super::formatters::thread_label;
+use crate::miner::stats::{MinerStats, ThreadStats};
+use ratatui::layout::Constraint;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Borders, Cell, Row, Table};
+use std::sync::atomic::Ordering;
+
+/// One line of the thread table
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreadRow {
+    pub label: String,
+    pub hashrate: f64,
+    pub peak_hashrate: f64,
+    pub accepted: u64,
+    pub rejected: u64,
+    pub stale: u64,
+    pub best_difficulty: u64,
+    pub target: u64,
+}
+
+impl ThreadRow {
+    pub fn from_stats(thread_id: usize, stats: &ThreadStats, gpu_devices: usize) -> Self {
+        Self {
+            label: thread_label(thread_id, gpu_devices),
+            hashrate: stats.get_hashrate(),
+            peak_hashrate: stats.get_peak_hashrate(),
+            accepted: stats.shares_found.load(Ordering::Relaxed),
+            rejected: stats.shares_rejected.load(Ordering::Relaxed),
+            stale: stats.shares_stale.load(Ordering::Relaxed),
+            best_difficulty: stats.best_difficulty.load(Ordering::Relaxed),
+            target: stats.current_difficulty_target.load(Ordering::Relaxed),
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.hashrate <= 0.0
+    }
+}
+
+/// Rows for every thread slot in the stats
+pub fn thread_rows(stats: &MinerStats, gpu_devices: usize) -> Vec<ThreadRow> {
+    stats
+        .thread_stats
+        .iter()
+        .enumerate()
+        .map(|(id, thread)| ThreadRow::from_stats(id, thread, gpu_devices))
+        .collect()
+}
+
+/// Table of thread rows starting at `offset`
+pub fn thread_table(rows: &[ThreadRow], offset: usize, title: String) -> Table<'static> {
+    let header = Row::new(vec![
+        "Device", "Hashrate", "Peak", "Accepted", "Rejected", "Stale", "Best", "Target",
+    ])
+    .style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let body = rows.iter().skip(offset).map(|row| {
+        let style = if row.is_idle() {
+            Style::default().fg(Color::DarkGray)
+        } else {
+            Style::default()
+        };
+        let problems = if row.rejected > 0 {
+            Color::Red
+        } else {
+            Color::Green
+        };
+        Row::new(vec![
+            Cell::from(row.label.clone()).style(Style::default().fg(Color::Cyan)),
+            Cell::from(MinerStats::format_hashrate(row.hashrate))
+                .style(Style::default().fg(Color::Green)),
+            Cell::from(MinerStats::format_hashrate(row.peak_hashrate)),
+            Cell::from(row.accepted.to_string()),
+            Cell::from(row.rejected.to_string()).style(Style::default().fg(problems)),
+            Cell::from(row.stale.to_string()),
+            Cell::from(MinerStats::format_number(row.best_difficulty))
+                .style(Style::default().fg(Color::Magenta)),
+            Cell::from(MinerStats::format_number(row.target)),
+        ])
+        .style(style)
+    });
+
+    Table::new(
+        body,
+        [
+            Constraint::Length(8),
+            Constraint::Length(13),
+            Constraint::Length(13),
+            Constraint::Length(9),
+            Constraint::Length(9),
+            Constraint::Length(6),
+            Constraint::Length(9),
+            Constraint::Length(9),
+        ],
+    )
+    .header(header)
+    .block(Block::default().borders(Borders::ALL).title(title))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thread_rows_label_gpu_devices_first() {
+        let stats = MinerStats::new(3);
+        stats.thread_stats[0].update_hashrate(1_000);
+        stats.thread_stats[2].record_share(5_000, true);
+
+        let rows = thread_rows(&stats, 1);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].label, "GPU 0");
+        assert_eq!(rows[1].label, "CPU 00");
+        assert_eq!(rows[2].label, "CPU 01");
+        assert!(!rows[0].is_idle());
+        assert!(rows[1].is_idle());
+        assert_eq!(rows[2].accepted, 1);
+        assert_eq!(rows[2].best_difficulty, 5_000);
+    }
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial thread / device table widget.
@@ -6,68 +6,159 @@
 // via pull requests to the project repository.
 //
 // File: src/web_server.rs
-// Version: 1.0.1
+// Version: 1.2.2
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // This file implements the web server and WebSocket functionality for the
 // real-time mining dashboard. It serves the HTML dashboard and broadcasts
-// live mining statistics via WebSocket connections.
+// live mining statistics via WebSocket connections. The HTTP JSON API
+// (web_api.rs) is served on the same listener under /api/v1, and with
+// --metrics the Prometheus exporter under /metrics.
 //
 // Tree Location:
 // - src/web_server.rs (web server and WebSocket handler)
-// - Depends on: axum, tokio-tungstenite, serde, miner/stats
+// - Depends on: axum, tokio-tungstenite, serde, miner/stats, web_api
 
+use crate::web_api::{self, ApiAuth, ApiState};
 use axum::{
     Router,
+    extract::State,
     extract::ws::{WebSocket, WebSocketUpgrade},
-    response::{Html, Response},
+    http::header,
+    middleware,
+    response::{Html, IntoResponse, Response},
     routing::get,
 };
-use graxil::miner::stats::MinerStats;
-use log::{debug, error, info};
+use graxil::core::MinerConfig;
+use graxil::core::types::Args;
+use graxil::miner::MinerControl;
+use graxil::miner::stats::{MinerStats, prometheus};
+use log::{debug, error, info, warn};
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 
 const LOG_TARGET: &str = "tari::graxil::web_server";
 
-/// Start the web server for the mining dashboard
-///
-/// Serves the dashboard at http://localhost:8080 and provides WebSocket
-/// endpoint at ws://localhost:8080/ws for real-time data streaming
-pub async fn start_web_server(ws: usize, web_server: bool, stats: Arc<MinerStats>) {
-    let mut app = Router::new()
-        .route("/ws", get(websocket_handler))
-        .with_state(stats);
+/// Listener, dashboard and API settings of the web server
+pub struct WebServerConfig {
+    pub port: usize,
+    pub bind: IpAddr,
+    /// Serve the HTML dashboard at / (the WebSocket and API are always served)
+    pub dashboard: bool,
+    pub auth: ApiAuth,
+    /// Effective configuration reported by /api/v1/config
+    pub config: MinerConfig,
+    /// Pool addresses in failover order
+    pub pools: Vec<String>,
+    /// Serve Prometheus metrics at /metrics
+    pub metrics: bool,
+    /// Worker label of the metrics
+    pub worker: String,
+}
 
-    if web_server {
-        app = app.route(
-            "/",
-            get(move || {
-                let ws = ws.clone();
-                async move { dashboard_handler(ws).await }
-            }),
-        );
-        info!(target: LOG_TARGET,"📊 Real-time GPU dashboard available at: http://localhost:{}", ws);
-        info!(target: LOG_TARGET,"📈 Live GPU charts accessible via the 'Live Charts' tab");
-    } else {
-        info!(target: LOG_TARGET,"💡 Add --web flag to enable real-time web dashboard");
+impl WebServerConfig {
+    pub fn from_args(args: &Args) -> Self {
+        let mut pools = args.pool_endpoints(&args.worker).unwrap_or_default();
+        pools.sort_by_key(|pool| pool.priority);
+        Self {
+            port: args.ws,
+            bind: args.web_bind,
+            dashboard: args.web,
+            auth: ApiAuth::new(args.api_token.clone(), args.api_auth.clone()),
+            config: web_api::redacted_config(MinerConfig::from_args(args).unwrap_or_default()),
+            pools: pools.into_iter().map(|pool| pool.address).collect(),
+            metrics: args.metrics,
+            worker: args.worker.clone(),
+        }
     }
+}
+
+/// Start the web server for the mining dashboard and HTTP API
+///
+/// Serves the dashboard at http://localhost:8080, the WebSocket endpoint at
+/// ws://localhost:8080/ws for real-time data streaming and the JSON API at
+/// http://localhost:8080/api/v1. `control` enables the API control endpoints.
+pub async fn start_web_server(
+    config: WebServerConfig,
+    stats: Arc<MinerStats>,
+    control: Option<MinerControl>,
+) {
+    let ws = config.port;
+    let bind = config.bind;
+    let app = router(config, stats, control);
 
-    let listener = match tokio::net::TcpListener::bind(format!("0.0.0.0:{}", ws)).await {
+    let addr = SocketAddr::new(bind, ws as u16);
+    let listener = match tokio::net::TcpListener::bind(addr).await {
         Ok(listener) => listener,
         Err(e) => {
-            error!(target: LOG_TARGET,"❌ Failed to bind web server to port {}: {}", ws, e);
+            error!(target: LOG_TARGET,"❌ Failed to bind web server to {}: {}", addr, e);
             error!(target: LOG_TARGET,"💡 Make sure port {} is not already in use", ws);
             return;
         }
     };
 
     info!(target: LOG_TARGET,"🔗 WebSocket endpoint: ws://localhost:{}/ws", ws);
+    info!(target: LOG_TARGET,"🔌 HTTP API: http://localhost:{}/api/v1", ws);
 
     if let Err(e) = axum::serve(listener, app).await {
         error!(target: LOG_TARGET,"❌ Web server error: {}", e);
     }
 }
 
+/// Routes of the dashboard, WebSocket, HTTP API and metrics, behind the
+/// configured authentication
+fn router(
+    config: WebServerConfig,
+    stats: Arc<MinerStats>,
+    control: Option<MinerControl>,
+) -> Router {
+    let ws = config.port;
+    let auth_enabled = config.auth.is_enabled();
+    let state = Arc::new(ApiState {
+        stats,
+        control,
+        auth: config.auth,
+        config: config.config,
+        pools: config.pools,
+    });
+
+    let mut app = Router::new()
+        .route("/ws", get(websocket_handler))
+        .nest("/api/v1", web_api::router());
+
+    if config.dashboard {
+        app = app.route("/", get(move || async move { dashboard_handler(ws).await }));
+        info!(target: LOG_TARGET,"📊 Real-time GPU dashboard available at: http://localhost:{}", ws);
+        info!(target: LOG_TARGET,"📈 Live GPU charts accessible via the 'Live Charts' tab");
+    } else {
+        info!(target: LOG_TARGET,"💡 Add --web flag to enable real-time web dashboard");
+    }
+
+    if config.metrics {
+        let worker = config.worker;
+        app = app.route(
+            "/metrics",
+            get(move |State(state): State<Arc<ApiState>>| async move {
+                metrics_handler(&state.stats, &worker)
+            }),
+        );
+        info!(target: LOG_TARGET,"📈 Prometheus metrics: http://localhost:{}/metrics", ws);
+    }
+
+    if auth_enabled {
+        app = app.layer(middleware::from_fn_with_state(
+            state.clone(),
+            web_api::require_auth,
+        ));
+        info!(target: LOG_TARGET,"🔒 Dashboard, WebSocket, API and metrics require the configured token or credentials");
+        info!(target: LOG_TARGET,"💡 With --api-token open the dashboard as http://localhost:{}/?token=TOKEN", ws);
+    } else if !config.bind.is_loopback() {
+        warn!(target: LOG_TARGET,"⚠️ The dashboard and API on {} have no authentication; control endpoints are disabled", config.bind);
+        warn!(target: LOG_TARGET,"💡 Set --api-token or --api-auth, or --web-bind 127.0.0.1");
+    }
+    app.with_state(state)
+}
+
 /// Handler for the main dashboard page
 ///
 /// Returns the HTML dashboard with embedded CSS and JavaScript
@@ -76,14 +167,20 @@ async fn dashboard_handler(ws: usize) -> Html<String> {
     Html(include_str!("dashboard.html").replace("{{GRAXIL_WS_PORT}}", ws.to_string().as_str()))
 }
 
+/// Handler for the Prometheus scrape endpoint
+fn metrics_handler(stats: &MinerStats, worker: &str) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, prometheus::CONTENT_TYPE)],
+        prometheus::render_metrics(stats, worker),
+    )
+}
+
 /// WebSocket upgrade handler
 ///
 /// Upgrades HTTP connections to WebSocket for real-time data streaming
-async fn websocket_handler(
-    ws: WebSocketUpgrade,
-    axum::extract::State(stats): axum::extract::State<Arc<MinerStats>>,
-) -> Response {
+async fn websocket_handler(ws: WebSocketUpgrade, State(state): State<Arc<ApiState>>) -> Response {
     debug!(target: LOG_TARGET,"🔌 WebSocket connection request received");
+    let stats = state.stats.clone();
     ws.on_upgrade(move |socket| handle_socket(socket, stats))
 }
 
@@ -122,7 +219,60 @@ async fn handle_socket(mut socket: WebSocket, stats: Arc<MinerStats>) {
     info!(target: LOG_TARGET,"🔌 WebSocket connection closed");
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// Status code of a WebSocket upgrade request for `path`
+    async fn upgrade_status(addr: SocketAddr, path: &str) -> u16 {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let request = format!(
+            "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: Upgrade\r\nUpgrade: websocket\r\n\
+             Sec-WebSocket-Version: 13\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n",
+            path
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut status_line = [0u8; 12];
+        stream.read_exact(&mut status_line).await.unwrap();
+        String::from_utf8_lossy(&status_line[9..]).parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_websocket_requires_token() {
+        let config = WebServerConfig {
+            port: 0,
+            bind: IpAddr::from([127, 0, 0, 1]),
+            dashboard: true,
+            auth: ApiAuth::new(Some("s3cret".to_string()), None),
+            config: MinerConfig::default(),
+            pools: Vec::new(),
+            metrics: false,
+            worker: "rig1".to_string(),
+        };
+        let app = router(config, Arc::new(MinerStats::new(1)), None);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        assert_eq!(upgrade_status(addr, "/ws").await, 401);
+        assert_eq!(upgrade_status(addr, "/ws?token=wrong").await, 401);
+        assert_eq!(upgrade_status(addr, "/ws?token=s3cret").await, 101);
+        assert_eq!(upgrade_status(addr, "/?token=s3cret").await, 200);
+    }
+}
+
 // Changelog:
+// - v1.2.2 (2025-06-28): One auth layer again over every route; the dashboard passes
+//   ?token= on to the WebSocket so --api-token protects both.
+// - v1.2.1 (2025-06-28): --api-token guards only /api/v1 and /metrics; the dashboard and
+//   WebSocket, which browsers cannot send a bearer token to, need --api-auth to be protected.
+// - v1.2.0 (2025-06-28): Optional Prometheus /metrics route (--metrics), behind the same auth.
+// - v1.1.0 (2025-06-28): HTTP API and access control.
+//   - Serves the /api/v1 JSON API (web_api.rs) next to the dashboard and WebSocket.
+//   - Optional bearer token / basic auth on every route; --web-bind listen address.
+//   - start_web_server takes a WebServerConfig and the MinerControl for API control.
 // - v1.0.1 (2025-06-23): Updated for 1-second updates.
 //   - Changed WebSocket update interval from 2 seconds to 1 second.
 //   - Updated info message to mention "Live Charts tab" instead of "Analytics tab".
@@ -6,20 +6,23 @@
 // via pull requests to the project repository.
 //
 // File: tests/bench_hashing.rs
-// Version: 1.0.0
+// Version: 1.1.1
 // Developer: OIEIEIO <oieieio@protonmail.com>
 //
 // This file contains core hash function performance tests for the SHA3x miner.
 // It validates the SHA3x implementation performance, correctness, and
-// consistency across different inputs and conditions.
+// consistency across different inputs and conditions, and measures the
+// leading-word early reject of the mining loop against calculate_difficulty.
 //
 // Tree Location:
 // - tests/bench_hashing.rs (hash function performance tests)
 // - Depends on: core/sha3x, core/difficulty, sha3 crate
 
-use graxil::core::{calculate_difficulty, sha3x::*};
+use graxil::core::difficulty::{sha3x_hash_word, sha3x_share_threshold};
+use graxil::core::{Algorithm, calculate_difficulty, sha3x::*};
 use sha3::{Digest, Sha3_256};
 use std::collections::HashMap;
+use std::hint::black_box;
 use std::time::Instant;
 
 #[test]
@@ -60,7 +63,7 @@ fn test_sha3x_performance_baseline() {
 
         for i in 0..iterations {
             let nonce = start_nonce + i as u64;
-            let _hash = sha3x_hash_with_nonce(&header, nonce);
+            let _hash = sha3x_hash_with_nonce(&header, nonce.to_le_bytes());
             hash_count += 1;
         }
 
@@ -98,8 +101,8 @@ fn test_sha3x_correctness() {
     println!("📊 Testing hash consistency...");
 
     let test_nonce = 98765u64;
-    let hash1 = sha3x_hash_with_nonce(&test_header, test_nonce);
-    let hash2 = sha3x_hash_with_nonce(&test_header, test_nonce);
+    let hash1 = sha3x_hash_with_nonce(&test_header, test_nonce.to_le_bytes());
+    let hash2 = sha3x_hash_with_nonce(&test_header, test_nonce.to_le_bytes());
 
     assert_eq!(hash1, hash2, "Same input should produce same hash");
     println!("  ✅ Hash consistency verified");
@@ -107,8 +110,8 @@ fn test_sha3x_correctness() {
     // Test that different nonces produce different hashes
     println!("📊 Testing nonce sensitivity...");
 
-    let hash_a = sha3x_hash_with_nonce(&test_header, 1000);
-    let hash_b = sha3x_hash_with_nonce(&test_header, 1001);
+    let hash_a = sha3x_hash_with_nonce(&test_header, 1000u64.to_le_bytes());
+    let hash_b = sha3x_hash_with_nonce(&test_header, 1001u64.to_le_bytes());
 
     assert_ne!(
         hash_a, hash_b,
@@ -122,8 +125,8 @@ fn test_sha3x_correctness() {
     let mut alt_header = test_header.clone();
     alt_header[0] = alt_header[0].wrapping_add(1);
 
-    let hash_orig = sha3x_hash_with_nonce(&test_header, test_nonce);
-    let hash_alt = sha3x_hash_with_nonce(&alt_header, test_nonce);
+    let hash_orig = sha3x_hash_with_nonce(&test_header, test_nonce.to_le_bytes());
+    let hash_alt = sha3x_hash_with_nonce(&alt_header, test_nonce.to_le_bytes());
 
     assert_ne!(
         hash_orig, hash_alt,
@@ -146,7 +149,7 @@ fn test_sha3x_correctness() {
         hash3.to_vec()
     };
 
-    let function_result = sha3x_hash_with_nonce(&test_header, test_nonce);
+    let function_result = sha3x_hash_with_nonce(&test_header, test_nonce.to_le_bytes());
 
     assert_eq!(
         manual_result, function_result,
@@ -175,7 +178,7 @@ fn test_difficulty_calculation_performance() {
     // Generate test hashes first
     let mut test_hashes = Vec::new();
     for i in 0..iterations {
-        let hash = sha3x_hash_with_nonce(&test_header, i as u64);
+        let hash = sha3x_hash_with_nonce(&test_header, (i as u64).to_le_bytes());
         test_hashes.push(hash);
     }
 
@@ -185,7 +188,7 @@ fn test_difficulty_calculation_performance() {
     let mut difficulty_sum = 0u64;
 
     for hash in &test_hashes {
-        let difficulty = calculate_difficulty(hash);
+        let difficulty = calculate_difficulty(hash, Algorithm::Sha3x);
         difficulty_sum = difficulty_sum.wrapping_add(difficulty);
     }
 
@@ -216,7 +219,7 @@ fn test_difficulty_calculation_performance() {
     ];
 
     for hash in &test_hashes {
-        let difficulty = calculate_difficulty(hash);
+        let difficulty = calculate_difficulty(hash, Algorithm::Sha3x);
 
         for &(min, max, label) in &ranges {
             if difficulty >= min && difficulty < max {
@@ -270,7 +273,7 @@ fn test_hash_distribution_quality() {
     let mut first_byte_zero_count = 0;
 
     for i in 0..sample_size {
-        let hash = sha3x_hash_with_nonce(&test_header, i as u64);
+        let hash = sha3x_hash_with_nonce(&test_header, (i as u64).to_le_bytes());
 
         // Count byte value distribution
         for (byte_pos, &byte_val) in hash.iter().enumerate() {
@@ -323,14 +326,14 @@ fn test_hash_distribution_quality() {
     println!("📊 Testing avalanche effect...");
 
     let base_nonce = 12345u64;
-    let base_hash = sha3x_hash_with_nonce(&test_header, base_nonce);
+    let base_hash = sha3x_hash_with_nonce(&test_header, base_nonce.to_le_bytes());
 
     let mut bit_flip_differences = 0;
     let test_count = 100;
 
     for i in 0..test_count {
         let flipped_nonce = base_nonce ^ (1u64 << (i % 64)); // Flip one bit
-        let flipped_hash = sha3x_hash_with_nonce(&test_header, flipped_nonce);
+        let flipped_hash = sha3x_hash_with_nonce(&test_header, flipped_nonce.to_le_bytes());
 
         // Count different bits
         for (b1, b2) in base_hash.iter().zip(flipped_hash.iter()) {
@@ -398,7 +401,7 @@ fn test_performance_consistency() {
 
         for i in 0..iterations_per_run {
             let nonce = (run * iterations_per_run + i) as u64;
-            let _hash = sha3x_hash_with_nonce(&test_header, nonce);
+            let _hash = sha3x_hash_with_nonce(&test_header, nonce.to_le_bytes());
         }
 
         let duration = start_time.elapsed();
@@ -463,7 +466,108 @@ fn test_performance_consistency() {
     );
 }
 
+#[test]
+fn test_early_reject_matches_difficulty() {
+    println!("🧪 Testing Early-Reject Share Check");
+    println!("===================================");
+
+    let test_header = vec![0x3cu8; 32];
+    let hasher = Sha3xHasher::new(&test_header);
+    let hashes: Vec<[u8; 32]> = (0..50_000u64).map(|nonce| hasher.hash(nonce)).collect();
+
+    for target_difficulty in [1u64, 2, 1_000, 65_536, 1_000_000] {
+        let threshold = sha3x_share_threshold(target_difficulty);
+        let mut shares = 0;
+        for (nonce, hash) in hashes.iter().enumerate() {
+            let is_share = calculate_difficulty(hash, Algorithm::Sha3x) >= target_difficulty;
+            let early_share = sha3x_hash_word(hash) <= threshold
+                && calculate_difficulty(hash, Algorithm::Sha3x) >= target_difficulty;
+            assert_eq!(
+                early_share, is_share,
+                "Early reject disagrees for nonce {} at difficulty {}",
+                nonce, target_difficulty
+            );
+            shares += usize::from(is_share);
+        }
+        println!("  Difficulty {}: {} shares", target_difficulty, shares);
+    }
+}
+
+/// Timing comparison; run with `cargo test --release -- --ignored`
+#[test]
+#[ignore]
+fn test_early_reject_performance() {
+    println!("🧪 Benchmarking Early-Reject Share Check");
+    println!("========================================");
+
+    let test_header = vec![0x3cu8; 32];
+    let hasher = Sha3xHasher::new(&test_header);
+    let hashes: Vec<[u8; 32]> = (0..200_000u64).map(|nonce| hasher.hash(nonce)).collect();
+    let target_difficulty = 1_000u64;
+    let rounds = 5;
+
+    println!(
+        "📊 Checking {} hashes against difficulty {}, best of {} runs...",
+        hashes.len(),
+        target_difficulty,
+        rounds
+    );
+
+    // Division per hash, as the mining loop used to do
+    let mut full_best = f64::INFINITY;
+    for _ in 0..rounds {
+        let start_time = Instant::now();
+        black_box(
+            hashes
+                .iter()
+                .filter(|hash| {
+                    calculate_difficulty(black_box(hash.as_slice()), Algorithm::Sha3x)
+                        >= target_difficulty
+                })
+                .count(),
+        );
+        full_best = full_best.min(start_time.elapsed().as_secs_f64());
+    }
+
+    // Leading-word compare, exact difficulty only for candidates
+    let mut early_best = f64::INFINITY;
+    for _ in 0..rounds {
+        let start_time = Instant::now();
+        let threshold = sha3x_share_threshold(target_difficulty);
+        black_box(
+            hashes
+                .iter()
+                .filter(|hash| sha3x_hash_word(black_box(hash)) <= threshold)
+                .filter(|hash| {
+                    calculate_difficulty(hash.as_slice(), Algorithm::Sha3x) >= target_difficulty
+                })
+                .count(),
+        );
+        early_best = early_best.min(start_time.elapsed().as_secs_f64());
+    }
+
+    println!(
+        "  calculate_difficulty per hash: {:.2} M checks/s",
+        hashes.len() as f64 / full_best / 1_000_000.0
+    );
+    println!(
+        "  Early reject:                  {:.2} M checks/s ({:.1}x)",
+        hashes.len() as f64 / early_best / 1_000_000.0,
+        full_best / early_best
+    );
+
+    println!();
+    println!("💡 Early-Reject Notes:");
+    println!("  - The threshold is derived once per job: u64::MAX / target difficulty");
+    println!("  - Only hashes at or below it pay for the exact difficulty division");
+}
+
 // Changelog:
+// - v1.1.1 (2025-06-28): test_early_reject_matches_difficulty checks the early reject against
+//   calculate_difficulty per nonce; the timing comparison is #[ignore]d.
+// - v1.1.0 (2025-06-28): Added test_early_reject_performance comparing the
+//   leading-word threshold check with calculate_difficulty per hash; updated
+//   calls to the [u8; 8] nonce and algorithm-aware difficulty APIs.
 // - v1.0.0 (2025-06-15): Initial hash function performance tests implementation.
 //   - Purpose: Validates SHA3x hash function performance, correctness, and
 //     distribution quality for mining operations.
@@ -0,0 +1,287 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: tests/mock_pool_test.rs
+// Version: 1.1.0
+//
+// This file contains pool integration tests run against the built-in mock
+// Stratum pool (pool::mock): CPU miner shares verified end to end, LuckyPool
+// XN nonces, vardiff, malformed pool lines, share rejections and reconnects.
+// Run with `cargo test --features mock-pool --test mock_pool_test`.
+//
+// Tree Location:
+// - tests/mock_pool_test.rs (pool session tests against the mock pool)
+// - Depends on: graxil (mock-pool feature), tokio
+
+#[cfg(test)]
+mod tests {
+    use graxil::core::sha3x::sha3x_hash_with_nonce;
+    use graxil::core::{Algorithm, MiningJob, ShareStatus, calculate_difficulty};
+    use graxil::miner::{CpuMiner, MinerStats};
+    use graxil::pool::mock::{MockJob, MockPool, MockVerdict};
+    use graxil::pool::{
+        FailoverSettings, PoolClient, PoolEndpoint, PoolSession, PoolSessionConfig, ShareOutcome,
+    };
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::broadcast;
+
+    const WALLET: &str = "12FrDkKLtGBUrMN4P3ALUqEMnv8xh2NexQZjnQwmYskCu";
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    fn start_session(pool: &MockPool) -> Arc<PoolSession> {
+        let session = Arc::new(
+            PoolSession::new(
+                PoolSessionConfig::single(
+                    pool.address(),
+                    WALLET.to_string(),
+                    "rig1".to_string(),
+                    Algorithm::Sha3x,
+                    "TEST",
+                ),
+                Arc::new(PoolClient::new()),
+                Arc::new(MinerStats::new(1)),
+            )
+            .unwrap(),
+        );
+        tokio::spawn(Arc::clone(&session).run());
+        session
+    }
+
+    async fn next_job(jobs: &mut broadcast::Receiver<MiningJob>) -> MiningJob {
+        tokio::time::timeout(TIMEOUT, jobs.recv())
+            .await
+            .expect("no job from pool")
+            .unwrap()
+    }
+
+    async fn next_outcome(outcomes: &mut broadcast::Receiver<ShareOutcome>) -> ShareOutcome {
+        tokio::time::timeout(TIMEOUT, outcomes.recv())
+            .await
+            .expect("no share verdict from pool")
+            .unwrap()
+    }
+
+    /// First nonce (little-endian hex), result and difficulty meeting the job difficulty,
+    /// searching upwards from `start`
+    fn find_share(job: &MiningJob, start: u64) -> (String, String, u64) {
+        (start..)
+            .map(|nonce| {
+                let hash = sha3x_hash_with_nonce(&job.mining_hash, nonce.to_le_bytes());
+                let difficulty = calculate_difficulty(&hash, Algorithm::Sha3x);
+                (
+                    hex::encode(nonce.to_le_bytes()),
+                    hex::encode(hash),
+                    difficulty,
+                )
+            })
+            .find(|(_, _, difficulty)| *difficulty >= job.target_difficulty)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cpu_miner_shares_verified_by_pool() {
+        let pool = MockPool::start().await.unwrap();
+        pool.send_job(MockJob::new("job-cpu", 1000, 2000));
+
+        let miner = CpuMiner::new(
+            WALLET.to_string(),
+            pool.address(),
+            "rig1".to_string(),
+            1,
+            Algorithm::Sha3x,
+        )
+        .into_arc();
+        tokio::spawn(miner.run());
+
+        let logins = pool.wait_for_logins(1, TIMEOUT).await.unwrap();
+        assert_eq!(logins[0].login, WALLET);
+        assert_eq!(logins[0].pass, "rig1");
+
+        let shares = pool.wait_for_shares(3, TIMEOUT).await.unwrap();
+        for share in &shares {
+            assert_eq!(share.verdict, MockVerdict::Accepted, "{:?}", share);
+            assert_eq!(share.login, "mock-session");
+            assert_eq!(share.job_id, "job-cpu");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cpu_miner_luckypool_xn_nonces() {
+        let pool = MockPool::start().await.unwrap();
+        pool.set_xn(Some("ad49"));
+        pool.send_job(MockJob::new("job-xn", 1000, 2000));
+
+        let miner = CpuMiner::new(
+            WALLET.to_string(),
+            pool.address(),
+            "rig1".to_string(),
+            1,
+            Algorithm::Sha3x,
+        )
+        .into_arc();
+        tokio::spawn(miner.run());
+
+        let shares = pool.wait_for_shares(3, TIMEOUT).await.unwrap();
+        for share in &shares {
+            assert!(share.nonce.starts_with("ad49"), "{:?}", share);
+            assert!(share.is_accepted(), "{:?}", share);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_vardiff_and_malformed_lines() {
+        let pool = MockPool::start().await.unwrap();
+        pool.send_job(MockJob::new("job-vardiff", 5, 100));
+
+        let session = start_session(&pool);
+        let mut jobs = session.subscribe_jobs();
+
+        let job = next_job(&mut jobs).await;
+        assert_eq!(job.job_id, "job-vardiff");
+        assert_eq!(job.target_difficulty, 100);
+        assert_eq!(job.height, 5);
+
+        // Garbage from the pool is logged and skipped, the connection stays up
+        pool.send_raw("{this is not json");
+        pool.send_raw("");
+        pool.set_difficulty(7777);
+
+        let job = next_job(&mut jobs).await;
+        assert_eq!(job.job_id, "job-vardiff");
+        assert_eq!(job.target_difficulty, 7777);
+        assert_eq!(pool.logins().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_session_share_verdicts() {
+        let pool = MockPool::start().await.unwrap();
+        pool.send_job(MockJob::new("job-verdicts", 5, 50));
+
+        let session = start_session(&pool);
+        let mut jobs = session.subscribe_jobs();
+        let mut outcomes = session.subscribe_share_results();
+        let share_tx = session.share_sender();
+        let job = next_job(&mut jobs).await;
+
+        let (nonce, result, difficulty) = find_share(&job, 0);
+        let submit = |job_id: &str, nonce: &str, result: &str| {
+            share_tx
+                .send((
+                    job_id.to_string(),
+                    nonce.to_string(),
+                    result.to_string(),
+                    0,
+                    difficulty,
+                    String::new(),
+                    0,
+                ))
+                .unwrap();
+        };
+
+        submit(&job.job_id, &nonce, &result);
+        assert_eq!(
+            next_outcome(&mut outcomes).await.status,
+            ShareStatus::Accepted
+        );
+
+        // Same nonce again is a duplicate
+        submit(&job.job_id, &nonce, &result);
+        assert_eq!(
+            next_outcome(&mut outcomes).await.status,
+            ShareStatus::Rejected
+        );
+
+        // Scripted rejection of an otherwise valid share
+        let next_start = u64::from_le_bytes(hex::decode(&nonce).unwrap().try_into().unwrap()) + 1;
+        let (nonce, result, _) = find_share(&job, next_start);
+        pool.reject_next_shares(1);
+        submit(&job.job_id, &nonce, &result);
+        let outcome = next_outcome(&mut outcomes).await;
+        assert_eq!(outcome.status, ShareStatus::Rejected);
+        assert!(outcome.error.unwrap().contains("mock pool script"));
+
+        // Unknown job ids are reported as stale
+        submit("job-gone", &nonce, &result);
+        assert_eq!(next_outcome(&mut outcomes).await.status, ShareStatus::Stale);
+
+        let verdicts: Vec<_> = pool.shares().into_iter().map(|s| s.is_accepted()).collect();
+        assert_eq!(verdicts, vec![true, false, false, false]);
+    }
+
+    #[tokio::test]
+    async fn test_session_reconnects_after_disconnect() {
+        let pool = MockPool::start().await.unwrap();
+        pool.send_job(MockJob::new("job-reconnect", 5, 100));
+
+        let session = start_session(&pool);
+        let mut jobs = session.subscribe_jobs();
+        next_job(&mut jobs).await;
+
+        pool.send_job(MockJob::new("job-after-reconnect", 6, 100));
+        next_job(&mut jobs).await;
+        pool.disconnect_all();
+
+        // The session logs in again and picks up the current job from the login response
+        pool.wait_for_logins(2, TIMEOUT).await.unwrap();
+        let job = next_job(&mut jobs).await;
+        assert_eq!(job.job_id, "job-after-reconnect");
+        assert_eq!(pool.connection_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_session_failover_drops_previous_xn() {
+        let primary = MockPool::start().await.unwrap();
+        primary.set_xn(Some("ad49"));
+        primary.send_job(MockJob::new("job-primary", 7, 100));
+        let backup = MockPool::start().await.unwrap();
+        backup.send_job(MockJob::new("job-backup", 7, 100));
+
+        let endpoint = |address: String| {
+            PoolEndpoint::new(
+                address,
+                WALLET.to_string(),
+                "rig1".to_string(),
+                "x".to_string(),
+            )
+        };
+        let session = Arc::new(
+            PoolSession::new(
+                PoolSessionConfig {
+                    pools: vec![endpoint(primary.address()), endpoint(backup.address())],
+                    failover: FailoverSettings {
+                        retry_budget: 1,
+                        initial_backoff: Duration::from_millis(100),
+                        ..FailoverSettings::default()
+                    },
+                    algo: Algorithm::Sha3x,
+                    label: "TEST".to_string(),
+                },
+                Arc::new(PoolClient::new()),
+                Arc::new(MinerStats::new(1)),
+            )
+            .unwrap(),
+        );
+        let mut jobs = session.subscribe_jobs();
+        tokio::spawn(Arc::clone(&session).run());
+
+        let job = next_job(&mut jobs).await;
+        assert_eq!(job.job_id, "job-primary");
+        assert_eq!(job.extranonce2.as_deref(), Some("ad49"));
+
+        // The backup pool sends no XN, so its jobs must not keep the primary's prefix
+        drop(primary);
+        let job = next_job(&mut jobs).await;
+        assert_eq!(job.job_id, "job-backup");
+        assert_eq!(job.extranonce2, None);
+        assert_eq!(backup.logins().len(), 1);
+    }
+}
+
+// Changelog:
+// - v1.1.0 (2025-06-28): Failover to a pool without XN drops the previous pool's XN.
+// - v1.0.0 (2025-06-28): Initial pool integration tests against the mock Stratum pool.
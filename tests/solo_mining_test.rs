@@ -0,0 +1,166 @@
+// SHA3x Miner - Free and Open Source Software Statement
+//
+// This project, sha3x-miner, is Free and Open Source Software (FOSS) licensed
+// under the MIT License. You are free to use, modify, and distribute this
+// software in accordance with the license terms. Contributions are welcome
+// via pull requests to the project repository.
+//
+// File: tests/solo_mining_test.rs
+// Version: 1.0.0
+//
+// This file contains solo mining integration tests run against the built-in
+// mock block template server (pool::mock_node): CPU miner blocks verified end
+// to end, coinbase wallet, chain tip tracking and stale block handling.
+// Run with `cargo test --features mock-pool --test solo_mining_test`.
+//
+// Tree Location:
+// - tests/solo_mining_test.rs (solo session tests against the mock node)
+// - Depends on: graxil (mock-pool feature), tokio
+
+#[cfg(test)]
+mod tests {
+    use graxil::core::sha3x::sha3x_hash_with_nonce;
+    use graxil::core::{Algorithm, MiningJob, ShareStatus, calculate_difficulty};
+    use graxil::miner::{CpuMiner, MinerStats};
+    use graxil::pool::mock_node::MockNode;
+    use graxil::pool::{PoolClient, PoolSession, PoolSessionConfig, ShareOutcome, ShareSubmission};
+    use std::sync::Arc;
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+    use tokio::sync::broadcast;
+
+    const WALLET: &str = "12FrDkKLtGBUrMN4P3ALUqEMnv8xh2NexQZjnQwmYskCu";
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    fn start_session(node: &MockNode, stats: Arc<MinerStats>) -> Arc<PoolSession> {
+        let session = Arc::new(
+            PoolSession::new(
+                PoolSessionConfig::single(
+                    node.address(),
+                    WALLET.to_string(),
+                    "rig1".to_string(),
+                    Algorithm::Sha3x,
+                    "TEST",
+                ),
+                Arc::new(PoolClient::new()),
+                stats,
+            )
+            .unwrap(),
+        );
+        tokio::spawn(Arc::clone(&session).run());
+        session
+    }
+
+    async fn next_job(jobs: &mut broadcast::Receiver<MiningJob>) -> MiningJob {
+        tokio::time::timeout(TIMEOUT, jobs.recv())
+            .await
+            .expect("no template from node")
+            .unwrap()
+    }
+
+    async fn next_outcome(outcomes: &mut broadcast::Receiver<ShareOutcome>) -> ShareOutcome {
+        tokio::time::timeout(TIMEOUT, outcomes.recv())
+            .await
+            .expect("no block verdict from node")
+            .unwrap()
+    }
+
+    /// First nonce (little-endian hex) meeting the job difficulty
+    fn find_nonce(job: &MiningJob) -> String {
+        (0u64..)
+            .find(|nonce| {
+                let hash = sha3x_hash_with_nonce(&job.mining_hash, nonce.to_le_bytes());
+                calculate_difficulty(&hash, Algorithm::Sha3x) >= job.target_difficulty
+            })
+            .map(|nonce| hex::encode(nonce.to_le_bytes()))
+            .unwrap()
+    }
+
+    fn submission(job: &MiningJob, nonce: &str) -> ShareSubmission {
+        (
+            job.job_id.clone(),
+            nonce.to_string(),
+            String::new(),
+            0,
+            job.target_difficulty,
+            String::new(),
+            0,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_cpu_miner_finds_blocks() {
+        let node = MockNode::start().await.unwrap();
+        node.set_template(100, 500);
+
+        let miner = CpuMiner::new(
+            WALLET.to_string(),
+            node.address(),
+            "rig1".to_string(),
+            1,
+            Algorithm::Sha3x,
+        )
+        .into_arc();
+        let stats = miner.get_stats();
+        tokio::spawn(miner.run());
+
+        let blocks = node.wait_for_blocks(3, TIMEOUT).await.unwrap();
+        let accepted: Vec<_> = blocks.iter().filter(|b| b.accepted).collect();
+        assert_eq!(accepted[0].height, 100);
+        assert_eq!(accepted[1].height, 101);
+        assert_eq!(accepted[2].height, 102);
+        assert!(node.wallets().iter().all(|wallet| wallet == WALLET));
+        assert!(stats.blocks_found.load(Ordering::Relaxed) >= 3);
+        assert_eq!(stats.network_difficulty.load(Ordering::Relaxed), 500);
+    }
+
+    #[tokio::test]
+    async fn test_session_follows_tip_and_reports_blocks() {
+        let node = MockNode::start().await.unwrap();
+        node.set_template(10, 50);
+
+        let stats = Arc::new(MinerStats::new(1));
+        let session = start_session(&node, Arc::clone(&stats));
+        let mut jobs = session.subscribe_jobs();
+        let mut outcomes = session.subscribe_share_results();
+        let share_tx = session.share_sender();
+
+        let job = next_job(&mut jobs).await;
+        assert_eq!(job.height, 10);
+        assert_eq!(job.target_difficulty, 50);
+        assert_eq!(job.algo, Algorithm::Sha3x);
+
+        let nonce = find_nonce(&job);
+        share_tx.send(submission(&job, &nonce)).unwrap();
+        assert_eq!(
+            next_outcome(&mut outcomes).await.status,
+            ShareStatus::Accepted
+        );
+        assert_eq!(stats.blocks_found.load(Ordering::Relaxed), 1);
+
+        // The accepted block moved the tip, so the next template is for height 11
+        let next = next_job(&mut jobs).await;
+        assert_eq!(next.height, 11);
+        assert_ne!(next.job_id, job.job_id);
+
+        // The same block again lost the race against the new tip
+        share_tx.send(submission(&job, &nonce)).unwrap();
+        assert_eq!(next_outcome(&mut outcomes).await.status, ShareStatus::Stale);
+
+        // Unknown templates never reach the node
+        share_tx
+            .send(submission(
+                &MiningJob {
+                    job_id: "gone".to_string(),
+                    ..next
+                },
+                &nonce,
+            ))
+            .unwrap();
+        assert_eq!(next_outcome(&mut outcomes).await.status, ShareStatus::Stale);
+        assert_eq!(node.blocks().len(), 2);
+    }
+}
+
+// Changelog:
+// - v1.0.0 (2025-06-28): Initial solo mining integration tests against the mock node.